@@ -0,0 +1,175 @@
+use std::fmt;
+
+use db_manager::entity::{invitation_code, user as user_entity};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use user_auth::db_exchange::{
+    ExchangeError, KeyRing, TokenPair, issue_token_pair, now_timestamp, revoke_user_tokens,
+};
+use user_auth::invitation::{InvitationError, InvitationOptions, issue_invitation};
+use user_auth::local_auth::{
+    LocalAuthError, create_local_account, find_local_account, reset_password,
+};
+use user_auth::personal_data::{PersonalDataError, PurgeReport, purge_due_accounts};
+use user_auth::user_auth::UserPermissionLevel;
+
+#[derive(Debug)]
+pub enum CliError {
+    UserNotFound(String),
+    NotAdmin(String),
+    Database(DbErr),
+    Exchange(ExchangeError),
+    Invitation(InvitationError),
+    LocalAuth(LocalAuthError),
+    PersonalData(PersonalDataError),
+    /// 数据库中仍有数据，未确认前拒绝执行会丢失数据的迁移
+    DataLossRefused(Vec<String>),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UserNotFound(open_id) => write!(f, "user not found: {}", open_id),
+            CliError::NotAdmin(open_id) => write!(f, "user is not an admin: {}", open_id),
+            CliError::Database(e) => write!(f, "database error: {}", e),
+            CliError::Exchange(e) => write!(f, "token error: {:?}", e),
+            CliError::Invitation(e) => write!(f, "{}", e.code_and_message().1),
+            CliError::LocalAuth(e) => write!(f, "{}", e.code_and_message().1),
+            CliError::PersonalData(e) => write!(f, "{}", e.code_and_message().1),
+            CliError::DataLossRefused(tables) => write!(
+                f,
+                "refusing to drop data, these tables are not empty: {} (pass --allow-data-loss to continue)",
+                tables.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<DbErr> for CliError {
+    fn from(err: DbErr) -> Self {
+        CliError::Database(err)
+    }
+}
+
+impl From<ExchangeError> for CliError {
+    fn from(err: ExchangeError) -> Self {
+        CliError::Exchange(err)
+    }
+}
+
+impl From<InvitationError> for CliError {
+    fn from(err: InvitationError) -> Self {
+        CliError::Invitation(err)
+    }
+}
+
+impl From<LocalAuthError> for CliError {
+    fn from(err: LocalAuthError) -> Self {
+        CliError::LocalAuth(err)
+    }
+}
+
+impl From<PersonalDataError> for CliError {
+    fn from(err: PersonalDataError) -> Self {
+        CliError::PersonalData(err)
+    }
+}
+
+async fn find_user<C>(db: &C, open_id: &str) -> Result<user_entity::Model, CliError>
+where
+    C: ConnectionTrait,
+{
+    user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| CliError::UserNotFound(open_id.to_string()))
+}
+
+/// Set a user's permission level, e.g. to create the first admin.
+pub async fn promote_user<C>(
+    db: &C,
+    open_id: &str,
+    level: UserPermissionLevel,
+) -> Result<user_entity::Model, CliError>
+where
+    C: ConnectionTrait,
+{
+    let user = find_user(db, open_id).await?;
+    let mut active: user_entity::ActiveModel = user.clone().into();
+    active.id = ActiveValue::Unchanged(user.id);
+    active.permission = Set(Some(level.level()));
+    Ok(active.update(db).await?)
+}
+
+/// List every user with the Admin permission.
+pub async fn list_admins<C>(db: &C) -> Result<Vec<user_entity::Model>, CliError>
+where
+    C: ConnectionTrait,
+{
+    Ok(user_entity::Entity::find()
+        .filter(user_entity::Column::Permission.eq(UserPermissionLevel::Admin.level()))
+        .order_by_asc(user_entity::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// Issue an invitation code on behalf of an existing admin.
+pub async fn issue_code<C>(
+    db: &C,
+    issuer_open_id: &str,
+    options: InvitationOptions,
+) -> Result<invitation_code::Model, CliError>
+where
+    C: ConnectionTrait,
+{
+    let issuer = find_user(db, issuer_open_id).await?;
+    if issuer.permission != Some(UserPermissionLevel::Admin.level()) {
+        return Err(CliError::NotAdmin(issuer_open_id.to_string()));
+    }
+    Ok(issue_invitation(db, &issuer, options).await?)
+}
+
+/// Revoke every token of a user and issue a fresh token pair.
+pub async fn rotate_tokens<C>(db: &C, keys: &KeyRing, open_id: &str) -> Result<TokenPair, CliError>
+where
+    C: ConnectionTrait,
+{
+    let user = find_user(db, open_id).await?;
+    let user = revoke_user_tokens(db, user).await?;
+    Ok(issue_token_pair(db, keys, &user).await?)
+}
+
+/// Create a local account for a user, or reset its password if one exists.
+///
+/// Returns `true` when a new account was created.
+pub async fn set_password<C>(
+    db: &C,
+    open_id: &str,
+    username: &str,
+    password: &str,
+) -> Result<bool, CliError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let user = find_user(db, open_id).await?;
+    if find_local_account(db, user.id).await?.is_some() {
+        reset_password(db, user.id, password).await?;
+        return Ok(false);
+    }
+    create_local_account(db, user.id, username, password).await?;
+    Ok(true)
+}
+
+/// Purge every account whose deletion grace period has ended, without
+/// waiting for the server's hourly run.
+pub async fn purge_deletions<C>(db: &C) -> Result<PurgeReport, CliError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    Ok(purge_due_accounts(db, now_timestamp()).await?)
+}
@@ -0,0 +1,10 @@
+//! 运维用的管理命令行工具
+//!
+//! 直接连接数据库执行管理员初始化、数据库迁移等操作，适用于尚无任何 Admin 的新部署。
+//! 命令实现与 `main.rs` 的参数解析分离，便于测试。
+
+pub mod command;
+pub mod migrate;
+
+pub use command::*;
+pub use migrate::*;
@@ -0,0 +1,210 @@
+use admin_cli::{
+    issue_code, list_admins, migrate_down, migrate_fresh, migrate_up, migration_status,
+    promote_user, purge_deletions, rotate_tokens, set_password,
+};
+use app_config::CliConfig;
+use clap::{Parser, Subcommand, ValueEnum};
+use dotenvy::dotenv;
+use sea_orm::Database;
+use user_auth::invitation::{DEFAULT_INVITATION_EXPIRE_SECONDS, InvitationOptions};
+use user_auth::user_auth::UserPermissionLevel;
+
+/// SD_backend 管理命令行，读取与服务端相同的配置文件与环境变量（数据库，以及签发 token 用的 JWT 密钥）
+#[derive(Parser)]
+#[command(name = "sd_admin", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 将用户设为指定权限（默认 Admin）
+    Promote {
+        open_id: String,
+        #[arg(long, value_enum, default_value_t = Level::Admin)]
+        level: Level,
+    },
+    /// 列出所有 Admin
+    ListAdmins,
+    /// 以某个 Admin 的名义签发邀请码
+    IssueCode {
+        /// 签发人 open_id，必须是 Admin
+        #[arg(long)]
+        issuer: String,
+        #[arg(long, value_enum, default_value_t = Level::Provider)]
+        level: Level,
+        #[arg(long, default_value_t = 1)]
+        max_uses: i32,
+        #[arg(long, default_value_t = DEFAULT_INVITATION_EXPIRE_SECONDS)]
+        expire_seconds: u64,
+        /// 仅允许该 open_id 兑换
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// 吊销用户已签发的全部 token，并签发新的 token 对
+    RotateToken { open_id: String },
+    /// 为用户开通管理后台的用户名密码登录，已开通时重置密码；密码从标准输入读取
+    SetPassword {
+        open_id: String,
+        /// 新开通时使用的用户名
+        #[arg(long)]
+        username: String,
+    },
+    /// 立即执行宽限期已过的账号注销（服务端每小时也会自动执行）
+    PurgeDeletions,
+    /// 数据库迁移（服务端启动时只执行尚未应用的迁移）
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// 列出全部迁移及是否已应用
+    Status,
+    /// 执行尚未应用的迁移
+    Up {
+        /// 最多执行的迁移数，默认全部
+        #[arg(long)]
+        steps: Option<u32>,
+    },
+    /// 回滚最近应用的迁移；数据库中有数据时需要 --allow-data-loss
+    Down {
+        #[arg(long, default_value_t = 1)]
+        steps: u32,
+        #[arg(long)]
+        allow_data_loss: bool,
+    },
+    /// 删除全部表后重新执行所有迁移；数据库中有数据时需要 --allow-data-loss
+    Fresh {
+        #[arg(long)]
+        allow_data_loss: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Level {
+    Admin,
+    Provider,
+    User,
+}
+
+impl From<Level> for UserPermissionLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Admin => UserPermissionLevel::Admin,
+            Level::Provider => UserPermissionLevel::Provider,
+            Level::User => UserPermissionLevel::User,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv().ok();
+    let cli = Cli::parse();
+    if let Err(err) = run(cli).await {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let config = CliConfig::load()?;
+    let db = Database::connect(config.database.connect_options()).await?;
+
+    match cli.command {
+        Command::Promote { open_id, level } => {
+            let user = promote_user(&db, &open_id, level.into()).await?;
+            println!(
+                "{} -> permission {}",
+                user.open_id,
+                user.permission.unwrap_or_default()
+            );
+        }
+        Command::ListAdmins => {
+            for user in list_admins(&db).await? {
+                println!(
+                    "{}\t{}\t{}",
+                    user.open_id,
+                    user.nickname.unwrap_or_default(),
+                    user.name.unwrap_or_default()
+                );
+            }
+        }
+        Command::IssueCode {
+            issuer,
+            level,
+            max_uses,
+            expire_seconds,
+            target,
+        } => {
+            let options = InvitationOptions {
+                apply_type: UserPermissionLevel::from(level).level(),
+                max_uses,
+                expire_seconds,
+                target_open_id: target,
+            };
+            let code = issue_code(&db, &issuer, options).await?;
+            println!(
+                "code {} (id {}, apply_type {}, max_uses {}, expires_at {})",
+                code.code, code.id, code.apply_type, code.max_uses, code.expires_at
+            );
+        }
+        Command::RotateToken { open_id } => {
+            let keys = config.key_ring.as_ref().ok_or(
+                "jwt.secret (SERVER_JWT_SECRET) or jwt.keys (SERVER_JWT_KEYS): is not set",
+            )?;
+            let tokens = rotate_tokens(&db, keys, &open_id).await?;
+            println!("access_token: {}", tokens.access_token);
+            println!("refresh_token: {}", tokens.refresh_token);
+        }
+        Command::SetPassword { open_id, username } => {
+            let mut password = String::new();
+            std::io::stdin().read_line(&mut password)?;
+            let password = password.trim_end_matches(['\r', '\n']);
+            if set_password(&db, &open_id, &username, password).await? {
+                println!("{} -> local account {}", open_id, username);
+            } else {
+                println!("{} -> password reset", open_id);
+            }
+        }
+        Command::PurgeDeletions => {
+            let report = purge_deletions(&db).await?;
+            for (user_id, err) in &report.failed {
+                eprintln!("failed to purge user {}: {:?}", user_id, err);
+            }
+            println!("purged {} accounts", report.purged.len());
+        }
+        Command::Migrate { action } => match action {
+            MigrateAction::Status => {
+                for (name, applied) in migration_status(&db).await? {
+                    println!("{}\t{}", if applied { "applied" } else { "pending" }, name);
+                }
+            }
+            MigrateAction::Up { steps } => {
+                let applied = migrate_up(&db, steps).await?;
+                for name in &applied {
+                    println!("applied {}", name);
+                }
+                println!("{} migrations applied", applied.len());
+            }
+            MigrateAction::Down {
+                steps,
+                allow_data_loss,
+            } => {
+                for name in migrate_down(&db, steps, allow_data_loss).await? {
+                    println!("rolled back {}", name);
+                }
+            }
+            MigrateAction::Fresh { allow_data_loss } => {
+                migrate_fresh(&db, allow_data_loss).await?;
+                println!("database recreated");
+            }
+        },
+    }
+
+    Ok(())
+}
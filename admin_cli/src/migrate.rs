@@ -0,0 +1,71 @@
+use db_manager::migrator::{Migrator, tables_with_data};
+use sea_orm::DatabaseConnection;
+use sea_orm_migration::{MigrationStatus, MigratorTrait};
+
+use crate::CliError;
+
+/// 迁移名称及是否已经应用，按执行顺序排列
+pub async fn migration_status(db: &DatabaseConnection) -> Result<Vec<(String, bool)>, CliError> {
+    Ok(Migrator::get_migration_with_status(db)
+        .await?
+        .iter()
+        .map(|m| (m.name().to_string(), m.status() == MigrationStatus::Applied))
+        .collect())
+}
+
+/// Apply pending migrations (at most `steps` of them) and return their names.
+pub async fn migrate_up(
+    db: &DatabaseConnection,
+    steps: Option<u32>,
+) -> Result<Vec<String>, CliError> {
+    let pending: Vec<String> = Migrator::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|m| m.name().to_string())
+        .take(steps.map_or(usize::MAX, |n| n as usize))
+        .collect();
+    Migrator::up(db, steps).await?;
+    Ok(pending)
+}
+
+/// Roll back the last `steps` applied migrations and return their names, newest first.
+///
+/// Rolling back usually drops tables or columns, so it is refused while the
+/// database holds data unless `allow_data_loss` is set.
+pub async fn migrate_down(
+    db: &DatabaseConnection,
+    steps: u32,
+    allow_data_loss: bool,
+) -> Result<Vec<String>, CliError> {
+    ensure_no_data(db, allow_data_loss).await?;
+    let applied: Vec<String> = Migrator::get_applied_migrations(db)
+        .await?
+        .iter()
+        .rev()
+        .map(|m| m.name().to_string())
+        .take(steps as usize)
+        .collect();
+    Migrator::down(db, Some(steps)).await?;
+    Ok(applied)
+}
+
+/// Drop every table and re-run all migrations.
+///
+/// Refused while the database holds data unless `allow_data_loss` is set.
+pub async fn migrate_fresh(db: &DatabaseConnection, allow_data_loss: bool) -> Result<(), CliError> {
+    ensure_no_data(db, allow_data_loss).await?;
+    Migrator::fresh(db).await?;
+    Ok(())
+}
+
+async fn ensure_no_data(db: &DatabaseConnection, allow_data_loss: bool) -> Result<(), CliError> {
+    if allow_data_loss {
+        return Ok(());
+    }
+    let tables = tables_with_data(db).await?;
+    if tables.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::DataLossRefused(tables))
+    }
+}
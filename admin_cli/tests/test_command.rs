@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use admin_cli::{CliError, issue_code, migrate_fresh, promote_user};
+use db_manager::entity::user as user_entity;
+use sea_orm::{DbBackend, MockDatabase, Value};
+use user_auth::invitation::InvitationOptions;
+use user_auth::user_auth::UserPermissionLevel;
+
+fn user(permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id: 1,
+        open_id: "cli-user".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(permission),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+#[tokio::test]
+async fn promote_sets_permission() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![user(1)]])
+        .append_query_results([vec![user(3)]])
+        .into_connection();
+
+    let promoted = promote_user(&db, "cli-user", UserPermissionLevel::Admin)
+        .await
+        .unwrap();
+    assert_eq!(promoted.permission, Some(3));
+}
+
+#[tokio::test]
+async fn promote_reports_missing_user() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<user_entity::Model>::new()])
+        .into_connection();
+
+    let err = promote_user(&db, "nobody", UserPermissionLevel::Admin)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CliError::UserNotFound(open_id) if open_id == "nobody"));
+}
+
+#[tokio::test]
+async fn issue_code_requires_admin_issuer() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![user(2)]])
+        .into_connection();
+
+    let err = issue_code(&db, "cli-user", InvitationOptions::new(2))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CliError::NotAdmin(_)));
+}
+
+#[tokio::test]
+async fn fresh_refuses_non_empty_database() {
+    let table = |name: &str| BTreeMap::from([("name".to_string(), Value::from(name))]);
+    let found = BTreeMap::from([("found".to_string(), Value::Int(Some(1)))]);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![
+            table("notice"),
+            table("role"),
+            table("seaql_migrations"),
+            table("user"),
+        ]])
+        .append_query_results([Vec::<BTreeMap<String, Value>>::new()])
+        .append_query_results([vec![found]])
+        .into_connection();
+
+    let err = migrate_fresh(&db, false).await.unwrap_err();
+    assert!(matches!(&err, CliError::DataLossRefused(tables) if tables == &["user"]));
+    assert!(err.to_string().contains("--allow-data-loss"));
+
+    // 只检查了 notice 与 user，没有执行任何删除
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 3);
+    assert!(
+        log.iter()
+            .all(|t| t.statements()[0].sql.starts_with("SELECT"))
+    );
+}
@@ -0,0 +1,15 @@
+//! 服务配置
+//!
+//! 配置按以下顺序叠加，后者覆盖前者：
+//! 1. 内置默认值
+//! 2. TOML 配置文件：`SERVER_CONFIG` 指定的路径，未指定时为当前目录下的 `config.toml`（可选）
+//! 3. 环境变量（`SERVER_*`，`.env` 由调用方先行加载）
+//!
+//! 启动时一次性校验全部配置项，所有问题合并到一个 `ConfigError` 中报告。
+//! 各子系统只接收校验后的配置值，不再自行读取环境变量。
+
+pub mod load;
+pub mod raw;
+pub mod r#struct;
+
+pub use r#struct::*;
@@ -0,0 +1,318 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::time::Duration;
+
+use db_manager::DatabaseConfig;
+use db_manager::config::{
+    DEFAULT_ACQUIRE_TIMEOUT_SECS, DEFAULT_MAX_CONNECTIONS, DEFAULT_MIN_CONNECTIONS,
+};
+use user_auth::db_exchange::{ExchangeError, KeyRing, SigningKey};
+use user_auth::local_auth::console_cookie_key;
+use user_auth::totp::TotpPolicy;
+use user_auth::wx_auth::{DEFAULT_WX_BASE_URL, WxAuthServerConfig};
+
+use crate::raw::{RawConfig, RawDatabase, RawJwt, jwt_key_env};
+use crate::{
+    AppConfig, AuthConfig, CONFIG_PATH_ENV, CliConfig, ConfigError, ConfigFile,
+    DEFAULT_BIND_ADDRESS, DEFAULT_CONFIG_PATH, DEFAULT_LOG_LEVEL, LogConfig, ServerConfig,
+};
+
+/// 数据库连接串支持的协议
+const DATABASE_SCHEMES: [&str; 4] = ["postgres://", "postgresql://", "mysql://", "sqlite:"];
+
+impl ConfigFile {
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref().to_path_buf();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Ok(ConfigFile { path, contents }),
+            Err(source) => Err(ConfigError::Io { path, source }),
+        }
+    }
+
+    /// 定位配置文件：`SERVER_CONFIG` 指定的文件必须存在，默认的 `config.toml` 不存在时跳过
+    pub fn locate<E>(env: E) -> Result<Option<Self>, ConfigError>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        match env(CONFIG_PATH_ENV).filter(|v| !v.trim().is_empty()) {
+            Some(path) => ConfigFile::read(path.trim()).map(Some),
+            None if Path::new(DEFAULT_CONFIG_PATH).is_file() => {
+                ConfigFile::read(DEFAULT_CONFIG_PATH).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// 读取文件并叠加环境变量，返回叠加后的配置与已经发现的问题
+fn merge<E>(file: Option<&ConfigFile>, env: E) -> Result<(RawConfig, Vec<String>), ConfigError>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    let mut raw = RawConfig::parse(file)?;
+    let mut problems = Vec::new();
+    raw.overlay_env(env, &mut problems);
+    Ok((raw, problems))
+}
+
+fn finish<T>(value: Option<T>, problems: Vec<String>) -> Result<T, ConfigError> {
+    match value {
+        Some(value) if problems.is_empty() => Ok(value),
+        _ => Err(ConfigError::Invalid(problems)),
+    }
+}
+
+impl AppConfig {
+    /// 从配置文件与进程环境变量读取服务端配置
+    pub fn load() -> Result<Self, ConfigError> {
+        let file = ConfigFile::locate(process_env)?;
+        AppConfig::from_sources(file.as_ref(), process_env)
+    }
+
+    /// 从给定的文件内容与环境变量读取，`env` 返回 `None` 表示未设置
+    pub fn from_sources<E>(file: Option<&ConfigFile>, env: E) -> Result<Self, ConfigError>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let (raw, mut problems) = merge(file, env)?;
+        let server = validate_server(&raw, &mut problems);
+        let log = validate_log(&raw, &mut problems);
+        let database = validate_database(&raw.database, &mut problems);
+        let wx = validate_wx(&raw, &mut problems);
+        let auth = validate_auth(&raw, &mut problems);
+
+        let config = match (server, log, database, wx, auth) {
+            (Some(server), Some(log), Some(database), Some(wx), Some(auth)) => Some(AppConfig {
+                server,
+                log,
+                database,
+                wx,
+                auth,
+            }),
+            _ => None,
+        };
+        finish(config, problems)
+    }
+}
+
+impl CliConfig {
+    /// 从配置文件与进程环境变量读取命令行配置
+    pub fn load() -> Result<Self, ConfigError> {
+        let file = ConfigFile::locate(process_env)?;
+        CliConfig::from_sources(file.as_ref(), process_env)
+    }
+
+    pub fn from_sources<E>(file: Option<&ConfigFile>, env: E) -> Result<Self, ConfigError>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let (raw, mut problems) = merge(file, env)?;
+        let database = validate_database(&raw.database, &mut problems);
+        let key_ring = match raw.jwt.secret.is_some() || !raw.jwt.keys.is_empty() {
+            true => validate_key_ring(&raw.jwt, &mut problems),
+            false => None,
+        };
+        let config = database.map(|database| CliConfig { database, key_ring });
+        finish(config, problems)
+    }
+}
+
+fn validate_server(raw: &RawConfig, problems: &mut Vec<String>) -> Option<ServerConfig> {
+    let value = raw
+        .server
+        .bind_address
+        .as_deref()
+        .unwrap_or(DEFAULT_BIND_ADDRESS);
+    let bind_address = match value.trim().parse::<SocketAddr>() {
+        Ok(bind_address) => Some(bind_address),
+        Err(_) => {
+            problems.push(format!(
+                "server.bind_address (SERVER_BIND_ADDRESS): expected ip:port, got {:?}",
+                value
+            ));
+            None
+        }
+    };
+    let mut trusted_proxies = Vec::with_capacity(raw.server.trusted_proxies.len());
+    for value in &raw.server.trusted_proxies {
+        match value.trim().parse::<IpAddr>() {
+            Ok(ip) => trusted_proxies.push(ip),
+            Err(_) => problems.push(format!(
+                "server.trusted_proxies (SERVER_TRUSTED_PROXIES): expected ip address, got {:?}",
+                value
+            )),
+        }
+    }
+    if trusted_proxies.len() != raw.server.trusted_proxies.len() {
+        return None;
+    }
+    Some(ServerConfig {
+        bind_address: bind_address?,
+        trusted_proxies,
+    })
+}
+
+fn validate_log(raw: &RawConfig, problems: &mut Vec<String>) -> Option<LogConfig> {
+    let value = raw.log.level.as_deref().unwrap_or(DEFAULT_LOG_LEVEL);
+    match value.trim().parse::<tracing::Level>() {
+        Ok(level) => Some(LogConfig { level }),
+        Err(_) => {
+            problems.push(format!(
+                "log.level (SERVER_LOG_LEVEL): expected one of trace, debug, info, warn, error, got {:?}",
+                value
+            ));
+            None
+        }
+    }
+}
+
+fn validate_database(raw: &RawDatabase, problems: &mut Vec<String>) -> Option<DatabaseConfig> {
+    let max = raw.max_connections.unwrap_or(DEFAULT_MAX_CONNECTIONS);
+    let min = raw.min_connections.unwrap_or(DEFAULT_MIN_CONNECTIONS);
+    let timeout = raw
+        .acquire_timeout_secs
+        .unwrap_or(DEFAULT_ACQUIRE_TIMEOUT_SECS);
+    let before = problems.len();
+    if max == 0 {
+        problems.push(
+            "database.max_connections (SERVER_DB_MAX_CONNECTIONS): must be at least 1".to_string(),
+        );
+    }
+    if min > max {
+        problems.push(format!(
+            "database.min_connections (SERVER_DB_MIN_CONNECTIONS): {} is greater than max_connections {}",
+            min, max
+        ));
+    }
+    if timeout == 0 {
+        problems.push(
+            "database.acquire_timeout_secs (SERVER_DB_ACQUIRE_TIMEOUT_SECS): must be at least 1"
+                .to_string(),
+        );
+    }
+    let uri = match raw.uri.as_deref().map(str::trim) {
+        None => {
+            problems.push("database.uri (SERVER_DB_URI): is not set".to_string());
+            None
+        }
+        Some(uri) if !DATABASE_SCHEMES.iter().any(|s| uri.starts_with(s)) => {
+            problems.push(format!(
+                "database.uri (SERVER_DB_URI): unsupported scheme, expected one of {}",
+                DATABASE_SCHEMES.join(" ")
+            ));
+            None
+        }
+        Some(uri) => Some(uri.to_string()),
+    };
+    if problems.len() > before {
+        return None;
+    }
+
+    let mut config = DatabaseConfig::new(uri?);
+    config.max_connections = max;
+    config.min_connections = min;
+    config.acquire_timeout = Duration::from_secs(timeout);
+    Some(config)
+}
+
+fn validate_wx(raw: &RawConfig, problems: &mut Vec<String>) -> Option<WxAuthServerConfig> {
+    let mut required = |value: &Option<String>, name: &str| match value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(value) => Some(value.to_string()),
+        None => {
+            problems.push(format!("{}: is not set", name));
+            None
+        }
+    };
+    let appid = required(&raw.wx.appid, "wx.appid (SERVER_WX_APPID)");
+    let secret = required(&raw.wx.secret, "wx.secret (SERVER_WX_SECRET)");
+    let base_url = raw
+        .wx
+        .base_url
+        .as_deref()
+        .unwrap_or(DEFAULT_WX_BASE_URL)
+        .trim()
+        .trim_end_matches('/')
+        .to_string();
+    if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+        problems.push(format!(
+            "wx.base_url (SERVER_WX_BASEURL): expected an http(s) URL, got {:?}",
+            base_url
+        ));
+        return None;
+    }
+    Some(WxAuthServerConfig::new(appid?, secret?, base_url))
+}
+
+fn validate_key_ring(raw: &RawJwt, problems: &mut Vec<String>) -> Option<KeyRing> {
+    let describe = |err: ExchangeError| match err {
+        ExchangeError::OtherError(message) => message,
+        other => format!("{:?}", other),
+    };
+    if raw.keys.is_empty() {
+        return match raw.secret.as_deref() {
+            None => {
+                problems.push(
+                    "jwt.secret (SERVER_JWT_SECRET) or jwt.keys (SERVER_JWT_KEYS): is not set"
+                        .to_string(),
+                );
+                None
+            }
+            Some(secret) => KeyRing::single(secret)
+                .map_err(|e| problems.push(format!("jwt.secret: {}", describe(e))))
+                .ok(),
+        };
+    }
+
+    let mut keys = Vec::with_capacity(raw.keys.len());
+    for key in &raw.keys {
+        match &key.secret {
+            Some(secret) => keys.push(SigningKey::new(
+                key.kid.clone(),
+                secret.as_str(),
+                key.retire_at,
+            )),
+            None => problems.push(format!(
+                "jwt.keys[{}].secret ({}): is not set",
+                key.kid,
+                jwt_key_env(&key.kid)
+            )),
+        }
+    }
+    if keys.len() < raw.keys.len() {
+        return None;
+    }
+    KeyRing::new(raw.keys[0].kid.clone(), keys)
+        .map_err(|e| problems.push(format!("jwt.keys: {}", describe(e))))
+        .ok()
+}
+
+fn validate_auth(raw: &RawConfig, problems: &mut Vec<String>) -> Option<AuthConfig> {
+    let admin_totp_policy = match raw.auth.admin_totp_policy.as_deref() {
+        None => Some(TotpPolicy::default()),
+        Some(value) => {
+            let policy = TotpPolicy::parse(value);
+            if policy.is_none() {
+                problems.push(format!(
+                    "auth.admin_totp_policy (SERVER_ADMIN_TOTP_POLICY): expected one of off, enrolled, required, got {:?}",
+                    value
+                ));
+            }
+            policy
+        }
+    };
+    let key_ring = validate_key_ring(&raw.jwt, problems)?;
+    let cookie_key = console_cookie_key(raw.auth.cookie_secret.as_deref(), &key_ring);
+    Some(AuthConfig {
+        key_ring,
+        cookie_key,
+        admin_totp_policy: admin_totp_policy?,
+    })
+}
@@ -0,0 +1,197 @@
+//! 未校验的配置：TOML 文件反序列化后再叠加环境变量
+
+use serde::Deserialize;
+
+use crate::ConfigError;
+use crate::ConfigFile;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawConfig {
+    pub server: RawServer,
+    pub log: RawLog,
+    pub database: RawDatabase,
+    pub wx: RawWx,
+    pub jwt: RawJwt,
+    pub auth: RawAuth,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawServer {
+    pub bind_address: Option<String>,
+    pub trusted_proxies: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawLog {
+    pub level: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawDatabase {
+    pub uri: Option<String>,
+    pub max_connections: Option<u32>,
+    pub min_connections: Option<u32>,
+    pub acquire_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawWx {
+    pub appid: Option<String>,
+    pub secret: Option<String>,
+    pub base_url: Option<String>,
+}
+
+/// `secret` 为单个密钥；`keys` 为命名密钥列表，第一个为当前签名密钥，设置后忽略 `secret`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawJwt {
+    pub secret: Option<String>,
+    pub keys: Vec<RawJwtKey>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawJwtKey {
+    pub kid: String,
+    pub secret: Option<String>,
+    /// unix 秒
+    pub retire_at: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawAuth {
+    pub cookie_secret: Option<String>,
+    pub admin_totp_policy: Option<String>,
+}
+
+/// 命名密钥 `kid` 对应的环境变量：kid 转大写，`-` / `.` 替换为 `_`
+pub fn jwt_key_env(kid: &str) -> String {
+    format!(
+        "SERVER_JWT_KEY_{}",
+        kid.to_ascii_uppercase().replace(['-', '.'], "_")
+    )
+}
+
+impl RawConfig {
+    /// 解析配置文件，未提供文件时全部使用默认值
+    pub fn parse(file: Option<&ConfigFile>) -> Result<Self, ConfigError> {
+        match file {
+            Some(file) => toml::from_str(&file.contents).map_err(|e| ConfigError::Parse {
+                path: file.path.clone(),
+                message: e.to_string(),
+            }),
+            None => Ok(RawConfig::default()),
+        }
+    }
+
+    /// 用环境变量覆盖文件中的配置，空值视为未设置；无法解析的值记录到 `problems`
+    pub fn overlay_env<E>(&mut self, env: E, problems: &mut Vec<String>)
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let env = |name: &str| env(name).filter(|v| !v.trim().is_empty());
+
+        let strings: [(&str, &mut Option<String>); 9] = [
+            ("SERVER_BIND_ADDRESS", &mut self.server.bind_address),
+            ("SERVER_LOG_LEVEL", &mut self.log.level),
+            ("SERVER_DB_URI", &mut self.database.uri),
+            ("SERVER_WX_APPID", &mut self.wx.appid),
+            ("SERVER_WX_SECRET", &mut self.wx.secret),
+            ("SERVER_WX_BASEURL", &mut self.wx.base_url),
+            ("SERVER_JWT_SECRET", &mut self.jwt.secret),
+            ("SERVER_COOKIE_SECRET", &mut self.auth.cookie_secret),
+            ("SERVER_ADMIN_TOTP_POLICY", &mut self.auth.admin_totp_policy),
+        ];
+        for (name, field) in strings {
+            if let Some(value) = env(name) {
+                *field = Some(value);
+            }
+        }
+
+        let connections: [(&str, &mut Option<u32>); 2] = [
+            (
+                "SERVER_DB_MAX_CONNECTIONS",
+                &mut self.database.max_connections,
+            ),
+            (
+                "SERVER_DB_MIN_CONNECTIONS",
+                &mut self.database.min_connections,
+            ),
+        ];
+        for (name, field) in connections {
+            if let Some(n) = parse_env(&env, name, problems) {
+                *field = Some(n);
+            }
+        }
+        if let Some(n) = parse_env(&env, "SERVER_DB_ACQUIRE_TIMEOUT_SECS", problems) {
+            self.database.acquire_timeout_secs = Some(n);
+        }
+
+        // 逗号分隔，整体替换文件中的列表
+        if let Some(list) = env("SERVER_TRUSTED_PROXIES") {
+            self.server.trusted_proxies = list
+                .split(',')
+                .map(str::trim)
+                .filter(|ip| !ip.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        // SERVER_JWT_KEYS 整体替换文件中的密钥列表，列表中的密钥可以沿用文件中同名密钥的配置
+        if let Some(list) = env("SERVER_JWT_KEYS") {
+            let file_keys = std::mem::take(&mut self.jwt.keys);
+            self.jwt.keys = list
+                .split(',')
+                .map(str::trim)
+                .filter(|kid| !kid.is_empty())
+                .map(|kid| {
+                    file_keys
+                        .iter()
+                        .find(|k| k.kid == kid)
+                        .cloned()
+                        .unwrap_or_else(|| RawJwtKey {
+                            kid: kid.to_string(),
+                            ..Default::default()
+                        })
+                })
+                .collect();
+            if self.jwt.keys.is_empty() {
+                problems.push("SERVER_JWT_KEYS: no key id listed".to_string());
+            }
+        }
+        for key in &mut self.jwt.keys {
+            let var = jwt_key_env(&key.kid);
+            if let Some(secret) = env(&var) {
+                key.secret = Some(secret);
+            }
+            if let Some(at) = parse_env(&env, &format!("{}_RETIRE_AT", var), problems) {
+                key.retire_at = Some(at);
+            }
+        }
+    }
+}
+
+/// 读取数字类型的环境变量
+fn parse_env<E, T>(env: &E, name: &str, problems: &mut Vec<String>) -> Option<T>
+where
+    E: Fn(&str) -> Option<String>,
+    T: std::str::FromStr,
+{
+    let value = env(name)?;
+    match value.trim().parse::<T>() {
+        Ok(n) => Some(n),
+        Err(_) => {
+            problems.push(format!(
+                "{}: expected a non-negative integer, got {:?}",
+                name, value
+            ));
+            None
+        }
+    }
+}
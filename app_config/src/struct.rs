@@ -0,0 +1,116 @@
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use db_manager::DatabaseConfig;
+use user_auth::db_exchange::KeyRing;
+use user_auth::totp::TotpPolicy;
+use user_auth::wx_auth::WxAuthServerConfig;
+
+/// 指定配置文件路径的环境变量
+pub const CONFIG_PATH_ENV: &str = "SERVER_CONFIG";
+/// 未指定路径时尝试读取的配置文件
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+/// 默认监听地址
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:3001";
+/// 默认日志级别
+pub const DEFAULT_LOG_LEVEL: &str = "debug";
+
+/// 服务端的完整配置
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub log: LogConfig,
+    pub database: DatabaseConfig,
+    pub wx: WxAuthServerConfig,
+    pub auth: AuthConfig,
+}
+
+/// 管理命令行使用的配置，只要求数据库；未配置 JWT 密钥时无法签发 token
+#[derive(Debug, Clone)]
+pub struct CliConfig {
+    pub database: DatabaseConfig,
+    pub key_ring: Option<KeyRing>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// `server.bind_address` / `SERVER_BIND_ADDRESS`
+    pub bind_address: SocketAddr,
+    /// `server.trusted_proxies` / `SERVER_TRUSTED_PROXIES`：反向代理的地址，
+    /// 只有来自这些地址的请求才采用 `X-Forwarded-For` / `X-Real-IP` 中的客户端 IP
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    /// `log.level` / `SERVER_LOG_LEVEL`：trace、debug、info、warn、error
+    pub level: tracing::Level,
+}
+
+/// 鉴权相关的配置
+#[derive(Clone)]
+pub struct AuthConfig {
+    pub key_ring: KeyRing,
+    /// 后台会话 cookie 的加密密钥，见 `user_auth::local_auth::console_cookie_key`
+    pub cookie_key: [u8; 64],
+    pub admin_totp_policy: TotpPolicy,
+}
+
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("key_ring", &self.key_ring)
+            .field("admin_totp_policy", &self.admin_totp_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 读取到的配置文件
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// 配置文件无法读取
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// 配置文件不是合法的 TOML，或包含未知的配置项
+    Parse { path: PathBuf, message: String },
+    /// 配置项缺失或取值不合法，包含发现的全部问题
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "cannot read config file {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "invalid config file {}: {}", path.display(), message)
+            }
+            ConfigError::Invalid(problems) => {
+                write!(f, "invalid configuration:")?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
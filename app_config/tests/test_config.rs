@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use app_config::{AppConfig, CliConfig, ConfigError, ConfigFile};
+use user_auth::totp::TotpPolicy;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+fn file(contents: &str) -> ConfigFile {
+    ConfigFile {
+        path: PathBuf::from("config.toml"),
+        contents: contents.to_string(),
+    }
+}
+
+const REQUIRED: [(&str, &str); 4] = [
+    (
+        "SERVER_DB_URI",
+        "postgres://postgres@localhost:5432/postgres",
+    ),
+    ("SERVER_WX_APPID", "wx123456"),
+    ("SERVER_WX_SECRET", "abcdef"),
+    ("SERVER_JWT_SECRET", "test-secret"),
+];
+
+fn problems(err: ConfigError) -> Vec<String> {
+    match err {
+        ConfigError::Invalid(problems) => problems,
+        other => panic!("unexpected error: {}", other),
+    }
+}
+
+#[test]
+fn env_only_uses_defaults() {
+    let config = AppConfig::from_sources(None, env(&REQUIRED)).unwrap();
+
+    assert_eq!(config.server.bind_address.to_string(), "0.0.0.0:3001");
+    assert!(config.server.trusted_proxies.is_empty());
+    assert_eq!(config.log.level, tracing::Level::DEBUG);
+    assert_eq!(config.database.max_connections, 10);
+    assert_eq!(config.database.min_connections, 1);
+    assert_eq!(config.wx.base_url, "https://api.weixin.qq.com");
+    assert_eq!(config.auth.key_ring.active_key().kid, "default");
+    assert_eq!(config.auth.admin_totp_policy, TotpPolicy::Enrolled);
+}
+
+#[test]
+fn env_overrides_file() {
+    let toml = file(
+        r#"
+        [server]
+        bind_address = "127.0.0.1:8080"
+        trusted_proxies = ["10.0.0.1"]
+
+        [log]
+        level = "info"
+
+        [database]
+        uri = "postgres://file@db/sd"
+        max_connections = 20
+        acquire_timeout_secs = 5
+
+        [wx]
+        base_url = "http://localhost:5000/"
+
+        [auth]
+        admin_totp_policy = "required"
+        "#,
+    );
+    let mut vars = REQUIRED.to_vec();
+    vars.retain(|(k, _)| *k != "SERVER_DB_URI");
+    vars.push(("SERVER_DB_MAX_CONNECTIONS", "5"));
+    vars.push(("SERVER_LOG_LEVEL", ""));
+    vars.push(("SERVER_TRUSTED_PROXIES", "10.0.0.2, ::1"));
+
+    let config = AppConfig::from_sources(Some(&toml), env(&vars)).unwrap();
+    assert_eq!(config.server.bind_address.to_string(), "127.0.0.1:8080");
+    assert_eq!(
+        config.server.trusted_proxies,
+        [
+            "10.0.0.2".parse::<IpAddr>().unwrap(),
+            "::1".parse().unwrap()
+        ]
+    );
+    // 空的环境变量视为未设置
+    assert_eq!(config.log.level, tracing::Level::INFO);
+    assert_eq!(config.database.uri(), "postgres://file@db/sd");
+    assert_eq!(config.database.max_connections, 5);
+    assert_eq!(config.database.acquire_timeout, Duration::from_secs(5));
+    assert_eq!(config.wx.base_url, "http://localhost:5000");
+    assert_eq!(config.auth.admin_totp_policy, TotpPolicy::Required);
+}
+
+#[test]
+fn jwt_keys_from_file_and_env() {
+    let toml = file(
+        r#"
+        [[jwt.keys]]
+        kid = "2026a"
+        secret = "from-file"
+        retire_at = 4102444800
+        "#,
+    );
+    let mut vars = REQUIRED.to_vec();
+    vars.push(("SERVER_JWT_KEYS", "2026b,2026a"));
+    vars.push(("SERVER_JWT_KEY_2026B", "from-env"));
+
+    let config = AppConfig::from_sources(Some(&toml), env(&vars)).unwrap();
+    let ring = &config.auth.key_ring;
+    assert_eq!(ring.active_key().kid, "2026b");
+    assert_eq!(ring.keys().len(), 2);
+    assert_eq!(ring.keys()[1].kid, "2026a");
+    assert_eq!(ring.keys()[1].retire_at, Some(4102444800));
+
+    // 列表中的密钥没有配置密钥时指明对应的环境变量
+    let vars = [
+        ("SERVER_DB_URI", "postgres://localhost/sd"),
+        ("SERVER_JWT_KEYS", "k9"),
+    ];
+    let err = CliConfig::from_sources(None, env(&vars)).unwrap_err();
+    assert_eq!(
+        problems(err),
+        vec!["jwt.keys[k9].secret (SERVER_JWT_KEY_K9): is not set"]
+    );
+}
+
+#[test]
+fn reports_every_problem() {
+    let vars = [
+        ("SERVER_BIND_ADDRESS", "localhost"),
+        ("SERVER_TRUSTED_PROXIES", "proxy.local"),
+        ("SERVER_LOG_LEVEL", "verbose"),
+        ("SERVER_DB_MAX_CONNECTIONS", "2"),
+        ("SERVER_DB_MIN_CONNECTIONS", "4"),
+        ("SERVER_WX_SECRET", "abcdef"),
+        ("SERVER_ADMIN_TOTP_POLICY", "sometimes"),
+    ];
+    let err = AppConfig::from_sources(None, env(&vars)).unwrap_err();
+    let message = err.to_string();
+    let problems = problems(err);
+
+    assert_eq!(problems.len(), 8, "{:?}", problems);
+    for key in [
+        "server.bind_address",
+        "server.trusted_proxies",
+        "log.level",
+        "database.min_connections",
+        "database.uri",
+        "wx.appid",
+        "jwt.secret",
+        "auth.admin_totp_policy",
+    ] {
+        assert!(message.contains(key), "missing {} in {}", key, message);
+    }
+
+    let err =
+        AppConfig::from_sources(None, env(&[("SERVER_DB_MAX_CONNECTIONS", "ten")])).unwrap_err();
+    assert!(err.to_string().contains("SERVER_DB_MAX_CONNECTIONS"));
+}
+
+#[test]
+fn rejects_unknown_keys_in_file() {
+    let toml = file(
+        r#"
+        [database]
+        url = "postgres://localhost/sd"
+        "#,
+    );
+    let err = AppConfig::from_sources(Some(&toml), env(&REQUIRED)).unwrap_err();
+    assert!(matches!(err, ConfigError::Parse { .. }));
+    assert!(err.to_string().contains("url"));
+}
+
+#[test]
+fn cli_only_requires_database() {
+    let vars = [("SERVER_DB_URI", "postgres://localhost/sd")];
+    let config = CliConfig::from_sources(None, env(&vars)).unwrap();
+    assert!(config.key_ring.is_none());
+
+    let err = CliConfig::from_sources(None, env(&[])).unwrap_err();
+    assert_eq!(
+        problems(err),
+        vec!["database.uri (SERVER_DB_URI): is not set"]
+    );
+}
@@ -1,20 +1,45 @@
-use std::env;
+use std::time::Duration;
 
+use sea_orm::ConnectOptions;
+
+/// 连接池默认最大连接数
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+/// 连接池默认最小连接数
+pub const DEFAULT_MIN_CONNECTIONS: u32 = 1;
+/// 默认获取连接的超时时间（秒）
+pub const DEFAULT_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DatabaseConfig {
     uri: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
 }
 
 impl DatabaseConfig {
     pub fn new(uri: String) -> Self {
-        Self { uri }
-    }
-
-    pub fn from_env() -> Self {
-        let uri = env::var("SERVER_DB_URI").expect("SERVER_DB_URI must be set");
-        Self::new(uri)
+        Self {
+            uri,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            min_connections: DEFAULT_MIN_CONNECTIONS,
+            acquire_timeout: Duration::from_secs(DEFAULT_ACQUIRE_TIMEOUT_SECS),
+        }
     }
 
     pub fn uri(&self) -> String {
         self.uri.clone()
     }
+
+    /// 按配置生成连接选项，sqlx 自身的语句日志关闭，只保留慢查询等警告
+    pub fn connect_options(&self) -> ConnectOptions {
+        let mut options = ConnectOptions::new(&self.uri);
+        options
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .sqlx_logging(false)
+            .sqlx_logging_level(log::LevelFilter::Warn);
+        options
+    }
 }
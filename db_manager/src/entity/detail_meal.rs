@@ -11,9 +11,25 @@ pub struct Model {
     pub date_time: Option<String>,
     pub meal_info: Option<Json>,
     pub belong_to: Option<String>,
+    pub dinner_provider_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::dinner_provider::Entity",
+        from = "Column::DinnerProviderId",
+        to = "super::dinner_provider::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    DinnerProvider,
+}
+
+impl Related<super::dinner_provider::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DinnerProvider.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
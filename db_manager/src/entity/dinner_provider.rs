@@ -21,6 +21,23 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::detail_meal::Entity")]
+    DetailMeal,
+    #[sea_orm(has_many = "super::dinner_provider_owner::Entity")]
+    DinnerProviderOwner,
+}
+
+impl Related<super::detail_meal::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DetailMeal.def()
+    }
+}
+
+impl Related<super::dinner_provider_owner::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DinnerProviderOwner.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -10,6 +10,7 @@ pub struct Model {
     pub r#type: Option<String>,
     pub content: Option<String>,
     pub phone: Option<String>,
+    pub openid: Option<String>,
     pub created_time: DateTimeWithTimeZone,
 }
 
@@ -2,20 +2,37 @@
 
 pub mod prelude;
 
+pub mod account_deletion;
+pub mod admin_totp;
 pub mod ai_chat;
+pub mod audit_log;
+pub mod audit_subject;
+pub mod caregiver_action_log;
+pub mod caregiver_link;
 pub mod community_service;
 pub mod detail_meal;
 pub mod dinner_provider;
+pub mod dinner_provider_owner;
 pub mod feedback;
 pub mod health_guide_content;
 pub mod health_guide_type;
+pub mod invitation_code;
+pub mod invitation_redemption;
+pub mod local_credential;
 pub mod medical_service;
 pub mod mutil_media;
 pub mod notice;
 pub mod policy_file;
 pub mod policy_type;
+pub mod refresh_token;
 pub mod resource_service;
+pub mod role;
+pub mod role_permission;
 pub mod service_map_content;
 pub mod service_map_type;
 pub mod slideshow;
+pub mod totp_recovery_code;
 pub mod user;
+pub mod user_role;
+pub mod user_status_log;
+pub mod wx_session;
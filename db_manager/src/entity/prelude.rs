@@ -1,19 +1,36 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+pub use super::account_deletion::Entity as AccountDeletion;
+pub use super::admin_totp::Entity as AdminTotp;
 pub use super::ai_chat::Entity as AiChat;
+pub use super::audit_log::Entity as AuditLog;
+pub use super::audit_subject::Entity as AuditSubject;
+pub use super::caregiver_action_log::Entity as CaregiverActionLog;
+pub use super::caregiver_link::Entity as CaregiverLink;
 pub use super::community_service::Entity as CommunityService;
 pub use super::detail_meal::Entity as DetailMeal;
 pub use super::dinner_provider::Entity as DinnerProvider;
+pub use super::dinner_provider_owner::Entity as DinnerProviderOwner;
 pub use super::feedback::Entity as Feedback;
 pub use super::health_guide_content::Entity as HealthGuideContent;
 pub use super::health_guide_type::Entity as HealthGuideType;
+pub use super::invitation_code::Entity as InvitationCode;
+pub use super::invitation_redemption::Entity as InvitationRedemption;
+pub use super::local_credential::Entity as LocalCredential;
 pub use super::medical_service::Entity as MedicalService;
 pub use super::mutil_media::Entity as MutilMedia;
 pub use super::notice::Entity as Notice;
 pub use super::policy_file::Entity as PolicyFile;
 pub use super::policy_type::Entity as PolicyType;
+pub use super::refresh_token::Entity as RefreshToken;
 pub use super::resource_service::Entity as ResourceService;
+pub use super::role::Entity as Role;
+pub use super::role_permission::Entity as RolePermission;
 pub use super::service_map_content::Entity as ServiceMapContent;
 pub use super::service_map_type::Entity as ServiceMapType;
 pub use super::slideshow::Entity as Slideshow;
+pub use super::totp_recovery_code::Entity as TotpRecoveryCode;
 pub use super::user::Entity as User;
+pub use super::user_role::Entity as UserRole;
+pub use super::user_status_log::Entity as UserStatusLog;
+pub use super::wx_session::Entity as WxSession;
@@ -16,9 +16,22 @@ pub struct Model {
     pub phone_number: Option<String>,
     pub address: Option<String>,
     pub is_important: Option<bool>,
+    pub token_version: i32,
+    pub status: String,
+    pub suspended_reason: Option<String>,
+    pub suspended_until: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::refresh_token::Entity")]
+    RefreshToken,
+}
+
+impl Related<super::refresh_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RefreshToken.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,47 @@
+//! 基线迁移：改为增量迁移之前已经发布的全部表结构
+//!
+//! 这些迁移已经冻结，名称（文件名）与内容都不要再修改，否则已经部署的数据库会与迁移记录不一致。
+//! 新增的表与字段都写成 `m<年月日>_<序号>_<说明>` 迁移，见 `migrator/mod.rs`。
+
+use sea_orm_migration::prelude::*;
+
+pub mod ai_chat;
+pub mod community_service;
+pub mod detail_meal;
+pub mod dinner_provider;
+pub mod feedback;
+pub mod health_guide_content;
+pub mod health_guide_type;
+pub mod medical_service;
+pub mod mutil_media;
+pub mod notice;
+pub mod policy_file;
+pub mod policy_type;
+pub mod resource_service;
+pub mod service_map_content;
+pub mod service_map_type;
+pub mod slideshow;
+pub mod user;
+
+/// 按依赖顺序排列的基线迁移
+pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+    vec![
+        Box::new(community_service::Migration),
+        Box::new(dinner_provider::Migration),
+        Box::new(medical_service::Migration),
+        Box::new(notice::Migration),
+        Box::new(policy_file::Migration),
+        Box::new(policy_type::Migration),
+        Box::new(resource_service::Migration),
+        Box::new(slideshow::Migration),
+        Box::new(detail_meal::Migration),
+        Box::new(health_guide_type::Migration),
+        Box::new(health_guide_content::Migration),
+        Box::new(service_map_type::Migration),
+        Box::new(service_map_content::Migration),
+        Box::new(feedback::Migration),
+        Box::new(user::Migration),
+        Box::new(ai_chat::Migration),
+        Box::new(mutil_media::Migration),
+    ]
+}
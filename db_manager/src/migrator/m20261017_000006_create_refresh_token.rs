@@ -0,0 +1,85 @@
+//! 新增 refresh_token：刷新令牌，只保存哈希
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RefreshToken::Table)
+                    .col(
+                        ColumnDef::new(RefreshToken::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(RefreshToken::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(RefreshToken::TokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(RefreshToken::FamilyId).string().not_null())
+                    .col(
+                        ColumnDef::new(RefreshToken::ExpiresAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RefreshToken::RevokedAt).big_integer())
+                    .col(ColumnDef::new(RefreshToken::ReplacedBy).integer())
+                    .col(
+                        ColumnDef::new(RefreshToken::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_refresh_token_user")
+                            .from(RefreshToken::Table, RefreshToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RefreshToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum RefreshToken {
+    Table,
+    Id,
+    UserId,
+    TokenHash,  // refresh token 的 SHA-256 摘要，明文不落库
+    FamilyId,   // 同一次登录轮换出的 token 共享 family，用于重放检测时整体吊销
+    ExpiresAt,  // unix 秒
+    RevokedAt,  // 轮换、注销或吊销的时间，非空即失效
+    ReplacedBy, // 轮换后的新 token id
+    CreatedAt,
+}
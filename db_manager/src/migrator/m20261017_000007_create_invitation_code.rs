@@ -0,0 +1,114 @@
+//! 新增 invitation_code：邀请码
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InvitationCode::Table)
+                    .col(
+                        ColumnDef::new(InvitationCode::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationCode::Code)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationCode::ApplyType)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationCode::IssuedBy)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InvitationCode::TargetUserId).integer())
+                    .col(
+                        ColumnDef::new(InvitationCode::MaxUses)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationCode::UsedCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationCode::ExpiresAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InvitationCode::RevokedAt).big_integer())
+                    .col(
+                        ColumnDef::new(InvitationCode::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invitation_code_issued_by")
+                            .from(InvitationCode::Table, InvitationCode::IssuedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invitation_code_target_user")
+                            .from(InvitationCode::Table, InvitationCode::TargetUserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InvitationCode::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum InvitationCode {
+    Table,
+    Id,
+    Code,
+    ApplyType,    // 兑换后授予的权限：2 或 3
+    IssuedBy,     // 签发的管理员 user.id
+    TargetUserId, // 非空时仅该用户可兑换
+    MaxUses,
+    UsedCount,
+    ExpiresAt, // unix 秒
+    RevokedAt, // 非空即已吊销
+    CreatedAt,
+}
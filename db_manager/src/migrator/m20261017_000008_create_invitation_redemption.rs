@@ -0,0 +1,89 @@
+//! 新增 invitation_redemption：邀请码的使用记录
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+use super::m20261017_000007_create_invitation_code::InvitationCode;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InvitationRedemption::Table)
+                    .col(
+                        ColumnDef::new(InvitationRedemption::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationRedemption::InvitationId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InvitationRedemption::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InvitationRedemption::PreviousPermission).integer())
+                    .col(
+                        ColumnDef::new(InvitationRedemption::RedeemedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invitation_redemption_invitation")
+                            .from(
+                                InvitationRedemption::Table,
+                                InvitationRedemption::InvitationId,
+                            )
+                            .to(InvitationCode::Table, InvitationCode::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invitation_redemption_user")
+                            .from(InvitationRedemption::Table, InvitationRedemption::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InvitationRedemption::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum InvitationRedemption {
+    Table,
+    Id,
+    InvitationId,
+    UserId,             // 兑换人 user.id
+    PreviousPermission, // 兑换前的权限，便于追溯
+    RedeemedAt,         // unix 秒
+}
@@ -0,0 +1,87 @@
+//! 新增 user_status_log：账号状态变更记录
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserStatusLog::Table)
+                    .col(
+                        ColumnDef::new(UserStatusLog::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(UserStatusLog::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserStatusLog::OperatorId).integer())
+                    .col(
+                        ColumnDef::new(UserStatusLog::FromStatus)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserStatusLog::ToStatus).string().not_null())
+                    .col(ColumnDef::new(UserStatusLog::Reason).string())
+                    .col(ColumnDef::new(UserStatusLog::SuspendedUntil).big_integer())
+                    .col(
+                        ColumnDef::new(UserStatusLog::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_status_log_user")
+                            .from(UserStatusLog::Table, UserStatusLog::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_status_log_operator")
+                            .from(UserStatusLog::Table, UserStatusLog::OperatorId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserStatusLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum UserStatusLog {
+    Table,
+    Id,
+    UserId,
+    OperatorId, // 操作的管理员，命令行操作时为空
+    FromStatus,
+    ToStatus,
+    Reason,
+    SuspendedUntil,
+    CreatedAt, // unix 秒
+}
@@ -0,0 +1,77 @@
+//! 新增 role：角色，并写入内置角色
+
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+/// 内置角色，与 `UserPermissionLevel` 的四个等级一一对应
+pub const BUILTIN_ROLES: [(&str, &str); 4] = [
+    ("admin", "管理员，拥有全部权限"),
+    ("provider", "餐食服务商"),
+    ("user", "普通用户"),
+    ("guest", "访客"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Role::Table)
+                    .col(
+                        ColumnDef::new(Role::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Role::Name).string().not_null().unique_key())
+                    .col(ColumnDef::new(Role::Description).string())
+                    .col(
+                        ColumnDef::new(Role::IsBuiltin)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut insert = Query::insert();
+        insert
+            .into_table(Role::Table)
+            .columns([Role::Name, Role::Description, Role::IsBuiltin]);
+        for (name, description) in BUILTIN_ROLES {
+            insert.values_panic([name.into(), description.into(), true.into()]);
+        }
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Role::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Role {
+    Table,
+    Id,
+    Name, // 唯一角色名，如 health_guide_editor
+    Description,
+    IsBuiltin, // 内置角色不可删除
+}
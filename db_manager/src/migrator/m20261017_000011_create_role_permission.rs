@@ -0,0 +1,94 @@
+//! 新增 role_permission：角色拥有的权限，并写入内置角色的权限
+
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000010_create_role::Role;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+/// 内置角色的默认权限，保持与原有四级权限相同的行为
+const BUILTIN_ROLE_PERMISSIONS: [(&str, &str); 2] = [("admin", "*"), ("provider", "meal.write")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermission::Table)
+                    .col(
+                        ColumnDef::new(RolePermission::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(RolePermission::RoleId).integer().not_null())
+                    .col(
+                        ColumnDef::new(RolePermission::Permission)
+                            .string()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_role_permission_unique")
+                            .col(RolePermission::RoleId)
+                            .col(RolePermission::Permission)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_permission_role")
+                            .from(RolePermission::Table, RolePermission::RoleId)
+                            .to(Role::Table, Role::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for (role, permission) in BUILTIN_ROLE_PERMISSIONS {
+            let insert = Query::insert()
+                .into_table(RolePermission::Table)
+                .columns([RolePermission::RoleId, RolePermission::Permission])
+                .select_from(
+                    Query::select()
+                        .column(Role::Id)
+                        .expr(Expr::val(permission))
+                        .from(Role::Table)
+                        .and_where(Expr::col(Role::Name).eq(role))
+                        .to_owned(),
+                )
+                .map_err(|e| DbErr::Migration(e.to_string()))?
+                .to_owned();
+            manager.exec_stmt(insert).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RolePermission::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum RolePermission {
+    Table,
+    Id,
+    RoleId,
+    Permission, // 如 health_guide.write；"*" 表示全部，"meal.*" 表示 meal 下全部
+}
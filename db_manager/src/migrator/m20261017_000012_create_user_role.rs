@@ -0,0 +1,88 @@
+//! 新增 user_role：用户与角色的对应关系
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+use super::m20261017_000010_create_role::Role;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserRole::Table)
+                    .col(
+                        ColumnDef::new(UserRole::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(UserRole::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserRole::RoleId).integer().not_null())
+                    .col(ColumnDef::new(UserRole::GrantedBy).integer())
+                    .col(ColumnDef::new(UserRole::CreatedAt).big_integer().not_null())
+                    .index(
+                        Index::create()
+                            .name("idx_user_role_unique")
+                            .col(UserRole::UserId)
+                            .col(UserRole::RoleId)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_role_user")
+                            .from(UserRole::Table, UserRole::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_role_role")
+                            .from(UserRole::Table, UserRole::RoleId)
+                            .to(Role::Table, Role::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_role_granted_by")
+                            .from(UserRole::Table, UserRole::GrantedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum UserRole {
+    Table,
+    Id,
+    UserId,
+    RoleId,
+    GrantedBy, // 分配角色的管理员
+    CreatedAt, // unix 秒
+}
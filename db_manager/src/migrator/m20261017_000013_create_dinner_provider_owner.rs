@@ -0,0 +1,103 @@
+//! 新增 dinner_provider_owner：供餐点与负责人的对应关系
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::dinner_provider::DinnerProvider;
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DinnerProviderOwner::Table)
+                    .col(
+                        ColumnDef::new(DinnerProviderOwner::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DinnerProviderOwner::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DinnerProviderOwner::DinnerProviderId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DinnerProviderOwner::GrantedBy).integer())
+                    .col(
+                        ColumnDef::new(DinnerProviderOwner::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_dinner_provider_owner_unique")
+                            .col(DinnerProviderOwner::UserId)
+                            .col(DinnerProviderOwner::DinnerProviderId)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dinner_provider_owner_user")
+                            .from(DinnerProviderOwner::Table, DinnerProviderOwner::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dinner_provider_owner_dinner_provider")
+                            .from(
+                                DinnerProviderOwner::Table,
+                                DinnerProviderOwner::DinnerProviderId,
+                            )
+                            .to(DinnerProvider::Table, DinnerProvider::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dinner_provider_owner_granted_by")
+                            .from(DinnerProviderOwner::Table, DinnerProviderOwner::GrantedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DinnerProviderOwner::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum DinnerProviderOwner {
+    Table,
+    Id,
+    UserId,
+    DinnerProviderId,
+    GrantedBy, // 分配归属的管理员
+    CreatedAt, // unix 秒
+}
@@ -0,0 +1,73 @@
+//! 新增 wx_session：微信登录的 session_key
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WxSession::Table)
+                    .col(
+                        ColumnDef::new(WxSession::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WxSession::UserId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(WxSession::SessionKey).string().not_null())
+                    .col(
+                        ColumnDef::new(WxSession::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_wx_session_user")
+                            .from(WxSession::Table, WxSession::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WxSession::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum WxSession {
+    Table,
+    Id,
+    UserId,     // 每个用户只保存最近一次登录的 session_key
+    SessionKey, // jscode2session 返回的会话密钥，仅服务端保存
+    UpdatedAt,  // unix 秒
+}
@@ -0,0 +1,99 @@
+//! 新增 local_credential：账号密码登录的凭据
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LocalCredential::Table)
+                    .col(
+                        ColumnDef::new(LocalCredential::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LocalCredential::UserId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LocalCredential::Username)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LocalCredential::PasswordHash)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LocalCredential::FailedAttempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(LocalCredential::LockedUntil).big_integer())
+                    .col(
+                        ColumnDef::new(LocalCredential::PasswordChangedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LocalCredential::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_local_credential_user")
+                            .from(LocalCredential::Table, LocalCredential::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LocalCredential::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum LocalCredential {
+    Table,
+    Id,
+    UserId,
+    Username,
+    PasswordHash,      // argon2 PHC 字符串
+    FailedAttempts,    // 连续登录失败次数，成功或锁定后清零
+    LockedUntil,       // unix 秒，锁定期间拒绝登录
+    PasswordChangedAt, // unix 秒
+    CreatedAt,         // unix 秒
+}
@@ -0,0 +1,93 @@
+//! 新增 admin_totp：管理员的二次验证密钥
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AdminTotp::Table)
+                    .col(
+                        ColumnDef::new(AdminTotp::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AdminTotp::UserId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(AdminTotp::Secret).string().not_null())
+                    .col(
+                        ColumnDef::new(AdminTotp::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(AdminTotp::LastUsedStep).big_integer())
+                    .col(
+                        ColumnDef::new(AdminTotp::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AdminTotp::EnabledAt).big_integer())
+                    .col(
+                        ColumnDef::new(AdminTotp::FailedAttempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(AdminTotp::LockedUntil).big_integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_admin_totp_user")
+                            .from(AdminTotp::Table, AdminTotp::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AdminTotp::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum AdminTotp {
+    Table,
+    Id,
+    UserId,
+    Secret,         // base32 编码的 TOTP 密钥
+    Enabled,        // 首次校验通过后才启用
+    LastUsedStep,   // 最近一次通过校验的时间步，防止同一验证码被重放
+    CreatedAt,      // unix 秒
+    EnabledAt,      // unix 秒
+    FailedAttempts, // 连续失败次数，达到上限后锁定
+    LockedUntil,    // unix 秒，为空表示未锁定
+}
@@ -0,0 +1,78 @@
+//! 新增 totp_recovery_code：二次验证的恢复码
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TotpRecoveryCode::Table)
+                    .col(
+                        ColumnDef::new(TotpRecoveryCode::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TotpRecoveryCode::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TotpRecoveryCode::CodeHash)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TotpRecoveryCode::UsedAt).big_integer())
+                    .col(
+                        ColumnDef::new(TotpRecoveryCode::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_totp_recovery_code_user")
+                            .from(TotpRecoveryCode::Table, TotpRecoveryCode::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TotpRecoveryCode::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum TotpRecoveryCode {
+    Table,
+    Id,
+    UserId,
+    CodeHash,  // 恢复码的 sha256，明文只在生成时返回一次
+    UsedAt,    // unix 秒，非空表示已使用
+    CreatedAt, // unix 秒
+}
@@ -0,0 +1,112 @@
+//! 新增 caregiver_link：照护人与老人的绑定关系
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CaregiverLink::Table)
+                    .col(
+                        ColumnDef::new(CaregiverLink::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CaregiverLink::CaregiverId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CaregiverLink::ElderId).integer().not_null())
+                    .col(ColumnDef::new(CaregiverLink::Status).string().not_null())
+                    .col(ColumnDef::new(CaregiverLink::Relationship).string())
+                    .col(ColumnDef::new(CaregiverLink::RequestedBy).integer())
+                    .col(ColumnDef::new(CaregiverLink::ApprovedBy).integer())
+                    .col(
+                        ColumnDef::new(CaregiverLink::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CaregiverLink::ApprovedAt).big_integer())
+                    .col(ColumnDef::new(CaregiverLink::RevokedAt).big_integer())
+                    .index(
+                        Index::create()
+                            .name("idx_caregiver_link_unique")
+                            .col(CaregiverLink::CaregiverId)
+                            .col(CaregiverLink::ElderId)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_link_caregiver")
+                            .from(CaregiverLink::Table, CaregiverLink::CaregiverId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_link_elder")
+                            .from(CaregiverLink::Table, CaregiverLink::ElderId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_link_requested_by")
+                            .from(CaregiverLink::Table, CaregiverLink::RequestedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_link_approved_by")
+                            .from(CaregiverLink::Table, CaregiverLink::ApprovedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CaregiverLink::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum CaregiverLink {
+    Table,
+    Id,
+    CaregiverId,  // 照护人（家属、护工）
+    ElderId,      // 被照护的用户
+    Status,       // pending / active / rejected / revoked
+    Relationship, // 关系说明，如 "女儿"
+    RequestedBy,  // 发起关联的用户
+    ApprovedBy,   // 同意关联的被照护人或审批的管理员
+    CreatedAt,    // unix 秒
+    ApprovedAt,   // unix 秒
+    RevokedAt,    // unix 秒
+}
@@ -0,0 +1,87 @@
+//! 新增 caregiver_action_log：照护人代为操作的记录
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CaregiverActionLog::Table)
+                    .col(
+                        ColumnDef::new(CaregiverActionLog::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(CaregiverActionLog::CaregiverId).integer())
+                    .col(
+                        ColumnDef::new(CaregiverActionLog::ElderId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CaregiverActionLog::Action)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CaregiverActionLog::Detail).text())
+                    .col(
+                        ColumnDef::new(CaregiverActionLog::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_action_log_caregiver")
+                            .from(CaregiverActionLog::Table, CaregiverActionLog::CaregiverId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_caregiver_action_log_elder")
+                            .from(CaregiverActionLog::Table, CaregiverActionLog::ElderId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CaregiverActionLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum CaregiverActionLog {
+    Table,
+    Id,
+    CaregiverId, // 照护人，账号删除后置空以保留记录
+    ElderId,
+    Action,    // 如 user.modify、link.approve
+    Detail,    // 变更的字段等补充说明
+    CreatedAt, // unix 秒
+}
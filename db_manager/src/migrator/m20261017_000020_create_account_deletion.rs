@@ -0,0 +1,77 @@
+//! 新增 account_deletion：账号注销申请
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::user::User;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountDeletion::Table)
+                    .col(
+                        ColumnDef::new(AccountDeletion::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AccountDeletion::UserId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AccountDeletion::RequestedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AccountDeletion::PurgeAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_account_deletion_user")
+                            .from(AccountDeletion::Table, AccountDeletion::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountDeletion::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum AccountDeletion {
+    Table,
+    Id,
+    UserId,      // 每个用户最多一条待执行的注销申请
+    RequestedAt, // unix 秒
+    PurgeAt,     // 宽限期结束时间，之后匿名化账号并删除个人数据
+}
@@ -0,0 +1,72 @@
+//! 新增 audit_subject：审计记录中 open_id 与化名的对应关系
+//!
+//! audit_log 只允许追加，其中只写化名；删除对应关系即可让已注销用户的审计记录无法再关联到本人。
+
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditSubject::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditSubject::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AuditSubject::OpenId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AuditSubject::Pseudonym)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AuditSubject::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditSubject::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditSubject {
+    Table,
+    Id,
+    OpenId,    // 用户的 open_id
+    Pseudonym, // 写入 audit_log 的化名，随机生成
+    CreatedAt, // unix 秒
+}
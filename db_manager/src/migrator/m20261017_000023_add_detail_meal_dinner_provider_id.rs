@@ -0,0 +1,203 @@
+//! 新增 detail_meal.dinner_provider_id：明细餐所属的供餐点
+//!
+//! 之前 belong_to 保存的是自由文本（负责人的姓名或 open_id，后来改为供餐点 id），
+//! 迁移时按以下顺序回填，能唯一确定供餐点的才写入，其余保持为空，由 meal.manage 权限的用户补填：
+//! 1) belong_to 是已存在的供餐点 id
+//! 2) belong_to 与某个供餐点的名称相同
+//! 3) belong_to 是某个负责人的 open_id 或姓名，且该负责人只负责一个供餐点
+
+use std::collections::BTreeSet;
+
+use sea_orm_migration::prelude::*;
+
+use super::baseline::dinner_provider::DinnerProvider;
+use super::baseline::user::User;
+use super::m20261017_000013_create_dinner_provider_owner::DinnerProviderOwner;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        let path = file!();
+        std::path::Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DetailMeal::Table)
+                    .add_column(ColumnDef::new(DetailMeal::DinnerProviderId).integer())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_detail_meal_dinner_provider")
+                            .from_tbl(DetailMeal::Table)
+                            .from_col(DetailMeal::DinnerProviderId)
+                            .to_tbl(DinnerProvider::Table)
+                            .to_col(DinnerProvider::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_detail_meal_dinner_provider_id")
+                    .table(DetailMeal::Table)
+                    .col(DetailMeal::DinnerProviderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        for (belong_to, dinner_provider_id) in legacy_owners(manager).await? {
+            let update = Query::update()
+                .table(DetailMeal::Table)
+                .value(DetailMeal::DinnerProviderId, dinner_provider_id)
+                .and_where(Expr::col(DetailMeal::BelongTo).eq(belong_to))
+                .to_owned();
+            manager.exec_stmt(update).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_detail_meal_dinner_provider_id")
+                    .table(DetailMeal::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DetailMeal::Table)
+                    .drop_foreign_key(Alias::new("fk_detail_meal_dinner_provider"))
+                    .drop_column(DetailMeal::DinnerProviderId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// 每个不同的 belong_to 能唯一确定的供餐点
+async fn legacy_owners(manager: &SchemaManager<'_>) -> Result<Vec<(String, i32)>, DbErr> {
+    let db = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let providers: Vec<(i32, Option<String>)> = db
+        .query_all(
+            backend.build(
+                Query::select()
+                    .columns([DinnerProvider::Id, DinnerProvider::Name])
+                    .from(DinnerProvider::Table),
+            ),
+        )
+        .await?
+        .iter()
+        .map(|row| Ok((row.try_get("", "id")?, row.try_get("", "name")?)))
+        .collect::<Result<_, DbErr>>()?;
+
+    // 负责人的 open_id、姓名与其负责的供餐点
+    let owners: Vec<(i32, String, Option<String>)> = db
+        .query_all(
+            backend.build(
+                Query::select()
+                    .column((
+                        DinnerProviderOwner::Table,
+                        DinnerProviderOwner::DinnerProviderId,
+                    ))
+                    .column((User::Table, User::OpenId))
+                    .column((User::Table, User::Name))
+                    .from(DinnerProviderOwner::Table)
+                    .inner_join(
+                        User::Table,
+                        Expr::col((User::Table, User::Id))
+                            .equals((DinnerProviderOwner::Table, DinnerProviderOwner::UserId)),
+                    ),
+            ),
+        )
+        .await?
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get("", "dinner_provider_id")?,
+                row.try_get("", "open_id")?,
+                row.try_get("", "name")?,
+            ))
+        })
+        .collect::<Result<_, DbErr>>()?;
+
+    let values: Vec<String> = db
+        .query_all(
+            backend.build(
+                Query::select()
+                    .distinct()
+                    .column(DetailMeal::BelongTo)
+                    .from(DetailMeal::Table)
+                    .and_where(Expr::col(DetailMeal::BelongTo).is_not_null()),
+            ),
+        )
+        .await?
+        .iter()
+        .map(|row| row.try_get("", "belong_to"))
+        .collect::<Result<_, DbErr>>()?;
+
+    Ok(values
+        .into_iter()
+        .filter_map(|value| {
+            let id = resolve(&value, &providers, &owners)?;
+            Some((value, id))
+        })
+        .collect())
+}
+
+fn resolve(
+    value: &str,
+    providers: &[(i32, Option<String>)],
+    owners: &[(i32, String, Option<String>)],
+) -> Option<i32> {
+    if let Ok(id) = value.parse::<i32>()
+        && providers.iter().any(|(p, _)| *p == id)
+    {
+        return Some(id);
+    }
+    let candidates = [
+        providers
+            .iter()
+            .filter(|(_, name)| name.as_deref() == Some(value))
+            .map(|(id, _)| *id)
+            .collect::<BTreeSet<_>>(),
+        owners
+            .iter()
+            .filter(|(_, open_id, _)| open_id == value)
+            .map(|(id, _, _)| *id)
+            .collect(),
+        owners
+            .iter()
+            .filter(|(_, _, name)| name.as_deref() == Some(value))
+            .map(|(id, _, _)| *id)
+            .collect(),
+    ];
+    candidates
+        .into_iter()
+        .find(|ids| !ids.is_empty())
+        .filter(|ids| ids.len() == 1)
+        .and_then(|ids| ids.into_iter().next())
+}
+
+#[derive(DeriveIden)]
+enum DetailMeal {
+    Table,
+    BelongTo,
+    DinnerProviderId, // 所属供餐点
+}
@@ -1,48 +1,151 @@
 // src/migrator/mod.rs
+//
+// 迁移记录只追加：已经发布的迁移不要修改或删除，表结构变更写成新的迁移文件，
+// 命名为 `m<年月日>_<序号>_<说明>.rs`（如 `m20261017_000001_add_user_email.rs`），
+// 在下面声明模块并追加到 `migrations()` 的末尾。
 
 use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend, Statement};
+use sea_orm_migration::seaql_migrations;
 
-pub mod ai_chat;
-pub mod community_service;
-pub mod detail_meal;
-pub mod dinner_provider;
-pub mod feedback;
-pub mod health_guide_content;
-pub mod health_guide_type;
-pub mod medical_service;
-pub mod mutil_media;
-pub mod notice;
-pub mod policy_file;
-pub mod policy_type;
-pub mod resource_service;
-pub mod service_map_content;
-pub mod service_map_type;
-pub mod slideshow;
-pub mod user;
+pub mod baseline;
+pub mod m20261017_000001_add_user_token_version;
+pub mod m20261017_000002_add_user_status;
+pub mod m20261017_000003_add_user_suspended_reason;
+pub mod m20261017_000004_add_user_suspended_until;
+pub mod m20261017_000005_add_feedback_openid;
+pub mod m20261017_000006_create_refresh_token;
+pub mod m20261017_000007_create_invitation_code;
+pub mod m20261017_000008_create_invitation_redemption;
+pub mod m20261017_000009_create_user_status_log;
+pub mod m20261017_000010_create_role;
+pub mod m20261017_000011_create_role_permission;
+pub mod m20261017_000012_create_user_role;
+pub mod m20261017_000013_create_dinner_provider_owner;
+pub mod m20261017_000014_create_wx_session;
+pub mod m20261017_000015_create_local_credential;
+pub mod m20261017_000016_create_admin_totp;
+pub mod m20261017_000017_create_totp_recovery_code;
+pub mod m20261017_000018_create_caregiver_link;
+pub mod m20261017_000019_create_caregiver_action_log;
+pub mod m20261017_000020_create_account_deletion;
+pub mod m20261017_000021_create_audit_log;
+pub mod m20261017_000022_create_audit_subject;
+pub mod m20261017_000023_add_detail_meal_dinner_provider_id;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![
-            Box::new(community_service::Migration),
-            Box::new(dinner_provider::Migration),
-            Box::new(medical_service::Migration),
-            Box::new(notice::Migration),
-            Box::new(policy_file::Migration),
-            Box::new(policy_type::Migration),
-            Box::new(resource_service::Migration),
-            Box::new(slideshow::Migration),
-            Box::new(detail_meal::Migration),
-            Box::new(health_guide_type::Migration),
-            Box::new(health_guide_content::Migration),
-            Box::new(service_map_type::Migration),
-            Box::new(service_map_content::Migration),
-            Box::new(feedback::Migration),
-            Box::new(user::Migration),
-            Box::new(ai_chat::Migration),
-            Box::new(mutil_media::Migration),
-        ]
+        let mut migrations = baseline::migrations();
+        let added: Vec<Box<dyn MigrationTrait>> = vec![
+            Box::new(m20261017_000001_add_user_token_version::Migration),
+            Box::new(m20261017_000002_add_user_status::Migration),
+            Box::new(m20261017_000003_add_user_suspended_reason::Migration),
+            Box::new(m20261017_000004_add_user_suspended_until::Migration),
+            Box::new(m20261017_000005_add_feedback_openid::Migration),
+            Box::new(m20261017_000006_create_refresh_token::Migration),
+            Box::new(m20261017_000007_create_invitation_code::Migration),
+            Box::new(m20261017_000008_create_invitation_redemption::Migration),
+            Box::new(m20261017_000009_create_user_status_log::Migration),
+            Box::new(m20261017_000010_create_role::Migration),
+            Box::new(m20261017_000011_create_role_permission::Migration),
+            Box::new(m20261017_000012_create_user_role::Migration),
+            Box::new(m20261017_000013_create_dinner_provider_owner::Migration),
+            Box::new(m20261017_000014_create_wx_session::Migration),
+            Box::new(m20261017_000015_create_local_credential::Migration),
+            Box::new(m20261017_000016_create_admin_totp::Migration),
+            Box::new(m20261017_000017_create_totp_recovery_code::Migration),
+            Box::new(m20261017_000018_create_caregiver_link::Migration),
+            Box::new(m20261017_000019_create_caregiver_action_log::Migration),
+            Box::new(m20261017_000020_create_account_deletion::Migration),
+            Box::new(m20261017_000021_create_audit_log::Migration),
+            Box::new(m20261017_000022_create_audit_subject::Migration),
+            Box::new(m20261017_000023_add_detail_meal_dinner_provider_id::Migration),
+        ];
+        migrations.extend(added);
+        migrations
     }
 }
+
+/// 迁移记录表，不算作业务数据
+pub const MIGRATION_TABLE: &str = "seaql_migrations";
+/// 迁移本身会写入初始数据（内置角色及其权限）的表，判断数据库是否为空时忽略
+pub const SEEDED_TABLES: [&str; 2] = ["role", "role_permission"];
+
+/// 当前 schema 中存在业务数据的表，用于在清空数据库前确认
+pub async fn tables_with_data<C>(db: &C) -> Result<Vec<String>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let backend = db.get_database_backend();
+    let sql = match backend {
+        DbBackend::Postgres => {
+            "SELECT table_name::text AS name FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' \
+             ORDER BY table_name"
+        }
+        DbBackend::MySql => {
+            "SELECT table_name AS name FROM information_schema.tables \
+             WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' \
+             ORDER BY table_name"
+        }
+        DbBackend::Sqlite => {
+            "SELECT name FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+        }
+    };
+    let tables = db
+        .query_all(Statement::from_string(backend, sql))
+        .await?
+        .iter()
+        .map(|row| row.try_get::<String>("", "name"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut non_empty = Vec::new();
+    for table in tables
+        .into_iter()
+        .filter(|t| t != MIGRATION_TABLE && !SEEDED_TABLES.contains(&t.as_str()))
+    {
+        let quoted = match backend {
+            DbBackend::MySql => format!("`{}`", table.replace('`', "``")),
+            _ => format!("\"{}\"", table.replace('"', "\"\"")),
+        };
+        let row = db
+            .query_one(Statement::from_string(
+                backend,
+                format!("SELECT 1 AS found FROM {} LIMIT 1", quoted),
+            ))
+            .await?;
+        if row.is_some() {
+            non_empty.push(table);
+        }
+    }
+    Ok(non_empty)
+}
+
+/// 尚未应用的迁移名称，按执行顺序排列
+///
+/// 只读取迁移记录，不会像 `MigratorTrait::get_pending_migrations` 那样创建迁移记录表，
+/// 供就绪检查反复调用；迁移记录表不存在时返回错误
+pub async fn pending_migrations<C>(db: &C) -> Result<Vec<String>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let stmt = Query::select()
+        .from(Migrator::migration_table_name())
+        .column(seaql_migrations::Column::Version)
+        .to_owned();
+    let applied: Vec<String> = db
+        .query_all(db.get_database_backend().build(&stmt))
+        .await?
+        .iter()
+        .map(|row| row.try_get("", "version"))
+        .collect::<Result<_, _>>()?;
+    Ok(Migrator::migrations()
+        .iter()
+        .map(|m| m.name().to_string())
+        .filter(|name| !applied.contains(name))
+        .collect())
+}
@@ -0,0 +1,70 @@
+use db_manager::config::DatabaseConfig;
+use db_manager::entity::detail_meal;
+use db_manager::migrator::Migrator;
+use dotenvy::dotenv;
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, EntityTrait, QueryOrder};
+use sea_orm_migration::prelude::*;
+
+async fn setup_connection() -> DatabaseConnection {
+    dotenv().ok();
+
+    let uri = std::env::var("SERVER_DB_URI").expect("SERVER_DB_URI must be set");
+    Database::connect(DatabaseConfig::new(uri).connect_options())
+        .await
+        .expect("failed to connect to database")
+}
+
+#[tokio::test]
+async fn detail_meal_provider_is_backfilled_from_belong_to()
+-> Result<(), Box<dyn std::error::Error>> {
+    let db = setup_connection().await;
+
+    // 1) 回到新增 dinner_provider_id 之前，写入旧格式的 belong_to
+    Migrator::refresh(&db).await?;
+    Migrator::down(&db, Some(1)).await?;
+    db.execute_unprepared(
+        r#"
+        INSERT INTO dinner_provider (id, name) VALUES
+            (1, 'Sunrise Canteen'), (2, 'Riverside'), (3, 'Riverside'), (4, 'Hillside');
+        INSERT INTO "user" (id, open_id, name) VALUES (10, 'op-a', 'Alice'), (11, 'op-b', 'Bob');
+        INSERT INTO dinner_provider_owner (user_id, dinner_provider_id, created_at) VALUES
+            (10, 4, 0), (11, 1, 0), (11, 2, 0);
+        INSERT INTO detail_meal (id, belong_to) VALUES
+            (1, '1'), (2, 'Sunrise Canteen'), (3, 'Riverside'), (4, 'op-a'), (5, 'Alice'),
+            (6, 'op-b'), (7, '99'), (8, NULL);
+        "#,
+    )
+    .await?;
+
+    // 2) 执行迁移后只有能唯一确定供餐点的记录被回填
+    Migrator::up(&db, None).await?;
+    let providers: Vec<(i32, Option<i32>)> = detail_meal::Entity::find()
+        .order_by_asc(detail_meal::Column::Id)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m.dinner_provider_id))
+        .collect();
+    assert_eq!(
+        providers,
+        [
+            (1, Some(1)),
+            (2, Some(1)),
+            (3, None),
+            (4, Some(4)),
+            (5, Some(4)),
+            (6, None),
+            (7, None),
+            (8, None),
+        ]
+    );
+
+    // 3) 外键生效：删除供餐点后明细餐不再归属它
+    db.execute_unprepared("DELETE FROM dinner_provider WHERE id = 4")
+        .await?;
+    let meal = detail_meal::Entity::find_by_id(4).one(&db).await?.unwrap();
+    assert_eq!(meal.dinner_provider_id, None);
+
+    Migrator::refresh(&db).await?;
+    Ok(())
+}
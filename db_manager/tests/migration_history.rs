@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use db_manager::migrator::Migrator;
+use sea_orm_migration::MigratorTrait;
+
+/// 已经发布的迁移，顺序与名称都不能改变；新的迁移只能追加在后面
+const RELEASED: [&str; 17] = [
+    "community_service",
+    "dinner_provider",
+    "medical_service",
+    "notice",
+    "policy_file",
+    "policy_type",
+    "resource_service",
+    "slideshow",
+    "detail_meal",
+    "health_guide_type",
+    "health_guide_content",
+    "service_map_type",
+    "service_map_content",
+    "feedback",
+    "user",
+    "ai_chat",
+    "mutil_media",
+];
+
+#[test]
+fn migration_history_is_append_only() {
+    let names: Vec<String> = Migrator::migrations()
+        .iter()
+        .map(|m| m.name().to_string())
+        .collect();
+
+    assert!(names.len() >= RELEASED.len());
+    assert_eq!(&names[..RELEASED.len()], RELEASED);
+
+    let unique: HashSet<&String> = names.iter().collect();
+    assert_eq!(unique.len(), names.len(), "duplicate migration names");
+
+    // 基线之后的迁移按 m<年月日>_<序号>_<说明> 命名，按名称排序即执行顺序
+    let added = &names[RELEASED.len()..];
+    for name in added {
+        let bytes = name.as_bytes();
+        assert!(
+            name.len() > 17
+                && bytes[0] == b'm'
+                && bytes[1..9].iter().all(u8::is_ascii_digit)
+                && bytes[9] == b'_'
+                && bytes[10..16].iter().all(u8::is_ascii_digit)
+                && bytes[16] == b'_',
+            "migration {} does not follow m<yyyymmdd>_<nnnnnn>_<name>",
+            name
+        );
+    }
+    assert!(
+        added.windows(2).all(|w| w[0] < w[1]),
+        "migrations out of order"
+    );
+}
+
+#[tokio::test]
+async fn pending_migrations_lists_unapplied_in_order() {
+    use db_manager::migrator::pending_migrations;
+    use sea_orm::{DbBackend, MockDatabase, Value};
+    use std::collections::BTreeMap;
+
+    let applied = |version: &str| {
+        BTreeMap::from([
+            ("version".to_string(), Value::from(version)),
+            ("applied_at".to_string(), Value::from(0i64)),
+        ])
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([RELEASED[..RELEASED.len() - 1]
+            .iter()
+            .map(|name| applied(name))
+            .collect::<Vec<_>>()])
+        .into_connection();
+
+    let pending = pending_migrations(&db).await.unwrap();
+    let total = Migrator::migrations().len();
+    assert_eq!(pending.len(), total - RELEASED.len() + 1);
+    assert_eq!(pending[0], RELEASED[RELEASED.len() - 1]);
+}
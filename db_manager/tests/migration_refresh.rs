@@ -1,6 +1,7 @@
-use db_manager::{config::DatabaseConfig, migrator::Migrator};
+use db_manager::config::DatabaseConfig;
+use db_manager::migrator::{Migrator, tables_with_data};
 use dotenvy::dotenv;
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{Database, DatabaseConnection};
 use sea_orm_migration::prelude::*;
 use std::sync::Once;
 
@@ -21,15 +22,8 @@ async fn setup_connection() -> DatabaseConnection {
     dotenv().ok();
     init_tracing();
 
-    let db_config = DatabaseConfig::from_env().uri();
-    let mut options = ConnectOptions::new(&db_config);
-    options
-        .max_connections(10)
-        .min_connections(1)
-        .sqlx_logging(false)
-        .sqlx_logging_level(log::LevelFilter::Debug);
-
-    Database::connect(options)
+    let uri = std::env::var("SERVER_DB_URI").expect("SERVER_DB_URI must be set");
+    Database::connect(DatabaseConfig::new(uri).connect_options())
         .await
         .expect("failed to connect to database")
 }
@@ -53,5 +47,12 @@ async fn migration_refresh_creates_expected_tables() -> Result<(), Box<dyn std::
         );
     }
 
+    // 刷新之后没有待执行的迁移，启动时的 up 不会改动数据库
+    assert!(Migrator::get_pending_migrations(&db).await?.is_empty());
+    Migrator::up(&db, None).await?;
+
+    // 只有迁移写入的内置角色，不算业务数据
+    assert!(tables_with_data(&db).await?.is_empty());
+
     Ok(())
 }
@@ -1,15 +1,17 @@
 from flask import Flask, request, jsonify
-import random
-import string
+import base64
+import json
+import os
 import logging
+import time
 
-app = Flask(__name__)
+from cryptography.hazmat.primitives import padding
+from cryptography.hazmat.primitives.ciphers import Cipher, algorithms, modes
 
+app = Flask(__name__)
 
-def generate_random_string(length=20):
-    """生成指定长度的随机字符串"""
-    characters = string.ascii_letters + string.digits
-    return "".join(random.choice(characters) for _ in range(length))
+# openid -> (appid, session_key)，用于模拟加密数据
+sessions = {}
 
 
 @app.route("/sns/jscode2session", methods=["GET"])
@@ -25,9 +27,9 @@ def jscode2session():
         f"收到请求 - appid: {appid}, secret: {secret}, js_code: {js_code}, grant_type: {grant_type}"
     )
 
-    # 生成随机的session_key和openid
-    session_key = generate_random_string(20)
-    openid = generate_random_string(20)
+    # 生成随机的session_key（base64 编码的 16 字节密钥，与微信一致）
+    session_key = base64.b64encode(os.urandom(16)).decode()
+    sessions[js_code] = (appid, session_key)
 
     # 返回JSON响应
     response = {"session_key": session_key, "openid": js_code}
@@ -35,6 +37,54 @@ def jscode2session():
     return jsonify(response)
 
 
+@app.route("/cgi-bin/token", methods=["GET"])
+def access_token():
+    """模拟获取接口调用凭证 access_token"""
+    appid = request.args.get("appid", "")
+    grant_type = request.args.get("grant_type", "")
+    print(f"收到 access_token 请求 - appid: {appid}, grant_type: {grant_type}")
+
+    if grant_type != "client_credential":
+        return jsonify({"errcode": 40002, "errmsg": "invalid grant_type"})
+    token = base64.urlsafe_b64encode(os.urandom(24)).decode()
+    return jsonify({"access_token": token, "expires_in": 7200})
+
+
+@app.route("/debug/encrypt_phone", methods=["GET"])
+def encrypt_phone():
+    """模拟小程序 wx.getPhoneNumber 返回的 encryptedData 与 iv
+
+    使用该 openid 最近一次 jscode2session 下发的 session_key 加密
+    """
+    openid = request.args.get("openid", "")
+    phone = request.args.get("phone", "13800138000")
+    if openid not in sessions:
+        return jsonify({"errcode": 40029, "errmsg": "no session for openid"}), 400
+    appid, session_key = sessions[openid]
+    appid = request.args.get("appid", appid)
+
+    payload = {
+        "phoneNumber": phone,
+        "purePhoneNumber": phone,
+        "countryCode": "86",
+        "watermark": {"appid": appid, "timestamp": int(time.time())},
+    }
+    padder = padding.PKCS7(128).padder()
+    data = padder.update(json.dumps(payload).encode()) + padder.finalize()
+    iv = os.urandom(16)
+    encryptor = Cipher(
+        algorithms.AES(base64.b64decode(session_key)), modes.CBC(iv)
+    ).encryptor()
+    encrypted = encryptor.update(data) + encryptor.finalize()
+
+    return jsonify(
+        {
+            "encryptedData": base64.b64encode(encrypted).decode(),
+            "iv": base64.b64encode(iv).decode(),
+        }
+    )
+
+
 if __name__ == "__main__":
     # 配置日志，使控制台输出更清晰
     logging.basicConfig(level=logging.INFO)
@@ -45,6 +95,9 @@ if __name__ == "__main__":
     print(
         "示例: http://localhost:5000/sns/jscode2session?appid=wx123456&secret=abcdef&js_code=testcode123&grant_type=authorization_code"
     )
+    print(
+        "加密手机号: http://localhost:5000/debug/encrypt_phone?openid=testcode123&phone=13800138000"
+    )
     print("按 Ctrl+C 停止服务器\n")
 
     # 启动Flask应用，监听所有IP地址的5000端口
@@ -1,26 +1,48 @@
 use std::io::Result;
+use std::path::PathBuf;
+
 fn main() -> Result<()> {
-    prost_build::compile_protos(
-        &[
-            "src/proto/user.proto",
-            "src/proto/notice.proto",
-            "src/proto/mutil_media.proto",
-            "src/proto/slideshow.proto",
-            "src/proto/community_service.proto",
-            "src/proto/resource_service.proto",
-            "src/proto/medical_service.proto",
-            "src/proto/feedback.proto",
-            "src/proto/dinner_provider.proto",
-            "src/proto/detail_meal.proto",
-            "src/proto/service_map_type.proto",
-            "src/proto/health_guide_type.proto",
-            "src/proto/service_map_content.proto",
-            "src/proto/health_guide_content.proto",
-            "src/proto/policy_type.proto",
-            "src/proto/policy_file.proto",
-            "src/proto/ai_chat.proto",
-        ],
-        &["src"],
-    )?;
+    let descriptor_path =
+        PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("proto_descriptor.bin");
+    prost_build::Config::new()
+        .file_descriptor_set_path(&descriptor_path)
+        .compile_protos(
+            &[
+                "src/proto/common.proto",
+                "src/proto/user.proto",
+                "src/proto/notice.proto",
+                "src/proto/mutil_media.proto",
+                "src/proto/slideshow.proto",
+                "src/proto/community_service.proto",
+                "src/proto/resource_service.proto",
+                "src/proto/medical_service.proto",
+                "src/proto/feedback.proto",
+                "src/proto/dinner_provider.proto",
+                "src/proto/detail_meal.proto",
+                "src/proto/service_map_type.proto",
+                "src/proto/health_guide_type.proto",
+                "src/proto/service_map_content.proto",
+                "src/proto/health_guide_content.proto",
+                "src/proto/policy_type.proto",
+                "src/proto/policy_file.proto",
+                "src/proto/ai_chat.proto",
+                "src/proto/role.proto",
+                "src/proto/console.proto",
+                "src/proto/totp.proto",
+                "src/proto/caregiver.proto",
+                "src/proto/audit_log.proto",
+                "src/proto/health.proto",
+            ],
+            &["src"],
+        )?;
+
+    // 为所有消息生成 serde 实现（protobuf JSON 映射），字段名与 .proto 中一致
+    let descriptor_set = std::fs::read(descriptor_path)?;
+    pbjson_build::Builder::new()
+        .register_descriptors(&descriptor_set)?
+        .preserve_proto_field_names()
+        .emit_fields()
+        .ignore_unknown_fields()
+        .build(&[".sd_backend"])?;
     Ok(())
 }
@@ -1,47 +1,81 @@
+pub mod common {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.common.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.common.serde.rs"));
+}
 pub mod user {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.user.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.user.serde.rs"));
 }
 pub mod notice {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.notice.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.notice.serde.rs"));
 }
 pub mod mutil_media {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.mutil_media.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.mutil_media.serde.rs"));
 }
 
 pub mod slideshow {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.slideshow.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.slideshow.serde.rs"));
 }
 
 pub mod community_service {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.community_service.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.community_service.serde.rs"
+    ));
 }
 
 pub mod resource_service {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.resource_service.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.resource_service.serde.rs"
+    ));
 }
 
 pub mod medical_service {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.medical_service.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.medical_service.serde.rs"
+    ));
 }
 
 pub mod feedback {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.feedback.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.feedback.serde.rs"));
 }
 
 pub mod dinner_provider {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.dinner_provider.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.dinner_provider.serde.rs"
+    ));
 }
 
 pub mod detail_meal {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.detail_meal.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.detail_meal.serde.rs"));
 }
 
 pub mod service_map_type {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.service_map_type.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.service_map_type.serde.rs"
+    ));
 }
 
 pub mod health_guide_type {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.health_guide_type.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.health_guide_type.serde.rs"
+    ));
 }
 
 pub mod service_map_content {
@@ -49,6 +83,10 @@ pub mod service_map_content {
         env!("OUT_DIR"),
         "/sd_backend.service_map_content.rs"
     ));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.service_map_content.serde.rs"
+    ));
 }
 
 pub mod health_guide_content {
@@ -56,16 +94,53 @@ pub mod health_guide_content {
         env!("OUT_DIR"),
         "/sd_backend.health_guide_content.rs"
     ));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/sd_backend.health_guide_content.serde.rs"
+    ));
 }
 
 pub mod policy_type {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.policy_type.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.policy_type.serde.rs"));
 }
 
 pub mod policy_file {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.policy_file.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.policy_file.serde.rs"));
 }
 
 pub mod ai_chat {
     include!(concat!(env!("OUT_DIR"), "/sd_backend.ai_chat.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.ai_chat.serde.rs"));
+}
+
+pub mod role {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.role.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.role.serde.rs"));
+}
+
+pub mod console {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.console.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.console.serde.rs"));
+}
+
+pub mod totp {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.totp.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.totp.serde.rs"));
+}
+
+pub mod caregiver {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.caregiver.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.caregiver.serde.rs"));
+}
+
+pub mod audit_log {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.audit_log.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.audit_log.serde.rs"));
+}
+
+pub mod health {
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.health.rs"));
+    include!(concat!(env!("OUT_DIR"), "/sd_backend.health.serde.rs"));
 }
@@ -0,0 +1,170 @@
+//! 审计钩子
+//!
+//! 所有修改数据的 handler 通过 `Auditor` 提取器把变更写入 `audit_log`（见 `user_auth::audit`）。
+//! 审计记录与变更在同一个事务中写入：写入失败时返回错误，整个事务回滚，请求失败，
+//! 不会出现变更成功却没有审计记录的情况：
+//!
+//! ```rust,ignore
+//! async fn delete_notice(State(state): State<AppState>, audit: Auditor, ...) -> Result<_, ApiError> {
+//!     let txn = db.begin().await?;
+//!     notice.clone().delete(&txn).await?;
+//!     audit.delete(&txn, &notice).await?;
+//!     txn.commit().await?;
+//! }
+//! ```
+//!
+//! 操作者取自当前登录用户（未登录的接口可用 `as_user` 指定）。IP 为连接的对端地址；
+//! 对端是配置的可信反向代理（`TrustedProxies`）时，改用代理传入的 `X-Forwarded-For` / `X-Real-IP`，
+//! 其他来源的这两个头可以任意伪造，一律忽略。
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::{ConnectInfo, FromRequestParts, OptionalFromRequestParts};
+use axum::http::request::Parts;
+use sea_orm::{DatabaseTransaction, JsonValue, ModelTrait};
+use user_auth::audit::{
+    AuditActor, AuditChange, AuditEntry, AuditError, audit_entry, build_entry, record_audit_entry,
+};
+
+use crate::auth::AuthUser;
+
+/// 当前请求的审计记录器
+#[derive(Debug, Clone)]
+pub struct Auditor(pub AuditActor);
+
+impl Auditor {
+    /// 未登录的接口（注册、微信登录）以新建的用户作为操作者
+    pub fn as_user(mut self, open_id: &str) -> Self {
+        self.0.open_id = Some(open_id.to_string());
+        self
+    }
+
+    pub async fn insert<M>(&self, txn: &DatabaseTransaction, model: &M) -> Result<(), AuditError>
+    where
+        M: ModelTrait,
+    {
+        self.record(txn, AuditChange::Insert(model)).await
+    }
+
+    pub async fn update<M>(
+        &self,
+        txn: &DatabaseTransaction,
+        before: &M,
+        after: &M,
+    ) -> Result<(), AuditError>
+    where
+        M: ModelTrait,
+    {
+        self.record(txn, AuditChange::Update(before, after)).await
+    }
+
+    pub async fn delete<M>(&self, txn: &DatabaseTransaction, model: &M) -> Result<(), AuditError>
+    where
+        M: ModelTrait,
+    {
+        self.record(txn, AuditChange::Delete(model)).await
+    }
+
+    /// 变更前后的行由调用方分别查询时使用，按是否存在推断新建、修改或删除
+    pub async fn change<M>(
+        &self,
+        txn: &DatabaseTransaction,
+        before: Option<&M>,
+        after: Option<&M>,
+    ) -> Result<(), AuditError>
+    where
+        M: ModelTrait,
+    {
+        match (before, after) {
+            (None, Some(after)) => self.insert(txn, after).await,
+            (Some(before), Some(after)) => self.update(txn, before, after).await,
+            (Some(before), None) => self.delete(txn, before).await,
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// 记录没有对应单个 model 的变更（如角色及其权限、关联表），`before` / `after` 为 JSON 对象
+    pub async fn fields(
+        &self,
+        txn: &DatabaseTransaction,
+        entity: &str,
+        entity_id: impl ToString,
+        before: Option<JsonValue>,
+        after: Option<JsonValue>,
+    ) -> Result<(), AuditError> {
+        match build_entry(entity, entity_id.to_string(), before, after) {
+            Some(entry) => self.write(txn, entry).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn record<M>(
+        &self,
+        txn: &DatabaseTransaction,
+        change: AuditChange<'_, M>,
+    ) -> Result<(), AuditError>
+    where
+        M: ModelTrait,
+    {
+        match audit_entry(change) {
+            Some(entry) => self.write(txn, entry).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn write(&self, txn: &DatabaseTransaction, entry: AuditEntry) -> Result<(), AuditError> {
+        record_audit_entry(txn, &self.0, entry).await?;
+        Ok(())
+    }
+}
+
+/// 可信的反向代理地址，见 `app_config::ServerConfig::trusted_proxies`
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(pub Vec<IpAddr>);
+
+/// 客户端 IP：连接的对端地址；对端是可信代理时取代理头中的第一跳
+pub fn client_ip(parts: &Parts) -> Option<String> {
+    let peer = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let trusted = match (peer, parts.extensions.get::<TrustedProxies>()) {
+        (Some(peer), Some(TrustedProxies(proxies))) => proxies.contains(&peer),
+        _ => false,
+    };
+    let forwarded = parts
+        .headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .or_else(|| parts.headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+        .filter(|v| trusted && !v.is_empty());
+    match forwarded {
+        Some(ip) => Some(ip.to_string()),
+        None => peer.map(|ip| ip.to_string()),
+    }
+}
+
+impl<S> FromRequestParts<S> for Auditor
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = match parts.extensions.get::<AuthUser>() {
+            Some(user) => Some(user.clone()),
+            // 鉴权失败由 handler 自己的提取器拒绝，这里只记录为匿名
+            None => <AuthUser as OptionalFromRequestParts<S>>::from_request_parts(parts, state)
+                .await
+                .ok()
+                .flatten(),
+        };
+        Ok(Auditor(AuditActor {
+            open_id: user.map(|u| u.0.open_id),
+            ip: client_ip(parts),
+        }))
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axum::http::{Method, request::Parts};
+use axum_extra::extract::cookie::{Cookie, Key, PrivateCookieJar, SameSite};
+use db_manager::entity::user as user_entity;
+use sea_orm::DatabaseConnection;
+use user_auth::local_auth::{
+    CONSOLE_SESSION_COOKIE, CSRF_HEADER, ConsoleSession, verify_console_session,
+};
+
+use super::AuthRejection;
+
+/// 读取请求中的后台会话 cookie，未注入密钥或未携带 cookie 时为 `None`
+pub fn console_session(parts: &Parts) -> Option<ConsoleSession> {
+    let key = parts.extensions.get::<Key>()?.clone();
+    let jar = PrivateCookieJar::from_headers(&parts.headers, key);
+    let cookie = jar.get(CONSOLE_SESSION_COOKIE)?;
+    ConsoleSession::decode(cookie.value())
+}
+
+/// 校验后台 cookie 会话
+///
+/// 非 GET/HEAD/OPTIONS 请求必须在 `x-csrf-token` 头中回传会话的 CSRF token
+pub async fn verify_console_request(
+    parts: &Parts,
+    session: &ConsoleSession,
+    db: Arc<DatabaseConnection>,
+) -> Result<user_entity::Model, AuthRejection> {
+    let safe = matches!(parts.method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if !safe {
+        let csrf = parts
+            .headers
+            .get(CSRF_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        if csrf.is_empty() || csrf != session.csrf {
+            return Err(AuthRejection::forbidden("CSRF token mismatch"));
+        }
+    }
+    Ok(verify_console_session(db.as_ref(), session).await?)
+}
+
+/// 构造保存后台会话的 cookie（由 `PrivateCookieJar` 加密）
+///
+/// 不设置 Max-Age，有效期以会话内的 `exp` 为准
+pub fn session_cookie(session: &ConsoleSession) -> Cookie<'static> {
+    Cookie::build((CONSOLE_SESSION_COOKIE, session.encode()))
+        .path("/api")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Strict)
+        .build()
+}
+
+/// 用于清除后台会话的 cookie
+pub fn removal_cookie() -> Cookie<'static> {
+    Cookie::build(CONSOLE_SESSION_COOKIE).path("/api").build()
+}
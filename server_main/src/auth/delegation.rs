@@ -0,0 +1,30 @@
+use db_manager::entity::user as user_entity;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use user_auth::caregiver::{Delegation, resolve_delegation};
+
+/// 解析按用户操作的接口的目标用户
+///
+/// 未传 `target_openid` 或与自己相同时返回 `Delegation::Own`；
+/// 存在生效的照护关联时返回 `Delegation::Caregiver`，否则由调用方决定是否放行
+pub async fn resolve_target(
+    db: &DatabaseConnection,
+    actor: &user_entity::Model,
+    target_openid: Option<&str>,
+) -> Result<(user_entity::Model, Delegation), (i32, String)> {
+    let Some(target_openid) = target_openid.filter(|o| *o != actor.open_id) else {
+        return Ok((actor.clone(), Delegation::Own));
+    };
+    let target = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(target_openid))
+        .one(db)
+        .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => return Err((404, "target user not found".to_string())),
+        Err(err) => return Err((500, format!("Database error: {}", err))),
+    };
+    let delegation = resolve_delegation(db, actor, &target)
+        .await
+        .map_err(|e| e.code_and_message())?;
+    Ok((target, delegation))
+}
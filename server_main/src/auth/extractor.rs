@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::http::{header, request::Parts};
+use db_manager::entity::user as user_entity;
+use sea_orm::DatabaseConnection;
+use user_auth::db_exchange::{KeyRing, User, model2user, verify_token};
+use user_auth::rbac::{PermissionSet, load_permissions};
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::AuthRejection;
+use super::console::{console_session, verify_console_request};
+
+/// 已登录用户（权限 0-3 均可）
+///
+/// 从 `Authorization` 头读取 token（可带 `Bearer ` 前缀），校验 token 版本号后
+/// 返回数据库中的最新用户信息（权限以数据库为准，而不是 token 中的旧值）。
+/// 未携带 token 时回退到管理后台的加密 cookie 会话，写操作需校验 CSRF token。
+/// 若路由级中间件已完成解析，则直接复用请求扩展中的结果。
+///
+/// 数据库连接与签名密钥从请求扩展 `Arc<DatabaseConnection>`、`Arc<KeyRing>` 中获取，由 `run` 统一注入。
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub User);
+
+impl AuthUser {
+    /// 当前用户的权限等级，缺省视为 Guest
+    pub fn level(&self) -> UserPermissionLevel {
+        self.0.permission.unwrap_or(0).into()
+    }
+
+    /// 当前用户是否拥有不低于 `required` 的权限
+    pub fn has_level(&self, required: &UserPermissionLevel) -> bool {
+        self.level() >= *required
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+
+        let db = parts
+            .extensions
+            .get::<Arc<DatabaseConnection>>()
+            .cloned()
+            .ok_or_else(|| AuthRejection {
+                code: 500,
+                message: "Database connection unavailable".to_string(),
+            })?;
+
+        let model = match parts.headers.get(header::AUTHORIZATION) {
+            Some(value) => {
+                let raw = value
+                    .to_str()
+                    .map_err(|_| AuthRejection::unauthorized("Invalid token format"))?;
+                let token = raw.strip_prefix("Bearer ").unwrap_or(raw).trim();
+                let keys = parts
+                    .extensions
+                    .get::<Arc<KeyRing>>()
+                    .ok_or_else(|| AuthRejection {
+                        code: 500,
+                        message: "Key ring unavailable".to_string(),
+                    })?;
+                verify_token(db.as_ref(), keys, token).await?
+            }
+            // 未携带 token 时回退到后台 cookie 会话
+            None => {
+                let session = console_session(parts)
+                    .ok_or_else(|| AuthRejection::unauthorized("Missing token"))?;
+                verify_console_request(parts, &session, db).await?
+            }
+        };
+
+        let user = AuthUser(model2user(&model));
+        parts.extensions.insert(user.clone());
+        parts.extensions.insert(CurrentUser(model));
+        Ok(user)
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if !parts.headers.contains_key(header::AUTHORIZATION) && console_session(parts).is_none() {
+            return Ok(None);
+        }
+        <AuthUser as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// 已登录用户的数据库记录
+///
+/// 与 `AuthUser` 共用同一次校验，需要 `user.id` 等完整字段时使用，避免再次按 open_id 查询
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub user_entity::Model);
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if parts.extensions.get::<CurrentUser>().is_none() {
+            <AuthUser as FromRequestParts<S>>::from_request_parts(parts, state).await?;
+        }
+        parts
+            .extensions
+            .get::<CurrentUser>()
+            .cloned()
+            .ok_or_else(|| AuthRejection::unauthorized("Missing token"))
+    }
+}
+
+/// 当前用户生效的角色权限（内置角色 + 分配的角色），同一请求内只加载一次
+#[derive(Debug, Clone)]
+pub struct Permissions(pub PermissionSet);
+
+impl Permissions {
+    pub fn allows(&self, permission: &str) -> bool {
+        self.0.allows(permission)
+    }
+}
+
+impl<S> FromRequestParts<S> for Permissions
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(permissions) = parts.extensions.get::<Permissions>() {
+            return Ok(permissions.clone());
+        }
+        let CurrentUser(model) =
+            <CurrentUser as FromRequestParts<S>>::from_request_parts(parts, state).await?;
+        let db = parts
+            .extensions
+            .get::<Arc<DatabaseConnection>>()
+            .cloned()
+            .ok_or_else(|| AuthRejection {
+                code: 500,
+                message: "Database connection unavailable".to_string(),
+            })?;
+        let permissions = load_permissions(db.as_ref(), &model)
+            .await
+            .map(Permissions)
+            .map_err(|e| {
+                let (code, message) = e.code_and_message();
+                AuthRejection { code, message }
+            })?;
+        parts.extensions.insert(permissions.clone());
+        Ok(permissions)
+    }
+}
+
+/// 可选登录用户
+///
+/// 未携带 `Authorization` 头及后台会话 cookie 时为 `None`；携带了但 token 无效时仍然拒绝请求
+#[derive(Debug, Clone)]
+pub struct OptionalUser(pub Option<User>);
+
+impl<S> FromRequestParts<S> for OptionalUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user =
+            <AuthUser as OptionalFromRequestParts<S>>::from_request_parts(parts, state).await?;
+        Ok(OptionalUser(user.map(|u| u.0)))
+    }
+}
+
+/// 需要 Provider 及以上权限（permission >= 2）
+#[derive(Debug, Clone)]
+pub struct RequireProvider(pub User);
+
+impl<S> FromRequestParts<S> for RequireProvider
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = <AuthUser as FromRequestParts<S>>::from_request_parts(parts, state).await?;
+        if !user.has_level(&UserPermissionLevel::Provider) {
+            return Err(AuthRejection::insufficient(&UserPermissionLevel::Provider));
+        }
+        Ok(RequireProvider(user.0))
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use user_auth::rbac::permission::is_privileged;
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::step_up::admin_step_up;
+use super::{AuthRejection, AuthUser, CurrentUser, Permissions};
+
+/// 路由级权限中间件
+///
+/// 在路由中声明所需的最低权限，解析出的 `AuthUser` 会写入请求扩展供 handler 复用。
+/// 要求 Admin 权限时还会按 `TotpPolicy` 检查二次验证（`x-step-up-token` 头）：
+///
+/// ```ignore
+/// Router::new()
+///     .route("/", post(insert_notice))
+///     .route_layer(from_fn_with_state(UserPermissionLevel::Admin, require_permission))
+/// ```
+pub async fn require_permission(
+    State(required): State<UserPermissionLevel>,
+    user: AuthUser,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !user.has_level(&required) {
+        return AuthRejection::insufficient(&required).into_response();
+    }
+    if required == UserPermissionLevel::Admin
+        && let Err(rejection) = admin_step_up(&request).await
+    {
+        return rejection.into_response();
+    }
+    next.run(request).await
+}
+
+/// 路由级角色权限中间件
+///
+/// 与 `require_permission` 用法相同，但检查的是数据库中配置的角色权限，
+/// 适用于不按等级划分的角色（如健康指南编辑、反馈处理人）。需要二次验证的权限
+/// （见 `permission::PRIVILEGED_PERMISSIONS`）或 Admin 用户同样按 `TotpPolicy` 检查 step-up：
+///
+/// ```ignore
+/// Router::new()
+///     .route("/", post(insert_health_guide_type))
+///     .route_layer(from_fn_with_state(permission::HEALTH_GUIDE_WRITE, require_role_permission))
+/// ```
+pub async fn require_role_permission(
+    State(required): State<&'static str>,
+    CurrentUser(user): CurrentUser,
+    permissions: Permissions,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !permissions.allows(required) {
+        return AuthRejection::missing_permission(required).into_response();
+    }
+    let admin =
+        UserPermissionLevel::from(user.permission.unwrap_or(0)) == UserPermissionLevel::Admin;
+    if (admin || is_privileged(required))
+        && let Err(rejection) = admin_step_up(&request).await
+    {
+        return rejection.into_response();
+    }
+    next.run(request).await
+}
@@ -0,0 +1,31 @@
+//! 鉴权模块
+//!
+//! 基于 `user_auth` 提供统一的 axum 鉴权提取器与路由级权限声明：
+//! - `AuthUser`: 必须携带有效 token（权限 0-3 均可）
+//! - `CurrentUser`: 同 `AuthUser`，但返回完整的数据库记录
+//! - `Permissions`: 当前用户在数据库中配置的角色权限
+//! - `OptionalUser`: 可选 token，未携带时为 `None`
+//! - `RequireProvider`: 需要 Provider 及以上权限
+//! - `require_permission`: 路由级权限中间件，配合 `from_fn_with_state` 在路由中声明所需权限；
+//!   要求 Admin 时按 `TotpPolicy` 检查二次验证
+//! - `require_role_permission`: 路由级角色权限中间件，声明所需的权限标识（见 `user_auth::rbac::permission`）
+//!
+//! - `resolve_target`: 解析 `target_openid`，判断当前用户是否为目标用户生效的照护人
+//!
+//! 除 `Authorization` 头外，也接受管理后台登录后下发的加密 cookie 会话（见 `console`），
+//! 此时非 GET/HEAD/OPTIONS 请求必须携带 `x-csrf-token` 头。
+//!
+//! 所有拒绝均以 `ErrorResponse`（code/message）返回，格式与各接口的 Response 一致，
+//! HTTP 状态码与 code 相同（见 `crate::error::ApiError`）。
+
+pub mod console;
+mod delegation;
+mod extractor;
+mod middleware;
+mod rejection;
+mod step_up;
+
+pub use delegation::resolve_target;
+pub use extractor::{AuthUser, CurrentUser, OptionalUser, Permissions, RequireProvider};
+pub use middleware::{require_permission, require_role_permission};
+pub use rejection::AuthRejection;
@@ -0,0 +1,73 @@
+use axum::response::{IntoResponse, Response};
+use user_auth::db_exchange::ExchangeError;
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::error::ApiError;
+
+/// 鉴权失败时的统一拒绝
+///
+/// 以 `ApiError` 返回（HTTP 状态码与 code 一致），code 为 401（未登录/token 无效）、403（权限不足）、
+/// 423（账号封禁）或 410（账号已删除）
+#[derive(Debug, Clone)]
+pub struct AuthRejection {
+    pub code: i32,
+    pub message: String,
+}
+
+impl AuthRejection {
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        AuthRejection {
+            code: 401,
+            message: message.into(),
+        }
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        AuthRejection {
+            code: 403,
+            message: message.into(),
+        }
+    }
+
+    /// 权限不足时的拒绝，消息中注明所需的最低权限
+    pub fn insufficient(required: &UserPermissionLevel) -> Self {
+        Self::forbidden(format!(
+            "Permission denied: requires {:?} permission",
+            required
+        ))
+    }
+
+    /// 缺少角色权限时的拒绝，消息中注明所需的权限标识
+    pub fn missing_permission(permission: &str) -> Self {
+        Self::forbidden(format!("Permission denied: requires {}", permission))
+    }
+}
+
+impl From<ExchangeError> for AuthRejection {
+    fn from(err: ExchangeError) -> Self {
+        let message = match err {
+            ExchangeError::InvalidToken => "Invalid token".to_string(),
+            ExchangeError::TokenExpired => "Token expired".to_string(),
+            ExchangeError::TokenRevoked => "Token revoked".to_string(),
+            ExchangeError::RefreshTokenReused => "Refresh token reused".to_string(),
+            ExchangeError::AccountBlocked(e) => {
+                let (code, message) = e.code_and_message();
+                return AuthRejection { code, message };
+            }
+            ExchangeError::DatabaseError(e) => {
+                return AuthRejection {
+                    code: 500,
+                    message: format!("Database error: {}", e),
+                };
+            }
+            ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
+        };
+        AuthRejection::unauthorized(message)
+    }
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        ApiError::from(self).into_response()
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::extract::Request;
+use db_manager::entity::user as user_entity;
+use sea_orm::DatabaseConnection;
+use user_auth::db_exchange::KeyRing;
+use user_auth::totp::{STEP_UP_HEADER, TotpPolicy, check_admin_step_up};
+
+use super::{AuthRejection, CurrentUser};
+
+/// 按 `TotpPolicy` 检查 Admin 请求（或需要特权权限的请求）是否已完成二次验证
+///
+/// 策略与签名密钥从请求扩展中读取，由 `run` 统一注入；未注入时视为 `Off`。
+/// 先同步取出所需的值，返回的 future 不借用 `Request`（它不是 `Sync`）
+pub fn admin_step_up(
+    request: &Request,
+) -> impl Future<Output = Result<(), AuthRejection>> + Send + 'static {
+    let inputs = step_up_inputs(request);
+    async move {
+        let Some((policy, model, db, keys, token)) = inputs? else {
+            return Ok(());
+        };
+        check_admin_step_up(db.as_ref(), &keys, policy, &model, token.as_deref())
+            .await
+            .map_err(|e| {
+                let (code, message) = e.code_and_message();
+                AuthRejection { code, message }
+            })
+    }
+}
+
+type StepUpInputs = (
+    TotpPolicy,
+    user_entity::Model,
+    Arc<DatabaseConnection>,
+    Arc<KeyRing>,
+    Option<String>,
+);
+
+fn step_up_inputs(request: &Request) -> Result<Option<StepUpInputs>, AuthRejection> {
+    let policy = request
+        .extensions()
+        .get::<TotpPolicy>()
+        .copied()
+        .unwrap_or(TotpPolicy::Off);
+    if policy == TotpPolicy::Off {
+        return Ok(None);
+    }
+
+    let CurrentUser(model) = request
+        .extensions()
+        .get::<CurrentUser>()
+        .cloned()
+        .ok_or_else(|| AuthRejection::unauthorized("Missing token"))?;
+    let db = request
+        .extensions()
+        .get::<Arc<DatabaseConnection>>()
+        .cloned()
+        .ok_or_else(|| AuthRejection {
+            code: 500,
+            message: "Database connection unavailable".to_string(),
+        })?;
+    let keys = request
+        .extensions()
+        .get::<Arc<KeyRing>>()
+        .cloned()
+        .ok_or_else(|| AuthRejection {
+            code: 500,
+            message: "Key ring unavailable".to_string(),
+        })?;
+    let token = request
+        .headers()
+        .get(STEP_UP_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    Ok(Some((policy, model, db, keys, token)))
+}
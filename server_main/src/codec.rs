@@ -0,0 +1,160 @@
+//! 请求/响应的格式协商
+//!
+//! 所有接口使用 `crate::codec::Protobuf`，用法与 `axum_extra::protobuf::Protobuf` 相同，
+//! 同一个 handler 同时支持 protobuf 与 JSON（protobuf JSON 映射，字段名与 .proto 一致）：
+//! - 请求体按 `Content-Type` 解析：`application/json` 为 JSON，其余按 protobuf 解码
+//! - 响应按 `Accept` 选择：明确要求 JSON 或 protobuf 时按要求返回；未指定时与请求体一致，
+//!   multipart 上传返回 JSON，其余默认 protobuf
+//!
+//! 响应格式由 `negotiate` 中间件在请求进入时确定，未注册该中间件时（如测试）始终返回 protobuf
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use interface_types::proto::common::ErrorResponse;
+use prost::Message;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::error::{ApiError, error_status};
+
+/// protobuf 响应的 Content-Type，与 `axum_extra::protobuf::Protobuf` 一致
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/octet-stream";
+/// JSON 响应的 Content-Type
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Protobuf,
+    Json,
+}
+
+tokio::task_local! {
+    static RESPONSE_FORMAT: Format;
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> &str {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+}
+
+fn is_json(mime: &str) -> bool {
+    let essence = mime.split(';').next().unwrap_or("").trim();
+    essence == JSON_CONTENT_TYPE || essence.ends_with("+json")
+}
+
+impl Format {
+    /// 请求体的格式
+    pub fn of_body(headers: &HeaderMap) -> Format {
+        if is_json(header_str(headers, header::CONTENT_TYPE)) {
+            Format::Json
+        } else {
+            Format::Protobuf
+        }
+    }
+
+    /// 客户端期望的响应格式
+    pub fn of_response(headers: &HeaderMap) -> Format {
+        for accepted in header_str(headers, header::ACCEPT).split(',') {
+            let essence = accepted.split(';').next().unwrap_or("").trim();
+            if is_json(essence) {
+                return Format::Json;
+            }
+            if essence == PROTOBUF_CONTENT_TYPE || essence == "application/x-protobuf" {
+                return Format::Protobuf;
+            }
+        }
+        let content_type = header_str(headers, header::CONTENT_TYPE);
+        if is_json(content_type) || content_type.starts_with("multipart/form-data") {
+            Format::Json
+        } else {
+            Format::Protobuf
+        }
+    }
+
+    /// 当前请求协商出的响应格式
+    pub fn current() -> Format {
+        RESPONSE_FORMAT
+            .try_with(|format| *format)
+            .unwrap_or(Format::Protobuf)
+    }
+}
+
+/// 格式协商中间件，在 `lib.rs` 中注册在错误处理之外，使错误响应同样按协商的格式返回
+pub async fn negotiate(request: Request, next: Next) -> Response {
+    let format = Format::of_response(request.headers());
+    RESPONSE_FORMAT.scope(format, next.run(request)).await
+}
+
+/// protobuf / JSON 消息的提取器与响应
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Protobuf<T>(pub T);
+
+impl<T, S> FromRequest<S> for Protobuf<T>
+where
+    T: Message + Default + DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let format = Format::of_body(req.headers());
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+        // 空的请求体视为所有字段均为默认值
+        if bytes.is_empty() {
+            return Ok(Protobuf(T::default()));
+        }
+        match format {
+            Format::Json => serde_json::from_slice(&bytes).map(Protobuf).map_err(|err| {
+                ApiError::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    format!("Failed to parse the request body as JSON: {}", err),
+                )
+            }),
+            Format::Protobuf => T::decode(bytes).map(Protobuf).map_err(|err| {
+                ApiError::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    format!("Failed to decode the request body as protobuf: {}", err),
+                )
+            }),
+        }
+    }
+}
+
+/// 响应的 HTTP 状态码取自消息中的 code（所有 *Response 的 code 字段编号均为 2），
+/// code 为 4xx/5xx 时使用对应的状态码，否则为 200
+impl<T> IntoResponse for Protobuf<T>
+where
+    T: Message + Serialize,
+{
+    fn into_response(self) -> Response {
+        let bytes = self.0.encode_to_vec();
+        let status = ErrorResponse::decode(bytes.as_slice())
+            .ok()
+            .and_then(|envelope| error_status(envelope.code))
+            .unwrap_or(StatusCode::OK);
+        match Format::current() {
+            Format::Protobuf => (
+                status,
+                [(header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE)],
+                bytes,
+            )
+                .into_response(),
+            Format::Json => match serde_json::to_vec(&self.0) {
+                Ok(body) => {
+                    (status, [(header::CONTENT_TYPE, JSON_CONTENT_TYPE)], body).into_response()
+                }
+                Err(err) => {
+                    ApiError::internal(format!("Failed to encode JSON: {}", err)).into_response()
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! 统一的接口错误
+//!
+//! - `ApiError`: handler 返回 `Result<_, ApiError>` 即可，响应使用对应的 HTTP 状态码，
+//!   body 仍是 `ErrorResponse`（code/message），现有客户端可以用接口的 Response 直接解码
+//! - `api_errors`: 全局中间件，把提取器拒绝（`Query`、`Multipart` 等返回的纯文本）、
+//!   未匹配的路由转换为 `ErrorResponse`
+//!
+//! 仍在 body 中返回错误码的接口由 `crate::codec::Protobuf` 按 code 设置 HTTP 状态码
+//! - `panic_response`: 配合 `CatchPanicLayer` 把 handler 中的 panic 转换为 500
+
+use std::any::Any;
+
+use axum::{
+    body::to_bytes,
+    extract::Request,
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use interface_types::proto::common::ErrorResponse;
+use sea_orm::DbErr;
+use user_auth::audit::AuditError;
+
+use crate::auth::AuthRejection;
+use crate::codec::{JSON_CONTENT_TYPE, PROTOBUF_CONTENT_TYPE, Protobuf};
+
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// 由现有的 `(code, message)` 错误转换，code 不是 4xx/5xx 时视为 500
+    pub fn from_code(code: i32, message: impl Into<String>) -> Self {
+        Self::new(
+            error_status(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            message,
+        )
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl From<(i32, String)> for ApiError {
+    fn from((code, message): (i32, String)) -> Self {
+        ApiError::from_code(code, message)
+    }
+}
+
+impl From<DbErr> for ApiError {
+    fn from(err: DbErr) -> Self {
+        ApiError::internal(format!("Database error: {}", err))
+    }
+}
+
+impl From<AuditError> for ApiError {
+    fn from(err: AuditError) -> Self {
+        err.code_and_message().into()
+    }
+}
+
+impl From<AuthRejection> for ApiError {
+    fn from(rejection: AuthRejection) -> Self {
+        ApiError::from_code(rejection.code, rejection.message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if self.status.is_server_error() {
+            tracing::error!("{}: {}", self.status, self.message);
+        }
+        let body = Protobuf(ErrorResponse {
+            code: self.status.as_u16() as i32,
+            message: self.message,
+        });
+        (self.status, body).into_response()
+    }
+}
+
+/// 4xx/5xx 的错误码对应的状态码，其余（200、0 等）返回 `None`
+pub(crate) fn error_status(code: i32) -> Option<StatusCode> {
+    u16::try_from(code)
+        .ok()
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .filter(|status| status.is_client_error() || status.is_server_error())
+}
+
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// 全局错误中间件，在 `lib.rs` 中注册在所有路由之外
+///
+/// 状态码为 4xx/5xx 但不是由 `Protobuf` 生成的响应（提取器拒绝、404、405）转换为 `ErrorResponse`，
+/// 原响应的纯文本作为 message
+pub async fn api_errors(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    let is_message = matches!(
+        content_type(response.headers()),
+        Some(PROTOBUF_CONTENT_TYPE | JSON_CONTENT_TYPE)
+    );
+
+    if (status.is_client_error() || status.is_server_error()) && !is_message {
+        let (parts, body) = response.into_parts();
+        let text = to_bytes(body, usize::MAX)
+            .await
+            .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+            .unwrap_or_default();
+        let message = if text.is_empty() {
+            status.canonical_reason().unwrap_or("Error").to_string()
+        } else {
+            text
+        };
+        let mut converted = ApiError::new(status, message).into_response();
+        // 保留 `Allow` 等其余响应头
+        for (name, value) in parts.headers.iter() {
+            if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+                converted.headers_mut().insert(name, value.clone());
+            }
+        }
+        return converted;
+    }
+
+    response
+}
+
+/// `CatchPanicLayer::custom` 使用的 panic 处理，panic 信息只写日志，不返回给客户端
+pub fn panic_response(panic: Box<dyn Any + Send + 'static>) -> Response {
+    let details = if let Some(s) = panic.downcast_ref::<String>() {
+        s.as_str()
+    } else if let Some(s) = panic.downcast_ref::<&str>() {
+        s
+    } else {
+        "unknown panic"
+    };
+    tracing::error!("handler panicked: {}", details);
+    ApiError::internal("Internal server error").into_response()
+}
@@ -1,14 +1,28 @@
+pub mod audit;
+pub mod auth;
+pub mod codec;
+pub mod error;
+pub mod listing;
+pub mod resource;
 mod router;
 
-use axum::Router;
+use app_config::AppConfig;
+use audit::TrustedProxies;
+use axum::{Extension, Router, middleware::from_fn};
+use axum_extra::extract::cookie::Key;
+use codec::negotiate;
 use db_manager::migrator::Migrator;
-use db_manager::*;
 use dotenvy::dotenv;
+use error::{api_errors, panic_response};
 use router::ai_chat;
+use router::audit_log;
+use router::caregiver;
 use router::community_service;
+use router::console;
 use router::detail_meal;
 use router::dinner_provider;
 use router::feedback;
+use router::health;
 use router::health_guide_content;
 use router::health_guide_type;
 use router::medical_service;
@@ -17,57 +31,126 @@ use router::notice;
 use router::policy_file;
 use router::policy_type;
 use router::resource_service;
+use router::role;
 use router::service_map_content;
 use router::service_map_type;
 use router::slide_show;
+use router::totp;
 use router::user;
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{Database, DatabaseConnection};
 use sea_orm_migration::prelude::*;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tower_http::catch_panic::CatchPanicLayer;
 #[allow(unused_imports)]
 use tower_http::trace::TraceLayer;
+use user_auth::db_exchange::now_timestamp;
+use user_auth::personal_data::purge_due_accounts;
 
 #[derive(Clone)]
 pub struct AppState {
     pub database: Arc<DatabaseConnection>,
 }
 
-async fn build_database_connection() -> DatabaseConnection {
-    let db_config = DatabaseConfig::from_env().uri();
-    let mut options = ConnectOptions::new(&db_config);
-    options
-        .max_connections(10)
-        .min_connections(1)
-        .sqlx_logging(false)
-        .sqlx_logging_level(log::LevelFilter::Warn);
-
-    Database::connect(options)
-        .await
-        .expect("failed to connect to database")
+/// 清理宽限期已过的注销申请的间隔
+const ACCOUNT_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 后台定期执行到期的账号注销
+fn spawn_account_purge(database: Arc<DatabaseConnection>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ACCOUNT_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match purge_due_accounts(database.as_ref(), now_timestamp()).await {
+                Ok(report) => {
+                    if !report.purged.is_empty() {
+                        tracing::info!("purged {} deleted accounts", report.purged.len());
+                    }
+                    for (user_id, err) in &report.failed {
+                        tracing::warn!("account purge failed for user {}: {:?}", user_id, err);
+                    }
+                }
+                Err(err) => tracing::warn!("account purge failed: {:?}", err),
+            }
+        }
+    })
 }
 
-/// Build the application router under `/api` and attach shared state.
-/// Downstream routers should be nested under `/api`.
+/// 等待 SIGTERM 或 Ctrl+C
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::warn!("failed to listen for ctrl-c: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::warn!("failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("shutdown signal received, draining in-flight requests");
+}
+
+/// Load `.env` and the layered configuration, then serve until the listener fails.
 pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
+    let config = AppConfig::load()?;
+    serve(config).await
+}
+
+/// Build the application router under `/api` and attach shared state.
+/// Downstream routers should be nested under `/api`; health probes live at the root.
+/// On SIGTERM / Ctrl+C the server stops accepting connections, waits for in-flight
+/// requests to finish, then closes the database pool.
+pub async fn serve(config: AppConfig) -> Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::DEBUG)
+        .with_max_level(config.log.level)
         .init();
-    let database = build_database_connection().await;
+    let database = Database::connect(config.database.connect_options()).await?;
 
-    Migrator::refresh(&database).await?;
+    // 只执行尚未应用的迁移，清空重建数据库使用 `sd_admin migrate fresh`
+    Migrator::up(&database, None).await?;
 
+    let database = Arc::new(database);
+    let key_ring = Arc::new(config.auth.key_ring.clone());
+    let cookie_key = Key::from(&config.auth.cookie_key);
     let state = AppState {
-        database: Arc::new(database),
+        database: database.clone(),
     };
+    let account_purge = spawn_account_purge(database.clone());
 
     let api_router = Router::new()
+        .nest("/user", user::sign_in_router())
         .nest("/user", user::register_router())
         .nest("/user", user::login_router())
         .nest("/user", user::modify_router())
         .nest("/user", user::info_router())
         .nest("/user", user::apply_permission_router())
         .nest("/user", user::admin_manager_router())
+        .nest("/user", user::refresh_router())
+        .nest("/user", user::logout_router())
+        .nest("/user", user::revoke_token_router())
+        .nest("/user", user::status_router())
+        .nest("/user", user::phone_number_router())
+        .nest("/user", user::personal_data_router())
         .nest("/ai_chat", ai_chat::ai_chat_router())
         .nest("/notice", notice::notice_router())
         .nest("/mutil_media", mutil_media::mutil_media_router())
@@ -107,16 +190,48 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
             service_map_content::service_map_content_router(),
         )
         .nest("/policy_type", policy_type::policy_type_router())
-        .nest("/policy_file", policy_file::policy_file_router());
+        .nest("/policy_file", policy_file::policy_file_router())
+        .nest("/role", role::role_router())
+        .nest("/console", console::console_router())
+        .nest("/totp", totp::totp_router())
+        .nest("/caregiver", caregiver::caregiver_router())
+        .nest("/audit_log", audit_log::audit_log_router());
 
     let app = Router::new()
+        .merge(health::health_router())
         .nest("/api", api_router)
         .with_state(state)
+        // handler 中的 panic 转换为 500，错误响应统一为 `ErrorResponse` 并使用对应的 HTTP 状态码
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(from_fn(api_errors))
+        // 按 Accept / Content-Type 选择 JSON 或 protobuf，需要包住上面的错误处理
+        .layer(from_fn(negotiate))
+        .layer(Extension(database.clone()))
+        .layer(Extension(key_ring))
+        .layer(Extension(cookie_key))
+        .layer(Extension(config.auth.admin_totp_policy))
+        .layer(Extension(config.wx.clone()))
+        .layer(Extension(TrustedProxies(
+            config.server.trusted_proxies.clone(),
+        )))
         .layer(TraceLayer::new_for_http());
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3001").await.unwrap();
+    let listener = tokio::net::TcpListener::bind(config.server.bind_address).await?;
+    tracing::info!("listening on {}", config.server.bind_address);
+
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
-    axum::serve(listener, app).await?;
+    // 请求处理完毕后停止后台任务并关闭连接池
+    account_purge.abort();
+    if let Err(err) = database.close_by_ref().await {
+        tracing::warn!("failed to close database pool: {}", err);
+    }
+    tracing::info!("server stopped");
 
     Ok(())
 }
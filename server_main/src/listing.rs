@@ -0,0 +1,333 @@
+//! 列表接口通用的分页、排序与筛选
+//!
+//! 查询参数（均为可选）：
+//! - `page` / `page_size`: 分页，page 从 0 开始，page_size 默认 20，最大 200
+//! - `cursor` / `page_size`: 按 id 的游标分页，cursor 为上一页响应中的 next_cursor（首页不传 cursor 只传 page_size）
+//! - `sort`: 排序字段，逗号分隔，`-` 前缀为倒序，如 `sort=-create_time,name`
+//! - `q`: 在资源声明的文本字段中模糊搜索
+//! - `since` / `until`: create_time 的范围（unix 秒，含边界）
+//! - `<字段>=<值>`: 资源声明的可筛选字段按值相等筛选
+//!
+//! 未传 page、page_size、cursor 时返回全部记录，与之前的接口行为一致。
+//! 响应中的 total 为满足筛选条件的记录总数，next_cursor 在游标分页且还有下一页时不为空。
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use chrono::DateTime;
+use sea_orm::sea_query::LikeExpr;
+use sea_orm::{
+    ColumnTrait, ColumnType, Condition, DatabaseConnection, EntityTrait, FromQueryResult,
+    IdenStatic, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select,
+    Value,
+};
+
+use crate::error::ApiError;
+
+/// 默认每页条数
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+/// 每页最大条数
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+/// 列表接口的查询参数
+#[derive(Debug, Default, Clone)]
+pub struct ListQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    pub q: Option<String>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// 其余的 `字段=值` 参数
+    pub fields: Vec<(String, String)>,
+}
+
+fn parse_param<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("Invalid value for {}: {}", name, value)))
+}
+
+impl ListQuery {
+    pub fn from_pairs(pairs: Vec<(String, String)>) -> Result<Self, ApiError> {
+        let mut query = ListQuery::default();
+        for (name, value) in pairs {
+            match name.as_str() {
+                "page" => query.page = Some(parse_param(&name, &value)?),
+                "page_size" => query.page_size = Some(parse_param(&name, &value)?),
+                "cursor" => query.cursor = Some(value),
+                "sort" => query.sort = Some(value),
+                "q" => query.q = Some(value),
+                "since" => query.since = Some(parse_param(&name, &value)?),
+                "until" => query.until = Some(parse_param(&name, &value)?),
+                _ => query.fields.push((name, value)),
+            }
+        }
+        Ok(query)
+    }
+
+    fn page_size(&self) -> u64 {
+        self.page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+impl<S> FromRequestParts<S> for ListQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(pairs) = Query::<Vec<(String, String)>>::try_from_uri(&parts.uri)
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+        ListQuery::from_pairs(pairs)
+    }
+}
+
+/// 列表支持的排序、搜索与筛选字段，未声明的字段返回 400
+pub struct Listing<E: EntityTrait> {
+    /// 可用于 `sort` 的字段（id 始终可用）
+    pub sort: &'static [E::Column],
+    /// `q` 搜索的文本字段
+    pub search: &'static [E::Column],
+    /// 可按 `字段=值` 筛选的字段
+    pub filter: &'static [E::Column],
+    /// `since` / `until` 使用的创建时间字段
+    pub created: Option<E::Column>,
+}
+
+impl<E: EntityTrait> Listing<E> {
+    /// 只支持分页与按 id 排序
+    pub const NONE: Listing<E> = Listing {
+        sort: &[],
+        search: &[],
+        filter: &[],
+        created: None,
+    };
+}
+
+/// 一页记录
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// 满足筛选条件的记录总数
+    pub total: u64,
+    /// 下一页的游标，没有下一页时为空
+    pub next_cursor: String,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// 不分页的记录（新增、修改、按 id 查询的结果）
+impl<T> From<Vec<T>> for Page<T> {
+    fn from(items: Vec<T>) -> Self {
+        Page {
+            total: items.len() as u64,
+            items,
+            next_cursor: String::new(),
+        }
+    }
+}
+
+fn find_column<C: IdenStatic>(columns: &[C], name: &str) -> Option<C> {
+    columns.iter().copied().find(|c| c.as_str() == name)
+}
+
+/// 把查询参数中的值转换为字段对应的类型
+fn column_value<C: ColumnTrait>(column: C, value: &str) -> Result<Value, ApiError> {
+    let name = column.as_str();
+    Ok(match column.def().get_column_type() {
+        ColumnType::TinyInteger | ColumnType::SmallInteger | ColumnType::Integer => {
+            parse_param::<i32>(name, value)?.into()
+        }
+        ColumnType::BigInteger => parse_param::<i64>(name, value)?.into(),
+        ColumnType::Float | ColumnType::Double => parse_param::<f64>(name, value)?.into(),
+        ColumnType::Boolean => parse_param::<bool>(name, value)?.into(),
+        _ => value.into(),
+    })
+}
+
+fn timestamp(name: &str, secs: i64) -> Result<Value, ApiError> {
+    DateTime::from_timestamp(secs, 0)
+        .map(|t| t.naive_utc().into())
+        .ok_or_else(|| ApiError::bad_request(format!("Invalid value for {}: {}", name, secs)))
+}
+
+/// `q` 按字面值匹配：转义 LIKE 的通配符 `%`、`_` 与转义符 `\` 本身
+fn contains_literal(q: &str) -> LikeExpr {
+    let mut pattern = String::with_capacity(q.len() + 2);
+    pattern.push('%');
+    for c in q.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    LikeExpr::new(pattern).escape('\\')
+}
+
+/// 按 `q`、`since` / `until` 与 `字段=值` 筛选
+fn filtered<E: EntityTrait>(
+    mut select: Select<E>,
+    listing: &Listing<E>,
+    query: &ListQuery,
+) -> Result<Select<E>, ApiError> {
+    for (name, value) in &query.fields {
+        let column = find_column(listing.filter, name)
+            .ok_or_else(|| ApiError::bad_request(format!("Unsupported filter: {}", name)))?;
+        select = select.filter(column.eq(column_value(column, value)?));
+    }
+
+    if let Some(q) = query.q.as_deref().filter(|q| !q.is_empty()) {
+        if listing.search.is_empty() {
+            return Err(ApiError::bad_request("Search is not supported"));
+        }
+        let condition = listing.search.iter().fold(Condition::any(), |c, column| {
+            c.add(column.like(contains_literal(q)))
+        });
+        select = select.filter(condition);
+    }
+
+    if query.since.is_some() || query.until.is_some() {
+        let created = listing
+            .created
+            .ok_or_else(|| ApiError::bad_request("Filtering by create_time is not supported"))?;
+        if let Some(since) = query.since {
+            select = select.filter(created.gte(timestamp("since", since)?));
+        }
+        if let Some(until) = query.until {
+            select = select.filter(created.lte(timestamp("until", until)?));
+        }
+    }
+
+    Ok(select)
+}
+
+/// 解析 `sort`，返回排序字段与方向
+fn sort_order<E: EntityTrait>(
+    id: E::Column,
+    listing: &Listing<E>,
+    query: &ListQuery,
+) -> Result<Vec<(E::Column, Order)>, ApiError> {
+    let Some(sort) = query.sort.as_deref() else {
+        return Ok(vec![]);
+    };
+    sort.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (name, order) = match s.strip_prefix('-') {
+                Some(name) => (name, Order::Desc),
+                None => (s, Order::Asc),
+            };
+            let column = if name == id.as_str() {
+                Some(id)
+            } else {
+                find_column(listing.sort, name)
+            };
+            column
+                .map(|c| (c, order))
+                .ok_or_else(|| ApiError::bad_request(format!("Unsupported sort field: {}", name)))
+        })
+        .collect()
+}
+
+fn id_of<M: ModelTrait>(model: &M, id: <M::Entity as EntityTrait>::Column) -> String {
+    match model.get(id) {
+        Value::Int(Some(v)) => v.to_string(),
+        Value::BigInt(Some(v)) => v.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// 查询一页记录，`id` 为主键列，用于稳定排序与游标分页
+pub async fn fetch<E>(
+    db: &DatabaseConnection,
+    select: Select<E>,
+    id: E::Column,
+    listing: &Listing<E>,
+    query: &ListQuery,
+) -> Result<Page<E::Model>, ApiError>
+where
+    E: EntityTrait,
+    E::Model: FromQueryResult + Send + Sync,
+{
+    // 1) 筛选与排序，id 作为最后的排序字段保证顺序稳定
+    let select = filtered(select, listing, query)?;
+    let order = sort_order(id, listing, query)?;
+
+    // 2) 游标分页：只支持按 id 排序
+    if query.cursor.is_some() || (query.page_size.is_some() && query.page.is_none()) {
+        let direction = match order.as_slice() {
+            [] => Order::Asc,
+            [(column, order)] if column.as_str() == id.as_str() => order.clone(),
+            _ => {
+                return Err(ApiError::bad_request(
+                    "Cursor pagination only supports sorting by id",
+                ));
+            }
+        };
+        let total = select.clone().count(db).await?;
+        let mut select = select.order_by(id, direction.clone());
+        if let Some(cursor) = query.cursor.as_deref().filter(|c| !c.is_empty()) {
+            let after = column_value(id, cursor)
+                .map_err(|_| ApiError::bad_request(format!("Invalid cursor: {}", cursor)))?;
+            select = match direction {
+                Order::Desc => select.filter(id.lt(after)),
+                _ => select.filter(id.gt(after)),
+            };
+        }
+        let page_size = query.page_size();
+        let mut items = select.limit(page_size + 1).all(db).await?;
+        let next_cursor = if items.len() as u64 > page_size {
+            items.truncate(page_size as usize);
+            items.last().map(|m| id_of(m, id)).unwrap_or_default()
+        } else {
+            String::new()
+        };
+        return Ok(Page {
+            items,
+            total,
+            next_cursor,
+        });
+    }
+
+    let mut sorted = select.clone();
+    for (column, direction) in order {
+        sorted = sorted.order_by(column, direction);
+    }
+    let sorted = sorted.order_by_asc(id);
+
+    // 3) 按页码分页
+    if let Some(page) = query.page {
+        let page_size = query.page_size();
+        // 数据库的 OFFSET 为有符号 64 位整数
+        let offset = page
+            .checked_mul(page_size)
+            .filter(|offset| i64::try_from(*offset).is_ok())
+            .ok_or_else(|| ApiError::bad_request(format!("Invalid value for page: {}", page)))?;
+        let total = select.count(db).await?;
+        let items = sorted.offset(offset).limit(page_size).all(db).await?;
+        return Ok(Page {
+            items,
+            total,
+            next_cursor: String::new(),
+        });
+    }
+
+    // 4) 未分页时返回全部记录
+    Ok(Page::from(sorted.all(db).await?))
+}
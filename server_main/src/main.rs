@@ -1,7 +1,8 @@
 use server_main::run;
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    run().await?;
-
-    Ok(())
+async fn main() {
+    if let Err(err) = run().await {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,372 @@
+//! 通用的内容资源（CRUD）
+//!
+//! 社区服务、医疗服务、政策类型、明细餐等内容表的增删改查只在字段上不同，实现 `Resource` 后用
+//! `resource_router::<R>()` 生成路由，挂载在 `/api/<资源>` 下：
+//! - GET / - 获取列表（分页、排序与筛选见 `crate::listing`，支持的字段由 `R::LISTING` 声明）
+//! - GET /{id} - 按 id 获取单条记录
+//! - POST / - 新增（payload 为 `R::Request`）
+//! - PUT /?id=xxx - 修改，payload 中非空/非零的字段覆盖，其他保持不变
+//! - DELETE /?id=xxx - 删除
+//!
+//! 每个动作按 `Access` 声明所需权限，错误统一以 `ApiError` 返回，变更与审计日志在同一事务中写入。
+//! 行级校验（如 Provider 只能操作自己负责的记录）在 `authorize_create` / `authorize_update` /
+//! `authorize_delete` 中完成；修改与删除默认按 `?id=` 查找记录，可通过 `locate` 换成其他参数
+//! （如健康指南内容按 type_one 与 type_two 查找）。
+//!
+//! 轮播图的新增需要上传图片（multipart），不使用本模块。
+//!
+//! ```rust,ignore
+//! pub struct PolicyType;
+//!
+//! impl Resource for PolicyType {
+//!     type Entity = policy_type::Entity;
+//!     type Model = policy_type::Model;
+//!     type ActiveModel = policy_type::ActiveModel;
+//!     type Proto = ProtoPolicyType;
+//!     type Request = PolicyTypeRequest;
+//!     type Response = PolicyTypeResponse;
+//!
+//!     const NAME: &'static str = "policy type";
+//!     const ID: policy_type::Column = policy_type::Column::Id;
+//!     const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+//!     // to_proto / response / assign
+//! }
+//! ```
+
+use std::future::Future;
+
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    middleware::from_fn_with_state,
+    routing::{delete, get, post, put},
+};
+use db_manager::entity::user as user_entity;
+use prost::Message;
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection,
+    EntityTrait, FromQueryResult, IntoActiveModel, ModelTrait, QueryFilter, Select, Set,
+    TransactionTrait, prelude::Json,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, Permissions, require_permission, require_role_permission};
+use crate::codec::Protobuf;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page, fetch};
+
+/// 接口所需的权限
+#[derive(Debug, Clone)]
+pub enum Access {
+    /// 无需登录
+    Public,
+    /// 不低于该权限等级（见 `require_permission`）
+    Level(UserPermissionLevel),
+    /// 持有该角色权限（见 `require_role_permission`）
+    Permission(&'static str),
+}
+
+impl Access {
+    fn guard(self, router: Router<AppState>) -> Router<AppState> {
+        match self {
+            Access::Public => router,
+            Access::Level(level) => {
+                router.route_layer(from_fn_with_state(level, require_permission))
+            }
+            Access::Permission(permission) => {
+                router.route_layer(from_fn_with_state(permission, require_role_permission))
+            }
+        }
+    }
+}
+
+pub trait Resource: Sized + Send + Sync + 'static {
+    type Entity: EntityTrait<Model = Self::Model>;
+    type Model: ModelTrait<Entity = Self::Entity>
+        + FromQueryResult
+        + IntoActiveModel<Self::ActiveModel>
+        + Clone
+        + Send
+        + Sync;
+    type ActiveModel: ActiveModelTrait<Entity = Self::Entity>
+        + ActiveModelBehavior
+        + Default
+        + Send
+        + Sync;
+    /// 单条记录的 proto 消息
+    type Proto;
+    /// 新增与修改的 payload
+    type Request: Message + Default + DeserializeOwned + Send + 'static;
+    /// 列表响应，code/message 字段编号为 2/3
+    type Response: Message + Serialize + Send + 'static;
+
+    /// 资源名称，用于响应消息（如 "community service"）
+    const NAME: &'static str;
+    /// 主键列，`?id=` 按该列查找
+    const ID: <Self::Entity as EntityTrait>::Column;
+    /// 列表支持的排序、搜索与筛选字段
+    const LISTING: Listing<Self::Entity> = Listing::NONE;
+
+    const READ: Access = Access::Public;
+    const WRITE: Access;
+    const CREATE: Access = Self::WRITE;
+    const UPDATE: Access = Self::WRITE;
+    const DELETE: Access = Self::WRITE;
+
+    fn to_proto(model: Self::Model) -> Self::Proto;
+
+    /// 成功的响应，code 为 200
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response;
+
+    /// 把 payload 中非空/非零的字段写入 `active`（新增与修改共用），格式错误时返回 400
+    fn assign(request: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError>;
+
+    /// 新增前的校验，如必填字段
+    fn validate_create(_request: &Self::Request) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    /// `LISTING` 之外的列表筛选或参数校验，如必填的筛选字段、旧的参数名
+    fn filter(
+        select: Select<Self::Entity>,
+        _query: &mut ListQuery,
+    ) -> Result<Select<Self::Entity>, ApiError> {
+        Ok(select)
+    }
+
+    /// 按修改与删除的查询参数查找目标记录，默认按 `?id=`
+    fn locate(
+        db: &DatabaseConnection,
+        params: &[(String, String)],
+    ) -> impl Future<Output = Result<Self::Model, ApiError>> + Send {
+        async move {
+            let id = required_param(params, "id")?;
+            let id = id
+                .parse::<i32>()
+                .map_err(|_| ApiError::bad_request(format!("Invalid value for id: {}", id)))?;
+            find::<Self>(db, id).await
+        }
+    }
+
+    /// 新增前的行级校验，可补全 `active` 中的字段（如 Provider 省略的所属供餐点）
+    fn authorize_create(
+        _db: &DatabaseConnection,
+        _user: &user_entity::Model,
+        _permissions: &Permissions,
+        _active: &mut Self::ActiveModel,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// 修改前的行级校验（如 Provider 只能修改自己负责的记录），`active` 为已应用 payload 的记录
+    fn authorize_update(
+        _db: &DatabaseConnection,
+        _user: &user_entity::Model,
+        _permissions: &Permissions,
+        _model: &Self::Model,
+        _active: &Self::ActiveModel,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// 删除前的行级校验（如 Provider 只能删除自己负责的记录）
+    fn authorize_delete(
+        _db: &DatabaseConnection,
+        _user: &user_entity::Model,
+        _permissions: &Permissions,
+        _model: &Self::Model,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// 生成资源的增删改查路由
+pub fn resource_router<R: Resource>() -> Router<AppState> {
+    R::READ
+        .guard(
+            Router::new()
+                .route("/", get(list::<R>))
+                .route("/{id}", get(get_one::<R>)),
+        )
+        .merge(R::CREATE.guard(Router::new().route("/", post(create::<R>))))
+        .merge(R::UPDATE.guard(Router::new().route("/", put(update::<R>))))
+        .merge(R::DELETE.guard(Router::new().route("/", delete(remove::<R>))))
+}
+
+/// 取出非空的必填参数，缺失时返回 400
+pub fn required_param<'a>(params: &'a [(String, String)], name: &str) -> Result<&'a str, ApiError> {
+    params
+        .iter()
+        .find(|(key, value)| key == name && !value.is_empty())
+        .map(|(_, value)| value.as_str())
+        .ok_or_else(|| ApiError::bad_request(format!("Missing required parameter: {}", name)))
+}
+
+/// 非默认值（非空字符串、非零）时写入字段
+pub fn patch<T>(field: &mut ActiveValue<Option<T>>, value: T)
+where
+    T: Default + PartialEq,
+    Option<T>: Into<sea_orm::Value>,
+{
+    if value != T::default() {
+        *field = Set(Some(value));
+    }
+}
+
+/// 解析以字符串传入的 JSON 字段，空字符串为 `Json::Null`（配合 `patch` 时不写入）
+pub fn parse_json(value: &str, field: &str) -> Result<Json, ApiError> {
+    if value.is_empty() {
+        return Ok(Json::Null);
+    }
+    value
+        .parse::<Json>()
+        .map_err(|_| ApiError::bad_request(format!("Invalid JSON format for {}", field)))
+}
+
+/// 首字母大写的资源名称，用于错误消息
+fn title<R: Resource>() -> String {
+    let mut chars = R::NAME.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// 按主键查找，不存在时返回 404
+async fn find<R: Resource>(db: &DatabaseConnection, id: i32) -> Result<R::Model, ApiError> {
+    R::Entity::find()
+        .filter(R::ID.eq(id))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("{} with id '{}' not found", title::<R>(), id)))
+}
+
+/// GET / - 获取列表
+async fn list<R: Resource>(
+    State(state): State<AppState>,
+    mut query: ListQuery,
+) -> Result<Protobuf<R::Response>, ApiError> {
+    // 1) 资源自定义的筛选
+    let db = state.database.clone();
+    let select = R::filter(R::Entity::find(), &mut query)?;
+
+    // 2) 分页查询并转换为 proto
+    let page = fetch(db.as_ref(), select, R::ID, &R::LISTING, &query).await?;
+    Ok(Protobuf(R::response(
+        page.map(R::to_proto),
+        format!("Get {} list success", R::NAME),
+    )))
+}
+
+/// GET /{id} - 按 id 获取
+async fn get_one<R: Resource>(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Protobuf<R::Response>, ApiError> {
+    let db = state.database.clone();
+    let model = find::<R>(db.as_ref(), id).await?;
+    Ok(Protobuf(R::response(
+        vec![R::to_proto(model)].into(),
+        format!("Get {} success", R::NAME),
+    )))
+}
+
+/// POST / - 新增
+async fn create<R: Resource>(
+    State(state): State<AppState>,
+    audit: Auditor,
+    CurrentUser(current_user): CurrentUser,
+    permissions: Permissions,
+    Protobuf(payload): Protobuf<R::Request>,
+) -> Result<Protobuf<R::Response>, ApiError> {
+    // 1) 校验并构造新记录
+    R::validate_create(&payload)?;
+    let mut active = <R::ActiveModel as Default>::default();
+    R::assign(payload, &mut active)?;
+
+    // 2) 行级校验
+    let db = state.database.clone();
+    R::authorize_create(db.as_ref(), &current_user, &permissions, &mut active).await?;
+
+    // 3) 插入，审计记录在同一事务中写入
+    let txn = db.begin().await?;
+    let inserted = active
+        .insert(&txn)
+        .await
+        .map_err(|err| ApiError::internal(format!("Failed to insert {}: {}", R::NAME, err)))?;
+    audit.insert(&txn, &inserted).await?;
+    txn.commit().await?;
+
+    // 4) 返回新增的记录
+    Ok(Protobuf(R::response(
+        vec![R::to_proto(inserted)].into(),
+        format!("Insert {} success", R::NAME),
+    )))
+}
+
+/// PUT /?id=xxx - 修改
+async fn update<R: Resource>(
+    State(state): State<AppState>,
+    audit: Auditor,
+    CurrentUser(current_user): CurrentUser,
+    permissions: Permissions,
+    Query(params): Query<Vec<(String, String)>>,
+    Protobuf(payload): Protobuf<R::Request>,
+) -> Result<Protobuf<R::Response>, ApiError> {
+    // 1) 查找目标记录
+    let db = state.database.clone();
+    let target = R::locate(db.as_ref(), &params).await?;
+
+    // 2) 应用部分更新（主键保持不变）并做行级校验
+    let mut active = target.clone().into_active_model();
+    R::assign(payload, &mut active)?;
+    R::authorize_update(db.as_ref(), &current_user, &permissions, &target, &active).await?;
+
+    // 3) 更新，审计记录在同一事务中写入
+    let txn = db.begin().await?;
+    let updated = active
+        .update(&txn)
+        .await
+        .map_err(|err| ApiError::internal(format!("Failed to update {}: {}", R::NAME, err)))?;
+    audit.update(&txn, &target, &updated).await?;
+    txn.commit().await?;
+
+    // 4) 返回更新后的记录
+    Ok(Protobuf(R::response(
+        vec![R::to_proto(updated)].into(),
+        format!("Modify {} success", R::NAME),
+    )))
+}
+
+/// DELETE /?id=xxx - 删除
+async fn remove<R: Resource>(
+    State(state): State<AppState>,
+    audit: Auditor,
+    CurrentUser(current_user): CurrentUser,
+    permissions: Permissions,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Protobuf<R::Response>, ApiError> {
+    // 1) 查找要删除的记录并做行级校验
+    let db = state.database.clone();
+    let target = R::locate(db.as_ref(), &params).await?;
+    R::authorize_delete(db.as_ref(), &current_user, &permissions, &target).await?;
+
+    // 2) 删除，审计记录在同一事务中写入
+    let txn = db.begin().await?;
+    target
+        .clone()
+        .delete(&txn)
+        .await
+        .map_err(|err| ApiError::internal(format!("Failed to delete {}: {}", R::NAME, err)))?;
+    audit.delete(&txn, &target).await?;
+    txn.commit().await?;
+
+    // 3) 返回成功响应
+    Ok(Protobuf(R::response(
+        Page::from(vec![]),
+        format!("Delete {} success", R::NAME),
+    )))
+}
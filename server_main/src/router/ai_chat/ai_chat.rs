@@ -1,82 +1,84 @@
-use axum::{Router, extract::State, http::HeaderMap, routing::post};
-use axum_extra::protobuf::Protobuf;
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::post};
 use db_manager::entity::ai_chat as ai_chat_entity;
 use interface_types::proto::ai_chat::{AiChat as ProtoAiChat, AiChatRequest, AiChatResponse};
-use sea_orm::{ActiveModelTrait, Set};
-use user_auth::db_exchange::{ExchangeError, token2user};
+use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
+use user_auth::caregiver::{Delegation, action, record_caregiver_action};
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, require_permission, resolve_target};
+use crate::codec::Protobuf;
 
 /// 创建 ai_chat 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(insert_ai_chat))
+    Router::new()
+        .route("/", post(insert_ai_chat))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
 /// POST /api/ai_chat - 新增 AI 聊天记录（所有权限 0-3 都可以访问）
+/// 生效的照护人可以通过 target_openid 代被照护人记录
 async fn insert_ai_chat(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
     Protobuf(payload): Protobuf<AiChatRequest>,
 ) -> Protobuf<AiChatResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
+    // 1) 解析记录归属的用户
+    let db = state.database.clone();
+    let (owner, delegation) =
+        match resolve_target(db.as_ref(), &current, payload.target_openid.as_deref()).await {
+            Ok(v) => v,
+            Err((code, message)) => {
                 return Protobuf(AiChatResponse {
                     ai_chat: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
+                    code,
+                    message,
                 });
             }
-        },
-        None => {
-            return Protobuf(AiChatResponse {
-                ai_chat: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息（从 token 中获取 openid）
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(AiChatResponse {
-                ai_chat: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 权限校验：所有权限 0-3 都可以访问
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission < 0 || user_permission > 3 {
+        };
+    if matches!(delegation, Delegation::Unrelated) {
         return Protobuf(AiChatResponse {
             ai_chat: None,
             code: 403,
-            message: "Permission denied: Invalid permission level".to_string(),
+            message: "Permission denied: not a caregiver of target user".to_string(),
         });
     }
 
-    // 4) 创建新的 ActiveModel 并插入（openid 从 token 中获取）
-    let db = state.database.clone();
+    // 2) 创建新的 ActiveModel 并插入，照护人代为记录时同一事务内写入操作记录
     let new_ai_chat = ai_chat_entity::ActiveModel {
         index: Set(payload.index),
-        openid: Set(Some(auth_user.open_id)),
+        openid: Set(Some(owner.open_id)),
         long_content: Set(payload.long_content),
         ..Default::default()
     };
-
-    // 5) 执行插入
-    let inserted_ai_chat = match new_ai_chat.insert(db.as_ref()).await {
+    let inserted = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = new_ai_chat.insert(&txn).await.map_err(|e| e.to_string())?;
+        audit
+            .insert(&txn, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        if let Delegation::Caregiver(link) = &delegation {
+            record_caregiver_action(
+                &txn,
+                Some(link.caregiver_id),
+                link.elder_id,
+                action::AI_CHAT_INSERT,
+                Some(format!("ai_chat {}", model.id)),
+            )
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        }
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let inserted_ai_chat = match inserted {
         Ok(n) => n,
         Err(err) => {
             return Protobuf(AiChatResponse {
@@ -87,7 +89,7 @@ async fn insert_ai_chat(
         }
     };
 
-    // 6) 返回新增的 ai_chat（不包含 long_content 和 openid）
+    // 3) 返回新增的 ai_chat（不包含 long_content 和 openid）
     Protobuf(AiChatResponse {
         ai_chat: Some(ProtoAiChat {
             id: inserted_ai_chat.id,
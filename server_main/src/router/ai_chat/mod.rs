@@ -1,4 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod ai_chat;
 
 pub use ai_chat::router as ai_chat_router;
-
@@ -0,0 +1,109 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Response,
+    routing::get,
+};
+use user_auth::audit::{MAX_AUDIT_EXPORT_ROWS, export_audit_logs};
+use user_auth::rbac::permission;
+
+use super::AuditLogParams;
+use crate::AppState;
+use crate::auth::require_role_permission;
+use crate::error::ApiError;
+use crate::router::{XLSX_CONTENT_TYPE, attachment};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/export", get(export))
+        .route_layer(from_fn_with_state(
+            permission::AUDIT_READ,
+            require_role_permission,
+        ))
+}
+
+/// GET /api/audit_log/export?actor_openid=&action=&entity=&entity_id=&since=&until=
+/// 按条件导出审计记录（需要 audit.read 权限），最多导出最近的 10000 条
+///
+/// 返回 Excel 文件流
+async fn export(
+    State(state): State<AppState>,
+    Query(params): Query<AuditLogParams>,
+) -> Result<Response, ApiError> {
+    // 1) 解析筛选条件
+    let query = params.to_query()?;
+
+    // 2) 查询
+    let db = state.database.clone();
+    let logs = export_audit_logs(db.as_ref(), &query, MAX_AUDIT_EXPORT_ROWS)
+        .await
+        .map_err(|err| err.code_and_message())?;
+
+    // 3) 生成 Excel
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // 表头
+    let headers = [
+        "序号",
+        "时间",
+        "操作者",
+        "动作",
+        "表",
+        "主键",
+        "变更前",
+        "变更后",
+        "IP",
+    ];
+    for (col, title) in headers.iter().enumerate() {
+        let _ = worksheet.write_string(0, col as u16, *title);
+    }
+
+    let cst = chrono::FixedOffset::east_opt(8 * 3600).unwrap();
+    for (idx, item) in logs.iter().enumerate() {
+        let row = (idx + 1) as u32;
+        let created_at = chrono::DateTime::from_timestamp(item.created_at, 0)
+            .map(|dt| {
+                dt.with_timezone(&cst)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let _ = worksheet.write_number(row, 0, (idx + 1) as f64);
+        let _ = worksheet.write_string(row, 1, created_at);
+        let _ = worksheet.write_string(row, 2, item.actor.clone().unwrap_or_default());
+        let _ = worksheet.write_string(row, 3, &item.action);
+        let _ = worksheet.write_string(row, 4, &item.entity);
+        let _ = worksheet.write_string(row, 5, &item.entity_id);
+        let _ = worksheet.write_string(
+            row,
+            6,
+            item.before
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        );
+        let _ = worksheet.write_string(
+            row,
+            7,
+            item.after
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        );
+        let _ = worksheet.write_string(row, 8, item.ip.clone().unwrap_or_default());
+    }
+
+    let buffer = workbook
+        .save_to_buffer()
+        .map_err(|err| ApiError::internal(format!("Failed to build xlsx: {}", err)))?;
+
+    // 4) 返回文件流
+    let filename = format!(
+        "audit_log_{}_{}.xlsx",
+        query.since.unwrap_or_default(),
+        query.until.unwrap_or_default()
+    );
+    Ok(attachment(XLSX_CONTENT_TYPE, &filename, buffer))
+}
@@ -0,0 +1,83 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::get,
+};
+use interface_types::proto::audit_log::{AuditLog as ProtoAuditLog, AuditLogResponse};
+use user_auth::audit::{DEFAULT_AUDIT_PAGE_SIZE, MAX_AUDIT_PAGE_SIZE, list_audit_logs};
+use user_auth::rbac::permission;
+
+use super::AuditLogParams;
+use crate::AppState;
+use crate::auth::require_role_permission;
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list))
+        .route_layer(from_fn_with_state(
+            permission::AUDIT_READ,
+            require_role_permission,
+        ))
+}
+
+/// GET /api/audit_log?actor_openid=&action=&entity=&entity_id=&since=&until=&page=&page_size=
+/// 按条件分页查询审计记录（需要 audit.read 权限），按时间倒序
+async fn list(
+    State(state): State<AppState>,
+    Query(params): Query<AuditLogParams>,
+) -> Protobuf<AuditLogResponse> {
+    // 1) 解析筛选条件与分页
+    let query = match params.to_query() {
+        Ok(q) => q,
+        Err((code, message)) => {
+            return Protobuf(AuditLogResponse {
+                logs: vec![],
+                total: 0,
+                code,
+                message,
+            });
+        }
+    };
+    let page = params.page.unwrap_or(0);
+    let page_size = params
+        .page_size
+        .unwrap_or(DEFAULT_AUDIT_PAGE_SIZE)
+        .clamp(1, MAX_AUDIT_PAGE_SIZE);
+
+    // 2) 查询
+    let db = state.database.clone();
+    let (logs, total) = match list_audit_logs(db.as_ref(), &query, page, page_size).await {
+        Ok(r) => r,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(AuditLogResponse {
+                logs: vec![],
+                total: 0,
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(AuditLogResponse {
+        logs: logs
+            .into_iter()
+            .map(|l| ProtoAuditLog {
+                id: l.id,
+                actor_openid: l.actor,
+                action: l.action,
+                entity: l.entity,
+                entity_id: l.entity_id,
+                before: l.before.map(|v| v.to_string()),
+                after: l.after.map(|v| v.to_string()),
+                ip: l.ip,
+                created_at: l.created_at,
+            })
+            .collect(),
+        total,
+        code: 200,
+        message: "Get audit logs success".to_string(),
+    })
+}
@@ -0,0 +1,58 @@
+//! AuditLog 路由模块
+//!
+//! 查询与导出 `audit_log` 中的审计记录（见 `crate::audit`），需要 audit.read 权限：
+//! - GET /api/audit_log - 按条件分页查询，返回记录与总数
+//! - GET /api/audit_log/export - 按相同条件导出 Excel
+//!
+//! 筛选条件均为可选：actor_openid、action（insert | update | delete）、entity（表名）、
+//! entity_id、since / until（unix 秒，含边界）。
+
+mod export;
+mod list;
+
+use axum::Router;
+use serde::Deserialize;
+use user_auth::audit::{AuditAction, AuditQuery};
+
+use crate::AppState;
+
+/// 创建并返回 audit_log 的完整路由
+pub fn audit_log_router() -> Router<AppState> {
+    Router::new().merge(list::router()).merge(export::router())
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct AuditLogParams {
+    actor_openid: Option<String>,
+    action: Option<String>,
+    entity: Option<String>,
+    entity_id: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    /// 页码，从 0 开始（仅查询接口）
+    page: Option<u64>,
+    /// 每页条数（仅查询接口）
+    page_size: Option<u64>,
+}
+
+impl AuditLogParams {
+    /// 转换为筛选条件，action 不合法时返回 400
+    fn to_query(&self) -> Result<AuditQuery, (i32, String)> {
+        let action = match self.action.as_deref() {
+            Some(action) => Some(
+                AuditAction::parse(action)
+                    .ok_or_else(|| (400, format!("Invalid action: {}", action)))?,
+            ),
+            None => None,
+        };
+        Ok(AuditQuery {
+            actor_open_id: self.actor_openid.clone(),
+            action,
+            entity: self.entity.clone(),
+            entity_id: self.entity_id.clone(),
+            since: self.since,
+            until: self.until,
+        })
+    }
+}
@@ -0,0 +1,257 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::{delete, get, post},
+};
+use db_manager::entity::caregiver_link;
+use interface_types::proto::caregiver::{
+    CaregiverApproveRequest, CaregiverLinkResponse, CaregiverRequest, CaregiverRespondRequest,
+};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::Deserialize;
+use user_auth::caregiver::{approve_link, list_links, request_link, respond_link, revoke_link};
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::{find_link, find_user, is_admin, links_to_proto};
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, require_permission};
+use crate::codec::Protobuf;
+
+#[derive(Debug, Deserialize)]
+struct RevokeQuery {
+    caregiver_openid: String,
+    elder_openid: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/approve",
+            post(approve).route_layer(from_fn_with_state(
+                UserPermissionLevel::Admin,
+                require_permission,
+            )),
+        )
+        .route(
+            "/request",
+            post(request).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+        .route(
+            "/respond",
+            post(respond).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+        .route(
+            "/",
+            delete(revoke).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+        .route(
+            "/list",
+            get(list).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+}
+
+fn error_response(code: i32, message: String) -> Protobuf<CaregiverLinkResponse> {
+    Protobuf(CaregiverLinkResponse {
+        links: vec![],
+        code,
+        message,
+    })
+}
+
+/// 转换为响应，用户 id 替换为 open_id
+async fn links_response(
+    db: &DatabaseConnection,
+    links: Vec<caregiver_link::Model>,
+    message: &str,
+) -> Protobuf<CaregiverLinkResponse> {
+    match links_to_proto(db, links).await {
+        Ok(links) => Protobuf(CaregiverLinkResponse {
+            links,
+            code: 200,
+            message: message.to_string(),
+        }),
+        Err((code, message)) => error_response(code, message),
+    }
+}
+
+/// POST /api/caregiver/request
+/// 照护人发起关联，等待被照护人同意或 Admin 审批
+async fn request(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<CaregiverRequest>,
+) -> Protobuf<CaregiverLinkResponse> {
+    // 1) 查询被照护人
+    let db = state.database.clone();
+    let elder = match find_user(db.as_ref(), &payload.elder_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+
+    // 2) 创建待处理的关联，审计记录在同一事务中写入
+    let before = find_link(db.as_ref(), current.id, elder.id).await;
+    let changed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let link = request_link(&txn, &current, &elder, payload.relationship)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .change(&txn, before.as_ref(), Some(&link))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(link)
+    }
+    .await;
+    match changed {
+        Ok(link) => links_response(db.as_ref(), vec![link], "Request caregiver link success").await,
+        Err((code, message)) => error_response(code, message),
+    }
+}
+
+/// POST /api/caregiver/respond
+/// 被照护人本人同意或拒绝待处理的申请
+async fn respond(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<CaregiverRespondRequest>,
+) -> Protobuf<CaregiverLinkResponse> {
+    // 1) 查询照护人
+    let db = state.database.clone();
+    let caregiver = match find_user(db.as_ref(), &payload.caregiver_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+
+    // 2) 处理申请，审计记录在同一事务中写入
+    let before = find_link(db.as_ref(), caregiver.id, current.id).await;
+    let changed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let link = respond_link(&txn, &current, caregiver.id, payload.accept)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .change(&txn, before.as_ref(), Some(&link))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(link)
+    }
+    .await;
+    match changed {
+        Ok(link) => links_response(db.as_ref(), vec![link], "Respond caregiver link success").await,
+        Err((code, message)) => error_response(code, message),
+    }
+}
+
+/// POST /api/caregiver/approve
+/// 仅 Admin 权限：直接建立生效的关联，用于无法自行同意的被照护人
+async fn approve(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<CaregiverApproveRequest>,
+) -> Protobuf<CaregiverLinkResponse> {
+    // 1) 查询双方
+    let db = state.database.clone();
+    let caregiver = match find_user(db.as_ref(), &payload.caregiver_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+    let elder = match find_user(db.as_ref(), &payload.elder_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+
+    // 2) 建立关联，审计记录在同一事务中写入
+    let before = find_link(db.as_ref(), caregiver.id, elder.id).await;
+    let changed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let link = approve_link(&txn, &current, &caregiver, &elder, payload.relationship)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .change(&txn, before.as_ref(), Some(&link))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(link)
+    }
+    .await;
+    match changed {
+        Ok(link) => links_response(db.as_ref(), vec![link], "Approve caregiver link success").await,
+        Err((code, message)) => error_response(code, message),
+    }
+}
+
+/// DELETE /api/caregiver?caregiver_openid=xxx&elder_openid=xxx
+/// 关联的任一方或 Admin 解除关联，待处理的申请也可以撤回
+async fn revoke(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Query(query): Query<RevokeQuery>,
+) -> Protobuf<CaregiverLinkResponse> {
+    // 1) 查询双方
+    let db = state.database.clone();
+    let caregiver = match find_user(db.as_ref(), &query.caregiver_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+    let elder = match find_user(db.as_ref(), &query.elder_openid).await {
+        Ok(u) => u,
+        Err((code, message)) => return error_response(code, message),
+    };
+
+    // 2) 解除关联，审计记录在同一事务中写入
+    let before = find_link(db.as_ref(), caregiver.id, elder.id).await;
+    let changed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let link = revoke_link(&txn, &current, is_admin(&current), caregiver.id, elder.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .change(&txn, before.as_ref(), Some(&link))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(link)
+    }
+    .await;
+    match changed {
+        Ok(link) => links_response(db.as_ref(), vec![link], "Revoke caregiver link success").await,
+        Err((code, message)) => error_response(code, message),
+    }
+}
+
+/// GET /api/caregiver/list
+/// 查看自己作为照护人或被照护人的所有关联
+async fn list(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+) -> Protobuf<CaregiverLinkResponse> {
+    let db = state.database.clone();
+    match list_links(db.as_ref(), current.id).await {
+        Ok(links) => links_response(db.as_ref(), links, "Get caregiver links success").await,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            error_response(code, message)
+        }
+    }
+}
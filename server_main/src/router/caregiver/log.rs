@@ -0,0 +1,105 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::get,
+};
+use interface_types::proto::caregiver::{
+    CaregiverActionLog as ProtoCaregiverActionLog, CaregiverActionLogResponse,
+};
+use serde::Deserialize;
+use user_auth::caregiver::list_caregiver_actions;
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::{find_user, is_admin, open_ids};
+use crate::AppState;
+use crate::auth::{CurrentUser, require_permission};
+use crate::codec::Protobuf;
+
+#[derive(Debug, Deserialize)]
+struct CaregiverLogQuery {
+    /// 不传则查看自己
+    elder_openid: Option<String>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/log", get(action_log))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// GET /api/caregiver/log?elder_openid=xxx
+/// 被照护人本人或 Admin：查看照护人对该用户的所有操作，按时间倒序
+async fn action_log(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    Query(query): Query<CaregiverLogQuery>,
+) -> Protobuf<CaregiverActionLogResponse> {
+    // 1) 解析被照护人并校验权限
+    let db = state.database.clone();
+    let elder = match query.elder_openid.as_deref() {
+        None => current.clone(),
+        Some(open_id) if open_id == current.open_id => current.clone(),
+        Some(open_id) => {
+            if !is_admin(&current) {
+                return Protobuf(CaregiverActionLogResponse {
+                    logs: vec![],
+                    code: 403,
+                    message: "Permission denied: only the elder or Admin can view the log"
+                        .to_string(),
+                });
+            }
+            match find_user(db.as_ref(), open_id).await {
+                Ok(u) => u,
+                Err((code, message)) => {
+                    return Protobuf(CaregiverActionLogResponse {
+                        logs: vec![],
+                        code,
+                        message,
+                    });
+                }
+            }
+        }
+    };
+
+    // 2) 查询操作记录及照护人
+    let logs = match list_caregiver_actions(db.as_ref(), elder.id).await {
+        Ok(logs) => logs,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(CaregiverActionLogResponse {
+                logs: vec![],
+                code,
+                message,
+            });
+        }
+    };
+    let caregivers = match open_ids(db.as_ref(), logs.iter().filter_map(|l| l.caregiver_id)).await {
+        Ok(m) => m,
+        Err((code, message)) => {
+            return Protobuf(CaregiverActionLogResponse {
+                logs: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(CaregiverActionLogResponse {
+        logs: logs
+            .into_iter()
+            .map(|l| ProtoCaregiverActionLog {
+                caregiver_openid: l.caregiver_id.and_then(|id| caregivers.get(&id).cloned()),
+                elder_openid: elder.open_id.clone(),
+                action: l.action,
+                detail: l.detail,
+                created_at: l.created_at,
+            })
+            .collect(),
+        code: 200,
+        message: "Get caregiver log success".to_string(),
+    })
+}
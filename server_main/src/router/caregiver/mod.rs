@@ -0,0 +1,116 @@
+//! Caregiver 路由模块
+//!
+//! 照护人关联（见 `user_auth::caregiver`），关联生效后照护人可以通过 `target_openid`
+//! 调用 `/api/user/info`、`/api/user/modify` 与 `/api/ai_chat` 代被照护人操作：
+//! - POST /api/caregiver/request - 照护人发起关联申请
+//! - POST /api/caregiver/respond - 被照护人同意或拒绝申请
+//! - POST /api/caregiver/approve - Admin 直接建立关联（被照护人无法自行操作时）
+//! - DELETE /api/caregiver?caregiver_openid=xxx&elder_openid=xxx - 任一方或 Admin 解除关联
+//! - GET /api/caregiver/list - 查看自己作为任一方的关联
+//! - GET /api/caregiver/log?elder_openid=xxx - 被照护人本人或 Admin 查看照护人的操作记录
+
+mod link;
+mod log;
+
+use std::collections::HashMap;
+
+use axum::Router;
+use db_manager::entity::{caregiver_link, user as user_entity};
+use interface_types::proto::caregiver::CaregiverLink as ProtoCaregiverLink;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+
+/// 创建并返回 caregiver 的完整路由
+pub fn caregiver_router() -> Router<AppState> {
+    Router::new().merge(link::router()).merge(log::router())
+}
+
+fn is_admin(user: &user_entity::Model) -> bool {
+    UserPermissionLevel::from(user.permission.unwrap_or(0)) == UserPermissionLevel::Admin
+}
+
+/// 按 open_id 查询用户
+async fn find_user(
+    db: &DatabaseConnection,
+    open_id: &str,
+) -> Result<user_entity::Model, (i32, String)> {
+    match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(db)
+        .await
+    {
+        Ok(Some(u)) => Ok(u),
+        Ok(None) => Err((404, format!("User not found: {}", open_id))),
+        Err(err) => Err((500, format!("Database error: {}", err))),
+    }
+}
+
+/// 双方之间的关联（任意状态），用于审计记录变更前的状态；查询失败时视为不存在
+async fn find_link(
+    db: &DatabaseConnection,
+    caregiver_id: i32,
+    elder_id: i32,
+) -> Option<caregiver_link::Model> {
+    caregiver_link::Entity::find()
+        .filter(caregiver_link::Column::CaregiverId.eq(caregiver_id))
+        .filter(caregiver_link::Column::ElderId.eq(elder_id))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// 按 id 批量查询 open_id
+async fn open_ids(
+    db: &DatabaseConnection,
+    ids: impl IntoIterator<Item = i32>,
+) -> Result<HashMap<i32, String>, (i32, String)> {
+    let ids: Vec<i32> = ids.into_iter().collect();
+    match user_entity::Entity::find()
+        .filter(user_entity::Column::Id.is_in(ids))
+        .all(db)
+        .await
+    {
+        Ok(users) => Ok(users.into_iter().map(|u| (u.id, u.open_id)).collect()),
+        Err(err) => Err((500, format!("Database error: {}", err))),
+    }
+}
+
+/// 将关联转换为 protobuf，用户 id 替换为 open_id
+async fn links_to_proto(
+    db: &DatabaseConnection,
+    links: Vec<caregiver_link::Model>,
+) -> Result<Vec<ProtoCaregiverLink>, (i32, String)> {
+    let users = open_ids(
+        db,
+        links.iter().flat_map(|l| {
+            [
+                Some(l.caregiver_id),
+                Some(l.elder_id),
+                l.requested_by,
+                l.approved_by,
+            ]
+            .into_iter()
+            .flatten()
+        }),
+    )
+    .await?;
+    let open_id = |id: i32| users.get(&id).cloned().unwrap_or_default();
+    Ok(links
+        .into_iter()
+        .map(|l| ProtoCaregiverLink {
+            id: l.id,
+            caregiver_openid: open_id(l.caregiver_id),
+            elder_openid: open_id(l.elder_id),
+            status: l.status,
+            relationship: l.relationship,
+            requested_by: l.requested_by.map(open_id),
+            approved_by: l.approved_by.map(open_id),
+            created_at: l.created_at,
+            approved_at: l.approved_at,
+            revoked_at: l.revoked_at,
+        })
+        .collect())
+}
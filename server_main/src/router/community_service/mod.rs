@@ -1,20 +1,83 @@
-pub mod delete;
-pub mod get;
-pub mod insert;
-pub mod modify;
-
 use axum::Router;
+use db_manager::entity::community_service as community_service_entity;
+use interface_types::proto::community_service::{
+    CommunityService as ProtoCommunityService, CommunityServiceRequest, CommunityServiceResponse,
+};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, patch, resource_router};
 
 /// 创建 community_service 路由
 ///
 /// 路由定义：
-/// - GET /api/community_service: 获取所有社区服务列表（所有权限 0-3 都可以访问）
+/// - GET /api/community_service: 获取社区服务列表（所有权限 0-3 都可以访问，支持分页、排序与筛选）
+/// - GET /api/community_service/{id}: 获取指定的社区服务（所有权限均可访问）
 /// - POST /api/community_service: 新增社区服务（仅 Admin 权限）
 /// - DELETE /api/community_service?id=xxx: 删除社区服务（仅 Admin 权限，id 通过查询参数传递）
 /// - PUT /api/community_service?id=xxx: 修改社区服务（仅 Admin 权限，id 通过查询参数传递，其他字段通过 proto body 传递）
 pub fn community_service_router() -> Router<crate::AppState> {
-    get::router()
-        .merge(insert::router())
-        .merge(delete::router())
-        .merge(modify::router())
+    resource_router::<CommunityService>()
+}
+
+pub struct CommunityService;
+
+impl Resource for CommunityService {
+    type Entity = community_service_entity::Entity;
+    type Model = community_service_entity::Model;
+    type ActiveModel = community_service_entity::ActiveModel;
+    type Proto = ProtoCommunityService;
+    type Request = CommunityServiceRequest;
+    type Response = CommunityServiceResponse;
+
+    const NAME: &'static str = "community service";
+    const ID: community_service_entity::Column = community_service_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            community_service_entity::Column::Name,
+            community_service_entity::Column::CreateTime,
+        ],
+        search: &[
+            community_service_entity::Column::Name,
+            community_service_entity::Column::Address,
+        ],
+        filter: &[
+            community_service_entity::Column::Name,
+            community_service_entity::Column::Phone,
+        ],
+        created: Some(community_service_entity::Column::CreateTime),
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(s: Self::Model) -> Self::Proto {
+        ProtoCommunityService {
+            id: s.id,
+            name: s.name.unwrap_or_default(),
+            address: s.address.unwrap_or_default(),
+            phone: s.phone.unwrap_or_default(),
+            latitude: s.latitude.unwrap_or_default(),
+            longitude: s.longitude.unwrap_or_default(),
+            create_time: s.create_time.and_utc().timestamp(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        CommunityServiceResponse {
+            community_services: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.name, payload.name);
+        patch(&mut active.address, payload.address);
+        patch(&mut active.phone, payload.phone);
+        patch(&mut active.latitude, payload.latitude);
+        patch(&mut active.longitude, payload.longitude);
+        Ok(())
+    }
 }
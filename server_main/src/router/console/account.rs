@@ -0,0 +1,73 @@
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::post};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::console::{ConsoleResponse, LocalAccountRequest};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use user_auth::local_auth::create_local_account;
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/account", post(create_account))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
+}
+
+/// POST /api/console/account
+/// 仅 Admin 权限：为已有用户开通用户名密码登录
+async fn create_account(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<LocalAccountRequest>,
+) -> Protobuf<ConsoleResponse> {
+    // 1) 查询目标用户
+    let db = state.database.clone();
+    let target = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(payload.target_openid.clone()))
+        .one(db.as_ref())
+        .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return Protobuf(ConsoleResponse {
+                code: 404,
+                message: "Target user not found".to_string(),
+            });
+        }
+        Err(err) => {
+            return Protobuf(ConsoleResponse {
+                code: 500,
+                message: format!("Database error: {}", err),
+            });
+        }
+    };
+
+    // 2) 创建本地账号（审计中密码哈希已脱敏），审计记录在同一事务中写入
+    let created = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let account = create_local_account(&txn, target.id, &payload.username, &payload.password)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .insert(&txn, &account)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = created {
+        return Protobuf(ConsoleResponse { code, message });
+    }
+
+    Protobuf(ConsoleResponse {
+        code: 200,
+        message: "Create local account success".to_string(),
+    })
+}
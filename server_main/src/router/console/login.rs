@@ -0,0 +1,79 @@
+use axum::{Extension, Router, extract::State, http::HeaderMap, routing::post};
+use axum_extra::extract::cookie::{Key, PrivateCookieJar};
+use interface_types::proto::console::{
+    ConsoleResponse, ConsoleSessionInfo, ConsoleSessionResponse, PasswordLoginRequest,
+};
+use user_auth::local_auth::{ConsoleSession, login_with_password};
+
+use crate::AppState;
+use crate::auth::console::{removal_cookie, session_cookie};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/login", post(login))
+        .route("/logout", post(logout))
+}
+
+/// POST /api/console/login
+/// 无需登录：校验用户名与密码，成功后下发加密的会话 cookie
+async fn login(
+    State(state): State<AppState>,
+    Extension(key): Extension<Key>,
+    headers: HeaderMap,
+    Protobuf(payload): Protobuf<PasswordLoginRequest>,
+) -> (PrivateCookieJar, Protobuf<ConsoleSessionResponse>) {
+    let jar = PrivateCookieJar::from_headers(&headers, key);
+
+    // 1) 校验用户名与密码
+    let db = state.database.clone();
+    let user = match login_with_password(db.as_ref(), &payload.username, &payload.password).await {
+        Ok(u) => u,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return (
+                jar,
+                Protobuf(ConsoleSessionResponse {
+                    session: None,
+                    code,
+                    message,
+                }),
+            );
+        }
+    };
+
+    // 2) 建立会话并写入 cookie
+    let session = ConsoleSession::new(&user);
+    let jar = jar.add(session_cookie(&session));
+
+    (
+        jar,
+        Protobuf(ConsoleSessionResponse {
+            session: Some(ConsoleSessionInfo {
+                open_id: user.open_id,
+                nickname: user.nickname,
+                permission: user.permission,
+                csrf_token: session.csrf,
+                expires_at: session.exp,
+            }),
+            code: 200,
+            message: "Login success".to_string(),
+        }),
+    )
+}
+
+/// POST /api/console/logout
+/// 清除会话 cookie
+async fn logout(
+    Extension(key): Extension<Key>,
+    headers: HeaderMap,
+) -> (PrivateCookieJar, Protobuf<ConsoleResponse>) {
+    let jar = PrivateCookieJar::from_headers(&headers, key).remove(removal_cookie());
+    (
+        jar,
+        Protobuf(ConsoleResponse {
+            code: 200,
+            message: "Logout success".to_string(),
+        }),
+    )
+}
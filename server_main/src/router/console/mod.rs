@@ -0,0 +1,26 @@
+//! Console 路由模块
+//!
+//! 管理后台的用户名 + 密码登录，登录后通过加密 cookie 保持会话：
+//! - POST /api/console/login - 用户名密码登录，下发会话 cookie 与 CSRF token
+//! - POST /api/console/logout - 清除会话 cookie
+//! - PUT /api/console/password - 修改自己的密码，并吊销已签发的 token 与会话
+//! - POST /api/console/reset_password - 仅 Admin：重置其他用户的密码
+//! - POST /api/console/account - 仅 Admin：为已有用户开通本地账号
+//!
+//! 连续 5 次密码错误会锁定账号 15 分钟，锁定期间返回 429。
+
+mod account;
+mod login;
+mod password;
+
+use axum::Router;
+
+use crate::AppState;
+
+/// 创建并返回 console 的完整路由
+pub fn console_router() -> Router<AppState> {
+    Router::new()
+        .merge(login::router())
+        .merge(password::router())
+        .merge(account::router())
+}
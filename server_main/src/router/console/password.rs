@@ -0,0 +1,171 @@
+use axum::{
+    Extension, Router,
+    extract::State,
+    http::HeaderMap,
+    middleware::from_fn_with_state,
+    routing::{post, put},
+};
+use axum_extra::extract::cookie::{Key, PrivateCookieJar};
+use db_manager::entity::{local_credential, user as user_entity};
+use interface_types::proto::console::{
+    ChangePasswordRequest, ConsoleResponse, ResetPasswordRequest,
+};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait};
+use user_auth::local_auth::{
+    CONSOLE_SESSION_COOKIE, ConsoleSession, change_password, find_local_account, reset_password,
+};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::console::session_cookie;
+use crate::auth::{CurrentUser, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/password",
+            put(change).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+        .route(
+            "/reset_password",
+            post(reset).route_layer(from_fn_with_state(
+                UserPermissionLevel::Admin,
+                require_permission,
+            )),
+        )
+}
+
+/// 审计用：读取本地账号的当前状态，查询失败时不记录
+async fn local_account<C: ConnectionTrait>(
+    db: &C,
+    user_id: i32,
+) -> Option<local_credential::Model> {
+    find_local_account(db, user_id).await.ok().flatten()
+}
+
+/// PUT /api/console/password
+/// 修改自己的密码，会吊销此前签发的所有 token 与会话；
+/// 通过 cookie 会话调用时同时下发新的会话 cookie
+async fn change(
+    State(state): State<AppState>,
+    Extension(key): Extension<Key>,
+    headers: HeaderMap,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<ChangePasswordRequest>,
+) -> (PrivateCookieJar, Protobuf<ConsoleResponse>) {
+    let jar = PrivateCookieJar::from_headers(&headers, key);
+
+    // 1) 校验旧密码并更新，审计记录在同一事务中写入
+    let db = state.database.clone();
+    let updated = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let before = local_account(&txn, current.id).await;
+        let updated = change_password(
+            &txn,
+            current.id,
+            &payload.old_password,
+            &payload.new_password,
+        )
+        .await
+        .map_err(|e| e.code_and_message())?;
+        let after = local_account(&txn, current.id).await;
+        audit
+            .change(&txn, before.as_ref(), after.as_ref())
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(updated)
+    }
+    .await;
+    let updated = match updated {
+        Ok(u) => u,
+        Err((code, message)) => {
+            return (jar, Protobuf(ConsoleResponse { code, message }));
+        }
+    };
+
+    // 2) 旧会话已失效，重新下发
+    let jar = if jar.get(CONSOLE_SESSION_COOKIE).is_some() {
+        jar.add(session_cookie(&ConsoleSession::new(&updated)))
+    } else {
+        jar
+    };
+
+    (
+        jar,
+        Protobuf(ConsoleResponse {
+            code: 200,
+            message: "Change password success".to_string(),
+        }),
+    )
+}
+
+/// POST /api/console/reset_password
+/// 仅 Admin 权限：重置其他用户的密码，同时解除锁定并吊销其已签发的 token
+async fn reset(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<ResetPasswordRequest>,
+) -> Protobuf<ConsoleResponse> {
+    // 1) 不允许重置自己的密码，需走修改密码流程
+    if payload.target_openid == current.open_id {
+        return Protobuf(ConsoleResponse {
+            code: 400,
+            message: "Cannot reset own password".to_string(),
+        });
+    }
+
+    // 2) 查询目标用户
+    let db = state.database.clone();
+    let target = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(payload.target_openid.clone()))
+        .one(db.as_ref())
+        .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return Protobuf(ConsoleResponse {
+                code: 404,
+                message: "Target user not found".to_string(),
+            });
+        }
+        Err(err) => {
+            return Protobuf(ConsoleResponse {
+                code: 500,
+                message: format!("Database error: {}", err),
+            });
+        }
+    };
+
+    // 3) 重置密码，审计记录在同一事务中写入
+    let reset = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let before = local_account(&txn, target.id).await;
+        reset_password(&txn, target.id, &payload.new_password)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        let after = local_account(&txn, target.id).await;
+        audit
+            .change(&txn, before.as_ref(), after.as_ref())
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = reset {
+        return Protobuf(ConsoleResponse { code, message });
+    }
+
+    Protobuf(ConsoleResponse {
+        code: 200,
+        message: "Reset password success".to_string(),
+    })
+}
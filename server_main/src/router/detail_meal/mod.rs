@@ -1,20 +1,207 @@
-pub mod delete;
-pub mod get;
-pub mod insert;
-pub mod modify;
-
 use axum::Router;
+use db_manager::entity::{
+    detail_meal as detail_meal_entity, dinner_provider as dinner_provider_entity,
+    user as user_entity,
+};
+use interface_types::proto::detail_meal::{
+    DetailMeal as ProtoDetailMeal, DetailMealRequest, DetailMealResponse,
+};
+use sea_orm::{ActiveValue, DatabaseConnection, EntityTrait, Select, Set};
+use user_auth::rbac::{ensure_provider_owner, owned_provider_ids, permission};
+
+use crate::auth::Permissions;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page};
+use crate::resource::{Access, Resource, parse_json, patch, resource_router};
 
 /// 创建 detail_meal 路由
 ///
 /// 路由定义：
-/// - GET /api/detail_meal: 获取明细餐列表（所有权限 0-3 都可以访问，支持查询参数筛选）
-/// - POST /api/detail_meal: 新增明细餐（仅 Provider/Admin 权限）
-/// - PUT /api/detail_meal?id=xxx: 修改明细餐（仅 Provider/Admin 权限，id 通过查询参数传递）
-/// - DELETE /api/detail_meal?id=xxx: 删除明细餐（仅 Provider/Admin 权限，id 通过查询参数传递）
+/// - GET /api/detail_meal: 获取明细餐列表（所有权限 0-3 都可以访问，支持分页、排序与按 dinner_provider_id / belong_to / date_time / type 筛选）
+/// - GET /api/detail_meal/{id}: 获取指定的明细餐（所有权限均可访问）
+/// - POST /api/detail_meal: 新增明细餐（需要 meal.write 权限）
+/// - PUT /api/detail_meal?id=xxx: 修改明细餐（需要 meal.write 权限，id 通过查询参数传递）
+/// - DELETE /api/detail_meal?id=xxx: 删除明细餐（需要 meal.write 权限，id 通过查询参数传递）
+///
+/// dinner_provider_id 为所属供餐点（外键），belong_to 只是展示用的文本；
+/// 不具备 meal.manage 权限的用户（如 Provider）只能操作自己负责的供餐点的明细餐
 pub fn detail_meal_router() -> Router<crate::AppState> {
-    get::router()
-        .merge(insert::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<DetailMeal>()
+}
+
+/// 旧的查询参数名与对应的字段
+const LEGACY_PARAMS: [(&str, &str); 2] = [("belongto", "belong_to"), ("datetime", "date_time")];
+
+pub struct DetailMeal;
+
+impl Resource for DetailMeal {
+    type Entity = detail_meal_entity::Entity;
+    type Model = detail_meal_entity::Model;
+    type ActiveModel = detail_meal_entity::ActiveModel;
+    type Proto = ProtoDetailMeal;
+    type Request = DetailMealRequest;
+    type Response = DetailMealResponse;
+
+    const NAME: &'static str = "detail meal";
+    const ID: detail_meal_entity::Column = detail_meal_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            detail_meal_entity::Column::DateTime,
+            detail_meal_entity::Column::Type,
+            detail_meal_entity::Column::DinnerProviderId,
+            detail_meal_entity::Column::BelongTo,
+        ],
+        search: &[],
+        filter: &[
+            detail_meal_entity::Column::DinnerProviderId,
+            detail_meal_entity::Column::BelongTo,
+            detail_meal_entity::Column::DateTime,
+            detail_meal_entity::Column::Type,
+        ],
+        created: None,
+    };
+    const WRITE: Access = Access::Permission(permission::MEAL_WRITE);
+
+    fn to_proto(s: Self::Model) -> Self::Proto {
+        ProtoDetailMeal {
+            id: s.id,
+            r#type: s.r#type.unwrap_or_default(),
+            date_time: s.date_time.unwrap_or_default(),
+            meal_info: s.meal_info.map(|v| v.to_string()).unwrap_or_default(),
+            belong_to: s.belong_to.unwrap_or_default(),
+            dinner_provider_id: s.dinner_provider_id.unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        DetailMealResponse {
+            detail_meals: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        // meal_info 以 JSON 字符串传入
+        let meal_info = parse_json(&payload.meal_info, "meal_info")?;
+        patch(&mut active.r#type, payload.r#type);
+        patch(&mut active.date_time, payload.date_time);
+        patch(&mut active.belong_to, payload.belong_to);
+        patch(&mut active.dinner_provider_id, payload.dinner_provider_id);
+        patch(&mut active.meal_info, meal_info);
+        Ok(())
+    }
+
+    /// 旧的 `belongto`、`datetime` 参数名仍然可用
+    fn filter(
+        select: Select<Self::Entity>,
+        query: &mut ListQuery,
+    ) -> Result<Select<Self::Entity>, ApiError> {
+        for (name, _) in &mut query.fields {
+            if let Some((_, column)) = LEGACY_PARAMS.iter().find(|(legacy, _)| legacy == name) {
+                *name = column.to_string();
+            }
+        }
+        Ok(select)
+    }
+
+    /// Provider 只能为自己负责的供餐点新增明细餐，仅负责一个供餐点时可省略 dinner_provider_id
+    async fn authorize_create(
+        db: &DatabaseConnection,
+        user: &user_entity::Model,
+        permissions: &Permissions,
+        active: &mut Self::ActiveModel,
+    ) -> Result<(), ApiError> {
+        if permissions.allows(permission::MEAL_MANAGE) {
+            return match &active.dinner_provider_id {
+                ActiveValue::Set(Some(id)) => ensure_provider_exists(db, *id).await,
+                _ => Ok(()),
+            };
+        }
+        let dinner_provider_id = match &active.dinner_provider_id {
+            ActiveValue::Set(Some(id)) => *id,
+            _ => {
+                let owned = owned_provider_ids(db, user.id)
+                    .await
+                    .map_err(|err| ApiError::from(err.code_and_message()))?;
+                match owned.as_slice() {
+                    [id] => *id,
+                    [] => {
+                        return Err(ApiError::forbidden(
+                            "Permission denied: no dinner provider assigned",
+                        ));
+                    }
+                    _ => return Err(ApiError::bad_request("dinner_provider_id is required")),
+                }
+            }
+        };
+        ensure_meal_owner(db, user.id, Some(dinner_provider_id)).await?;
+        active.dinner_provider_id = Set(Some(dinner_provider_id));
+        Ok(())
+    }
+
+    /// Provider 只能修改自己负责的供餐点的明细餐，也只能转移到自己负责的其他供餐点
+    async fn authorize_update(
+        db: &DatabaseConnection,
+        user: &user_entity::Model,
+        permissions: &Permissions,
+        model: &Self::Model,
+        active: &Self::ActiveModel,
+    ) -> Result<(), ApiError> {
+        let moved_to = match &active.dinner_provider_id {
+            ActiveValue::Set(Some(id)) if model.dinner_provider_id != Some(*id) => Some(*id),
+            _ => None,
+        };
+        if permissions.allows(permission::MEAL_MANAGE) {
+            return match moved_to {
+                Some(id) => ensure_provider_exists(db, id).await,
+                None => Ok(()),
+            };
+        }
+        ensure_meal_owner(db, user.id, model.dinner_provider_id).await?;
+        if moved_to.is_some() {
+            ensure_meal_owner(db, user.id, moved_to).await?;
+        }
+        Ok(())
+    }
+
+    /// Provider 只能删除自己负责的供餐点的明细餐
+    async fn authorize_delete(
+        db: &DatabaseConnection,
+        user: &user_entity::Model,
+        permissions: &Permissions,
+        model: &Self::Model,
+    ) -> Result<(), ApiError> {
+        if permissions.allows(permission::MEAL_MANAGE) {
+            return Ok(());
+        }
+        ensure_meal_owner(db, user.id, model.dinner_provider_id).await
+    }
+}
+
+/// 校验明细餐所属的供餐点由该用户负责，未指定供餐点的明细餐只有 meal.manage 权限可以操作
+async fn ensure_meal_owner(
+    db: &DatabaseConnection,
+    user_id: i32,
+    dinner_provider_id: Option<i32>,
+) -> Result<(), ApiError> {
+    let Some(dinner_provider_id) = dinner_provider_id else {
+        return Err(ApiError::forbidden(
+            "Permission denied: detail meal does not belong to own dinner provider",
+        ));
+    };
+    ensure_provider_owner(db, user_id, dinner_provider_id)
+        .await
+        .map_err(|err| err.code_and_message().into())
+}
+
+/// 指定的供餐点必须存在
+async fn ensure_provider_exists(db: &DatabaseConnection, id: i32) -> Result<(), ApiError> {
+    dinner_provider_entity::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| ApiError::bad_request(format!("Dinner provider with id '{}' not found", id)))
 }
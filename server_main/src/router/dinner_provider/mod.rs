@@ -1,20 +1,116 @@
-pub mod delete;
-pub mod get;
-pub mod insert;
-pub mod modify;
+pub mod owner;
 
 use axum::Router;
+use db_manager::entity::{dinner_provider as dinner_provider_entity, user as user_entity};
+use interface_types::proto::dinner_provider::{
+    DinnerProvider as ProtoDinnerProvider, DinnerProviderRequest, DinnerProviderResponse,
+};
+use sea_orm::DatabaseConnection;
+use user_auth::rbac::{ensure_provider_owner, permission};
+
+use crate::auth::Permissions;
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, patch, resource_router};
 
 /// 创建 dinner_provider 路由
 ///
 /// 路由定义：
-/// - GET /api/dinner_provider: 获取所有供餐点列表（所有权限 0-3 都可以访问）
-/// - POST /api/dinner_provider: 新增供餐点（仅 Admin 权限）
-/// - DELETE /api/dinner_provider?id=xxx: 删除供餐点（仅 Admin 权限，id 通过查询参数传递）
-/// - PUT /api/dinner_provider?id=xxx: 修改供餐点（仅 Manager/Admin 权限，id 通过查询参数传递，其他字段通过 proto body 传递）
+/// - GET /api/dinner_provider: 获取供餐点列表（所有权限 0-3 都可以访问，支持分页、排序与筛选）
+/// - GET /api/dinner_provider/{id}: 获取指定的供餐点（所有权限均可访问）
+/// - POST /api/dinner_provider: 新增供餐点（需要 meal.manage 权限）
+/// - DELETE /api/dinner_provider?id=xxx: 删除供餐点（需要 meal.manage 权限，id 通过查询参数传递）
+/// - PUT /api/dinner_provider?id=xxx: 修改供餐点（需要 meal.write 权限，id 通过查询参数传递，其他字段通过 proto body 传递；不具备 meal.manage 时只能修改自己负责的供餐点）
+/// - GET /api/dinner_provider/owned: 获取当前用户负责的供餐点（需要 meal.write 权限）
+/// - GET /api/dinner_provider/owner?id=xxx: 查看供餐点的负责人（需要 meal.manage 权限）
+/// - POST /api/dinner_provider/owner/assign?id=xxx&open_id=xxx: 指定供餐点负责人（需要 meal.manage 权限）
+/// - POST /api/dinner_provider/owner/unassign?id=xxx&open_id=xxx: 取消供餐点负责人（需要 meal.manage 权限）
 pub fn dinner_provider_router() -> Router<crate::AppState> {
-    get::router()
-        .merge(insert::router())
-        .merge(delete::router())
-        .merge(modify::router())
+    resource_router::<DinnerProvider>().merge(owner::router())
+}
+
+pub struct DinnerProvider;
+
+impl Resource for DinnerProvider {
+    type Entity = dinner_provider_entity::Entity;
+    type Model = dinner_provider_entity::Model;
+    type ActiveModel = dinner_provider_entity::ActiveModel;
+    type Proto = ProtoDinnerProvider;
+    type Request = DinnerProviderRequest;
+    type Response = DinnerProviderResponse;
+
+    const NAME: &'static str = "dinner provider";
+    const ID: dinner_provider_entity::Column = dinner_provider_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            dinner_provider_entity::Column::Name,
+            dinner_provider_entity::Column::CreateTime,
+        ],
+        search: &[
+            dinner_provider_entity::Column::Name,
+            dinner_provider_entity::Column::Address,
+            dinner_provider_entity::Column::MealStyle,
+        ],
+        filter: &[
+            dinner_provider_entity::Column::Name,
+            dinner_provider_entity::Column::Phone,
+            dinner_provider_entity::Column::MealStyle,
+        ],
+        created: Some(dinner_provider_entity::Column::CreateTime),
+    };
+    const WRITE: Access = Access::Permission(permission::MEAL_MANAGE);
+    const UPDATE: Access = Access::Permission(permission::MEAL_WRITE);
+
+    fn to_proto(s: Self::Model) -> Self::Proto {
+        ProtoDinnerProvider {
+            id: s.id,
+            name: s.name.unwrap_or_default(),
+            address: s.address.unwrap_or_default(),
+            phone: s.phone.unwrap_or_default(),
+            latitude: s.latitude.unwrap_or_default(),
+            longitude: s.longitude.unwrap_or_default(),
+            service_time: s.service_time.unwrap_or_default(),
+            bonus_info: s.bonus_info.unwrap_or_default(),
+            meal_style: s.meal_style.unwrap_or_default(),
+            create_time: s.create_time.and_utc().timestamp(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        DinnerProviderResponse {
+            dinner_providers: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.name, payload.name);
+        patch(&mut active.address, payload.address);
+        patch(&mut active.phone, payload.phone);
+        patch(&mut active.latitude, payload.latitude);
+        patch(&mut active.longitude, payload.longitude);
+        patch(&mut active.service_time, payload.service_time);
+        patch(&mut active.bonus_info, payload.bonus_info);
+        patch(&mut active.meal_style, payload.meal_style);
+        Ok(())
+    }
+
+    /// Provider 只能修改自己负责的供餐点
+    async fn authorize_update(
+        db: &DatabaseConnection,
+        user: &user_entity::Model,
+        permissions: &Permissions,
+        model: &Self::Model,
+        _active: &Self::ActiveModel,
+    ) -> Result<(), ApiError> {
+        if permissions.allows(permission::MEAL_MANAGE) {
+            return Ok(());
+        }
+        ensure_provider_owner(db, user.id, model.id)
+            .await
+            .map_err(|err| err.code_and_message().into())
+    }
 }
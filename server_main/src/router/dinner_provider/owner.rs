@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+use db_manager::entity::{dinner_provider as dinner_provider_entity, user as user_entity};
+use interface_types::proto::dinner_provider::{
+    DinnerProviderOwner as ProtoDinnerProviderOwner, DinnerProviderOwnerResponse,
+    DinnerProviderResponse,
+};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::Deserialize;
+use serde_json::json;
+use user_auth::rbac::{
+    assign_provider_owner, list_provider_owners, owned_provider_ids, permission,
+    unassign_provider_owner,
+};
+
+use super::DinnerProvider;
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, require_role_permission};
+use crate::codec::Protobuf;
+use crate::listing::Page;
+use crate::resource::Resource;
+
+/// 创建 dinner_provider 负责人路由
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/owner", get(get_owners))
+        .route("/owner/assign", post(assign_owner))
+        .route("/owner/unassign", post(unassign_owner))
+        .route_layer(from_fn_with_state(
+            permission::MEAL_MANAGE,
+            require_role_permission,
+        ))
+        .route(
+            "/owned",
+            get(get_owned).route_layer(from_fn_with_state(
+                permission::MEAL_WRITE,
+                require_role_permission,
+            )),
+        )
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct OwnerQuery {
+    /// 供餐点的 ID
+    id: i32,
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct AssignOwnerQuery {
+    /// 供餐点的 ID
+    id: i32,
+    /// 负责人 open_id
+    open_id: String,
+}
+
+/// 查询供餐点的全部负责人
+async fn owners_of(
+    db: &DatabaseConnection,
+    dinner_provider_id: i32,
+) -> Result<Vec<ProtoDinnerProviderOwner>, (i32, String)> {
+    let owners = list_provider_owners(db, dinner_provider_id)
+        .await
+        .map_err(|e| e.code_and_message())?;
+    let user_ids: Vec<i32> = owners
+        .iter()
+        .flat_map(|o| [Some(o.user_id), o.granted_by])
+        .flatten()
+        .collect();
+    let open_ids: HashMap<i32, String> = user_entity::Entity::find()
+        .filter(user_entity::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|err| (500, format!("Database error: {}", err)))?
+        .into_iter()
+        .map(|u| (u.id, u.open_id))
+        .collect();
+
+    Ok(owners
+        .into_iter()
+        .map(|o| ProtoDinnerProviderOwner {
+            dinner_provider_id: o.dinner_provider_id,
+            open_id: open_ids.get(&o.user_id).cloned().unwrap_or_default(),
+            granted_by: o.granted_by.and_then(|id| open_ids.get(&id).cloned()),
+            created_at: o.created_at,
+        })
+        .collect())
+}
+
+async fn find_user(
+    db: &DatabaseConnection,
+    open_id: &str,
+) -> Result<user_entity::Model, (i32, String)> {
+    match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(db)
+        .await
+    {
+        Ok(Some(u)) => Ok(u),
+        Ok(None) => Err((404, "Target user not found".to_string())),
+        Err(err) => Err((500, format!("Database error: {}", err))),
+    }
+}
+
+fn owner_response(
+    result: Result<Vec<ProtoDinnerProviderOwner>, (i32, String)>,
+    message: &str,
+) -> Protobuf<DinnerProviderOwnerResponse> {
+    match result {
+        Ok(owners) => Protobuf(DinnerProviderOwnerResponse {
+            owners,
+            code: 200,
+            message: message.to_string(),
+        }),
+        Err((code, message)) => Protobuf(DinnerProviderOwnerResponse {
+            owners: vec![],
+            code,
+            message,
+        }),
+    }
+}
+
+/// GET /api/dinner_provider/owner?id=xxx - 查看供餐点的负责人（需要 meal.manage 权限）
+async fn get_owners(
+    State(state): State<AppState>,
+    Query(params): Query<OwnerQuery>,
+) -> Protobuf<DinnerProviderOwnerResponse> {
+    let db = state.database.clone();
+    owner_response(
+        owners_of(db.as_ref(), params.id).await,
+        "Get dinner provider owners success",
+    )
+}
+
+/// POST /api/dinner_provider/owner/assign?id=xxx&open_id=xxx - 指定供餐点负责人（需要 meal.manage 权限）
+async fn assign_owner(
+    State(state): State<AppState>,
+    audit: Auditor,
+    CurrentUser(operator): CurrentUser,
+    Query(params): Query<AssignOwnerQuery>,
+) -> Protobuf<DinnerProviderOwnerResponse> {
+    // 1) 查询负责人
+    let db = state.database.clone();
+    let target = match find_user(db.as_ref(), &params.open_id).await {
+        Ok(u) => u,
+        Err(err) => return owner_response(Err(err), ""),
+    };
+
+    // 2) 写入归属关系，审计记录在同一事务中写入
+    let assigned = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        assign_provider_owner(&txn, target.id, params.id, Some(operator.id))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "dinner_provider_owner",
+                format!("{},{}", params.id, target.id),
+                None,
+                Some(json!({ "dinner_provider_id": params.id, "user_id": target.id, "granted_by": operator.id })),
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err(err) = assigned {
+        return owner_response(Err(err), "");
+    }
+
+    // 3) 返回最新负责人列表
+    owner_response(
+        owners_of(db.as_ref(), params.id).await,
+        "Assign dinner provider owner success",
+    )
+}
+
+/// POST /api/dinner_provider/owner/unassign?id=xxx&open_id=xxx - 取消供餐点负责人（需要 meal.manage 权限）
+async fn unassign_owner(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Query(params): Query<AssignOwnerQuery>,
+) -> Protobuf<DinnerProviderOwnerResponse> {
+    // 1) 查询负责人
+    let db = state.database.clone();
+    let target = match find_user(db.as_ref(), &params.open_id).await {
+        Ok(u) => u,
+        Err(err) => return owner_response(Err(err), ""),
+    };
+
+    // 2) 删除归属关系，审计记录在同一事务中写入
+    let unassigned = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        unassign_provider_owner(&txn, target.id, params.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "dinner_provider_owner",
+                format!("{},{}", params.id, target.id),
+                Some(json!({ "dinner_provider_id": params.id, "user_id": target.id })),
+                None,
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err(err) = unassigned {
+        return owner_response(Err(err), "");
+    }
+
+    // 3) 返回最新负责人列表
+    owner_response(
+        owners_of(db.as_ref(), params.id).await,
+        "Unassign dinner provider owner success",
+    )
+}
+
+/// GET /api/dinner_provider/owned - 获取当前用户负责的供餐点（需要 meal.write 权限）
+async fn get_owned(
+    State(state): State<AppState>,
+    CurrentUser(current_user): CurrentUser,
+) -> Protobuf<DinnerProviderResponse> {
+    // 1) 查询负责的供餐点 id
+    let db = state.database.clone();
+    let ids = match owned_provider_ids(db.as_ref(), current_user.id).await {
+        Ok(ids) => ids,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(DinnerProviderResponse {
+                dinner_providers: vec![],
+                code,
+                message,
+                total: 0,
+                next_cursor: String::new(),
+            });
+        }
+    };
+
+    // 2) 查询供餐点信息
+    let dinner_providers = match dinner_provider_entity::Entity::find()
+        .filter(dinner_provider_entity::Column::Id.is_in(ids))
+        .order_by_asc(dinner_provider_entity::Column::Id)
+        .all(db.as_ref())
+        .await
+    {
+        Ok(n) => n,
+        Err(err) => {
+            return Protobuf(DinnerProviderResponse {
+                dinner_providers: vec![],
+                code: 500,
+                message: format!("Database error: {}", err),
+                total: 0,
+                next_cursor: String::new(),
+            });
+        }
+    };
+
+    Protobuf(DinnerProvider::response(
+        Page::from(dinner_providers).map(DinnerProvider::to_proto),
+        "Get owned dinner provider list success".to_string(),
+    ))
+}
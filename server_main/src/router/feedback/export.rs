@@ -1,89 +1,60 @@
 use axum::{
-    Router,
-    body::Body,
-    extract::State,
-    http::{HeaderMap, Response, StatusCode, header},
-    response::IntoResponse,
-    routing::get,
+    Router, extract::State, middleware::from_fn_with_state, response::Response, routing::get,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::feedback as feedback_entity;
 use interface_types::proto::feedback::FeedbackExportRequest;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use user_auth::db_exchange::token2user;
+use user_auth::rbac::permission;
 
 use crate::AppState;
+use crate::auth::require_role_permission;
+use crate::codec::Protobuf;
+use crate::error::ApiError;
+use crate::router::{XLSX_CONTENT_TYPE, attachment};
 
 /// 创建 feedback 导出路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/export", get(export_feedback))
+    Router::new()
+        .route("/export", get(export_feedback))
+        .route_layer(from_fn_with_state(
+            permission::FEEDBACK_MANAGE,
+            require_role_permission,
+        ))
 }
 
-/// GET /api/feedback/export - 导出反馈（所有权限 0-3 都可以访问）
+/// GET /api/feedback/export - 导出反馈（需要 feedback.manage 权限）
 ///
 /// 传入 proto（FeedbackExportRequest），包含 start_time/end_time 时间戳
 /// 返回 Excel 文件流
 async fn export_feedback(
     State(state): State<AppState>,
-    headers: HeaderMap,
     Protobuf(payload): Protobuf<FeedbackExportRequest>,
-) -> impl IntoResponse {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return StatusCode::UNAUTHORIZED.into_response();
-            }
-        },
-        None => {
-            return StatusCode::UNAUTHORIZED.into_response();
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(_err) => {
-            return StatusCode::UNAUTHORIZED.into_response();
-        }
-    };
-
-    // 3) 权限校验：所有权限 0-3 都可以访问
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission == 3 {
-        return StatusCode::FORBIDDEN.into_response();
-    }
-
-    // 4) 时间戳校验
+) -> Result<Response, ApiError> {
+    // 1) 时间戳校验
     let start_ts = payload.start_time;
     let end_ts = payload.end_time;
     if start_ts <= 0 || end_ts <= 0 || end_ts < start_ts {
-        return StatusCode::BAD_REQUEST.into_response();
+        return Err(ApiError::bad_request("Invalid time range"));
     }
 
-    let start_dt = match chrono::DateTime::from_timestamp(start_ts, 0) {
-        Some(dt) => dt.with_timezone(&chrono::FixedOffset::east_opt(0).unwrap()),
-        None => return StatusCode::BAD_REQUEST.into_response(),
-    };
-    let end_dt = match chrono::DateTime::from_timestamp(end_ts, 0) {
-        Some(dt) => dt.with_timezone(&chrono::FixedOffset::east_opt(0).unwrap()),
-        None => return StatusCode::BAD_REQUEST.into_response(),
+    let (Some(start_dt), Some(end_dt)) = (
+        chrono::DateTime::from_timestamp(start_ts, 0),
+        chrono::DateTime::from_timestamp(end_ts, 0),
+    ) else {
+        return Err(ApiError::bad_request("Invalid time range"));
     };
+    let (start_dt, end_dt) = (start_dt.fixed_offset(), end_dt.fixed_offset());
 
-    // 5) 查询时间范围内的反馈
+    // 2) 查询时间范围内的反馈
     let db = state.database.clone();
-    let feedbacks = match feedback_entity::Entity::find()
+    let feedbacks = feedback_entity::Entity::find()
         .filter(feedback_entity::Column::CreatedTime.between(start_dt, end_dt))
         .all(db.as_ref())
-        .await
-    {
-        Ok(list) => list,
-        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    };
+        .await?;
 
-    // 6) 生成 Excel
+    // 3) 生成 Excel
     // 需要添加依赖：rust_xlsxwriter = "0.70"
+    let cst = chrono::FixedOffset::east_opt(8 * 3600).unwrap();
     let mut workbook = rust_xlsxwriter::Workbook::new();
     let worksheet = workbook.add_worksheet();
 
@@ -100,33 +71,16 @@ async fn export_feedback(
         let _ = worksheet.write_string(row, 1, item.r#type.clone().unwrap_or_default());
         let _ = worksheet.write_string(row, 2, item.content.clone().unwrap_or_default());
         let _ = worksheet.write_string(row, 3, item.phone.clone().unwrap_or_default());
-        let cst_dt = item
-            .created_time
-            .with_timezone(&chrono::FixedOffset::east_opt(8 * 3600).unwrap());
+        let cst_dt = item.created_time.with_timezone(&cst);
         let created_time_str = cst_dt.format("%Y-%m-%d %H:%M:%S").to_string();
         let _ = worksheet.write_string(row, 4, created_time_str);
     }
 
-    let buffer = match workbook.save_to_buffer() {
-        Ok(buf) => buf,
-        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    };
+    let buffer = workbook
+        .save_to_buffer()
+        .map_err(|err| ApiError::internal(format!("Failed to build xlsx: {}", err)))?;
 
-    // 7) 返回文件流
+    // 4) 返回文件流
     let filename = format!("feedback_{}_{}.xlsx", start_ts, end_ts);
-    let mut response = Response::new(Body::from(buffer));
-    *response.status_mut() = StatusCode::OK;
-    response.headers_mut().insert(
-        header::CONTENT_TYPE,
-        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
-            .parse()
-            .unwrap(),
-    );
-    response.headers_mut().insert(
-        header::CONTENT_DISPOSITION,
-        format!("attachment; filename=\"{}\"", filename)
-            .parse()
-            .unwrap(),
-    );
-    response
+    Ok(attachment(XLSX_CONTENT_TYPE, &filename, buffer))
 }
@@ -1,74 +1,34 @@
-use axum::{Router, extract::State, http::HeaderMap, routing::post};
-use axum_extra::protobuf::Protobuf;
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::post};
 use db_manager::entity::feedback as feedback_entity;
 use interface_types::proto::feedback::{
     Feedback as ProtoFeedback, FeedbackRequest, FeedbackResponse,
 };
-use sea_orm::{ActiveModelTrait, Set};
-use user_auth::db_exchange::{ExchangeError, token2user};
+use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{AuthUser, require_permission};
+use crate::codec::Protobuf;
 
 /// 创建 feedback 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(insert_feedback))
+    Router::new()
+        .route("/", post(insert_feedback))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
 /// POST /api/feedback - 新增反馈（所有权限 0-3 都可以访问）
 async fn insert_feedback(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    audit: Auditor,
+    AuthUser(auth_user): AuthUser,
     Protobuf(payload): Protobuf<FeedbackRequest>,
 ) -> Protobuf<FeedbackResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(FeedbackResponse {
-                    feedback: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(FeedbackResponse {
-                feedback: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(FeedbackResponse {
-                feedback: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 权限校验：所有权限 0-3 都可以访问
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission < 0 || user_permission > 3 {
-        return Protobuf(FeedbackResponse {
-            feedback: None,
-            code: 403,
-            message: "Permission denied: Invalid permission level".to_string(),
-        });
-    }
-
-    // 4) 创建新的 ActiveModel 并插入
+    // 1) 创建新的 ActiveModel 并插入（openid 从 token 中获取，用于个人数据导出与注销）
     let db = state.database.clone();
     let new_feedback = feedback_entity::ActiveModel {
         r#type: Set(if payload.r#type.is_empty() {
@@ -82,22 +42,37 @@ async fn insert_feedback(
             Some(payload.content)
         }),
         phone: Set(payload.phone),
+        openid: Set(Some(auth_user.open_id)),
         ..Default::default()
     };
 
-    // 5) 执行插入
-    let inserted_feedback = match new_feedback.insert(db.as_ref()).await {
+    // 2) 执行插入，审计记录在同一事务中写入
+    let inserted = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = new_feedback
+            .insert(&txn)
+            .await
+            .map_err(|e| format!("Failed to insert feedback: {}", e))?;
+        audit
+            .insert(&txn, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let inserted_feedback = match inserted {
         Ok(n) => n,
         Err(err) => {
             return Protobuf(FeedbackResponse {
                 feedback: None,
                 code: 500,
-                message: format!("Failed to insert feedback: {}", err),
+                message: err,
             });
         }
     };
 
-    // 6) 返回新增的 feedback
+    // 3) 返回新增的 feedback
     Protobuf(FeedbackResponse {
         feedback: Some(ProtoFeedback {
             id: inserted_feedback.id,
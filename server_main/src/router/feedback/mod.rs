@@ -7,7 +7,7 @@ use axum::Router;
 ///
 /// 路由定义：
 /// - POST /api/feedback: 新增反馈（所有权限 0-3 都可以访问）
-/// - GET /api/feedback/export: 导出反馈（所有权限 0-3 都可以访问）
+/// - GET /api/feedback/export: 导出反馈（需要 feedback.manage 权限）
 pub fn feedback_router() -> Router<crate::AppState> {
     insert::router().merge(export::router())
 }
@@ -0,0 +1,90 @@
+//! Health 路由模块
+//!
+//! 供负载均衡与容器编排探测，挂载在根路径（不在 `/api` 下），均无需登录：
+//! - GET /healthz - 存活检查，进程能处理请求即返回 200
+//! - GET /readyz - 就绪检查：数据库连接可用且迁移均已应用时返回 200，否则返回 503
+//! - GET /version - 包版本与构建时的 git commit
+
+use axum::{Router, extract::State, routing::get};
+use db_manager::migrator::pending_migrations;
+use interface_types::proto::health::{HealthCheck, HealthResponse, VersionResponse};
+use sea_orm::DatabaseConnection;
+
+use crate::AppState;
+use crate::codec::Protobuf;
+
+/// 创建并返回 health 的完整路由
+pub fn health_router() -> Router<AppState> {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/version", get(version))
+}
+
+/// GET /healthz - 存活检查
+async fn healthz() -> Protobuf<HealthResponse> {
+    Protobuf(HealthResponse {
+        checks: vec![],
+        code: 200,
+        message: "ok".to_string(),
+    })
+}
+
+fn check(name: &str, result: Result<String, String>) -> HealthCheck {
+    let ok = result.is_ok();
+    HealthCheck {
+        name: name.to_string(),
+        ok,
+        detail: result.unwrap_or_else(|err| err),
+    }
+}
+
+/// 数据库连接与迁移状态
+async fn readiness(db: &DatabaseConnection) -> Vec<HealthCheck> {
+    // 1) 数据库连接
+    if let Err(err) = db.ping().await {
+        return vec![
+            check("database", Err(err.to_string())),
+            check("migrations", Err("database unavailable".to_string())),
+        ];
+    }
+
+    // 2) 迁移是否均已应用
+    let migrations = match pending_migrations(db).await {
+        Ok(pending) if pending.is_empty() => Ok(String::new()),
+        Ok(pending) => Err(format!("pending migrations: {}", pending.join(", "))),
+        Err(err) => Err(err.to_string()),
+    };
+    vec![
+        check("database", Ok(String::new())),
+        check("migrations", migrations),
+    ]
+}
+
+/// GET /readyz - 就绪检查，未就绪时 code 为 503
+async fn readyz(State(state): State<AppState>) -> Protobuf<HealthResponse> {
+    let checks = readiness(state.database.as_ref()).await;
+    if checks.iter().all(|c| c.ok) {
+        Protobuf(HealthResponse {
+            checks,
+            code: 200,
+            message: "ready".to_string(),
+        })
+    } else {
+        Protobuf(HealthResponse {
+            checks,
+            code: 503,
+            message: "not ready".to_string(),
+        })
+    }
+}
+
+/// GET /version - 版本信息
+async fn version() -> Protobuf<VersionResponse> {
+    Protobuf(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        code: 200,
+        message: "ok".to_string(),
+        git_commit: env!("GIT_COMMIT").to_string(),
+    })
+}
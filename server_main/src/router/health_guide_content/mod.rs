@@ -1,10 +1,11 @@
 //! Health Guide Content 路由模块
 //!
 //! 提供健康指南内容的 CRUD 接口：
-//! - GET /api/health_guide_content?type_one=xxx&type_two=xxx - 获取健康指南内容（所有权限均可访问，必须提供 type_one 和 type_two 参数）
-//! - POST /api/health_guide_content - 创建新的健康指南内容（仅 Admin 权限）
-//! - PUT /api/health_guide_content?type_one=xxx&type_two=xxx - 修改指定的健康指南内容（仅 Admin 权限，通过 type_one 和 type_two 筛选）
-//! - DELETE /api/health_guide_content?type_one=xxx&type_two=xxx - 删除指定的健康指南内容（仅 Admin 权限，通过 type_one 和 type_two 筛选）
+//! - GET /api/health_guide_content?type_one=xxx&type_two=xxx - 获取健康指南内容（所有权限均可访问，必须提供 type_one 和 type_two 参数，分页见 `crate::listing`）
+//! - GET /api/health_guide_content/{id} - 获取指定的健康指南内容（所有权限均可访问）
+//! - POST /api/health_guide_content - 创建新的健康指南内容（需要 health_guide.write 权限）
+//! - PUT /api/health_guide_content?type_one=xxx&type_two=xxx - 修改指定的健康指南内容（需要 health_guide.write 权限，通过 type_one 和 type_two 筛选）
+//! - DELETE /api/health_guide_content?type_one=xxx&type_two=xxx - 删除指定的健康指南内容（需要 health_guide.write 权限，通过 type_one 和 type_two 筛选）
 //!
 //! ## 参数说明
 //! - `type_one`: 一级类型 ID（整数类型，匹配 HealthGuideType 的 id）
@@ -12,27 +13,120 @@
 //!
 //! 注意：GET、PUT、DELETE 接口的 type_one 和 type_two 参数为必填项，缺失将返回 400 错误
 
-mod delete;
-mod get;
-mod modify;
-mod post;
-
 use axum::Router;
+use db_manager::entity::health_guide_content as health_guide_content_entity;
+use interface_types::proto::health_guide_content::{
+    HealthGuideContent as ProtoHealthGuideContent, HealthGuideContentRequest,
+    HealthGuideContentResponse,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Select};
+use user_auth::rbac::permission;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page};
+use crate::resource::{Access, Resource, parse_json, patch, required_param, resource_router};
 
 /// 创建并返回 health_guide_content 的完整路由
-///
-/// 此函数合并了所有 health_guide_content 相关的子路由：
-/// - get::router() - 获取内容（需要 type_one 和 type_two 参数）
-/// - post::router() - 创建内容
-/// - modify::router() - 修改内容（需要 type_one 和 type_two 参数）
-/// - delete::router() - 删除内容（需要 type_one 和 type_two 参数）
 pub fn health_guide_content_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<HealthGuideContent>()
 }
 
+pub struct HealthGuideContent;
+
+impl Resource for HealthGuideContent {
+    type Entity = health_guide_content_entity::Entity;
+    type Model = health_guide_content_entity::Model;
+    type ActiveModel = health_guide_content_entity::ActiveModel;
+    type Proto = ProtoHealthGuideContent;
+    type Request = HealthGuideContentRequest;
+    type Response = HealthGuideContentResponse;
+
+    const NAME: &'static str = "health guide content";
+    const ID: health_guide_content_entity::Column = health_guide_content_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[],
+        search: &[],
+        filter: &[
+            health_guide_content_entity::Column::TypeOne,
+            health_guide_content_entity::Column::TypeTwo,
+        ],
+        created: None,
+    };
+    const WRITE: Access = Access::Permission(permission::HEALTH_GUIDE_WRITE);
+
+    fn to_proto(c: Self::Model) -> Self::Proto {
+        ProtoHealthGuideContent {
+            id: c.id,
+            type_one: c.type_one.unwrap_or_default(),
+            type_two: c.type_two.unwrap_or_default(),
+            content: c.content.map(|json| json.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        HealthGuideContentResponse {
+            health_guide_contents: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        // content 以 JSON 字符串传入
+        let content = parse_json(&payload.content, "content")?;
+        patch(&mut active.type_one, payload.type_one);
+        patch(&mut active.type_two, payload.type_two);
+        patch(&mut active.content, content);
+        Ok(())
+    }
+
+    fn validate_create(payload: &Self::Request) -> Result<(), ApiError> {
+        if payload.type_one == 0 {
+            return Err(ApiError::bad_request(
+                "Missing required parameter: type_one",
+            ));
+        }
+        if payload.type_two.is_empty() {
+            return Err(ApiError::bad_request(
+                "Missing required parameter: type_two",
+            ));
+        }
+        Ok(())
+    }
+
+    /// 必须提供 type_one 和 type_two 参数，按类型筛选由 `LISTING` 完成
+    fn filter(
+        select: Select<Self::Entity>,
+        query: &mut ListQuery,
+    ) -> Result<Select<Self::Entity>, ApiError> {
+        required_param(&query.fields, "type_one")?;
+        required_param(&query.fields, "type_two")?;
+        Ok(select)
+    }
+
+    /// 修改与删除按 type_one 和 type_two 查找
+    async fn locate(
+        db: &DatabaseConnection,
+        params: &[(String, String)],
+    ) -> Result<Self::Model, ApiError> {
+        let type_one = required_param(params, "type_one")?;
+        let type_two = required_param(params, "type_two")?;
+        let type_one_id = type_one.parse::<i32>().map_err(|_| {
+            ApiError::bad_request(format!("Invalid value for type_one: {}", type_one))
+        })?;
+        health_guide_content_entity::Entity::find()
+            .filter(health_guide_content_entity::Column::TypeOne.eq(type_one_id))
+            .filter(health_guide_content_entity::Column::TypeTwo.eq(type_two))
+            .one(db)
+            .await?
+            .ok_or_else(|| {
+                ApiError::not_found(format!(
+                    "Health guide content with type_one '{}' and type_two '{}' not found",
+                    type_one, type_two
+                ))
+            })
+    }
+}
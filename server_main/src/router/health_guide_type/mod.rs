@@ -1,31 +1,82 @@
 //! Health Guide Type 路由模块
 //!
 //! 提供健康指南类型的 CRUD 接口：
-//! - GET /api/health_guide_type - 获取所有健康指南类型列表（所有权限均可访问）
-//! - POST /api/health_guide_type - 创建新的健康指南类型（仅 Admin 权限）
-//! - PUT /api/health_guide_type?id=xxx - 修改指定的健康指南类型（仅 Admin 权限）
-//! - DELETE /api/health_guide_type?id=xxx - 删除指定的健康指南类型（仅 Admin 权限）
-
-mod delete;
-mod get;
-mod modify;
-mod post;
+//! - GET /api/health_guide_type - 获取健康指南类型列表（所有权限均可访问，支持分页、排序与筛选）
+//! - GET /api/health_guide_type/{id} - 获取指定的健康指南类型（所有权限均可访问）
+//! - POST /api/health_guide_type - 创建新的健康指南类型（需要 health_guide.write 权限）
+//! - PUT /api/health_guide_type?id=xxx - 修改指定的健康指南类型（需要 health_guide.write 权限）
+//! - DELETE /api/health_guide_type?id=xxx - 删除指定的健康指南类型（需要 health_guide.write 权限）
 
 use axum::Router;
+use db_manager::entity::health_guide_type as health_guide_type_entity;
+use interface_types::proto::health_guide_type::{
+    HealthGuideType as ProtoHealthGuideType, HealthGuideTypeRequest, HealthGuideTypeResponse,
+};
+use user_auth::rbac::permission;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, parse_json, patch, resource_router};
 
 /// 创建并返回 health_guide_type 的完整路由
-///
-/// 此函数合并了所有 health_guide_type 相关的子路由：
-/// - get::router() - 获取列表
-/// - post::router() - 创建
-/// - modify::router() - 修改
-/// - delete::router() - 删除
 pub fn health_guide_type_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<HealthGuideType>()
+}
+
+pub struct HealthGuideType;
+
+impl Resource for HealthGuideType {
+    type Entity = health_guide_type_entity::Entity;
+    type Model = health_guide_type_entity::Model;
+    type ActiveModel = health_guide_type_entity::ActiveModel;
+    type Proto = ProtoHealthGuideType;
+    type Request = HealthGuideTypeRequest;
+    type Response = HealthGuideTypeResponse;
+
+    const NAME: &'static str = "health guide type";
+    const ID: health_guide_type_entity::Column = health_guide_type_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            health_guide_type_entity::Column::TypeName,
+            health_guide_type_entity::Column::TypeSum,
+        ],
+        search: &[health_guide_type_entity::Column::TypeName],
+        filter: &[
+            health_guide_type_entity::Column::TypeName,
+            health_guide_type_entity::Column::Icon,
+        ],
+        created: None,
+    };
+    const WRITE: Access = Access::Permission(permission::HEALTH_GUIDE_WRITE);
+
+    fn to_proto(t: Self::Model) -> Self::Proto {
+        ProtoHealthGuideType {
+            id: t.id,
+            type_name: t.type_name.unwrap_or_default(),
+            icon: t.icon.unwrap_or_default(),
+            type_sum: t.type_sum.unwrap_or_default(),
+            type_one: t.type_one.map(|json| json.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        HealthGuideTypeResponse {
+            health_guide_types: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        // type_one 以 JSON 字符串传入
+        let type_one = parse_json(&payload.type_one, "type_one")?;
+        patch(&mut active.type_name, payload.type_name);
+        patch(&mut active.icon, payload.icon);
+        patch(&mut active.type_sum, payload.type_sum);
+        patch(&mut active.type_one, type_one);
+        Ok(())
+    }
 }
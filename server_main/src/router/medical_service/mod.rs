@@ -1,21 +1,85 @@
-pub mod delete;
-pub mod get;
-pub mod insert;
-pub mod modify;
-
 use axum::Router;
+use db_manager::entity::medical_service as medical_service_entity;
+use interface_types::proto::medical_service::{
+    MedicalService as ProtoMedicalService, MedicalServiceRequest, MedicalServiceResponse,
+};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, patch, resource_router};
 
 /// 创建 medical_service 路由
 ///
 /// 路由定义：
-/// - GET /api/medical_service: 获取所有医疗服务列表（所有权限 0-3 都可以访问）
+/// - GET /api/medical_service: 获取医疗服务列表（所有权限 0-3 都可以访问，支持分页、排序与筛选）
+/// - GET /api/medical_service/{id}: 获取指定的医疗服务（所有权限均可访问）
 /// - POST /api/medical_service: 新增医疗服务（仅 Admin 权限）
 /// - DELETE /api/medical_service?id=xxx: 删除医疗服务（仅 Admin 权限，id 通过查询参数传递）
 /// - PUT /api/medical_service?id=xxx: 修改医疗服务（仅 Admin 权限，id 通过查询参数传递，其他字段通过 proto body 传递）
 pub fn medical_service_router() -> Router<crate::AppState> {
-    get::router()
-        .merge(insert::router())
-        .merge(delete::router())
-        .merge(modify::router())
+    resource_router::<MedicalService>()
 }
 
+pub struct MedicalService;
+
+impl Resource for MedicalService {
+    type Entity = medical_service_entity::Entity;
+    type Model = medical_service_entity::Model;
+    type ActiveModel = medical_service_entity::ActiveModel;
+    type Proto = ProtoMedicalService;
+    type Request = MedicalServiceRequest;
+    type Response = MedicalServiceResponse;
+
+    const NAME: &'static str = "medical service";
+    const ID: medical_service_entity::Column = medical_service_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            medical_service_entity::Column::Name,
+            medical_service_entity::Column::CreateTime,
+        ],
+        search: &[
+            medical_service_entity::Column::Name,
+            medical_service_entity::Column::Address,
+        ],
+        filter: &[
+            medical_service_entity::Column::Name,
+            medical_service_entity::Column::Phone,
+        ],
+        created: Some(medical_service_entity::Column::CreateTime),
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(s: Self::Model) -> Self::Proto {
+        ProtoMedicalService {
+            id: s.id,
+            name: s.name.unwrap_or_default(),
+            address: s.address.unwrap_or_default(),
+            phone: s.phone.unwrap_or_default(),
+            latitude: s.latitude.unwrap_or_default(),
+            longitude: s.longitude.unwrap_or_default(),
+            service_time: s.service_time.unwrap_or_default(),
+            create_time: s.create_time.and_utc().timestamp(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        MedicalServiceResponse {
+            medical_services: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.name, payload.name);
+        patch(&mut active.address, payload.address);
+        patch(&mut active.phone, payload.phone);
+        patch(&mut active.latitude, payload.latitude);
+        patch(&mut active.longitude, payload.longitude);
+        patch(&mut active.service_time, payload.service_time);
+        Ok(())
+    }
+}
@@ -1,8 +1,20 @@
+use axum::{
+    body::Body,
+    http::{HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+
 pub mod ai_chat;
+pub mod audit_log;
+pub mod caregiver;
 pub mod community_service;
+pub mod console;
 pub mod detail_meal;
 pub mod dinner_provider;
 pub mod feedback;
+pub mod health;
 pub mod health_guide_content;
 pub mod health_guide_type;
 pub mod medical_service;
@@ -11,7 +23,30 @@ pub mod notice;
 pub mod policy_file;
 pub mod policy_type;
 pub mod resource_service;
+pub mod role;
 pub mod service_map_content;
 pub mod service_map_type;
 pub mod slide_show;
+pub mod totp;
 pub mod user;
+
+/// xlsx 文件的 Content-Type
+pub(crate) const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// 文件下载响应，`Content-Disposition` 为 attachment
+pub(crate) fn attachment(content_type: &'static str, filename: &str, body: Vec<u8>) -> Response {
+    let mut response = Body::from(body).into_response();
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    match HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename)) {
+        Ok(value) => {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_DISPOSITION, value);
+            response
+        }
+        Err(_) => ApiError::internal("Invalid file name").into_response(),
+    }
+}
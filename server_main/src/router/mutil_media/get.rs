@@ -1,19 +1,22 @@
 use axum::{
     Router,
     extract::{Query, State},
-    http::{HeaderMap, StatusCode, header},
-    response::IntoResponse,
+    middleware::from_fn_with_state,
+    response::Response,
     routing::get,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::mutil_media as mutil_media_entity;
 use interface_types::proto::mutil_media::{Media as ProtoMedia, MediaResponse};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::Deserialize;
-use user_auth::db_exchange::{ExchangeError, token2user};
+use user_auth::user_auth::UserPermissionLevel;
 use uuid::Uuid;
 
 use crate::AppState;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
+use crate::error::ApiError;
+use crate::router::attachment;
 
 /// 获取多媒体文件的查询参数
 #[derive(Debug, Deserialize)]
@@ -27,6 +30,10 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/metadata", get(get_media_metadata))
         .route("/download", get(get_media_download))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
 /// GET /api/mutil_media/metadata?uuid=xxx
@@ -43,95 +50,30 @@ pub fn router() -> Router<AppState> {
 /// 示例：GET /api/mutil_media/metadata?uuid=xxx
 async fn get_media_metadata(
     State(state): State<AppState>,
-    headers: HeaderMap,
     Query(params): Query<MediaQuery>,
-) -> Protobuf<MediaResponse> {
-    // 1) 从 Header 提取并验证 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(MediaResponse {
-                    media: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(MediaResponse {
-                media: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    match token2user(&token) {
-        Ok(_) => {
-            // Token 验证成功，权限 0-3 均可访问，继续执行查询逻辑
-        }
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(MediaResponse {
-                media: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    }
-
+) -> Result<Protobuf<MediaResponse>, ApiError> {
     let db = state.database.clone();
 
-    // 3. 解析 UUID
-    let uuid = match Uuid::parse_str(&params.uuid) {
-        Ok(u) => u,
-        Err(_) => {
-            return Protobuf(MediaResponse {
-                media: None,
-                code: 400,
-                message: "Invalid UUID format".to_string(),
-            });
-        }
-    };
+    // 1. 解析 UUID
+    let uuid =
+        Uuid::parse_str(&params.uuid).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
 
-    // 4. 查询数据库（通过 UUID 查找，不是通过主键 ID）
-    let media = match mutil_media_entity::Entity::find()
+    // 2. 查询数据库（通过 UUID 查找，不是通过主键 ID）
+    let media = mutil_media_entity::Entity::find()
         .filter(mutil_media_entity::Column::Uuid.eq(uuid))
         .one(db.as_ref())
-        .await
-    {
-        Ok(Some(m)) => m,
-        Ok(None) => {
-            return Protobuf(MediaResponse {
-                media: None,
-                code: 404,
-                message: "Media not found".to_string(),
-            });
-        }
-        Err(err) => {
-            return Protobuf(MediaResponse {
-                media: None,
-                code: 500,
-                message: format!("Database error: {}", err),
-            });
-        }
-    };
+        .await?
+        .ok_or_else(|| ApiError::not_found("Media not found"))?;
 
-    // 5. 返回元数据（MediaResponse，protobuf 格式）
-    Protobuf(MediaResponse {
+    // 3. 返回元数据（MediaResponse，protobuf 格式）
+    Ok(Protobuf(MediaResponse {
         media: Some(ProtoMedia {
             uuid: media.uuid.map(|u| u.to_string()).unwrap_or_default(),
             r#type: media.r#type.unwrap_or_default(),
         }),
         code: 200,
         message: "Get media metadata success".to_string(),
-    })
+    }))
 }
 
 /// GET /api/mutil_media/download?uuid=xxx
@@ -147,82 +89,32 @@ async fn get_media_metadata(
 /// 示例：GET /api/mutil_media/download?uuid=xxx
 async fn get_media_download(
     State(state): State<AppState>,
-    headers: HeaderMap,
     Query(params): Query<MediaQuery>,
-) -> impl IntoResponse {
-    // 1) 从 Header 提取并验证 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return (StatusCode::UNAUTHORIZED, "Invalid token format").into_response();
-            }
-        },
-        None => {
-            return (StatusCode::UNAUTHORIZED, "Missing token").into_response();
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    if let Err(err) = token2user(&token) {
-        let msg = match err {
-            ExchangeError::InvalidToken => "Invalid token".to_string(),
-            ExchangeError::TokenExpired => "Token expired".to_string(),
-            ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-        };
-        return (StatusCode::UNAUTHORIZED, msg).into_response();
-    }
-
+) -> Result<Response, ApiError> {
     let db = state.database.clone();
 
-    // 3. 解析 UUID
-    let uuid = match Uuid::parse_str(&params.uuid) {
-        Ok(u) => u,
-        Err(_) => {
-            return (StatusCode::BAD_REQUEST, "Invalid UUID format").into_response();
-        }
-    };
+    // 1. 解析 UUID
+    let uuid =
+        Uuid::parse_str(&params.uuid).map_err(|_| ApiError::bad_request("Invalid UUID format"))?;
 
-    // 4. 查询数据库（通过 UUID 查找，不是通过主键 ID）
-    let media = match mutil_media_entity::Entity::find()
+    // 2. 查询数据库（通过 UUID 查找，不是通过主键 ID）
+    let media = mutil_media_entity::Entity::find()
         .filter(mutil_media_entity::Column::Uuid.eq(uuid))
         .one(db.as_ref())
-        .await
-    {
-        Ok(Some(m)) => m,
-        Ok(None) => {
-            return (StatusCode::NOT_FOUND, "Media not found").into_response();
-        }
-        Err(err) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Database error: {}", err),
-            )
-                .into_response();
-        }
-    };
+        .await?
+        .ok_or_else(|| ApiError::not_found("Media not found"))?;
 
-    // 5. 提取文件类型和文件数据
+    // 3. 提取文件类型和文件数据
     let media_type = media
         .r#type
         .unwrap_or("application/octet-stream".to_string());
     let file_data = media.file.unwrap_or_default();
 
-    // 6. 根据 type 构建正确的 MIME 类型
+    // 4. 根据 type 构建正确的 MIME 类型
     let content_type = determine_mime_type(&media_type);
 
-    // 5. 构建响应
-    let mut headers = HeaderMap::new();
-    headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
-    headers.insert(
-        header::CONTENT_DISPOSITION,
-        format!("attachment; filename=\"{}\"", uuid)
-            .parse()
-            .unwrap(),
-    );
-
-    // 7. 构建并返回响应
-    (headers, file_data).into_response()
+    // 5. 构建并返回响应
+    Ok(attachment(content_type, &uuid.to_string(), file_data))
 }
 
 /// 根据文件扩展名确定 MIME 类型
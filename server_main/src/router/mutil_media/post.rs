@@ -1,23 +1,32 @@
 use axum::{
-    Json, Router,
+    Router,
     extract::{Multipart, Query, State},
-    http::HeaderMap,
-    response::IntoResponse,
+    middleware::from_fn_with_state,
     routing::post,
 };
 use db_manager::entity::mutil_media as mutil_media_entity;
-use sea_orm::ActiveModelTrait;
-use serde::{Deserialize, Serialize};
-use user_auth::db_exchange::{ExchangeError, token2user};
+use interface_types::proto::mutil_media::{Media as ProtoMedia, MediaResponse};
+use sea_orm::{ActiveModelTrait, TransactionTrait};
+use serde::Deserialize;
+use user_auth::user_auth::UserPermissionLevel;
 use uuid::Uuid;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
+use crate::error::ApiError;
 
 use super::utils::{compress_to_webp, extract_file_type, process_avatar};
 
 /// 创建 mutil_media 的 POST 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(upload_media))
+    Router::new()
+        .route("/", post(upload_media))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
 /// 上传参数
@@ -31,26 +40,6 @@ struct UploadParams {
     avatar: bool,
 }
 
-/// JSON 响应结构
-#[derive(Serialize)]
-struct JsonMediaResponse {
-    /// 媒体信息
-    media: Option<JsonMedia>,
-    /// 状态码
-    code: i32,
-    /// 响应消息
-    message: String,
-}
-
-/// JSON 媒体信息
-#[derive(Serialize)]
-struct JsonMedia {
-    /// UUID
-    uuid: String,
-    /// 文件类型
-    r#type: String,
-}
-
 /// POST /api/mutil_media
 ///
 /// 上传多媒体文件（multipart/form-data 格式）
@@ -66,7 +55,7 @@ struct JsonMedia {
 /// - file: 文件数据（必需）
 /// - filename: 文件名（可选，默认使用原始文件名）
 ///
-/// 返回（JSON 格式）：
+/// 返回 MediaResponse，未指定 Accept 时为 JSON：
 /// ```json
 /// {
 ///   "media": {
@@ -87,51 +76,11 @@ struct JsonMedia {
 /// - 非图片文件不受 compress/avatar 参数影响
 async fn upload_media(
     State(state): State<AppState>,
+    audit: Auditor,
     Query(params): Query<UploadParams>,
-    headers: HeaderMap,
     mut multipart: Multipart,
-) -> impl IntoResponse {
-    // 1) 从 Header 提取并验证 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Json(JsonMediaResponse {
-                    media: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Json(JsonMediaResponse {
-                media: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    match token2user(&token) {
-        Ok(_) => {
-            // Token 验证成功，权限 0-3 均可访问，继续执行上传逻辑
-        }
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Json(JsonMediaResponse {
-                media: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 从 multipart 中提取文件数据和文件名
+) -> Result<Protobuf<MediaResponse>, ApiError> {
+    // 1) 从 multipart 中提取文件数据和文件名
     let mut file_data: Option<Vec<u8>> = None;
     let mut filename: Option<String> = None;
 
@@ -141,10 +90,10 @@ async fn upload_media(
         match name.as_str() {
             "file" => {
                 // 获取原始文件名（如果存在）
-                if filename.is_none() {
-                    if let Some(original_name) = field.file_name() {
-                        filename = Some(original_name.to_string());
-                    }
+                if filename.is_none()
+                    && let Some(original_name) = field.file_name()
+                {
+                    filename = Some(original_name.to_string());
                 }
                 // 读取文件数据
                 match field.bytes().await {
@@ -152,11 +101,10 @@ async fn upload_media(
                         file_data = Some(bytes.to_vec());
                     }
                     Err(err) => {
-                        return Json(JsonMediaResponse {
-                            media: None,
-                            code: 400,
-                            message: format!("Failed to read file data: {}", err),
-                        });
+                        return Err(ApiError::bad_request(format!(
+                            "Failed to read file data: {}",
+                            err
+                        )));
                     }
                 }
             }
@@ -167,11 +115,10 @@ async fn upload_media(
                         filename = Some(name);
                     }
                     Err(err) => {
-                        return Json(JsonMediaResponse {
-                            media: None,
-                            code: 400,
-                            message: format!("Failed to read filename: {}", err),
-                        });
+                        return Err(ApiError::bad_request(format!(
+                            "Failed to read filename: {}",
+                            err
+                        )));
                     }
                 }
             }
@@ -181,35 +128,30 @@ async fn upload_media(
         }
     }
 
-    // 4) 验证文件数据存在
+    // 2) 验证文件数据存在
     let file_data = match file_data {
         Some(data) => data,
         None => {
-            return Json(JsonMediaResponse {
-                media: None,
-                code: 400,
-                message: "No file data provided".to_string(),
-            });
+            return Err(ApiError::bad_request("No file data provided"));
         }
     };
 
-    // 5) 使用文件名（优先使用 filename 字段，否则使用文件的原始文件名）
+    // 3) 使用文件名（优先使用 filename 字段，否则使用文件的原始文件名）
     let filename = filename.unwrap_or_else(|| {
         // 从 multipart 字段中获取原始文件名
         "unknown".to_string()
     });
 
-    // 6) 处理图片（如果启用了 compress 或 avatar 参数）
+    // 4) 处理图片（如果启用了 compress 或 avatar 参数）
     let (processed_data, processed_filename) = if params.avatar {
         // 头像模式：压缩为 webp 并裁剪为 120x120
         match process_avatar(&file_data, &filename) {
             Ok((data, name)) => (data, name),
             Err(err) => {
-                return Json(JsonMediaResponse {
-                    media: None,
-                    code: 400,
-                    message: format!("Failed to process avatar: {}", err),
-                });
+                return Err(ApiError::bad_request(format!(
+                    "Failed to process avatar: {}",
+                    err
+                )));
             }
         }
     } else if params.compress {
@@ -217,11 +159,10 @@ async fn upload_media(
         match compress_to_webp(&file_data, &filename) {
             Ok((data, name)) => (data, name),
             Err(err) => {
-                return Json(JsonMediaResponse {
-                    media: None,
-                    code: 400,
-                    message: format!("Failed to compress image: {}", err),
-                });
+                return Err(ApiError::bad_request(format!(
+                    "Failed to compress image: {}",
+                    err
+                )));
             }
         }
     } else {
@@ -229,13 +170,13 @@ async fn upload_media(
         (file_data, filename)
     };
 
-    // 7) 从文件名提取文件类型（后缀）
+    // 5) 从文件名提取文件类型（后缀）
     let media_type = extract_file_type(&processed_filename);
 
-    // 8) 生成 UUID
+    // 6) 生成 UUID
     let uuid = Uuid::new_v4();
 
-    // 9) 创建 ActiveModel 并插入数据库
+    // 7) 创建 ActiveModel 并插入数据库
     let db = state.database.clone();
     let new_media = mutil_media_entity::ActiveModel {
         uuid: sea_orm::Set(Some(uuid)),
@@ -244,29 +185,24 @@ async fn upload_media(
         ..Default::default()
     };
 
-    // 10) 执行插入操作
-    match new_media.insert(db.as_ref()).await {
-        Ok(inserted_media) => {
-            // 插入成功，返回 JSON 响应
-            Json(JsonMediaResponse {
-                media: Some(JsonMedia {
-                    uuid: inserted_media
-                        .uuid
-                        .map(|u| u.to_string())
-                        .unwrap_or_default(),
-                    r#type: inserted_media.r#type.unwrap_or_default(),
-                }),
-                code: 200,
-                message: "Upload media success".to_string(),
-            })
-        }
-        Err(err) => {
-            // 插入失败，返回错误响应
-            Json(JsonMediaResponse {
-                media: None,
-                code: 500,
-                message: format!("Failed to upload media: {}", err),
-            })
-        }
-    }
+    // 8) 执行插入操作，审计记录在同一事务中写入（文件内容只记录长度）
+    let txn = db.begin().await?;
+    let inserted_media = new_media
+        .insert(&txn)
+        .await
+        .map_err(|err| ApiError::internal(format!("Failed to upload media: {}", err)))?;
+    audit.insert(&txn, &inserted_media).await?;
+    txn.commit().await?;
+
+    Ok(Protobuf(MediaResponse {
+        media: Some(ProtoMedia {
+            uuid: inserted_media
+                .uuid
+                .map(|u| u.to_string())
+                .unwrap_or_default(),
+            r#type: inserted_media.r#type.unwrap_or_default(),
+        }),
+        code: 200,
+        message: "Upload media success".to_string(),
+    }))
 }
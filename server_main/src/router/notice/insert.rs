@@ -1,23 +1,28 @@
 use axum::{
     Router,
     extract::State,
-    http::HeaderMap,
+    middleware::from_fn_with_state,
     routing::{get, post},
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::notice as notice_entity;
 use interface_types::proto::notice::{Notice as ProtoNotice, NoticeRequest, NoticeResponse};
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
-use user_auth::db_exchange::{ExchangeError, token2user};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set, TransactionTrait};
 use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
 
 /// 创建 notice 路由
 pub fn router() -> Router<AppState> {
-    Router::new()
-        .route("/insert", get(get_notice))
-        .route("/insert", post(insert_notice))
+    Router::new().route("/insert", get(get_notice)).route(
+        "/insert",
+        post(insert_notice).route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        )),
+    )
 }
 
 /// GET /api/notice - 获取 notice（所有权限 0-3 都可以访问）
@@ -59,77 +64,43 @@ async fn get_notice(State(state): State<AppState>) -> Protobuf<NoticeResponse> {
 /// POST /api/notice - 新增 notice（仅 Admin 权限可以访问）
 async fn insert_notice(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    audit: Auditor,
     Protobuf(payload): Protobuf<NoticeRequest>,
 ) -> Protobuf<NoticeResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(NoticeResponse {
-                    notice: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(NoticeResponse {
-                notice: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(NoticeResponse {
-                notice: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 权限校验：只有 Admin (permission=3) 才能新增 notice
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission != UserPermissionLevel::Admin.level() {
-        return Protobuf(NoticeResponse {
-            notice: None,
-            code: 403,
-            message: "Permission denied: Only Admin can insert notice".to_string(),
-        });
-    }
-
-    // 4) 创建新的 ActiveModel 并插入
+    // 1) 创建新的 ActiveModel 并插入
     let db = state.database.clone();
     let new_notice = notice_entity::ActiveModel {
         content: Set(Some(payload.content)),
         ..Default::default()
     };
 
-    // 5) 执行插入
-    let inserted_notice = match new_notice.insert(db.as_ref()).await {
+    // 2) 执行插入，审计记录在同一事务中写入
+    let inserted = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = new_notice
+            .insert(&txn)
+            .await
+            .map_err(|e| format!("Failed to insert notice: {}", e))?;
+        audit
+            .insert(&txn, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let inserted_notice = match inserted {
         Ok(n) => n,
         Err(err) => {
             return Protobuf(NoticeResponse {
                 notice: None,
                 code: 500,
-                message: format!("Failed to insert notice: {}", err),
+                message: err,
             });
         }
     };
 
-    // 6) 返回新增的 notice
+    // 3) 返回新增的 notice
     Protobuf(NoticeResponse {
         notice: Some(ProtoNotice {
             id: inserted_notice.id,
@@ -2,31 +2,89 @@
 //!
 //! 提供政策文件的 CRUD 接口：
 //! - GET /api/policy_file?type=xxx - 获取指定类型的政策文件列表（所有权限均可访问，必须提供 type 参数）
+//! - GET /api/policy_file/{id} - 获取指定的政策文件（所有权限均可访问）
 //! - POST /api/policy_file - 创建新的政策文件（仅 Admin 权限，id 和 create_time 由数据库自动处理）
 //! - PUT /api/policy_file?id=xxx - 修改指定的政策文件（仅 Admin 权限，通过 id 查找）
 //! - DELETE /api/policy_file?id=xxx - 删除指定的政策文件（仅 Admin 权限，通过 id 查找）
 
-
-mod delete;
-mod get;
-mod modify;
-mod post;
-
 use axum::Router;
+use db_manager::entity::policy_file as policy_file_entity;
+use interface_types::proto::policy_file::{
+    PolicyFile as ProtoPolicyFile, PolicyFileRequest, PolicyFileResponse,
+};
+use sea_orm::Select;
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page};
+use crate::resource::{Access, Resource, patch, required_param, resource_router};
 
 /// 创建并返回 policy_file 的完整路由
-///
-/// 此函数合并了所有 policy_file 相关的子路由：
-/// - get::router() - 获取列表（需要 type 参数）
-/// - post::router() - 创建
-/// - modify::router() - 修改（需要 id 参数）
-/// - delete::router() - 删除（需要 id 参数）
 pub fn policy_file_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<PolicyFile>()
+}
+
+pub struct PolicyFile;
+
+impl Resource for PolicyFile {
+    type Entity = policy_file_entity::Entity;
+    type Model = policy_file_entity::Model;
+    type ActiveModel = policy_file_entity::ActiveModel;
+    type Proto = ProtoPolicyFile;
+    type Request = PolicyFileRequest;
+    type Response = PolicyFileResponse;
+
+    const NAME: &'static str = "policy file";
+    const ID: policy_file_entity::Column = policy_file_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            policy_file_entity::Column::Title,
+            policy_file_entity::Column::Index,
+            policy_file_entity::Column::CreateTime,
+        ],
+        search: &[policy_file_entity::Column::Title],
+        filter: &[
+            policy_file_entity::Column::Type,
+            policy_file_entity::Column::Index,
+        ],
+        created: Some(policy_file_entity::Column::CreateTime),
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(f: Self::Model) -> Self::Proto {
+        ProtoPolicyFile {
+            id: f.id,
+            title: f.title.unwrap_or_default(),
+            r#type: f.r#type.unwrap_or_default(),
+            index: f.index.unwrap_or_default(),
+            create_time: f.create_time.and_utc().timestamp(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        PolicyFileResponse {
+            policy_files: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.title, payload.title);
+        patch(&mut active.r#type, payload.r#type);
+        patch(&mut active.index, payload.index);
+        Ok(())
+    }
+
+    /// 必须提供 type 参数，按类型筛选由 `LISTING` 完成
+    fn filter(
+        select: Select<Self::Entity>,
+        query: &mut ListQuery,
+    ) -> Result<Select<Self::Entity>, ApiError> {
+        required_param(&query.fields, "type")?;
+        Ok(select)
+    }
 }
@@ -1,31 +1,75 @@
 //! Policy Type 路由模块
 //!
 //! 提供政策类型的 CRUD 接口：
-//! - GET /api/policy_type - 获取所有政策类型列表（所有权限均可访问）
+//! - GET /api/policy_type - 获取政策类型列表（所有权限均可访问，支持分页、排序与筛选）
+//! - GET /api/policy_type/{id} - 获取指定的政策类型（所有权限均可访问）
 //! - POST /api/policy_type - 创建新的政策类型（仅 Admin 权限）
 //! - PUT /api/policy_type?id=xxx - 修改指定的政策类型（仅 Admin 权限）
 //! - DELETE /api/policy_type?id=xxx - 删除指定的政策类型（仅 Admin 权限）
 
-mod delete;
-mod get;
-mod modify;
-mod post;
-
 use axum::Router;
+use db_manager::entity::policy_type as policy_type_entity;
+use interface_types::proto::policy_type::{
+    PolicyType as ProtoPolicyType, PolicyTypeRequest, PolicyTypeResponse,
+};
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, patch, resource_router};
 
 /// 创建并返回 policy_type 的完整路由
-///
-/// 此函数合并了所有 policy_type 相关的子路由：
-/// - get::router() - 获取列表
-/// - post::router() - 创建
-/// - modify::router() - 修改
-/// - delete::router() - 删除
 pub fn policy_type_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<PolicyType>()
+}
+
+pub struct PolicyType;
+
+impl Resource for PolicyType {
+    type Entity = policy_type_entity::Entity;
+    type Model = policy_type_entity::Model;
+    type ActiveModel = policy_type_entity::ActiveModel;
+    type Proto = ProtoPolicyType;
+    type Request = PolicyTypeRequest;
+    type Response = PolicyTypeResponse;
+
+    const NAME: &'static str = "policy type";
+    const ID: policy_type_entity::Column = policy_type_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[policy_type_entity::Column::Type],
+        search: &[policy_type_entity::Column::Type],
+        filter: &[policy_type_entity::Column::Type],
+        created: None,
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(t: Self::Model) -> Self::Proto {
+        ProtoPolicyType {
+            id: t.id,
+            r#type: t.r#type.unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        PolicyTypeResponse {
+            policy_types: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.r#type, payload.r#type);
+        Ok(())
+    }
+
+    fn validate_create(payload: &Self::Request) -> Result<(), ApiError> {
+        if payload.r#type.is_empty() {
+            return Err(ApiError::bad_request("Missing required parameter: type"));
+        }
+        Ok(())
+    }
 }
@@ -1,21 +1,89 @@
-pub mod delete;
-pub mod get;
-pub mod insert;
-pub mod modify;
-
 use axum::Router;
+use db_manager::entity::resource_service as resource_service_entity;
+use interface_types::proto::resource_service::{
+    ResourceService as ProtoResourceService, ResourceServiceRequest, ResourceServiceResponse,
+};
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, patch, resource_router};
 
 /// 创建 resource_service 路由
 ///
 /// 路由定义：
-/// - GET /api/resource_service: 获取所有资源服务列表（所有权限 0-3 都可以访问）
+/// - GET /api/resource_service: 获取资源服务列表（所有权限 0-3 都可以访问，支持分页、排序与筛选）
+/// - GET /api/resource_service/{id}: 获取指定的资源服务（所有权限均可访问）
 /// - POST /api/resource_service: 新增资源服务（仅 Admin 权限）
 /// - DELETE /api/resource_service?id=xxx: 删除资源服务（仅 Admin 权限，id 通过查询参数传递）
 /// - PUT /api/resource_service?id=xxx: 修改资源服务（仅 Admin 权限，id 通过查询参数传递，其他字段通过 proto body 传递）
 pub fn resource_service_router() -> Router<crate::AppState> {
-    get::router()
-        .merge(insert::router())
-        .merge(delete::router())
-        .merge(modify::router())
+    resource_router::<ResourceService>()
 }
 
+pub struct ResourceService;
+
+impl Resource for ResourceService {
+    type Entity = resource_service_entity::Entity;
+    type Model = resource_service_entity::Model;
+    type ActiveModel = resource_service_entity::ActiveModel;
+    type Proto = ProtoResourceService;
+    type Request = ResourceServiceRequest;
+    type Response = ResourceServiceResponse;
+
+    const NAME: &'static str = "resource service";
+    const ID: resource_service_entity::Column = resource_service_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            resource_service_entity::Column::Name,
+            resource_service_entity::Column::CreateTime,
+        ],
+        search: &[
+            resource_service_entity::Column::Name,
+            resource_service_entity::Column::Address,
+            resource_service_entity::Column::Boss,
+        ],
+        filter: &[
+            resource_service_entity::Column::Name,
+            resource_service_entity::Column::Phone,
+            resource_service_entity::Column::Boss,
+        ],
+        created: Some(resource_service_entity::Column::CreateTime),
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(s: Self::Model) -> Self::Proto {
+        ProtoResourceService {
+            id: s.id,
+            name: s.name.unwrap_or_default(),
+            address: s.address.unwrap_or_default(),
+            phone: s.phone.unwrap_or_default(),
+            latitude: s.latitude.unwrap_or_default(),
+            longitude: s.longitude.unwrap_or_default(),
+            service_time: s.service_time.unwrap_or_default(),
+            boss: s.boss.unwrap_or_default(),
+            create_time: s.create_time.and_utc().timestamp(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        ResourceServiceResponse {
+            resource_services: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.name, payload.name);
+        patch(&mut active.address, payload.address);
+        patch(&mut active.phone, payload.phone);
+        patch(&mut active.latitude, payload.latitude);
+        patch(&mut active.longitude, payload.longitude);
+        patch(&mut active.service_time, payload.service_time);
+        patch(&mut active.boss, payload.boss);
+        Ok(())
+    }
+}
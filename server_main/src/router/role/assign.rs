@@ -0,0 +1,204 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::post,
+};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::role::{UserRoles as ProtoUserRoles, UserRolesResponse};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Deserialize;
+use serde_json::json;
+use user_auth::rbac::{assign_role, load_permissions, load_roles, permission, unassign_role};
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, Permissions, require_role_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/assign", post(assign))
+        .route("/unassign", post(unassign))
+        .route_layer(from_fn_with_state(
+            permission::ROLE_MANAGE,
+            require_role_permission,
+        ))
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct AssignParams {
+    /// 目标用户 open_id
+    open_id: String,
+    /// 角色名
+    role: String,
+}
+
+/// 查询用户并返回其当前的角色与生效权限
+pub(super) async fn user_roles(
+    db: &DatabaseConnection,
+    open_id: &str,
+) -> Result<ProtoUserRoles, (i32, String)> {
+    let target = find_target(db, open_id).await?;
+    let roles = load_roles(db, &target)
+        .await
+        .map_err(|e| e.code_and_message())?;
+    let permissions = load_permissions(db, &target)
+        .await
+        .map_err(|e| e.code_and_message())?;
+    Ok(ProtoUserRoles {
+        open_id: target.open_id,
+        roles: roles.into_iter().map(|r| r.name).collect(),
+        permissions: permissions.iter().cloned().collect(),
+    })
+}
+
+async fn find_target(
+    db: &DatabaseConnection,
+    open_id: &str,
+) -> Result<user_entity::Model, (i32, String)> {
+    match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(db)
+        .await
+    {
+        Ok(Some(u)) => Ok(u),
+        Ok(None) => Err((404, "Target user not found".to_string())),
+        Err(err) => Err((500, format!("Database error: {}", err))),
+    }
+}
+
+/// POST /api/role/assign?open_id=xxx&role=xxx - 为用户分配角色（需要 role.manage 权限）
+///
+/// 操作者需持有该角色的全部权限，admin 角色只能由持有 `*` 的操作者分配
+async fn assign(
+    State(state): State<AppState>,
+    audit: Auditor,
+    CurrentUser(operator): CurrentUser,
+    Permissions(granter): Permissions,
+    Query(params): Query<AssignParams>,
+) -> Protobuf<UserRolesResponse> {
+    // 1) 查询目标用户
+    let db = state.database.clone();
+    let target = match find_target(db.as_ref(), &params.open_id).await {
+        Ok(u) => u,
+        Err((code, message)) => {
+            return Protobuf(UserRolesResponse {
+                user_roles: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 分配角色，审计记录在同一事务中写入
+    let assigned = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        assign_role(&txn, &granter, target.id, &params.role, Some(operator.id))
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "user_role",
+                format!("{},{}", target.id, params.role),
+                None,
+                Some(
+                    json!({ "user_id": target.id, "role": params.role, "granted_by": operator.id }),
+                ),
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = assigned {
+        return Protobuf(UserRolesResponse {
+            user_roles: None,
+            code,
+            message,
+        });
+    }
+
+    // 3) 返回最新角色
+    match user_roles(db.as_ref(), &params.open_id).await {
+        Ok(r) => Protobuf(UserRolesResponse {
+            user_roles: Some(r),
+            code: 200,
+            message: "Assign role success".to_string(),
+        }),
+        Err((code, message)) => Protobuf(UserRolesResponse {
+            user_roles: None,
+            code,
+            message,
+        }),
+    }
+}
+
+/// POST /api/role/unassign?open_id=xxx&role=xxx - 取消用户的角色（需要 role.manage 权限）
+///
+/// 与分配相同，操作者需持有该角色的全部权限；
+/// 与权限等级对应的内置角色不能取消，需通过修改 permission 调整
+async fn unassign(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Permissions(granter): Permissions,
+    Query(params): Query<AssignParams>,
+) -> Protobuf<UserRolesResponse> {
+    // 1) 查询目标用户
+    let db = state.database.clone();
+    let target = match find_target(db.as_ref(), &params.open_id).await {
+        Ok(u) => u,
+        Err((code, message)) => {
+            return Protobuf(UserRolesResponse {
+                user_roles: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 取消角色，审计记录在同一事务中写入
+    let unassigned = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        unassign_role(&txn, &granter, target.id, &params.role)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "user_role",
+                format!("{},{}", target.id, params.role),
+                Some(json!({ "user_id": target.id, "role": params.role })),
+                None,
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = unassigned {
+        return Protobuf(UserRolesResponse {
+            user_roles: None,
+            code,
+            message,
+        });
+    }
+
+    // 3) 返回最新角色
+    match user_roles(db.as_ref(), &params.open_id).await {
+        Ok(r) => Protobuf(UserRolesResponse {
+            user_roles: Some(r),
+            code: 200,
+            message: "Unassign role success".to_string(),
+        }),
+        Err((code, message)) => Protobuf(UserRolesResponse {
+            user_roles: None,
+            code,
+            message,
+        }),
+    }
+}
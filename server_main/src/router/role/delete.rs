@@ -0,0 +1,81 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::delete,
+};
+use interface_types::proto::role::RoleResponse;
+use sea_orm::TransactionTrait;
+use serde::Deserialize;
+use user_auth::rbac::{delete_role, permission};
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_role_permission;
+use crate::codec::Protobuf;
+
+use super::role_snapshot;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", delete(delete_by_name))
+        .route_layer(from_fn_with_state(
+            permission::ROLE_MANAGE,
+            require_role_permission,
+        ))
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct RoleParams {
+    /// 角色名
+    name: String,
+}
+
+/// DELETE /api/role?name=xxx - 删除自定义角色（需要 role.manage 权限，内置角色不可删除）
+async fn delete_by_name(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Query(params): Query<RoleParams>,
+) -> Protobuf<RoleResponse> {
+    // 1) 记录删除前的角色
+    let db = state.database.clone();
+    let before = match role_snapshot(db.as_ref(), &params.name).await {
+        Ok(r) => r,
+        Err((code, message)) => {
+            return Protobuf(RoleResponse {
+                roles: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 删除角色，审计记录在同一事务中写入
+    let deleted = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        delete_role(&txn, &params.name)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(&txn, "role", &params.name, before, None)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = deleted {
+        return Protobuf(RoleResponse {
+            roles: vec![],
+            code,
+            message,
+        });
+    }
+
+    Protobuf(RoleResponse {
+        roles: vec![],
+        code: 200,
+        message: "Delete role success".to_string(),
+    })
+}
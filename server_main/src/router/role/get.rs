@@ -0,0 +1,48 @@
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::get};
+use interface_types::proto::role::{Role as ProtoRole, RoleResponse};
+use user_auth::rbac::{list_roles, permission};
+
+use crate::AppState;
+use crate::auth::require_role_permission;
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_roles))
+        .route_layer(from_fn_with_state(
+            permission::ROLE_MANAGE,
+            require_role_permission,
+        ))
+}
+
+/// GET /api/role - 获取所有角色及其权限（需要 role.manage 权限）
+async fn get_roles(State(state): State<AppState>) -> Protobuf<RoleResponse> {
+    // 1) 查询角色
+    let db = state.database.clone();
+    let roles = match list_roles(db.as_ref()).await {
+        Ok(r) => r,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(RoleResponse {
+                roles: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 转换为 Proto 模型
+    Protobuf(RoleResponse {
+        roles: roles
+            .into_iter()
+            .map(|r| ProtoRole {
+                name: r.name,
+                description: r.description,
+                is_builtin: r.is_builtin,
+                permissions: r.permissions,
+            })
+            .collect(),
+        code: 200,
+        message: "Get roles success".to_string(),
+    })
+}
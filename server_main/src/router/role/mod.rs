@@ -0,0 +1,48 @@
+//! Role 路由模块
+//!
+//! 管理数据库中的角色与权限，所有接口均需要 role.manage 权限，且只能授予操作者自己持有的权限：
+//! - GET /api/role - 获取所有角色及其权限
+//! - PUT /api/role - 新建或修改角色（整体替换权限）
+//! - DELETE /api/role?name=xxx - 删除自定义角色
+//! - POST /api/role/assign?open_id=xxx&role=xxx - 为用户分配角色
+//! - POST /api/role/unassign?open_id=xxx&role=xxx - 取消用户的角色
+//! - GET /api/role/user?open_id=xxx - 查看用户持有的角色与生效权限
+
+mod assign;
+mod delete;
+mod get;
+mod upsert;
+mod user;
+
+use axum::Router;
+use sea_orm::ConnectionTrait;
+use serde_json::{Value, json};
+use user_auth::rbac::list_roles;
+
+use crate::AppState;
+
+/// 角色及其权限的当前状态，用于审计记录；角色不存在时为 `None`
+async fn role_snapshot<C: ConnectionTrait>(
+    db: &C,
+    name: &str,
+) -> Result<Option<Value>, (i32, String)> {
+    let roles = list_roles(db).await.map_err(|e| e.code_and_message())?;
+    Ok(roles.into_iter().find(|r| r.name == name).map(|r| {
+        json!({
+            "name": r.name,
+            "description": r.description,
+            "is_builtin": r.is_builtin,
+            "permissions": r.permissions,
+        })
+    }))
+}
+
+/// 创建并返回 role 的完整路由
+pub fn role_router() -> Router<AppState> {
+    Router::new()
+        .merge(get::router())
+        .merge(upsert::router())
+        .merge(delete::router())
+        .merge(assign::router())
+        .merge(user::router())
+}
@@ -0,0 +1,101 @@
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::put};
+use interface_types::proto::role::{Role as ProtoRole, RoleRequest, RoleResponse};
+use sea_orm::TransactionTrait;
+use user_auth::rbac::{RbacError, permission, upsert_role};
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{Permissions, require_role_permission};
+use crate::codec::Protobuf;
+
+use super::role_snapshot;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", put(upsert))
+        .route_layer(from_fn_with_state(
+            permission::ROLE_MANAGE,
+            require_role_permission,
+        ))
+}
+
+/// PUT /api/role - 新建或修改角色（需要 role.manage 权限）
+///
+/// 角色已存在时整体替换其描述与权限；内置角色（admin 及与权限等级对应的角色）不可修改，
+/// 只能授予操作者自己持有的权限
+async fn upsert(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Permissions(granter): Permissions,
+    Protobuf(payload): Protobuf<RoleRequest>,
+) -> Protobuf<RoleResponse> {
+    // 1) 记录修改前的角色
+    let db = state.database.clone();
+    let before = match role_snapshot(db.as_ref(), &payload.name).await {
+        Ok(r) => r,
+        Err((code, message)) => {
+            return Protobuf(RoleResponse {
+                roles: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 内置角色不可修改
+    if before.as_ref().is_some_and(|r| r["is_builtin"] == true) {
+        let (code, message) = RbacError::BuiltinRole(payload.name).code_and_message();
+        return Protobuf(RoleResponse {
+            roles: vec![],
+            code,
+            message,
+        });
+    }
+
+    // 3) 写入角色与权限，审计记录在同一事务中写入
+    let saved = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let role = upsert_role(
+            &txn,
+            &granter,
+            &payload.name,
+            payload.description.clone(),
+            payload.permissions.clone(),
+        )
+        .await
+        .map_err(|e| e.code_and_message())?;
+        let after = role_snapshot(&txn, &role.name).await?;
+        audit
+            .fields(&txn, "role", &role.name, before, after)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(role)
+    }
+    .await;
+    let role = match saved {
+        Ok(r) => r,
+        Err((code, message)) => {
+            return Protobuf(RoleResponse {
+                roles: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    // 4) 返回写入后的角色
+    let mut permissions = payload.permissions;
+    permissions.sort();
+    permissions.dedup();
+    Protobuf(RoleResponse {
+        roles: vec![ProtoRole {
+            name: role.name,
+            description: role.description,
+            is_builtin: role.is_builtin,
+            permissions,
+        }],
+        code: 200,
+        message: "Save role success".to_string(),
+    })
+}
@@ -0,0 +1,50 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::get,
+};
+use interface_types::proto::role::UserRolesResponse;
+use serde::Deserialize;
+use user_auth::rbac::permission;
+
+use super::assign::user_roles;
+use crate::AppState;
+use crate::auth::require_role_permission;
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/user", get(get_user_roles))
+        .route_layer(from_fn_with_state(
+            permission::ROLE_MANAGE,
+            require_role_permission,
+        ))
+}
+
+/// 查询参数
+#[derive(Debug, Deserialize)]
+struct UserParams {
+    /// 目标用户 open_id
+    open_id: String,
+}
+
+/// GET /api/role/user?open_id=xxx - 查看用户持有的角色与生效权限（需要 role.manage 权限）
+async fn get_user_roles(
+    State(state): State<AppState>,
+    Query(params): Query<UserParams>,
+) -> Protobuf<UserRolesResponse> {
+    let db = state.database.clone();
+    match user_roles(db.as_ref(), &params.open_id).await {
+        Ok(r) => Protobuf(UserRolesResponse {
+            user_roles: Some(r),
+            code: 200,
+            message: "Get user roles success".to_string(),
+        }),
+        Err((code, message)) => Protobuf(UserRolesResponse {
+            user_roles: None,
+            code,
+            message,
+        }),
+    }
+}
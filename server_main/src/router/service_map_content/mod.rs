@@ -1,7 +1,8 @@
 //! Service Map Content 路由模块
 //!
 //! 提供服务地图内容的 CRUD 接口：
-//! - GET /api/service_map_content?type_one=xxx&type_two=xxx - 获取服务地图内容（所有权限均可访问，必须提供 type_one 和 type_two 参数）
+//! - GET /api/service_map_content?type_one=xxx&type_two=xxx - 获取服务地图内容（所有权限均可访问，必须提供 type_one 和 type_two 参数，分页见 `crate::listing`）
+//! - GET /api/service_map_content/{id} - 获取指定的服务地图内容（所有权限均可访问）
 //! - POST /api/service_map_content - 创建新的服务地图内容（仅 Admin 权限）
 //! - PUT /api/service_map_content?type_one=xxx&type_two=xxx - 修改指定的服务地图内容（仅 Admin 权限，通过 type_one 和 type_two 筛选）
 //! - DELETE /api/service_map_content?type_one=xxx&type_two=xxx - 删除指定的服务地图内容（仅 Admin 权限，通过 type_one 和 type_two 筛选）
@@ -12,26 +13,120 @@
 //!
 //! 注意：GET、PUT、DELETE 接口的 type_one 和 type_two 参数为必填项，缺失将返回 400 错误
 
-mod delete;
-mod get;
-mod modify;
-mod post;
-
 use axum::Router;
+use db_manager::entity::service_map_content as service_map_content_entity;
+use interface_types::proto::service_map_content::{
+    ServiceMapContent as ProtoServiceMapContent, ServiceMapContentRequest,
+    ServiceMapContentResponse,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Select};
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page};
+use crate::resource::{Access, Resource, parse_json, patch, required_param, resource_router};
 
 /// 创建并返回 service_map_content 的完整路由
-///
-/// 此函数合并了所有 service_map_content 相关的子路由：
-/// - get::router() - 获取内容（需要 type_one 和 type_two 参数）
-/// - post::router() - 创建内容
-/// - modify::router() - 修改内容（需要 type_one 和 type_two 参数）
-/// - delete::router() - 删除内容（需要 type_one 和 type_two 参数）
 pub fn service_map_content_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<ServiceMapContent>()
+}
+
+pub struct ServiceMapContent;
+
+impl Resource for ServiceMapContent {
+    type Entity = service_map_content_entity::Entity;
+    type Model = service_map_content_entity::Model;
+    type ActiveModel = service_map_content_entity::ActiveModel;
+    type Proto = ProtoServiceMapContent;
+    type Request = ServiceMapContentRequest;
+    type Response = ServiceMapContentResponse;
+
+    const NAME: &'static str = "service map content";
+    const ID: service_map_content_entity::Column = service_map_content_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[],
+        search: &[],
+        filter: &[
+            service_map_content_entity::Column::TypeOne,
+            service_map_content_entity::Column::TypeTwo,
+        ],
+        created: None,
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(c: Self::Model) -> Self::Proto {
+        ProtoServiceMapContent {
+            id: c.id,
+            type_one: c.type_one.unwrap_or_default(),
+            type_two: c.type_two.unwrap_or_default(),
+            content: c.content.map(|json| json.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        ServiceMapContentResponse {
+            service_map_contents: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        // content 以 JSON 字符串传入
+        let content = parse_json(&payload.content, "content")?;
+        patch(&mut active.type_one, payload.type_one);
+        patch(&mut active.type_two, payload.type_two);
+        patch(&mut active.content, content);
+        Ok(())
+    }
+
+    fn validate_create(payload: &Self::Request) -> Result<(), ApiError> {
+        if payload.type_one == 0 {
+            return Err(ApiError::bad_request(
+                "Missing required parameter: type_one",
+            ));
+        }
+        if payload.type_two.is_empty() {
+            return Err(ApiError::bad_request(
+                "Missing required parameter: type_two",
+            ));
+        }
+        Ok(())
+    }
+
+    /// 必须提供 type_one 和 type_two 参数，按类型筛选由 `LISTING` 完成
+    fn filter(
+        select: Select<Self::Entity>,
+        query: &mut ListQuery,
+    ) -> Result<Select<Self::Entity>, ApiError> {
+        required_param(&query.fields, "type_one")?;
+        required_param(&query.fields, "type_two")?;
+        Ok(select)
+    }
+
+    /// 修改与删除按 type_one 和 type_two 查找
+    async fn locate(
+        db: &DatabaseConnection,
+        params: &[(String, String)],
+    ) -> Result<Self::Model, ApiError> {
+        let type_one = required_param(params, "type_one")?;
+        let type_two = required_param(params, "type_two")?;
+        let type_one_id = type_one.parse::<i32>().map_err(|_| {
+            ApiError::bad_request(format!("Invalid value for type_one: {}", type_one))
+        })?;
+        service_map_content_entity::Entity::find()
+            .filter(service_map_content_entity::Column::TypeOne.eq(type_one_id))
+            .filter(service_map_content_entity::Column::TypeTwo.eq(type_two))
+            .one(db)
+            .await?
+            .ok_or_else(|| {
+                ApiError::not_found(format!(
+                    "Service map content with type_one '{}' and type_two '{}' not found",
+                    type_one, type_two
+                ))
+            })
+    }
 }
@@ -1,31 +1,77 @@
 //! Service Map Type 路由模块
 //!
 //! 提供服务地图类型的 CRUD 接口：
-//! - GET /api/service_map_type - 获取所有服务地图类型列表（所有权限均可访问）
+//! - GET /api/service_map_type - 获取服务地图类型列表（所有权限均可访问，支持分页、排序与筛选）
+//! - GET /api/service_map_type/{id} - 获取指定的服务地图类型（所有权限均可访问）
 //! - POST /api/service_map_type - 创建新的服务地图类型（仅 Admin 权限）
 //! - PUT /api/service_map_type?id=xxx - 修改指定的服务地图类型（仅 Admin 权限）
 //! - DELETE /api/service_map_type?id=xxx - 删除指定的服务地图类型（仅 Admin 权限）
 
-mod delete;
-mod get;
-mod modify;
-mod post;
-
 use axum::Router;
+use db_manager::entity::service_map_type as service_map_type_entity;
+use interface_types::proto::service_map_type::{
+    ServiceMapType as ProtoServiceMapType, ServiceMapTypeRequest, ServiceMapTypeResponse,
+};
+use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::error::ApiError;
+use crate::listing::{Listing, Page};
+use crate::resource::{Access, Resource, parse_json, patch, resource_router};
 
 /// 创建并返回 service_map_type 的完整路由
-///
-/// 此函数合并了所有 service_map_type 相关的子路由：
-/// - get::router() - 获取列表
-/// - post::router() - 创建
-/// - modify::router() - 修改
-/// - delete::router() - 删除
 pub fn service_map_type_router() -> Router<AppState> {
-    Router::new()
-        .merge(get::router())
-        .merge(post::router())
-        .merge(modify::router())
-        .merge(delete::router())
+    resource_router::<ServiceMapType>()
+}
+
+pub struct ServiceMapType;
+
+impl Resource for ServiceMapType {
+    type Entity = service_map_type_entity::Entity;
+    type Model = service_map_type_entity::Model;
+    type ActiveModel = service_map_type_entity::ActiveModel;
+    type Proto = ProtoServiceMapType;
+    type Request = ServiceMapTypeRequest;
+    type Response = ServiceMapTypeResponse;
+
+    const NAME: &'static str = "service map type";
+    const ID: service_map_type_entity::Column = service_map_type_entity::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[
+            service_map_type_entity::Column::CommunityName,
+            service_map_type_entity::Column::TypeSum,
+        ],
+        search: &[service_map_type_entity::Column::CommunityName],
+        filter: &[service_map_type_entity::Column::CommunityName],
+        created: None,
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(t: Self::Model) -> Self::Proto {
+        ProtoServiceMapType {
+            id: t.id,
+            community_name: t.community_name.unwrap_or_default(),
+            type_sum: t.type_sum.unwrap_or_default(),
+            type_name: t.type_name.map(|json| json.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        ServiceMapTypeResponse {
+            service_map_types: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+            code: 200,
+            message,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        // type_name 以 JSON 字符串传入
+        let type_name = parse_json(&payload.type_name, "type_name")?;
+        patch(&mut active.community_name, payload.community_name);
+        patch(&mut active.type_sum, payload.type_sum);
+        patch(&mut active.type_name, type_name);
+        Ok(())
+    }
 }
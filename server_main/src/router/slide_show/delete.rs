@@ -1,22 +1,28 @@
 use axum::{
     Router,
     extract::{Query, State},
-    http::HeaderMap,
+    middleware::from_fn_with_state,
     routing::delete,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::slideshow as slideshow_entity;
 use interface_types::proto::slideshow::SlideshowResponse;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
 use serde::Deserialize;
-use user_auth::db_exchange::{ExchangeError, token2user};
 use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
 
 /// 创建 slide_show 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", delete(delete_slideshow))
+    Router::new()
+        .route("/", delete(delete_slideshow))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
 }
 
 /// 查询参数
@@ -29,58 +35,10 @@ struct SlideShowParams {
 /// DELETE /api/slide_show?index=xxx - 删除 slideshow（仅 Admin 权限可以访问）
 async fn delete_slideshow(
     State(state): State<AppState>,
+    audit: Auditor,
     Query(params): Query<SlideShowParams>,
-    headers: HeaderMap,
 ) -> Protobuf<SlideshowResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(SlideshowResponse {
-                    slideshows: vec![],
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 权限校验：只有 Admin (permission=3) 才能删除 slideshow
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission != UserPermissionLevel::Admin.level() {
-        return Protobuf(SlideshowResponse {
-            slideshows: vec![],
-            code: 403,
-            message: "Permission denied: Only Admin can delete slideshow".to_string(),
-        });
-    }
-
-    // 4) 查找要删除的 slideshow
+    // 1) 查找要删除的 slideshow
     let db = state.database.clone();
     let slideshow_to_delete = match slideshow_entity::Entity::find()
         .filter(slideshow_entity::Column::Index.eq(&params.index))
@@ -93,6 +51,8 @@ async fn delete_slideshow(
                 slideshows: vec![],
                 code: 404,
                 message: format!("Slideshow with index '{}' not found", params.index),
+                total: 0,
+                next_cursor: String::new(),
             });
         }
         Err(err) => {
@@ -100,29 +60,42 @@ async fn delete_slideshow(
                 slideshows: vec![],
                 code: 500,
                 message: format!("Database error: {}", err),
+                total: 0,
+                next_cursor: String::new(),
             });
         }
     };
 
-    // 5) 执行删除
-    match slideshow_entity::Entity::delete_by_id(slideshow_to_delete.id)
-        .exec(db.as_ref())
-        .await
-    {
-        Ok(_) => {}
-        Err(err) => {
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 500,
-                message: format!("Failed to delete slideshow: {}", err),
-            });
-        }
-    };
+    // 2) 执行删除，审计记录在同一事务中写入
+    let deleted = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        slideshow_entity::Entity::delete_by_id(slideshow_to_delete.id)
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Failed to delete slideshow: {}", e))?;
+        audit
+            .delete(&txn, &slideshow_to_delete)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())
+    }
+    .await;
+    if let Err(err) = deleted {
+        return Protobuf(SlideshowResponse {
+            slideshows: vec![],
+            code: 500,
+            message: err,
+            total: 0,
+            next_cursor: String::new(),
+        });
+    }
 
-    // 6) 返回成功响应
+    // 3) 返回成功响应
     Protobuf(SlideshowResponse {
         slideshows: vec![],
         code: 200,
         message: "Delete slideshow success".to_string(),
+        total: 0,
+        next_cursor: String::new(),
     })
 }
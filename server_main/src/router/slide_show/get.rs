@@ -1,45 +1,80 @@
-use axum::{Router, extract::State, routing::get};
-use axum_extra::protobuf::Protobuf;
+use axum::{
+    Router,
+    extract::{Path, State},
+    routing::get,
+};
 use db_manager::entity::slideshow as slideshow_entity;
 use interface_types::proto::slideshow::{Slideshow as ProtoSlideshow, SlideshowResponse};
 use sea_orm::EntityTrait;
 
 use crate::AppState;
+use crate::codec::Protobuf;
+use crate::error::ApiError;
+use crate::listing::{ListQuery, Listing, Page, fetch};
 
 /// 创建 slide_show 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", get(get_slideshow))
+    Router::new()
+        .route("/", get(get_slideshow))
+        .route("/{id}", get(get_slideshow_by_id))
 }
 
-/// GET /api/slide_show - 获取所有 slideshow（所有权限 0-3 都可以访问）
-async fn get_slideshow(State(state): State<AppState>) -> Protobuf<SlideshowResponse> {
-    let db = state.database.clone();
-
-    // 查询所有 slideshow
-    let slideshows = match slideshow_entity::Entity::find().all(db.as_ref()).await {
-        Ok(n) => n,
-        Err(err) => {
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 500,
-                message: format!("Database error: {}", err),
-            });
-        }
-    };
+/// 列表支持的排序与筛选字段
+const LISTING: Listing<slideshow_entity::Entity> = Listing {
+    sort: &[
+        slideshow_entity::Column::Index,
+        slideshow_entity::Column::CreateTime,
+    ],
+    search: &[slideshow_entity::Column::Index],
+    filter: &[slideshow_entity::Column::Index],
+    created: Some(slideshow_entity::Column::CreateTime),
+};
 
-    // 转换为 proto 格式并返回
-    let proto_slideshows: Vec<ProtoSlideshow> = slideshows
-        .into_iter()
-        .map(|s| ProtoSlideshow {
-            id: s.id,
-            index: s.index.unwrap_or_default(),
-            create_time: s.create_time.and_utc().timestamp(),
-        })
-        .collect();
+fn to_proto(s: slideshow_entity::Model) -> ProtoSlideshow {
+    ProtoSlideshow {
+        id: s.id,
+        index: s.index.unwrap_or_default(),
+        create_time: s.create_time.and_utc().timestamp(),
+    }
+}
 
+fn response(page: Page<slideshow_entity::Model>, message: &str) -> Protobuf<SlideshowResponse> {
+    let page = page.map(to_proto);
     Protobuf(SlideshowResponse {
-        slideshows: proto_slideshows,
+        slideshows: page.items,
         code: 200,
-        message: "Get slideshow list success".to_string(),
+        message: message.to_string(),
+        total: page.total,
+        next_cursor: page.next_cursor,
     })
 }
+
+/// GET /api/slide_show - 获取 slideshow 列表（所有权限 0-3 都可以访问，分页与筛选见 `crate::listing`）
+async fn get_slideshow(
+    State(state): State<AppState>,
+    query: ListQuery,
+) -> Result<Protobuf<SlideshowResponse>, ApiError> {
+    let db = state.database.clone();
+    let page = fetch(
+        db.as_ref(),
+        slideshow_entity::Entity::find(),
+        slideshow_entity::Column::Id,
+        &LISTING,
+        &query,
+    )
+    .await?;
+    Ok(response(page, "Get slideshow list success"))
+}
+
+/// GET /api/slide_show/{id} - 获取指定的 slideshow（所有权限 0-3 都可以访问）
+async fn get_slideshow_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Protobuf<SlideshowResponse>, ApiError> {
+    let db = state.database.clone();
+    let slideshow = slideshow_entity::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Slideshow with id '{}' not found", id)))?;
+    Ok(response(vec![slideshow].into(), "Get slideshow success"))
+}
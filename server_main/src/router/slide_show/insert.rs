@@ -1,22 +1,28 @@
 use axum::{
     Router,
     extract::{Query, State},
-    http::HeaderMap,
+    middleware::from_fn_with_state,
     routing::post,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::slideshow as slideshow_entity;
 use interface_types::proto::slideshow::SlideshowResponse;
-use sea_orm::{ActiveModelTrait, Set};
+use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
 use serde::Deserialize;
-use user_auth::db_exchange::{ExchangeError, token2user};
 use user_auth::user_auth::UserPermissionLevel;
 
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
 
 /// 创建 slide_show 路由
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(insert_slideshow))
+    Router::new()
+        .route("/", post(insert_slideshow))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
 }
 
 /// 查询参数
@@ -29,77 +35,45 @@ struct SlideShowParams {
 /// POST /api/slide_show?index=xxx - 新增 slideshow（仅 Admin 权限可以访问）
 async fn insert_slideshow(
     State(state): State<AppState>,
+    audit: Auditor,
     Query(params): Query<SlideShowParams>,
-    headers: HeaderMap,
 ) -> Protobuf<SlideshowResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(SlideshowResponse {
-                    slideshows: vec![],
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(SlideshowResponse {
-                slideshows: vec![],
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    // 3) 权限校验：只有 Admin (permission=3) 才能新增 slideshow
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission != UserPermissionLevel::Admin.level() {
-        return Protobuf(SlideshowResponse {
-            slideshows: vec![],
-            code: 403,
-            message: "Permission denied: Only Admin can insert slideshow".to_string(),
-        });
-    }
-
-    // 4) 创建新的 ActiveModel 并插入
+    // 1) 创建新的 ActiveModel 并插入
     let db = state.database.clone();
     let new_slideshow = slideshow_entity::ActiveModel {
         index: Set(Some(params.index)),
         ..Default::default()
     };
 
-    // 5) 执行插入
-    let inserted_slideshow = match new_slideshow.insert(db.as_ref()).await {
+    // 2) 执行插入，审计记录在同一事务中写入
+    let inserted = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = new_slideshow
+            .insert(&txn)
+            .await
+            .map_err(|e| format!("Failed to insert slideshow: {}", e))?;
+        audit
+            .insert(&txn, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let inserted_slideshow = match inserted {
         Ok(n) => n,
         Err(err) => {
             return Protobuf(SlideshowResponse {
                 slideshows: vec![],
                 code: 500,
-                message: format!("Failed to insert slideshow: {}", err),
+                message: err,
+                total: 0,
+                next_cursor: String::new(),
             });
         }
     };
 
-    // 6) 返回新增的 slideshow
+    // 3) 返回新增的 slideshow
     use interface_types::proto::slideshow::Slideshow as ProtoSlideshow;
     Protobuf(SlideshowResponse {
         slideshows: vec![ProtoSlideshow {
@@ -109,5 +83,7 @@ async fn insert_slideshow(
         }],
         code: 200,
         message: "Insert slideshow success".to_string(),
+        total: 1,
+        next_cursor: String::new(),
     })
 }
@@ -9,7 +9,8 @@ use axum::Router;
 /// 路由定义：
 /// - POST /api/slide_show?index=xxx: 新增 slideshow（仅 Admin 权限）
 /// - DELETE /api/slide_show?index=xxx: 删除 slideshow（仅 Admin 权限）
-/// - GET /api/slide_show: 获取所有 slideshow 列表（所有权限）
+/// - GET /api/slide_show: 获取 slideshow 列表（所有权限，支持分页、排序与筛选）
+/// - GET /api/slide_show/{id}: 获取指定的 slideshow（所有权限）
 pub fn slide_show_router() -> Router<crate::AppState> {
     get::router()
         .merge(insert::router())
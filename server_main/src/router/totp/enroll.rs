@@ -0,0 +1,132 @@
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::post};
+use interface_types::proto::totp::{
+    TotpCodeRequest, TotpEnrollResponse, TotpEnrollment as ProtoTotpEnrollment,
+    TotpRecoveryCodesResponse,
+};
+use sea_orm::TransactionTrait;
+use user_auth::totp::{begin_totp_enrollment, confirm_totp_enrollment};
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::{current_totp, ensure_eligible};
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, Permissions, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/enroll", post(enroll))
+        .route("/confirm", post(confirm))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// POST /api/totp/enroll
+/// 仅 Admin 或持有特权权限的用户：生成新的密钥，确认前不会生效；已启用时需先解除绑定
+async fn enroll(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    permissions: Permissions,
+    audit: Auditor,
+) -> Protobuf<TotpEnrollResponse> {
+    // 1) 校验权限
+    if let Err((code, message)) = ensure_eligible(&current, &permissions) {
+        return Protobuf(TotpEnrollResponse {
+            enrollment: None,
+            code,
+            message,
+        });
+    }
+
+    // 2) 生成密钥，审计记录在同一事务中写入
+    let db = state.database.clone();
+    let enrollment = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let before = current_totp(&txn, current.id).await;
+        let enrollment = begin_totp_enrollment(&txn, &current)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        let after = current_totp(&txn, current.id).await;
+        audit
+            .change(&txn, before.as_ref(), after.as_ref())
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(enrollment)
+    }
+    .await;
+    let enrollment = match enrollment {
+        Ok(e) => e,
+        Err((code, message)) => {
+            return Protobuf(TotpEnrollResponse {
+                enrollment: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(TotpEnrollResponse {
+        enrollment: Some(ProtoTotpEnrollment {
+            secret: enrollment.secret,
+            otpauth_uri: enrollment.otpauth_uri,
+        }),
+        code: 200,
+        message: "Enroll totp success".to_string(),
+    })
+}
+
+/// POST /api/totp/confirm
+/// 仅 Admin 或持有特权权限的用户：提交验证器上的第一个验证码以启用，返回一次性恢复码
+async fn confirm(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    permissions: Permissions,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<TotpCodeRequest>,
+) -> Protobuf<TotpRecoveryCodesResponse> {
+    // 1) 校验权限
+    if let Err((code, message)) = ensure_eligible(&current, &permissions) {
+        return Protobuf(TotpRecoveryCodesResponse {
+            recovery_codes: vec![],
+            code,
+            message,
+        });
+    }
+
+    // 2) 校验验证码并启用，审计记录在同一事务中写入
+    let db = state.database.clone();
+    let confirmed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let before = current_totp(&txn, current.id).await;
+        let recovery_codes = confirm_totp_enrollment(&txn, current.id, &payload.code)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        let after = current_totp(&txn, current.id).await;
+        audit
+            .change(&txn, before.as_ref(), after.as_ref())
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(recovery_codes)
+    }
+    .await;
+    let recovery_codes = match confirmed {
+        Ok(codes) => codes,
+        Err((code, message)) => {
+            return Protobuf(TotpRecoveryCodesResponse {
+                recovery_codes: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(TotpRecoveryCodesResponse {
+        recovery_codes,
+        code: 200,
+        message: "Confirm totp success".to_string(),
+    })
+}
@@ -0,0 +1,185 @@
+use axum::{
+    Extension, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    routing::{delete, get, post},
+};
+use interface_types::proto::totp::{
+    TotpCodeRequest, TotpRecoveryCodesResponse, TotpResponse, TotpStatus as ProtoTotpStatus,
+    TotpStatusResponse,
+};
+use sea_orm::TransactionTrait;
+use serde_json::json;
+use user_auth::totp::{
+    TotpPolicy, disable_totp, find_totp, regenerate_recovery_codes, remaining_recovery_codes,
+    verify_second_factor,
+};
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::{current_totp, ensure_eligible};
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, Permissions, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/status", get(status))
+        .route("/recovery_codes", post(recovery_codes))
+        .route("/", delete(disable))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// GET /api/totp/status
+/// 查看自己的绑定状态与当前策略
+async fn status(
+    State(state): State<AppState>,
+    Extension(policy): Extension<TotpPolicy>,
+    CurrentUser(current): CurrentUser,
+) -> Protobuf<TotpStatusResponse> {
+    // 1) 查询绑定与剩余恢复码
+    let db = state.database.clone();
+    let totp = match find_totp(db.as_ref(), current.id).await {
+        Ok(t) => t,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(TotpStatusResponse {
+                status: None,
+                code,
+                message,
+            });
+        }
+    };
+    let remaining = match remaining_recovery_codes(db.as_ref(), current.id).await {
+        Ok(n) => n,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(TotpStatusResponse {
+                status: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(TotpStatusResponse {
+        status: Some(ProtoTotpStatus {
+            enabled: totp.as_ref().is_some_and(|t| t.enabled),
+            pending: totp.as_ref().is_some_and(|t| !t.enabled),
+            remaining_recovery_codes: remaining,
+            policy: policy.as_str().to_string(),
+        }),
+        code: 200,
+        message: "Get totp status success".to_string(),
+    })
+}
+
+/// POST /api/totp/recovery_codes
+/// 仅 Admin 或持有特权权限的用户：校验验证码后重新生成恢复码，旧恢复码全部作废
+async fn recovery_codes(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    permissions: Permissions,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<TotpCodeRequest>,
+) -> Protobuf<TotpRecoveryCodesResponse> {
+    // 1) 校验权限
+    if let Err((code, message)) = ensure_eligible(&current, &permissions) {
+        return Protobuf(TotpRecoveryCodesResponse {
+            recovery_codes: vec![],
+            code,
+            message,
+        });
+    }
+
+    // 2) 校验第二因素，在事务外进行，失败次数不会随事务回滚
+    let db = state.database.clone();
+    if let Err(err) = verify_second_factor(db.as_ref(), current.id, &payload.code).await {
+        let (code, message) = err.code_and_message();
+        return Protobuf(TotpRecoveryCodesResponse {
+            recovery_codes: vec![],
+            code,
+            message,
+        });
+    }
+
+    // 3) 重新生成，审计记录在同一事务中写入；恢复码本身不写入审计，只记录数量
+    let regenerated = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let recovery_codes = regenerate_recovery_codes(&txn, current.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "totp_recovery_code",
+                current.id,
+                None,
+                Some(json!({ "user_id": current.id, "count": recovery_codes.len() })),
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(recovery_codes)
+    }
+    .await;
+    let recovery_codes = match regenerated {
+        Ok(codes) => codes,
+        Err((code, message)) => {
+            return Protobuf(TotpRecoveryCodesResponse {
+                recovery_codes: vec![],
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(TotpRecoveryCodesResponse {
+        recovery_codes,
+        code: 200,
+        message: "Regenerate recovery codes success".to_string(),
+    })
+}
+
+/// DELETE /api/totp
+/// 校验验证码或恢复码后解除绑定
+async fn disable(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<TotpCodeRequest>,
+) -> Protobuf<TotpResponse> {
+    // 1) 校验第二因素
+    let db = state.database.clone();
+    if let Err(err) = verify_second_factor(db.as_ref(), current.id, &payload.code).await {
+        let (code, message) = err.code_and_message();
+        return Protobuf(TotpResponse { code, message });
+    }
+
+    // 2) 解除绑定，审计记录在同一事务中写入
+    let disabled = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let before = current_totp(&txn, current.id).await;
+        disable_totp(&txn, current.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .change(&txn, before.as_ref(), None)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(())
+    }
+    .await;
+    if let Err((code, message)) = disabled {
+        return Protobuf(TotpResponse { code, message });
+    }
+
+    Protobuf(TotpResponse {
+        code: 200,
+        message: "Disable totp success".to_string(),
+    })
+}
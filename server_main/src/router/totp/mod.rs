@@ -0,0 +1,54 @@
+//! TOTP 路由模块
+//!
+//! Admin 与持有特权权限（如 role.manage、audit.read）用户的二次验证（见 `user_auth::totp`），以下接口本身不要求 step-up，
+//! 以便在 `SERVER_ADMIN_TOTP_POLICY=required` 时也能完成绑定：
+//! - GET /api/totp/status - 查看绑定状态、剩余恢复码数量与当前策略
+//! - POST /api/totp/enroll - 生成密钥与 otpauth URI
+//! - POST /api/totp/confirm - 提交第一个验证码以启用，返回恢复码
+//! - POST /api/totp/verify - 校验验证码或恢复码，返回限时的 step-up token
+//! - POST /api/totp/recovery_codes - 校验后重新生成恢复码
+//! - DELETE /api/totp - 校验后解除绑定
+//!
+//! 启用后调用 Admin 接口或需要特权权限的接口时需在 `x-step-up-token` 头中携带 step-up token。
+
+mod enroll;
+mod manage;
+mod verify;
+
+use axum::Router;
+use db_manager::entity::{admin_totp, user as user_entity};
+use sea_orm::ConnectionTrait;
+use user_auth::totp::find_totp;
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::auth::Permissions;
+
+/// 创建并返回 totp 的完整路由
+pub fn totp_router() -> Router<AppState> {
+    Router::new()
+        .merge(enroll::router())
+        .merge(verify::router())
+        .merge(manage::router())
+}
+
+/// 审计用：读取当前的绑定状态，查询失败时不记录
+async fn current_totp<C: ConnectionTrait>(db: &C, user_id: i32) -> Option<admin_totp::Model> {
+    find_totp(db, user_id).await.ok().flatten()
+}
+
+/// 仅 Admin 或持有需要二次验证的权限（见 `permission::PRIVILEGED_PERMISSIONS`）的用户可以绑定二次验证
+fn ensure_eligible(
+    user: &user_entity::Model,
+    permissions: &Permissions,
+) -> Result<(), (i32, String)> {
+    if UserPermissionLevel::from(user.permission.unwrap_or(0)) != UserPermissionLevel::Admin
+        && !permissions.0.has_privileged()
+    {
+        return Err((
+            403,
+            "Permission denied: requires Admin or a privileged permission".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use axum::{Extension, Router, extract::State, middleware::from_fn_with_state, routing::post};
+use interface_types::proto::totp::{TotpCodeRequest, TotpStepUp, TotpVerifyResponse};
+use user_auth::db_exchange::KeyRing;
+use user_auth::totp::{issue_step_up_token, verify_second_factor};
+use user_auth::user_auth::UserPermissionLevel;
+
+use super::ensure_eligible;
+use crate::AppState;
+use crate::auth::{CurrentUser, Permissions, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/verify", post(verify))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// POST /api/totp/verify
+/// 仅 Admin 或持有特权权限的用户：校验验证码或恢复码，通过后签发限时的 step-up token
+async fn verify(
+    State(state): State<AppState>,
+    Extension(keys): Extension<Arc<KeyRing>>,
+    CurrentUser(current): CurrentUser,
+    permissions: Permissions,
+    Protobuf(payload): Protobuf<TotpCodeRequest>,
+) -> Protobuf<TotpVerifyResponse> {
+    // 1) 校验权限
+    if let Err((code, message)) = ensure_eligible(&current, &permissions) {
+        return Protobuf(TotpVerifyResponse {
+            step_up: None,
+            code,
+            message,
+        });
+    }
+
+    // 2) 校验第二因素
+    let db = state.database.clone();
+    if let Err(err) = verify_second_factor(db.as_ref(), current.id, &payload.code).await {
+        let (code, message) = err.code_and_message();
+        return Protobuf(TotpVerifyResponse {
+            step_up: None,
+            code,
+            message,
+        });
+    }
+
+    // 3) 签发 step-up token
+    let (step_up_token, expires_at) = match issue_step_up_token(&keys, &current) {
+        Ok(v) => v,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(TotpVerifyResponse {
+                step_up: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(TotpVerifyResponse {
+        step_up: Some(TotpStepUp {
+            step_up_token,
+            expires_at,
+        }),
+        code: 200,
+        message: "Verify totp success".to_string(),
+    })
+}
@@ -1,31 +1,38 @@
 use axum::{
     Router,
     extract::{Query, State},
-    http::HeaderMap,
+    middleware::from_fn_with_state,
     routing::{get, post},
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::user as user_entity;
 use interface_types::proto::user::{
-    ApplyPermission as ProtoApplyPermission, ApplyPermissionResponse,
+    ApplyPermission as ProtoApplyPermission, ApplyPermissionListResponse,
+    ApplyPermissionRedemption as ProtoApplyPermissionRedemption, ApplyPermissionResponse,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Deserialize;
+use serde_json::json;
+use user_auth::invitation::{
+    DEFAULT_INVITATION_EXPIRE_SECONDS, InvitationDetail, InvitationOptions, issue_invitation,
+    list_invitations, redeem_invitation, revoke_invitation,
 };
-use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, Set};
-use serde::{Deserialize, Serialize};
-use user_auth::db_exchange::{ExchangeError, jwt_secret_from_env, now_timestamp, token2user};
 use user_auth::user_auth::UserPermissionLevel;
 
-use hmac::{Hmac, digest::KeyInit};
-use jwt::{SignWithKey, VerifyWithKey};
-use sha2::Sha256;
-
 use crate::AppState;
-
-const APPLY_PERMISSION_EXPIRE_SECONDS: u64 = 180;
+use crate::audit::Auditor;
+use crate::auth::{AuthUser, require_permission};
+use crate::codec::Protobuf;
 
 #[derive(Debug, Deserialize)]
 struct ApplyPermissionQuery {
     /// 申请权限类型：2 或 3
     apply_type: i32,
+    /// 最多可兑换次数，默认 1
+    max_uses: Option<i32>,
+    /// 有效期（秒），默认 180
+    expire_seconds: Option<u64>,
+    /// 仅允许该用户兑换
+    target_open_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,283 +41,316 @@ struct ApplyPermissionCodeQuery {
     code: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ApplyPermissionClaims {
-    exp: u64,
-    apply_type: i32,
+#[derive(Debug, Deserialize)]
+struct RevokeApplyCodeQuery {
+    /// 邀请码 id
+    id: i32,
 }
 
 /// 创建 apply_permission 路由
 pub fn router() -> Router<AppState> {
     Router::new()
-        .route("/apply_permission", get(generate_apply_code))
-        .route("/apply_permission", post(apply_permission))
+        .route(
+            "/apply_permission",
+            get(generate_apply_code).route_layer(from_fn_with_state(
+                UserPermissionLevel::Admin,
+                require_permission,
+            )),
+        )
+        .route(
+            "/apply_permission",
+            post(apply_permission).route_layer(from_fn_with_state(
+                UserPermissionLevel::Guest,
+                require_permission,
+            )),
+        )
+        .route(
+            "/apply_permission/list",
+            get(list_apply_codes).route_layer(from_fn_with_state(
+                UserPermissionLevel::Admin,
+                require_permission,
+            )),
+        )
+        .route(
+            "/apply_permission/revoke",
+            post(revoke_apply_code).route_layer(from_fn_with_state(
+                UserPermissionLevel::Admin,
+                require_permission,
+            )),
+        )
+}
+
+/// 按 open_id 查询当前登录用户的数据库记录
+async fn find_user(
+    db: &DatabaseConnection,
+    open_id: &str,
+) -> Result<user_entity::Model, (i32, String)> {
+    match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(db)
+        .await
+    {
+        Ok(Some(u)) => Ok(u),
+        Ok(None) => Err((404, "User not found".to_string())),
+        Err(err) => Err((500, format!("Database error: {}", err))),
+    }
 }
 
-/// GET /api/user/apply_permission?apply_type=2|3
-/// 仅 Admin 权限可以获取校验码
+fn detail_to_proto(detail: InvitationDetail) -> ProtoApplyPermission {
+    let invitation = detail.invitation;
+    ProtoApplyPermission {
+        code: invitation.code,
+        apply_type: invitation.apply_type,
+        expire_time: invitation.expires_at,
+        id: invitation.id,
+        max_uses: invitation.max_uses,
+        used_count: invitation.used_count,
+        target_open_id: detail.target_open_id,
+        revoked_at: invitation.revoked_at,
+        issued_by: detail.issued_by_open_id,
+        created_at: invitation.created_at,
+        redemptions: detail
+            .redemptions
+            .into_iter()
+            .map(|(open_id, r)| ProtoApplyPermissionRedemption {
+                open_id,
+                redeemed_at: r.redeemed_at,
+            })
+            .collect(),
+    }
+}
+
+/// GET /api/user/apply_permission?apply_type=2|3&max_uses=&expire_seconds=&target_open_id=
+/// 仅 Admin 权限可以签发校验码，签发人、次数与有效期均持久化
 async fn generate_apply_code(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    AuthUser(auth_user): AuthUser,
+    audit: Auditor,
     Query(params): Query<ApplyPermissionQuery>,
-    headers: HeaderMap,
 ) -> Protobuf<ApplyPermissionResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(ApplyPermissionResponse {
-                    apply_permission: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 401,
-                message: "Missing token".to_string(),
-            });
-        }
-    };
-
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
+    // 1) 查询签发人
+    let db = state.database.clone();
+    let issuer = match find_user(db.as_ref(), &auth_user.open_id).await {
         Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 401,
-                message: msg,
+                code,
+                message,
             });
         }
     };
 
-    // 3) 权限校验：只有 Admin (permission=3) 才能获取校验码
-    let user_permission = auth_user.permission.unwrap_or(0);
-    if user_permission != UserPermissionLevel::Admin.level() {
-        return Protobuf(ApplyPermissionResponse {
-            apply_permission: None,
-            code: 403,
-            message: "Permission denied: Only Admin can generate apply code".to_string(),
-        });
-    }
-
-    // 4) 校验 apply_type
-    if params.apply_type != UserPermissionLevel::Provider.level()
-        && params.apply_type != UserPermissionLevel::Admin.level()
-    {
-        return Protobuf(ApplyPermissionResponse {
-            apply_permission: None,
-            code: 400,
-            message: "Invalid apply_type: must be 2 or 3".to_string(),
-        });
-    }
-
-    // 5) 生成 3 分钟有效期的校验码（JWT）
-    let expire_time = now_timestamp() + APPLY_PERMISSION_EXPIRE_SECONDS;
-    let claims = ApplyPermissionClaims {
-        exp: expire_time,
+    // 2) 生成并保存校验码，审计记录在同一事务中写入
+    let target_open_id = params.target_open_id.clone();
+    let options = InvitationOptions {
         apply_type: params.apply_type,
+        max_uses: params.max_uses.unwrap_or(1),
+        expire_seconds: params
+            .expire_seconds
+            .unwrap_or(DEFAULT_INVITATION_EXPIRE_SECONDS),
+        target_open_id: params.target_open_id,
     };
-
-    let secret = match jwt_secret_from_env() {
-        Ok(s) => s,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::OtherError(e) => e,
-                _ => "Failed to get jwt secret".to_string(),
-            };
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 500,
-                message: msg,
-            });
-        }
-    };
-    let key: Hmac<Sha256> = match Hmac::new_from_slice(secret.as_bytes()) {
-        Ok(k) => k,
-        Err(err) => {
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 500,
-                message: format!("Failed to init jwt key: {}", err),
-            });
-        }
-    };
-
-    let code = match claims.sign_with_key(&key) {
-        Ok(c) => c,
-        Err(err) => {
+    let issued = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let invitation = issue_invitation(&txn, &issuer, options)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .insert(&txn, &invitation)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(invitation)
+    }
+    .await;
+    let invitation = match issued {
+        Ok(i) => i,
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 500,
-                message: format!("Failed to generate apply code: {}", err),
+                code,
+                message,
             });
         }
     };
 
     Protobuf(ApplyPermissionResponse {
-        apply_permission: Some(ProtoApplyPermission {
-            code,
-            apply_type: params.apply_type,
-            expire_time: expire_time as i64,
-        }),
+        apply_permission: Some(detail_to_proto(InvitationDetail {
+            invitation,
+            issued_by_open_id: issuer.open_id,
+            target_open_id,
+            redemptions: vec![],
+        })),
         code: 200,
         message: "Generate apply code success".to_string(),
     })
 }
 
 /// POST /api/user/apply_permission?code=xxx
-/// 所有权限均可申请，通过校验码更新自身权限
+/// 所有权限均可申请，通过校验码更新自身权限，兑换记录会被保存
 async fn apply_permission(
     State(state): State<AppState>,
     Query(params): Query<ApplyPermissionCodeQuery>,
-    headers: HeaderMap,
+    AuthUser(auth_user): AuthUser,
+    audit: Auditor,
 ) -> Protobuf<ApplyPermissionResponse> {
-    // 1) 从 Header 提取 token
-    let token: &str = match headers.get("Authorization") {
-        Some(t) => match t.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                return Protobuf(ApplyPermissionResponse {
-                    apply_permission: None,
-                    code: 401,
-                    message: "Invalid token format".to_string(),
-                });
-            }
-        },
-        None => {
+    // 1) 查询兑换人
+    let db = state.database.clone();
+    let user = match find_user(db.as_ref(), &auth_user.open_id).await {
+        Ok(u) => u,
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 401,
-                message: "Missing token".to_string(),
+                code,
+                message,
             });
         }
     };
 
-    // 2) 解析 token，获取用户信息
-    let auth_user = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
+    // 2) 兑换校验码并更新权限，审计记录在同一事务中写入
+    let redeemed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let (invitation, updated) = redeem_invitation(&txn, &params.code, &user)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .update(&txn, &user, &updated)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(invitation)
+    }
+    .await;
+    let invitation = match redeemed {
+        Ok(i) => i,
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 401,
-                message: msg,
+                code,
+                message,
             });
         }
     };
 
-    // 3) 解码校验码
-    let secret = match jwt_secret_from_env() {
-        Ok(s) => s,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::OtherError(e) => e,
-                _ => "Failed to get jwt secret".to_string(),
-            };
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 500,
-                message: msg,
+    Protobuf(ApplyPermissionResponse {
+        apply_permission: Some(ProtoApplyPermission {
+            code: invitation.code,
+            apply_type: invitation.apply_type,
+            expire_time: invitation.expires_at,
+            id: invitation.id,
+            ..Default::default()
+        }),
+        code: 200,
+        message: "Apply permission success".to_string(),
+    })
+}
+
+/// GET /api/user/apply_permission/list
+/// 仅 Admin 权限：列出自己签发的校验码及兑换记录
+async fn list_apply_codes(
+    State(state): State<AppState>,
+    AuthUser(auth_user): AuthUser,
+) -> Protobuf<ApplyPermissionListResponse> {
+    // 1) 查询签发人
+    let db = state.database.clone();
+    let issuer = match find_user(db.as_ref(), &auth_user.open_id).await {
+        Ok(u) => u,
+        Err((code, message)) => {
+            return Protobuf(ApplyPermissionListResponse {
+                apply_permissions: vec![],
+                code,
+                message,
             });
         }
     };
-    let key: Hmac<Sha256> = match Hmac::new_from_slice(secret.as_bytes()) {
-        Ok(k) => k,
+
+    // 2) 查询校验码
+    let details = match list_invitations(db.as_ref(), issuer.id).await {
+        Ok(d) => d,
         Err(err) => {
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 500,
-                message: format!("Failed to init jwt key: {}", err),
+            let (code, message) = err.code_and_message();
+            return Protobuf(ApplyPermissionListResponse {
+                apply_permissions: vec![],
+                code,
+                message,
             });
         }
     };
 
-    let claims: ApplyPermissionClaims = match params.code.verify_with_key(&key) {
-        Ok(c) => c,
-        Err(_) => {
+    Protobuf(ApplyPermissionListResponse {
+        apply_permissions: details.into_iter().map(detail_to_proto).collect(),
+        code: 200,
+        message: "Get apply codes success".to_string(),
+    })
+}
+
+/// POST /api/user/apply_permission/revoke?id=xxx
+/// 仅 Admin 权限：吊销自己签发的校验码
+async fn revoke_apply_code(
+    State(state): State<AppState>,
+    AuthUser(auth_user): AuthUser,
+    audit: Auditor,
+    Query(params): Query<RevokeApplyCodeQuery>,
+) -> Protobuf<ApplyPermissionResponse> {
+    // 1) 查询签发人
+    let db = state.database.clone();
+    let issuer = match find_user(db.as_ref(), &auth_user.open_id).await {
+        Ok(u) => u,
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 400,
-                message: "Invalid apply code".to_string(),
+                code,
+                message,
             });
         }
     };
 
-    if claims.exp < now_timestamp() {
-        return Protobuf(ApplyPermissionResponse {
-            apply_permission: None,
-            code: 400,
-            message: "Apply code expired".to_string(),
-        });
+    // 2) 吊销，审计记录在同一事务中写入
+    let revoked = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let invitation = revoke_invitation(&txn, issuer.id, params.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "invitation_code",
+                invitation.id,
+                Some(json!({ "revoked_at": null })),
+                Some(json!({ "revoked_at": invitation.revoked_at })),
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(invitation)
     }
-
-    if claims.apply_type != UserPermissionLevel::Provider.level()
-        && claims.apply_type != UserPermissionLevel::Admin.level()
-    {
-        return Protobuf(ApplyPermissionResponse {
-            apply_permission: None,
-            code: 400,
-            message: "Invalid apply_type in code".to_string(),
-        });
-    }
-
-    // 4) 更新用户权限
-    let db = state.database.clone();
-    let target = match user_entity::Entity::find()
-        .filter(user_entity::Column::OpenId.eq(auth_user.open_id.clone()))
-        .one(db.as_ref())
-        .await
-    {
-        Ok(Some(u)) => u,
-        Ok(None) => {
+    .await;
+    let invitation = match revoked {
+        Ok(i) => i,
+        Err((code, message)) => {
             return Protobuf(ApplyPermissionResponse {
                 apply_permission: None,
-                code: 404,
-                message: "User not found".to_string(),
-            });
-        }
-        Err(err) => {
-            return Protobuf(ApplyPermissionResponse {
-                apply_permission: None,
-                code: 500,
-                message: format!("Database error: {}", err),
+                code,
+                message,
             });
         }
     };
 
-    let mut active: user_entity::ActiveModel = target.clone().into();
-    active.permission = Set(Some(claims.apply_type));
-    active.id = ActiveValue::Unchanged(target.id);
-    active.open_id = Set(target.open_id.clone());
-
-    if let Err(err) = active.update(db.as_ref()).await {
-        return Protobuf(ApplyPermissionResponse {
-            apply_permission: None,
-            code: 500,
-            message: format!("Failed to update permission: {}", err),
-        });
-    }
-
     Protobuf(ApplyPermissionResponse {
         apply_permission: Some(ProtoApplyPermission {
-            code: params.code,
-            apply_type: claims.apply_type,
-            expire_time: claims.exp as i64,
+            code: invitation.code,
+            apply_type: invitation.apply_type,
+            expire_time: invitation.expires_at,
+            id: invitation.id,
+            max_uses: invitation.max_uses,
+            used_count: invitation.used_count,
+            revoked_at: invitation.revoked_at,
+            issued_by: issuer.open_id,
+            created_at: invitation.created_at,
+            ..Default::default()
         }),
         code: 200,
-        message: "Apply permission success".to_string(),
+        message: "Revoke apply code success".to_string(),
     })
 }
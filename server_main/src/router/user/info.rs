@@ -1,85 +1,103 @@
-use axum::{Router, extract::State, http::HeaderMap, routing::get};
-use axum_extra::protobuf::Protobuf;
-use db_manager::entity::user as user_entity;
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, header},
+    middleware::from_fn_with_state,
+    routing::get,
+};
 use interface_types::proto::user::{User as ProtoUser, UserResponse};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use user_auth::db_exchange::{ExchangeError, User as AuthUser, token2user};
+use serde::Deserialize;
+use user_auth::caregiver::{Delegation, action, record_caregiver_action};
+use user_auth::field_policy::Viewer;
+use user_auth::user_auth::UserPermissionLevel;
 
+use super::user_to_proto;
 use crate::AppState;
+use crate::auth::{CurrentUser, require_permission, resolve_target};
+use crate::codec::Protobuf;
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/info", get(info))
+    Router::new()
+        .route("/info", get(info))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
+#[derive(Debug, Deserialize)]
+struct InfoQuery {
+    /// 照护人查看被照护人资料时传入，不传则查看自己
+    target_openid: Option<String>,
+}
+
+/// GET /api/user/info?target_openid=
+/// 查看自己的资料；生效的照护人可以查看被照护人的资料，每次查看都会记录
 async fn info(
     State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    Query(query): Query<InfoQuery>,
     headers: HeaderMap,
 ) -> Protobuf<UserResponse> {
-    // 1) 解析 token，拿到用户 openid
-    let token: &str = if let Some(token) = headers.get("Authorization") {
-        token.to_str().unwrap()
-    } else {
-        return Protobuf(UserResponse {
-            user: None,
-            code: 401,
-            message: "Missing token".to_string(),
-        });
-    };
-
-    let auth_user: AuthUser = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(UserResponse {
-                user: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
-
-    let openid = auth_user.open_id.clone();
-
-    // 2) 查询用户信息
+    // 1) 解析目标用户
     let db = state.database.clone();
-    let user = match user_entity::Entity::find()
-        .filter(user_entity::Column::OpenId.eq(openid.clone()))
-        .one(db.as_ref())
-        .await
-        .map_err(|e| e.to_string())
-    {
-        Ok(Some(u)) => u,
-        Ok(None) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 404,
-                message: "User not found".to_string(),
-            });
+    let (user, delegation) =
+        match resolve_target(db.as_ref(), &current, query.target_openid.as_deref()).await {
+            Ok(v) => v,
+            Err((code, message)) => {
+                return Protobuf(UserResponse {
+                    user: None,
+                    code,
+                    message,
+                });
+            }
+        };
+
+    // 2) 仅本人或照护人可以查看，照护人的查看写入操作记录
+    match &delegation {
+        Delegation::Own => {}
+        Delegation::Caregiver(link) => {
+            if let Err(err) = record_caregiver_action(
+                db.as_ref(),
+                Some(link.caregiver_id),
+                link.elder_id,
+                action::USER_INFO,
+                None,
+            )
+            .await
+            {
+                let (code, message) = err.code_and_message();
+                return Protobuf(UserResponse {
+                    user: None,
+                    code,
+                    message,
+                });
+            }
         }
-        Err(err) => {
+        Delegation::Unrelated => {
             return Protobuf(UserResponse {
                 user: None,
-                code: 500,
-                message: err,
+                code: 403,
+                message: "Permission denied: not a caregiver of target user".to_string(),
             });
         }
-    };
+    }
 
-    // 3) 构造返回
+    // 3) 构造返回：按字段策略隐藏无权查看的字段，代为查看时不回传自己的 token
+    let (viewer, token) = match delegation {
+        Delegation::Own => (
+            Viewer::own(&user),
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string()),
+        ),
+        _ => (Viewer::of(&current, &user, true), None),
+    };
     Protobuf(UserResponse {
         user: Some(ProtoUser {
-            token: Some(token.to_string()),
-            nickname: user.nickname,
-            name: user.name,
-            phone_number: user.phone_number,
-            address: user.address,
-            is_important: user.is_important.map(|b| b.to_string()),
-            avatar: user.avatar,
-            permission: user.permission.map(|p| p.to_string()),
+            token,
+            ..user_to_proto(&user, viewer)
         }),
         code: 200,
         message: "success".to_string(),
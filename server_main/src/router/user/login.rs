@@ -1,19 +1,25 @@
+use std::sync::Arc;
+
 use axum::{
-    Router,
+    Extension, Router,
     extract::{Query, State},
+    http::StatusCode,
     routing::get,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::user as user_entity;
 use interface_types::proto::user::User as ProtoUser;
 use interface_types::proto::user::UserResponse;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::Deserialize;
-use user_auth::db_exchange::User as AuthUser;
-use user_auth::db_exchange::user2token;
+use user_auth::db_exchange::{KeyRing, issue_token_pair};
+use user_auth::field_policy::Viewer;
+use user_auth::user_status::check_user_status;
 use user_auth::wx_auth::*;
 
+use super::user_to_proto;
 use crate::AppState;
+use crate::codec::Protobuf;
+use crate::error::ApiError;
 
 #[derive(Deserialize)]
 struct LoginQuery {
@@ -27,93 +33,64 @@ pub fn router() -> Router<AppState> {
 
 async fn login(
     State(state): State<AppState>,
+    Extension(wx): Extension<WxAuthServerConfig>,
+    Extension(keys): Extension<Arc<KeyRing>>,
     Query(query): Query<LoginQuery>,
-) -> Protobuf<UserResponse> {
-    // Use wx_auth to resolve the provided token/code into an openid.
-    let wx_result = wx_auth_session_to_json(&query.js_code).await;
-
-    let openid = match wx_result {
-        Ok(resp) => match resp.openid {
-            Some(oid) => oid,
-            None => {
-                return Protobuf(UserResponse {
-                    user: None,
-                    code: 400,
-                    message: "WeiXin auth did not return an openid".to_string(),
-                });
-            }
-        },
-        Err(err) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 500,
-                message: format!("failed to resolve openid: {:?}", err),
-            });
-        }
-    };
-
-    // Insert or update the user in the database (currently only insert is implemented).
-    let queryed_user = match query_user_in_db(&state, &openid).await {
-        Ok(u) => Some(u),
-        Err(err) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 500,
-                message: err,
-            });
-        }
+) -> Result<Protobuf<UserResponse>, ApiError> {
+    // 1) 通过微信接口把 js_code 换成 openid
+    let resp = wx_auth_session_to_json(&wx, &query.js_code)
+        .await
+        .map_err(|err| {
+            ApiError::new(
+                StatusCode::BAD_GATEWAY,
+                format!("failed to resolve openid: {:?}", err),
+            )
+        })?;
+    let Some(openid) = resp.openid else {
+        return Err(ApiError::bad_request(
+            "WeiXin auth did not return an openid",
+        ));
     };
 
-    let user = match queryed_user {
-        Some(u) => Some(u),
-        None => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 500,
-                message: "User not found".to_string(),
-            });
-        }
-    };
+    // 2) 查询用户并签发 token
+    let user = query_user_in_db(&state, &keys, &openid, resp.session_key.as_deref()).await?;
 
-    Protobuf(UserResponse {
-        user: user,
+    Ok(Protobuf(UserResponse {
+        user: Some(user),
         code: 200,
         message: "login success".to_string(),
-    })
+    }))
 }
 
-async fn query_user_in_db(state: &AppState, openid: &str) -> Result<ProtoUser, String> {
+async fn query_user_in_db(
+    state: &AppState,
+    keys: &KeyRing,
+    openid: &str,
+    session_key: Option<&str>,
+) -> Result<ProtoUser, ApiError> {
     let db = state.database.clone();
 
-    let user_queryed_result = user_entity::Entity::find()
+    let model = user_entity::Entity::find()
         .filter(user_entity::Column::OpenId.eq(openid))
         .one(db.as_ref())
-        .await
-        .unwrap();
-    if user_queryed_result == None {
-        return Err("User not found".to_string());
+        .await?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+    // 封禁或已删除的账号不签发 token
+    check_user_status(&model).map_err(|e| e.code_and_message())?;
+    // 保存 session_key，用于解密手机号等加密数据
+    if let Some(session_key) = session_key {
+        save_session_key(db.as_ref(), model.id, session_key)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
     }
-    let model = user_queryed_result.unwrap();
-    let user = AuthUser {
-        open_id: model.open_id,
-        nickname: model.nickname.clone(),
-        name: model.name.clone(),
-        phone_number: model.phone_number.clone(),
-        address: model.address.clone(),
-        is_important: model.is_important,
-        avatar: model.avatar.clone(),
-        permission: model.permission,
-    };
-    let jwt_token = user2token(&user).unwrap();
+
+    let tokens = issue_token_pair(db.as_ref(), keys, &model)
+        .await
+        .map_err(|e| ApiError::internal(format!("{:?}", e)))?;
 
     Ok(ProtoUser {
-        token: Some(jwt_token),
-        nickname: model.nickname,
-        name: model.name,
-        phone_number: model.phone_number,
-        address: model.address,
-        is_important: model.is_important.map(|b| b.to_string()),
-        avatar: model.avatar,
-        permission: model.permission.map(|p| p.to_string()),
+        token: Some(tokens.access_token),
+        refresh_token: Some(tokens.refresh_token),
+        ..user_to_proto(&model, Viewer::own(&model))
     })
 }
@@ -0,0 +1,66 @@
+use axum::{Router, extract::State, middleware::from_fn_with_state, routing::post};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::user::UserResponse;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use user_auth::db_exchange::revoke_user_tokens;
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::auth::{AuthUser, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/logout", post(logout))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// POST /api/user/logout
+/// 注销当前用户：吊销全部 refresh token 并递增 token_version，使已签发的 access token 一并失效
+async fn logout(
+    State(state): State<AppState>,
+    AuthUser(auth_user): AuthUser,
+) -> Protobuf<UserResponse> {
+    // 1) 查询当前用户
+    let db = state.database.clone();
+    let user = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(auth_user.open_id.clone()))
+        .one(db.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return Protobuf(UserResponse {
+                user: None,
+                code: 404,
+                message: "User not found".to_string(),
+            });
+        }
+        Err(err) => {
+            return Protobuf(UserResponse {
+                user: None,
+                code: 500,
+                message: err,
+            });
+        }
+    };
+
+    // 2) 吊销该用户所有 token
+    if let Err(err) = revoke_user_tokens(db.as_ref(), user).await {
+        return Protobuf(UserResponse {
+            user: None,
+            code: 500,
+            message: format!("{:?}", err),
+        });
+    }
+
+    Protobuf(UserResponse {
+        user: None,
+        code: 200,
+        message: "Logout success".to_string(),
+    })
+}
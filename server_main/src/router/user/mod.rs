@@ -2,12 +2,54 @@ pub mod admin_manager;
 pub mod apply_permission;
 pub mod info;
 pub mod login;
+pub mod logout;
 pub mod modify;
+pub mod personal_data;
+pub mod phone_number;
+pub mod refresh;
 pub mod register;
+pub mod revoke_token;
+pub mod sign_in;
+pub mod status;
 
 pub use admin_manager::router as admin_manager_router;
 pub use apply_permission::router as apply_permission_router;
 pub use info::router as info_router;
 pub use login::router as login_router;
+pub use logout::router as logout_router;
 pub use modify::router as modify_router;
+pub use personal_data::router as personal_data_router;
+pub use phone_number::router as phone_number_router;
+pub use refresh::router as refresh_router;
 pub use register::router as register_router;
+pub use revoke_token::router as revoke_token_router;
+pub use sign_in::router as sign_in_router;
+pub use status::router as status_router;
+
+use db_manager::entity::user as user_entity;
+use interface_types::proto::user::User as ProtoUser;
+use user_auth::field_policy::{USER_FIELDS, Viewer};
+
+/// 按 `USER_FIELDS` 策略将用户转换为 protobuf，访问者无权查看的字段为空；
+/// token 与 refresh_token 由调用方填入
+fn user_to_proto(model: &user_entity::Model, viewer: Viewer) -> ProtoUser {
+    ProtoUser {
+        token: None,
+        nickname: USER_FIELDS.read("nickname", viewer, model.nickname.clone()),
+        name: USER_FIELDS.read("name", viewer, model.name.clone()),
+        phone_number: USER_FIELDS.read("phone_number", viewer, model.phone_number.clone()),
+        address: USER_FIELDS.read("address", viewer, model.address.clone()),
+        is_important: USER_FIELDS.read(
+            "is_important",
+            viewer,
+            model.is_important.map(|b| b.to_string()),
+        ),
+        avatar: USER_FIELDS.read("avatar", viewer, model.avatar.clone()),
+        permission: USER_FIELDS.read(
+            "permission",
+            viewer,
+            model.permission.map(|p| p.to_string()),
+        ),
+        refresh_token: None,
+    }
+}
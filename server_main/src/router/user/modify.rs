@@ -1,49 +1,39 @@
-use axum::{Router, extract::State, http::HeaderMap, routing::put};
-use axum_extra::protobuf::Protobuf;
+use std::sync::Arc;
+
+use axum::{Extension, Router, extract::State, middleware::from_fn_with_state, routing::put};
 use db_manager::entity::user as user_entity;
 use interface_types::proto::user::{User as ProtoUser, UserRequest, UserResponse};
-use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, Set};
-use user_auth::db_exchange::{ExchangeError, User as AuthUser, token2user, user2token};
-use user_auth::user_auth::{UserPermissionAuthorizeResult, UserPermissionLevel, authorize_user};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+use user_auth::caregiver::{Delegation, action, record_caregiver_action};
+use user_auth::db_exchange::{KeyRing, model2token};
+use user_auth::field_policy::{USER_FIELDS, Viewer};
+use user_auth::user_auth::UserPermissionLevel;
 
+use super::user_to_proto;
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{AuthUser, require_permission, resolve_target};
+use crate::codec::Protobuf;
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/modify", put(modify))
+    Router::new()
+        .route("/modify", put(modify))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
 }
 
 async fn modify(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    Extension(keys): Extension<Arc<KeyRing>>,
+    AuthUser(auth_user): AuthUser,
+    audit: Auditor,
     Protobuf(payload): Protobuf<UserRequest>,
 ) -> Protobuf<UserResponse> {
-    // 1) 解析 token，拿到操作用户 openid 与权限
-    let token: &str = if let Some(token) = headers.get("Authorization") {
-        token.to_str().unwrap()
-    } else {
-        return Protobuf(UserResponse {
-            user: None,
-            code: 401,
-            message: "Missing token".to_string(),
-        });
-    };
-    // println!("token: {}", token);
-    // println!("nickname: {}", payload.nickname.clone().unwrap_or_default());
-    let auth_user: AuthUser = match token2user(&token) {
-        Ok(u) => u,
-        Err(err) => {
-            let msg = match err {
-                ExchangeError::InvalidToken => "Invalid token".to_string(),
-                ExchangeError::TokenExpired => "Token expired".to_string(),
-                ExchangeError::TokenGenerationError(e) | ExchangeError::OtherError(e) => e,
-            };
-            return Protobuf(UserResponse {
-                user: None,
-                code: 401,
-                message: msg,
-            });
-        }
-    };
+    // 1) 从已解析的 token 中获取操作用户 openid
     let actor_openid = auth_user.open_id.clone();
 
     // 2) 目标 openid（不传则修改自己）
@@ -77,41 +67,52 @@ async fn modify(
         }
     };
 
-    let target = match user_entity::Entity::find()
-        .filter(user_entity::Column::OpenId.eq(target_openid.clone()))
-        .one(db.as_ref())
-        .await
-        .map_err(|e| e.to_string())
-    {
-        Ok(Some(u)) => u,
-        Ok(None) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 404,
-                message: "target user not found".to_string(),
-            });
-        }
-        Err(err) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 500,
-                message: err,
-            });
-        }
-    };
+    let (target, delegation) =
+        match resolve_target(db.as_ref(), &actor, Some(target_openid.as_str())).await {
+            Ok(v) => v,
+            Err((code, message)) => {
+                return Protobuf(UserResponse {
+                    user: None,
+                    code,
+                    message,
+                });
+            }
+        };
 
-    // 4) 权限校验：自己或权限足够
-    let actor_perm_code = actor.permission.unwrap_or(0);
-    let target_perm_level: UserPermissionLevel = target.permission.unwrap_or(0).into();
-    if actor_openid != target_openid {
-        let auth_res = authorize_user(actor_perm_code, target_perm_level);
-        if auth_res != UserPermissionAuthorizeResult::Authorized {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 403,
-                message: "permission denied".to_string(),
-            });
-        }
+    // 4) 权限校验：自己、权限更高的用户或生效的照护人才能修改该用户，
+    //    每个字段能否修改再按 USER_FIELDS 策略判断（permission / is_important 仅 Admin）
+    let viewer = Viewer::of(
+        &actor,
+        &target,
+        matches!(delegation, Delegation::Caregiver(_)),
+    );
+    if !viewer.related() {
+        return Protobuf(UserResponse {
+            user: None,
+            code: 403,
+            message: "permission denied".to_string(),
+        });
+    }
+
+    let changed: Vec<&str> = [
+        ("nickname", payload.nickname.is_some()),
+        ("name", payload.name.is_some()),
+        ("phone_number", payload.phone_number.is_some()),
+        ("address", payload.address.is_some()),
+        ("avatar", payload.avatar.is_some()),
+        ("permission", payload.permission.is_some()),
+        ("is_important", payload.is_important.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, set)| set.then_some(field))
+    .collect();
+    if let Err(err) = USER_FIELDS.check_writes(changed.iter().copied(), viewer) {
+        let (code, message) = err.code_and_message();
+        return Protobuf(UserResponse {
+            user: None,
+            code,
+            message,
+        });
     }
 
     // 5) 应用部分更新：payload 中非 None 的字段覆盖，其他保持不变
@@ -133,22 +134,48 @@ async fn modify(
     }
     if let Some(v) = payload.permission.clone() {
         // permission 字符串转 i32，失败则保持不变
-        if let Ok(p) = v.parse::<i32>() {
+        // 权限变更时递增 token_version，使目标用户已签发的 token 立即失效
+        if let Ok(p) = v.parse::<i32>()
+            && target.permission != Some(p)
+        {
             active.permission = Set(Some(p));
+            active.token_version = Set(target.token_version + 1);
         }
     }
-    if let Some(v) = payload.is_important.clone() {
-        if let Ok(b) = v.parse::<bool>() {
-            active.is_important = Set(Some(b));
-        }
+    if let Some(v) = payload.is_important.clone()
+        && let Ok(b) = v.parse::<bool>()
+    {
+        active.is_important = Set(Some(b));
     }
 
     // 确保 openid 不变，并保留原主键
     active.open_id = Set(target_openid.clone());
     active.id = ActiveValue::Unchanged(target.id);
 
-    // 6) 更新数据库
-    let target_updated = match active.update(db.as_ref()).await.map_err(|e| e.to_string()) {
+    // 6) 更新数据库并写入审计；照护人代为修改时同一事务内写入操作记录
+    let updated = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = active.update(&txn).await.map_err(|e| e.to_string())?;
+        audit
+            .update(&txn, &target, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        if let Delegation::Caregiver(link) = &delegation {
+            record_caregiver_action(
+                &txn,
+                Some(link.caregiver_id),
+                link.elder_id,
+                action::USER_MODIFY,
+                Some(changed.join(",")),
+            )
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        }
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let target_updated = match updated {
         Ok(m) => m,
         Err(err) => {
             return Protobuf(UserResponse {
@@ -166,29 +193,14 @@ async fn modify(
         actor
     };
 
-    let auth_user = AuthUser {
-        open_id: actor_model.open_id.clone(),
-        nickname: actor_model.nickname.clone(),
-        avatar: actor_model.avatar.clone(),
-        permission: actor_model.permission,
-        name: actor_model.name.clone(),
-        phone_number: actor_model.phone_number.clone(),
-        address: actor_model.address.clone(),
-        is_important: actor_model.is_important,
-    };
-    let new_token = user2token(&auth_user).unwrap_or_default();
+    let new_token = model2token(&keys, &actor_model).unwrap_or_default();
 
     // 8) 构造返回（actor 信息）
     Protobuf(UserResponse {
         user: Some(ProtoUser {
             token: Some(new_token),
-            nickname: actor_model.nickname,
-            name: actor_model.name,
-            phone_number: actor_model.phone_number,
-            address: actor_model.address,
-            is_important: actor_model.is_important.map(|b| b.to_string()),
-            avatar: actor_model.avatar,
-            permission: actor_model.permission.map(|p| p.to_string()),
+            refresh_token: None,
+            ..user_to_proto(&actor_model, Viewer::own(&actor_model))
         }),
         code: 200,
         message: "modify success".to_string(),
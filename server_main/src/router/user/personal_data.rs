@@ -0,0 +1,177 @@
+use std::io::{Cursor, Write};
+
+use axum::{
+    Router, extract::State, middleware::from_fn_with_state, response::Response, routing::get,
+};
+use interface_types::proto::user::{
+    AccountDeletion as ProtoAccountDeletion, AccountDeletionResponse,
+};
+use sea_orm::TransactionTrait;
+use serde_json::json;
+use user_auth::personal_data::{
+    ExportFile, cancel_account_deletion, export_personal_data, find_account_deletion,
+    request_account_deletion,
+};
+use user_auth::user_auth::UserPermissionLevel;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, require_permission};
+use crate::codec::Protobuf;
+use crate::error::ApiError;
+use crate::router::attachment;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/data_export", get(data_export))
+        .route(
+            "/deletion",
+            get(deletion_status)
+                .post(request_deletion)
+                .delete(cancel_deletion),
+        )
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// 将导出的文件打包为 zip
+fn build_archive(files: Vec<ExportFile>) -> zip::result::ZipResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for file in files {
+        writer.start_file(file.path, options)?;
+        writer.write_all(&file.content)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// GET /api/user/data_export
+/// 导出与自己 open_id 关联的全部个人数据（资料、头像、AI 聊天记录、反馈、照护关联），返回 zip 文件
+async fn data_export(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+) -> Result<Response, ApiError> {
+    // 1) 收集个人数据
+    let db = state.database.clone();
+    let files = export_personal_data(db.as_ref(), &current)
+        .await
+        .map_err(|err| err.code_and_message())?;
+
+    // 2) 打包
+    let buffer = build_archive(files)
+        .map_err(|err| ApiError::internal(format!("Failed to build archive: {}", err)))?;
+
+    // 3) 返回文件流
+    let filename = format!("personal_data_{}.zip", current.id);
+    Ok(attachment("application/zip", &filename, buffer))
+}
+
+/// GET /api/user/deletion
+/// 查看自己待执行的注销申请
+async fn deletion_status(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+) -> Protobuf<AccountDeletionResponse> {
+    let db = state.database.clone();
+    match find_account_deletion(db.as_ref(), current.id).await {
+        Ok(deletion) => Protobuf(AccountDeletionResponse {
+            deletion: deletion.map(|d| ProtoAccountDeletion {
+                requested_at: d.requested_at,
+                purge_at: d.purge_at,
+            }),
+            code: 200,
+            message: "Get account deletion success".to_string(),
+        }),
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            Protobuf(AccountDeletionResponse {
+                deletion: None,
+                code,
+                message,
+            })
+        }
+    }
+}
+
+/// POST /api/user/deletion
+/// 申请注销账号，宽限期（15 天）结束后删除个人数据并匿名化账号，期间可以撤回
+async fn request_deletion(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+) -> Protobuf<AccountDeletionResponse> {
+    // 审计记录在同一事务中写入
+    let db = state.database.clone();
+    let requested = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let deletion = request_account_deletion(&txn, &current)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .insert(&txn, &deletion)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(deletion)
+    }
+    .await;
+    match requested {
+        Ok(d) => Protobuf(AccountDeletionResponse {
+            deletion: Some(ProtoAccountDeletion {
+                requested_at: d.requested_at,
+                purge_at: d.purge_at,
+            }),
+            code: 200,
+            message: "Request account deletion success".to_string(),
+        }),
+        Err((code, message)) => Protobuf(AccountDeletionResponse {
+            deletion: None,
+            code,
+            message,
+        }),
+    }
+}
+
+/// DELETE /api/user/deletion
+/// 在宽限期内撤回注销申请
+async fn cancel_deletion(
+    State(state): State<AppState>,
+    CurrentUser(current): CurrentUser,
+    audit: Auditor,
+) -> Protobuf<AccountDeletionResponse> {
+    // 审计记录在同一事务中写入
+    let db = state.database.clone();
+    let cancelled = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        cancel_account_deletion(&txn, current.id)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        audit
+            .fields(
+                &txn,
+                "account_deletion",
+                current.id,
+                Some(json!({ "user_id": current.id })),
+                None,
+            )
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))
+    }
+    .await;
+    match cancelled {
+        Ok(()) => Protobuf(AccountDeletionResponse {
+            deletion: None,
+            code: 200,
+            message: "Cancel account deletion success".to_string(),
+        }),
+        Err((code, message)) => Protobuf(AccountDeletionResponse {
+            deletion: None,
+            code,
+            message,
+        }),
+    }
+}
@@ -0,0 +1,107 @@
+use axum::{Extension, Router, extract::State, middleware::from_fn_with_state, routing::post};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::user::{User as ProtoUser, UserResponse, WxEncryptedData};
+use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
+use user_auth::field_policy::Viewer;
+use user_auth::user_auth::UserPermissionLevel;
+use user_auth::wx_auth::{WxAuthServerConfig, decrypt_phone_number, load_session_key};
+
+use super::user_to_proto;
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{CurrentUser, require_permission};
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/phone_number", post(phone_number))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Guest,
+            require_permission,
+        ))
+}
+
+/// POST /api/user/phone_number
+/// 所有权限均可调用：解密 wx.getPhoneNumber 返回的加密数据，并写入当前用户的 phone_number
+async fn phone_number(
+    State(state): State<AppState>,
+    Extension(wx): Extension<WxAuthServerConfig>,
+    CurrentUser(user): CurrentUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<WxEncryptedData>,
+) -> Protobuf<UserResponse> {
+    // 1) 读取登录时保存的 session_key
+    let db = state.database.clone();
+    let session_key = match load_session_key(db.as_ref(), user.id).await {
+        Ok(k) => k,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(UserResponse {
+                user: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    // 2) 解密并校验数据水印
+    let phone = match decrypt_phone_number(
+        &session_key,
+        &payload.encrypted_data,
+        &payload.iv,
+        &wx.appid,
+    ) {
+        Ok(p) => p,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(UserResponse {
+                user: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    // 3) 更新手机号（审计记录在同一事务中写入），国内号码保存不带区号的手机号
+    let phone_number = if phone.country_code == "86" {
+        phone.pure_phone_number
+    } else {
+        phone.phone_number
+    };
+    let mut active: user_entity::ActiveModel = user.clone().into();
+    active.phone_number = Set(Some(phone_number));
+    let updated = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let model = active
+            .update(&txn)
+            .await
+            .map_err(|e| format!("Failed to update phone number: {}", e))?;
+        audit
+            .update(&txn, &user, &model)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(model)
+    }
+    .await;
+    let updated = match updated {
+        Ok(u) => u,
+        Err(err) => {
+            return Protobuf(UserResponse {
+                user: None,
+                code: 500,
+                message: err,
+            });
+        }
+    };
+
+    Protobuf(UserResponse {
+        user: Some(ProtoUser {
+            token: None,
+            refresh_token: None,
+            ..user_to_proto(&updated, Viewer::own(&updated))
+        }),
+        code: 200,
+        message: "Update phone number success".to_string(),
+    })
+}
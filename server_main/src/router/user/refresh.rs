@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{Extension, Router, extract::State, routing::post};
+use interface_types::proto::user::{RefreshRequest, User as ProtoUser, UserResponse};
+use user_auth::db_exchange::{ExchangeError, KeyRing, rotate_refresh_token};
+use user_auth::field_policy::Viewer;
+
+use super::user_to_proto;
+use crate::AppState;
+use crate::codec::Protobuf;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/refresh", post(refresh))
+}
+
+/// POST /api/user/refresh
+/// 无需 access token：用 refresh token 换取新的 access token 与 refresh token
+/// 旧 refresh token 随即失效；若已失效的 token 被再次使用，视为泄露并吊销整条轮换链
+async fn refresh(
+    State(state): State<AppState>,
+    Extension(keys): Extension<Arc<KeyRing>>,
+    Protobuf(payload): Protobuf<RefreshRequest>,
+) -> Protobuf<UserResponse> {
+    // 1) 校验参数
+    if payload.refresh_token.is_empty() {
+        return Protobuf(UserResponse {
+            user: None,
+            code: 400,
+            message: "Missing refresh token".to_string(),
+        });
+    }
+
+    // 2) 轮换 refresh token
+    let db = state.database.clone();
+    let (model, tokens) =
+        match rotate_refresh_token(db.as_ref(), &keys, &payload.refresh_token).await {
+            Ok(v) => v,
+            Err(err) => {
+                let (code, message) = match err {
+                    ExchangeError::InvalidToken => (401, "Invalid refresh token".to_string()),
+                    ExchangeError::TokenExpired => (401, "Refresh token expired".to_string()),
+                    ExchangeError::TokenRevoked => (401, "Token revoked".to_string()),
+                    ExchangeError::RefreshTokenReused => (401, "Refresh token reused".to_string()),
+                    ExchangeError::AccountBlocked(e) => e.code_and_message(),
+                    other => (500, format!("{:?}", other)),
+                };
+                return Protobuf(UserResponse {
+                    user: None,
+                    code,
+                    message,
+                });
+            }
+        };
+
+    // 3) 构造返回
+    Protobuf(UserResponse {
+        user: Some(ProtoUser {
+            token: Some(tokens.access_token),
+            refresh_token: Some(tokens.refresh_token),
+            ..user_to_proto(&model, Viewer::own(&model))
+        }),
+        code: 200,
+        message: "refresh success".to_string(),
+    })
+}
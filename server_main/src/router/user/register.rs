@@ -1,19 +1,24 @@
+use std::sync::Arc;
+
 use axum::{
-    Router,
+    Extension, Router,
     extract::{Query, State},
     routing::get,
 };
-use axum_extra::protobuf::Protobuf;
 use db_manager::entity::user as user_entity;
 use interface_types::proto::user::User as ProtoUser;
 use interface_types::proto::user::UserResponse;
-use sea_orm::{ActiveModelTrait, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
 use serde::Deserialize;
-use user_auth::db_exchange::User as AuthUser;
-use user_auth::db_exchange::user2token;
+use user_auth::db_exchange::{KeyRing, issue_token_pair};
+use user_auth::field_policy::Viewer;
+use user_auth::user_status::check_user_status;
 use user_auth::wx_auth::*;
 
+use super::user_to_proto;
 use crate::AppState;
+use crate::audit::Auditor;
+use crate::codec::Protobuf;
 
 #[derive(Deserialize)]
 struct RegisterQuery {
@@ -27,14 +32,17 @@ pub fn router() -> Router<AppState> {
 
 async fn register(
     State(state): State<AppState>,
+    Extension(wx): Extension<WxAuthServerConfig>,
+    Extension(keys): Extension<Arc<KeyRing>>,
+    audit: Auditor,
     Query(query): Query<RegisterQuery>,
 ) -> Protobuf<UserResponse> {
     // Use wx_auth to resolve the provided token/code into an openid.
-    let wx_result = wx_auth_session_to_json(&query.js_code).await;
+    let wx_result = wx_auth_session_to_json(&wx, &query.js_code).await;
 
-    let openid = match wx_result {
+    let (openid, session_key) = match wx_result {
         Ok(resp) => match resp.openid {
-            Some(oid) => oid,
+            Some(oid) => (oid, resp.session_key),
             None => {
                 return Protobuf(UserResponse {
                     user: None,
@@ -53,16 +61,17 @@ async fn register(
     };
 
     // Insert or update the user in the database (currently only insert is implemented).
-    let created_user = match add_user_to_db(&state, &openid).await {
-        Ok(u) => Some(u),
-        Err(err) => {
-            return Protobuf(UserResponse {
-                user: None,
-                code: 500,
-                message: err,
-            });
-        }
-    };
+    let created_user =
+        match add_user_to_db(&state, &keys, audit, &openid, session_key.as_deref()).await {
+            Ok(u) => Some(u),
+            Err((code, message)) => {
+                return Protobuf(UserResponse {
+                    user: None,
+                    code,
+                    message,
+                });
+            }
+        };
     Protobuf(UserResponse {
         user: created_user,
         code: 200,
@@ -70,39 +79,57 @@ async fn register(
     })
 }
 
-async fn add_user_to_db(state: &AppState, openid: &str) -> Result<ProtoUser, String> {
+async fn add_user_to_db(
+    state: &AppState,
+    keys: &KeyRing,
+    audit: Auditor,
+    openid: &str,
+    session_key: Option<&str>,
+) -> Result<ProtoUser, (i32, String)> {
     let db = state.database.clone();
 
+    // 已封禁或已删除的账号不能重新注册
+    let existing = user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(openid))
+        .one(db.as_ref())
+        .await
+        .map_err(|e| (500, e.to_string()))?;
+    if let Some(existing) = existing {
+        check_user_status(&existing).map_err(|e| e.code_and_message())?;
+    }
+
     let active = user_entity::ActiveModel {
         open_id: Set(openid.to_string()),
         ..Default::default()
     };
 
+    // 创建用户、审计记录、session_key 与 token 在同一事务中写入
+    let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
     let model = active
-        .insert(db.as_ref())
+        .insert(&txn)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| (500, e.to_string()))?;
+    audit
+        .as_user(openid)
+        .insert(&txn, &model)
+        .await
+        .map_err(|e| e.code_and_message())?;
 
-    let user = AuthUser {
-        open_id: model.open_id,
-        nickname: model.nickname.clone(),
-        name: model.name.clone(),
-        phone_number: model.phone_number.clone(),
-        address: model.address.clone(),
-        is_important: model.is_important,
-        avatar: model.avatar.clone(),
-        permission: model.permission,
-    };
-    let jwt_token = user2token(&user).unwrap();
+    // 保存 session_key，用于解密手机号等加密数据
+    if let Some(session_key) = session_key {
+        save_session_key(&txn, model.id, session_key)
+            .await
+            .map_err(|e| (500, e.to_string()))?;
+    }
+
+    let tokens = issue_token_pair(&txn, keys, &model)
+        .await
+        .map_err(|e| (500, format!("{:?}", e)))?;
+    txn.commit().await.map_err(|e| (500, e.to_string()))?;
 
     Ok(ProtoUser {
-        token: Some(jwt_token),
-        nickname: model.nickname,
-        name: model.name,
-        phone_number: model.phone_number,
-        address: model.address,
-        is_important: model.is_important.map(|b| b.to_string()),
-        avatar: model.avatar,
-        permission: model.permission.map(|p| p.to_string()),
+        token: Some(tokens.access_token),
+        refresh_token: Some(tokens.refresh_token),
+        ..user_to_proto(&model, Viewer::own(&model))
     })
 }
@@ -0,0 +1,91 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::post,
+};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::user::UserResponse;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Deserialize;
+use user_auth::db_exchange::revoke_user_tokens;
+use user_auth::user_auth::UserPermissionLevel;
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::require_permission;
+use crate::codec::Protobuf;
+
+#[derive(Deserialize)]
+struct RevokeTokenQuery {
+    open_id: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/revoke_token", post(revoke_token))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
+}
+
+/// POST /api/user/revoke_token?open_id=xxx
+/// 仅 Admin 权限：强制吊销指定用户已签发的所有 token（如设备丢失、账号被盗）
+async fn revoke_token(
+    State(state): State<AppState>,
+    audit: Auditor,
+    Query(query): Query<RevokeTokenQuery>,
+) -> Protobuf<UserResponse> {
+    // 1) 查询目标用户
+    let db = state.database.clone();
+    let user = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(query.open_id.clone()))
+        .one(db.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return Protobuf(UserResponse {
+                user: None,
+                code: 404,
+                message: "Target user not found".to_string(),
+            });
+        }
+        Err(err) => {
+            return Protobuf(UserResponse {
+                user: None,
+                code: 500,
+                message: err,
+            });
+        }
+    };
+
+    // 2) 递增 token_version，审计记录在同一事务中写入
+    let revoked = async {
+        let txn = db.begin().await.map_err(|e| e.to_string())?;
+        let updated = revoke_user_tokens(&txn, user.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        audit
+            .update(&txn, &user, &updated)
+            .await
+            .map_err(|e| e.code_and_message().1)?;
+        txn.commit().await.map_err(|e| e.to_string())
+    }
+    .await;
+    if let Err(err) = revoked {
+        return Protobuf(UserResponse {
+            user: None,
+            code: 500,
+            message: err,
+        });
+    }
+
+    Protobuf(UserResponse {
+        user: None,
+        code: 200,
+        message: "Revoke token success".to_string(),
+    })
+}
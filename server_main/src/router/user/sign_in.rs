@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    routing::post,
+};
+use interface_types::proto::user::{SignInResponse, User as ProtoUser, UserRequest};
+use sea_orm::TransactionTrait;
+use serde::Deserialize;
+use user_auth::db_exchange::{ExchangeError, KeyRing, OnboardingProfile, sign_in};
+use user_auth::field_policy::Viewer;
+use user_auth::wx_auth::*;
+
+use super::user_to_proto;
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::codec::Protobuf;
+
+#[derive(Deserialize)]
+struct SignInQuery {
+    js_code: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/sign_in", post(sign_in_handler))
+}
+
+/// POST /api/user/sign_in?js_code=xxx
+/// 无需登录：用户不存在时自动注册，可重复调用；
+/// body 为可选的 UserRequest，仅在创建新用户时写入 nickname/name/phone_number/address
+async fn sign_in_handler(
+    State(state): State<AppState>,
+    Extension(wx): Extension<WxAuthServerConfig>,
+    Extension(keys): Extension<Arc<KeyRing>>,
+    audit: Auditor,
+    Query(query): Query<SignInQuery>,
+    Protobuf(payload): Protobuf<UserRequest>,
+) -> Protobuf<SignInResponse> {
+    // 1) 通过 js_code 换取 openid 与 session_key
+    let (openid, session_key) = match wx_auth_session_to_json(&wx, &query.js_code).await {
+        Ok(WxAuthResponse {
+            openid: Some(openid),
+            session_key,
+            ..
+        }) => (openid, session_key),
+        Ok(_) => {
+            return Protobuf(SignInResponse {
+                user: None,
+                code: 400,
+                message: "WeiXin auth did not return an openid".to_string(),
+                is_new: false,
+            });
+        }
+        Err(err) => {
+            return Protobuf(SignInResponse {
+                user: None,
+                code: 500,
+                message: format!("failed to resolve openid: {:?}", err),
+                is_new: false,
+            });
+        }
+    };
+
+    // 2) 登录或创建用户
+    let profile = OnboardingProfile {
+        nickname: payload.nickname,
+        name: payload.name,
+        phone_number: payload.phone_number,
+        address: payload.address,
+    };
+    let profile = (!profile.is_empty()).then_some(profile);
+    let db = state.database.clone();
+    let signed_in = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let result = sign_in(&txn, &keys, &openid, session_key.as_deref(), profile)
+            .await
+            .map_err(|err| match err {
+                ExchangeError::AccountBlocked(e) => e.code_and_message(),
+                other => (500, format!("{:?}", other)),
+            })?;
+        // 新用户以其自身作为操作者记录审计，与创建用户在同一事务中写入
+        if result.is_new {
+            audit
+                .as_user(&result.user.open_id)
+                .insert(&txn, &result.user)
+                .await
+                .map_err(|e| e.code_and_message())?;
+        }
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(result)
+    }
+    .await;
+    let result = match signed_in {
+        Ok(r) => r,
+        Err((code, message)) => {
+            return Protobuf(SignInResponse {
+                user: None,
+                code,
+                message,
+                is_new: false,
+            });
+        }
+    };
+
+    // 3) 构造返回
+    let model = result.user;
+    Protobuf(SignInResponse {
+        user: Some(ProtoUser {
+            token: Some(result.tokens.access_token),
+            refresh_token: Some(result.tokens.refresh_token),
+            ..user_to_proto(&model, Viewer::own(&model))
+        }),
+        code: 200,
+        message: "sign in success".to_string(),
+        is_new: result.is_new,
+    })
+}
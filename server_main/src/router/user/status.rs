@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    routing::{get, put},
+};
+use db_manager::entity::user as user_entity;
+use interface_types::proto::user::{
+    UserStatusInfo as ProtoUserStatusInfo, UserStatusLog as ProtoUserStatusLog,
+    UserStatusLogResponse, UserStatusRequest, UserStatusResponse,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Deserialize;
+use user_auth::db_exchange::now_timestamp;
+use user_auth::user_auth::UserPermissionLevel;
+use user_auth::user_status::{UserStatus, change_user_status, list_status_logs};
+
+use crate::AppState;
+use crate::audit::Auditor;
+use crate::auth::{AuthUser, require_permission};
+use crate::codec::Protobuf;
+
+#[derive(Debug, Deserialize)]
+struct StatusLogQuery {
+    open_id: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/status", put(change_status))
+        .route("/status_log", get(status_log))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
+}
+
+/// PUT /api/user/status
+/// 仅 Admin 权限：封禁、解封或删除账号，每次变更都会记录到 user_status_log
+async fn change_status(
+    State(state): State<AppState>,
+    AuthUser(auth_user): AuthUser,
+    audit: Auditor,
+    Protobuf(payload): Protobuf<UserStatusRequest>,
+) -> Protobuf<UserStatusResponse> {
+    // 1) 校验参数
+    let Some(status) = UserStatus::parse(&payload.status) else {
+        return Protobuf(UserStatusResponse {
+            status: None,
+            code: 400,
+            message: format!("Invalid status: {}", payload.status),
+        });
+    };
+    if payload.target_openid == auth_user.open_id {
+        return Protobuf(UserStatusResponse {
+            status: None,
+            code: 400,
+            message: "Cannot change own status".to_string(),
+        });
+    }
+    if status == UserStatus::Suspended
+        && payload
+            .suspended_until
+            .is_some_and(|until| until <= now_timestamp() as i64)
+    {
+        return Protobuf(UserStatusResponse {
+            status: None,
+            code: 400,
+            message: "suspended_until must be in the future".to_string(),
+        });
+    }
+
+    // 2) 查询操作者与目标用户
+    let db = state.database.clone();
+    let users = match user_entity::Entity::find()
+        .filter(
+            user_entity::Column::OpenId
+                .is_in([auth_user.open_id.clone(), payload.target_openid.clone()]),
+        )
+        .all(db.as_ref())
+        .await
+    {
+        Ok(users) => users,
+        Err(err) => {
+            return Protobuf(UserStatusResponse {
+                status: None,
+                code: 500,
+                message: format!("Database error: {}", err),
+            });
+        }
+    };
+    let operator_id = users
+        .iter()
+        .find(|u| u.open_id == auth_user.open_id)
+        .map(|u| u.id);
+    let Some(target) = users
+        .into_iter()
+        .find(|u| u.open_id == payload.target_openid)
+    else {
+        return Protobuf(UserStatusResponse {
+            status: None,
+            code: 404,
+            message: "Target user not found".to_string(),
+        });
+    };
+
+    // 3) 变更状态并记录，审计记录在同一事务中写入
+    let changed = async {
+        let txn = db.begin().await.map_err(|e| (500, e.to_string()))?;
+        let updated = change_user_status(
+            &txn,
+            operator_id,
+            target.clone(),
+            status,
+            payload.reason,
+            payload.suspended_until,
+        )
+        .await
+        .map_err(|e| e.code_and_message())?;
+        audit
+            .update(&txn, &target, &updated)
+            .await
+            .map_err(|e| e.code_and_message())?;
+        txn.commit().await.map_err(|e| (500, e.to_string()))?;
+        Ok::<_, (i32, String)>(updated)
+    }
+    .await;
+    let updated = match changed {
+        Ok(u) => u,
+        Err((code, message)) => {
+            return Protobuf(UserStatusResponse {
+                status: None,
+                code,
+                message,
+            });
+        }
+    };
+
+    Protobuf(UserStatusResponse {
+        status: Some(ProtoUserStatusInfo {
+            open_id: updated.open_id,
+            status: updated.status,
+            reason: updated.suspended_reason,
+            suspended_until: updated.suspended_until,
+        }),
+        code: 200,
+        message: "Change user status success".to_string(),
+    })
+}
+
+/// GET /api/user/status_log?open_id=xxx
+/// 仅 Admin 权限：查看某个用户的状态变更记录
+async fn status_log(
+    State(state): State<AppState>,
+    Query(query): Query<StatusLogQuery>,
+) -> Protobuf<UserStatusLogResponse> {
+    // 1) 查询目标用户
+    let db = state.database.clone();
+    let target = match user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(query.open_id.clone()))
+        .one(db.as_ref())
+        .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return Protobuf(UserStatusLogResponse {
+                logs: vec![],
+                code: 404,
+                message: "Target user not found".to_string(),
+            });
+        }
+        Err(err) => {
+            return Protobuf(UserStatusLogResponse {
+                logs: vec![],
+                code: 500,
+                message: format!("Database error: {}", err),
+            });
+        }
+    };
+
+    // 2) 查询变更记录及操作者
+    let logs = match list_status_logs(db.as_ref(), target.id).await {
+        Ok(logs) => logs,
+        Err(err) => {
+            let (code, message) = err.code_and_message();
+            return Protobuf(UserStatusLogResponse {
+                logs: vec![],
+                code,
+                message,
+            });
+        }
+    };
+    let operator_ids: Vec<i32> = logs.iter().filter_map(|l| l.operator_id).collect();
+    let operators: HashMap<i32, String> = match user_entity::Entity::find()
+        .filter(user_entity::Column::Id.is_in(operator_ids))
+        .all(db.as_ref())
+        .await
+    {
+        Ok(users) => users.into_iter().map(|u| (u.id, u.open_id)).collect(),
+        Err(err) => {
+            return Protobuf(UserStatusLogResponse {
+                logs: vec![],
+                code: 500,
+                message: format!("Database error: {}", err),
+            });
+        }
+    };
+
+    Protobuf(UserStatusLogResponse {
+        logs: logs
+            .into_iter()
+            .map(|l| ProtoUserStatusLog {
+                from_status: l.from_status,
+                to_status: l.to_status,
+                reason: l.reason,
+                suspended_until: l.suspended_until,
+                operator_open_id: l.operator_id.and_then(|id| operators.get(&id).cloned()),
+                created_at: l.created_at,
+            })
+            .collect(),
+        code: 200,
+        message: "Get user status log success".to_string(),
+    })
+}
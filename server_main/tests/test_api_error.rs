@@ -0,0 +1,144 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    middleware::from_fn,
+    routing::{get, post},
+};
+use interface_types::proto::common::ErrorResponse;
+use interface_types::proto::user::{UserRequest, UserResponse};
+use prost::Message;
+use server_main::codec::Protobuf;
+use server_main::error::{ApiError, api_errors, panic_response};
+use tower::ServiceExt;
+use tower_http::catch_panic::CatchPanicLayer;
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/envelope",
+            get(|| async {
+                Protobuf(UserResponse {
+                    user: None,
+                    code: 404,
+                    message: "User not found".to_string(),
+                })
+            }),
+        )
+        .route(
+            "/ok",
+            get(|| async {
+                Protobuf(UserResponse {
+                    user: None,
+                    code: 200,
+                    message: "ok".to_string(),
+                })
+            }),
+        )
+        .route(
+            "/error",
+            get(|| async { Err::<String, _>(ApiError::forbidden("nope")) }),
+        )
+        .route(
+            "/echo",
+            post(|Protobuf(_): Protobuf<UserRequest>| async { "ok" }),
+        )
+        .route(
+            "/download",
+            get(|| async {
+                (
+                    [(header::CONTENT_DISPOSITION, "attachment; filename=\"a\"")],
+                    // 恰好能按 ErrorResponse 解码出 code 500 的文件内容
+                    ErrorResponse {
+                        code: 500,
+                        message: String::new(),
+                    }
+                    .encode_to_vec(),
+                )
+            }),
+        )
+        .route(
+            "/panic",
+            get(|| async {
+                if true {
+                    panic!("boom");
+                }
+                "unreachable"
+            }),
+        )
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(from_fn(api_errors))
+}
+
+async fn call(request: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let response = app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, body.to_vec())
+}
+
+async fn get_uri(uri: &str) -> (StatusCode, Vec<u8>) {
+    call(Request::builder().uri(uri).body(Body::empty()).unwrap()).await
+}
+
+fn envelope(body: &[u8]) -> ErrorResponse {
+    ErrorResponse::decode(body).expect("should be an ErrorResponse")
+}
+
+#[tokio::test]
+async fn envelope_code_sets_status() {
+    let (status, body) = get_uri("/envelope").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    // body 保持不变，现有客户端仍按 UserResponse 解码
+    let response = UserResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.code, 404);
+    assert_eq!(response.message, "User not found");
+
+    let (status, _) = get_uri("/ok").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 其他二进制响应不受影响
+    let (status, _) = get_uri("/download").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn api_error_sets_status_and_envelope() {
+    let (status, body) = get_uri("/error").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let err = envelope(&body);
+    assert_eq!(err.code, 403);
+    assert_eq!(err.message, "nope");
+}
+
+#[tokio::test]
+async fn rejections_become_envelopes() {
+    // 无法解码的 protobuf body
+    let request = Request::builder()
+        .method("POST")
+        .uri("/echo")
+        .body(Body::from(vec![0xff, 0xff, 0xff]))
+        .unwrap();
+    let (status, body) = call(request).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let err = envelope(&body);
+    assert_eq!(err.code, 422);
+    assert!(!err.message.is_empty());
+
+    let (status, body) = get_uri("/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(envelope(&body).code, 404);
+
+    let (status, body) = get_uri("/echo").await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(envelope(&body).code, 405);
+}
+
+#[tokio::test]
+async fn panics_become_internal_errors() {
+    let (status, body) = get_uri("/panic").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    let err = envelope(&body);
+    assert_eq!(err.code, 500);
+    assert_eq!(err.message, "Internal server error");
+}
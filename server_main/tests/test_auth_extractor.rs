@@ -0,0 +1,501 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    body::{Body, to_bytes},
+    extract::ConnectInfo,
+    http::{Request, header},
+    middleware::from_fn_with_state,
+    response::IntoResponse,
+    routing::get,
+};
+use axum_extra::extract::cookie::{Key, PrivateCookieJar};
+use db_manager::entity::{admin_totp, role, user as user_entity};
+use interface_types::proto::common::ErrorResponse;
+use prost::Message;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, MockDatabase, TransactionTrait, Value};
+use server_main::audit::{Auditor, TrustedProxies};
+use server_main::auth::console::session_cookie;
+use server_main::auth::{AuthUser, OptionalUser, require_permission, require_role_permission};
+use tower::ServiceExt;
+use user_auth::audit::AuditActor;
+use user_auth::db_exchange::{KeyRing, model2token, user2token};
+use user_auth::local_auth::{CSRF_HEADER, ConsoleSession};
+use user_auth::rbac::permission;
+use user_auth::totp::{STEP_UP_HEADER, TotpPolicy, issue_step_up_token};
+use user_auth::user_auth::UserPermissionLevel;
+
+fn keys() -> KeyRing {
+    KeyRing::single("test-secret").unwrap()
+}
+
+fn model_with_permission(permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id: permission,
+        open_id: format!("openid-{}", permission),
+        nickname: None,
+        avatar: None,
+        permission: Some(permission),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn token_with_permission(permission: i32) -> String {
+    model2token(&keys(), &model_with_permission(permission))
+        .expect("token generation should succeed")
+}
+
+/// Mock database answering the extractor's user lookup with `rows`, in order.
+fn mock_db(rows: Vec<user_entity::Model>) -> Arc<DatabaseConnection> {
+    let results: Vec<Vec<user_entity::Model>> = rows.into_iter().map(|row| vec![row]).collect();
+    Arc::new(
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(results)
+            .into_connection(),
+    )
+}
+
+fn app(db: Arc<DatabaseConnection>) -> Router {
+    Router::new()
+        .route("/admin", get(|| async { "ok" }))
+        .route_layer(from_fn_with_state(
+            UserPermissionLevel::Admin,
+            require_permission,
+        ))
+        .merge(
+            Router::new()
+                .route("/meal", get(|| async { "meal" }))
+                .route_layer(from_fn_with_state(
+                    permission::MEAL_WRITE,
+                    require_role_permission,
+                )),
+        )
+        .merge(
+            Router::new()
+                .route("/roles", get(|| async { "roles" }))
+                .route_layer(from_fn_with_state(
+                    permission::ROLE_MANAGE,
+                    require_role_permission,
+                )),
+        )
+        .route(
+            "/me",
+            get(|AuthUser(user): AuthUser| async move { user.open_id }),
+        )
+        .route(
+            "/optional",
+            get(|OptionalUser(user): OptionalUser| async move {
+                user.map(|u| u.open_id)
+                    .unwrap_or_else(|| "anonymous".to_string())
+            }),
+        )
+        .layer(Extension(db))
+        .layer(Extension(Arc::new(keys())))
+}
+
+async fn call(uri: &str, token: Option<&str>, rows: Vec<user_entity::Model>) -> Vec<u8> {
+    let mut request = Request::builder().uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, token);
+    }
+    let response = app(mock_db(rows))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+#[tokio::test]
+async fn rejects_missing_token() {
+    let body = call("/me", None, vec![]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 401);
+    assert_eq!(err.message, "Missing token");
+}
+
+#[tokio::test]
+async fn rejects_invalid_token() {
+    let body = call("/me", Some("not-a-jwt"), vec![]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 401);
+}
+
+#[tokio::test]
+async fn accepts_bearer_prefix() {
+    let token = format!("Bearer {}", token_with_permission(1));
+    let body = call("/me", Some(&token), vec![model_with_permission(1)]).await;
+    assert_eq!(body, b"openid-1");
+}
+
+#[tokio::test]
+async fn route_level_guard_requires_admin() {
+    let provider = token_with_permission(2);
+    let body = call("/admin", Some(&provider), vec![model_with_permission(2)]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+
+    let admin = token_with_permission(3);
+    let body = call("/admin", Some(&admin), vec![model_with_permission(3)]).await;
+    assert_eq!(body, b"ok");
+}
+
+#[tokio::test]
+async fn optional_user_allows_anonymous() {
+    assert_eq!(call("/optional", None, vec![]).await, b"anonymous");
+    let token = token_with_permission(1);
+    assert_eq!(
+        call("/optional", Some(&token), vec![model_with_permission(1)]).await,
+        b"openid-1"
+    );
+}
+
+#[tokio::test]
+async fn rejects_revoked_token() {
+    let token = token_with_permission(1);
+    let mut bumped = model_with_permission(1);
+    bumped.token_version = 1;
+    let body = call("/me", Some(&token), vec![bumped]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 401);
+    assert_eq!(err.message, "Token revoked");
+}
+
+#[tokio::test]
+async fn permission_is_read_from_database() {
+    // The token was issued while the user was an admin; the row says provider now.
+    let token = user2token(
+        &keys(),
+        &user_auth::db_exchange::model2user(&model_with_permission(3)),
+        0,
+    )
+    .unwrap();
+    let mut demoted = model_with_permission(3);
+    demoted.permission = Some(2);
+    let body = call("/admin", Some(&token), vec![demoted]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+}
+
+#[tokio::test]
+async fn rejects_suspended_account() {
+    let token = token_with_permission(1);
+    let mut suspended = model_with_permission(1);
+    suspended.status = "suspended".to_string();
+    let body = call("/me", Some(&token), vec![suspended]).await;
+    let err = ErrorResponse::decode(body.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 423);
+}
+
+#[tokio::test]
+async fn role_guard_reads_permissions_from_database() {
+    let token = token_with_permission(1);
+    let tuple = |column: &str, value: Value| BTreeMap::from([(column.to_string(), value)]);
+    let editor = role::Model {
+        id: 7,
+        name: "meal_editor".to_string(),
+        description: None,
+        is_builtin: false,
+    };
+
+    // user lookup, assigned role ids, roles, permissions
+    let db = Arc::new(
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![model_with_permission(1)]])
+            .append_query_results([vec![tuple("role_id", 7.into())]])
+            .append_query_results([vec![editor]])
+            .append_query_results([vec![tuple("permission", permission::MEAL_WRITE.into())]])
+            .into_connection(),
+    );
+    let response = app(db)
+        .oneshot(
+            Request::builder()
+                .uri("/meal")
+                .header(header::AUTHORIZATION, &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body.as_ref(), b"meal");
+
+    let db = Arc::new(
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![model_with_permission(1)]])
+            .append_query_results([Vec::<BTreeMap<String, Value>>::new()])
+            .append_query_results([Vec::<role::Model>::new()])
+            .append_query_results([Vec::<BTreeMap<String, Value>>::new()])
+            .into_connection(),
+    );
+    let response = app(db)
+        .oneshot(
+            Request::builder()
+                .uri("/meal")
+                .header(header::AUTHORIZATION, &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let err = ErrorResponse::decode(body.as_ref()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+}
+
+#[tokio::test]
+async fn console_cookie_requires_csrf_for_writes() {
+    let key = Key::generate();
+    let session = ConsoleSession::new(&model_with_permission(3));
+    // Encrypt the session the same way the login handler does.
+    let response = PrivateCookieJar::new(key.clone())
+        .add(session_cookie(&session))
+        .into_response();
+    let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+    let cookie = set_cookie.split(';').next().unwrap().to_string();
+
+    let call = |method: &str, csrf: Option<&str>| {
+        let mut request = Request::builder()
+            .method(method)
+            .uri("/me")
+            .header(header::COOKIE, &cookie);
+        if let Some(csrf) = csrf {
+            request = request.header(CSRF_HEADER, csrf);
+        }
+        let app = Router::new()
+            .route(
+                "/me",
+                get(|AuthUser(user): AuthUser| async move { user.open_id })
+                    .post(|AuthUser(user): AuthUser| async move { user.open_id }),
+            )
+            .layer(Extension(mock_db(vec![model_with_permission(3)])))
+            .layer(Extension(key.clone()));
+        async move {
+            let response = app
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap()
+                .to_vec()
+        }
+    };
+
+    assert_eq!(call("GET", None).await, b"openid-3");
+    let err = ErrorResponse::decode(call("POST", None).await.as_slice())
+        .expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+    let err = ErrorResponse::decode(call("POST", Some("forged")).await.as_slice())
+        .expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+    assert_eq!(call("POST", Some(&session.csrf)).await, b"openid-3");
+}
+
+#[tokio::test]
+async fn admin_guard_applies_totp_policy() {
+    let admin = model_with_permission(3);
+    let token = model2token(&keys(), &admin).unwrap();
+    let call = |policy: TotpPolicy, binding: Option<admin_totp::Model>, step_up: Option<String>| {
+        let db = Arc::new(
+            MockDatabase::new(DbBackend::Postgres)
+                .append_query_results([vec![admin.clone()]])
+                .append_query_results([binding.into_iter().collect::<Vec<_>>()])
+                .into_connection(),
+        );
+        let mut request = Request::builder()
+            .uri("/admin")
+            .header(header::AUTHORIZATION, &token);
+        if let Some(step_up) = step_up {
+            request = request.header(STEP_UP_HEADER, step_up);
+        }
+        let app = app(db).layer(Extension(policy));
+        async move {
+            let response = app
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap()
+                .to_vec()
+        }
+    };
+    let enabled = admin_totp::Model {
+        id: 1,
+        user_id: admin.id,
+        secret: "GEZDGNBVGY3TQOJQ".to_string(),
+        enabled: true,
+        last_used_step: None,
+        created_at: 0,
+        enabled_at: Some(0),
+        failed_attempts: 0,
+        locked_until: None,
+    };
+
+    // Not enrolled: fine under `enrolled`, refused under `required`.
+    assert_eq!(call(TotpPolicy::Enrolled, None, None).await, b"ok");
+    let err = ErrorResponse::decode(call(TotpPolicy::Required, None, None).await.as_slice())
+        .expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+
+    // Enrolled: needs a step-up token.
+    let err = ErrorResponse::decode(
+        call(TotpPolicy::Enrolled, Some(enabled.clone()), None)
+            .await
+            .as_slice(),
+    )
+    .expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+    let (step_up, _) = issue_step_up_token(&keys(), &admin).unwrap();
+    assert_eq!(
+        call(TotpPolicy::Enrolled, Some(enabled), Some(step_up)).await,
+        b"ok"
+    );
+}
+
+#[tokio::test]
+async fn privileged_role_guard_applies_totp_policy() {
+    let manager = model_with_permission(1);
+    let token = model2token(&keys(), &manager).unwrap();
+    let tuple = |column: &str, value: Value| BTreeMap::from([(column.to_string(), value)]);
+    let enabled = admin_totp::Model {
+        id: 1,
+        user_id: manager.id,
+        secret: "GEZDGNBVGY3TQOJQ".to_string(),
+        enabled: true,
+        last_used_step: None,
+        created_at: 0,
+        enabled_at: Some(0),
+        failed_attempts: 0,
+        locked_until: None,
+    };
+    let call = |step_up: Option<String>| {
+        // user lookup, assigned role ids, roles, permissions, TOTP binding
+        let db = Arc::new(
+            MockDatabase::new(DbBackend::Postgres)
+                .append_query_results([vec![manager.clone()]])
+                .append_query_results([vec![tuple("role_id", 8.into())]])
+                .append_query_results([vec![role::Model {
+                    id: 8,
+                    name: "role_manager".to_string(),
+                    description: None,
+                    is_builtin: false,
+                }]])
+                .append_query_results([vec![tuple("permission", permission::ROLE_MANAGE.into())]])
+                .append_query_results([vec![enabled.clone()]])
+                .into_connection(),
+        );
+        let mut request = Request::builder()
+            .uri("/roles")
+            .header(header::AUTHORIZATION, &token);
+        if let Some(step_up) = step_up {
+            request = request.header(STEP_UP_HEADER, step_up);
+        }
+        let app = app(db).layer(Extension(TotpPolicy::Enrolled));
+        async move {
+            let response = app
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap()
+                .to_vec()
+        }
+    };
+
+    // role.manage 不是 Admin 等级的权限，同样需要 step-up
+    let err =
+        ErrorResponse::decode(call(None).await.as_slice()).expect("should be an ErrorResponse");
+    assert_eq!(err.code, 403);
+
+    let (step_up, _) = issue_step_up_token(&keys(), &manager).unwrap();
+    assert_eq!(call(Some(step_up)).await, b"roles");
+}
+
+/// Client IP recorded by the audit extractor for a request from `peer`.
+async fn audited_ip(peer: &str, trusted: &[&str], forwarded_for: Option<&str>) -> String {
+    let app = Router::new()
+        .route(
+            "/",
+            get(|audit: Auditor| async move { audit.0.ip.unwrap_or_default() }),
+        )
+        .layer(Extension(TrustedProxies(
+            trusted.iter().map(|ip| ip.parse().unwrap()).collect(),
+        )));
+    let mut request = Request::builder()
+        .uri("/")
+        .extension(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 50000)));
+    if let Some(value) = forwarded_for {
+        request = request.header("x-forwarded-for", value);
+    }
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn audit_ip_trusts_forwarded_headers_only_from_proxies() {
+    // 没有配置代理时忽略客户端自带的代理头
+    assert_eq!(
+        audited_ip("203.0.113.7", &[], Some("10.9.9.9")).await,
+        "203.0.113.7"
+    );
+    // 来自可信代理时取第一跳
+    assert_eq!(
+        audited_ip("10.0.0.2", &["10.0.0.2"], Some("198.51.100.4, 10.0.0.2")).await,
+        "198.51.100.4"
+    );
+    // 绕过代理直连的请求仍使用对端地址
+    assert_eq!(
+        audited_ip("203.0.113.7", &["10.0.0.2"], Some("10.9.9.9")).await,
+        "203.0.113.7"
+    );
+    assert_eq!(
+        audited_ip("10.0.0.2", &["10.0.0.2"], None).await,
+        "10.0.0.2"
+    );
+}
+
+#[tokio::test]
+async fn failed_audit_write_fails_the_request() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_errors([DbErr::Custom("audit_log is append-only".to_string())])
+        .into_connection();
+    let txn = db.begin().await.unwrap();
+
+    let audit = Auditor(AuditActor::default());
+    let result = audit
+        .fields(
+            &txn,
+            "role",
+            "auditor",
+            None,
+            Some(serde_json::json!({ "permissions": ["audit.read"] })),
+        )
+        .await;
+    // 审计写入失败时返回错误，由调用方回滚整个事务
+    let (code, _) = result.unwrap_err().code_and_message();
+    assert_eq!(code, 500);
+    txn.rollback().await.unwrap();
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(!log.contains("SAVEPOINT"));
+    assert!(log.contains("ROLLBACK"));
+    assert!(!log.contains("COMMIT"));
+}
@@ -0,0 +1,196 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    middleware::from_fn,
+    response::IntoResponse,
+    routing::post,
+};
+use interface_types::proto::audit_log::AuditLogResponse;
+use interface_types::proto::common::ErrorResponse;
+use interface_types::proto::console::ConsoleResponse;
+use interface_types::proto::totp::TotpResponse;
+use interface_types::proto::user::{User, UserRequest, UserResponse};
+use prost::Message;
+use serde_json::{Value, json};
+use server_main::codec::{Protobuf, negotiate};
+use server_main::error::api_errors;
+use tower::ServiceExt;
+
+/// 把请求中的昵称原样放进响应，昵称为空时返回 400
+async fn echo(Protobuf(payload): Protobuf<UserRequest>) -> Protobuf<UserResponse> {
+    let Some(nickname) = payload.nickname else {
+        return Protobuf(UserResponse {
+            user: None,
+            code: 400,
+            message: "nickname is required".to_string(),
+        });
+    };
+    Protobuf(UserResponse {
+        user: Some(User {
+            nickname: Some(nickname),
+            is_important: Some("true".to_string()),
+            ..Default::default()
+        }),
+        code: 200,
+        message: "ok".to_string(),
+    })
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/echo", post(echo))
+        .layer(from_fn(api_errors))
+        .layer(from_fn(negotiate))
+}
+
+async fn call(
+    content_type: Option<&str>,
+    accept: Option<&str>,
+    body: Vec<u8>,
+) -> (StatusCode, String, Vec<u8>) {
+    let mut request = Request::builder().method("POST").uri("/echo");
+    if let Some(content_type) = content_type {
+        request = request.header(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+    }
+    let response = app()
+        .oneshot(request.body(Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, body.to_vec())
+}
+
+fn nickname_request(nickname: &str) -> UserRequest {
+    UserRequest {
+        nickname: Some(nickname.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn protobuf_by_default() {
+    let body = nickname_request("alice").encode_to_vec();
+    let (status, content_type, body) = call(None, None, body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/octet-stream");
+    let response = UserResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.user.unwrap().nickname.as_deref(), Some("alice"));
+}
+
+#[tokio::test]
+async fn json_request_gets_json_response() {
+    let body = serde_json::to_vec(&json!({ "nickname": "bob" })).unwrap();
+    let (status, content_type, body) = call(Some("application/json"), None, body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json");
+
+    // 字段名与 .proto 一致，未设置的 optional 字段不输出
+    let value: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["code"], 200);
+    assert_eq!(value["user"]["nickname"], "bob");
+    assert_eq!(value["user"]["is_important"], "true");
+    assert!(value["user"].get("phone_number").is_none());
+}
+
+#[tokio::test]
+async fn accept_overrides_request_format() {
+    // protobuf 请求，要求 JSON 响应
+    let body = nickname_request("carol").encode_to_vec();
+    let (_, content_type, body) = call(None, Some("application/json"), body).await;
+    assert_eq!(content_type, "application/json");
+    let value: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["user"]["nickname"], "carol");
+
+    // JSON 请求，要求 protobuf 响应
+    let body = serde_json::to_vec(&json!({ "nickname": "dave" })).unwrap();
+    let (_, content_type, body) = call(
+        Some("application/json"),
+        Some("application/x-protobuf, */*"),
+        body,
+    )
+    .await;
+    assert_eq!(content_type, "application/octet-stream");
+    let response = UserResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.user.unwrap().nickname.as_deref(), Some("dave"));
+}
+
+#[tokio::test]
+async fn json_errors_keep_status_and_envelope() {
+    // body 中的错误码同样设置 HTTP 状态码
+    let (status, _, body) = call(Some("application/json"), None, b"{}".to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let value: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["code"], 400);
+    assert_eq!(value["message"], "nickname is required");
+
+    // 无法解析的 JSON 返回 422
+    let (status, content_type, body) =
+        call(Some("application/json"), None, b"{\"nickname\":".to_vec()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(content_type, "application/json");
+    let value: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["code"], 422);
+
+    // protobuf 客户端收到的仍是 ErrorResponse
+    let (status, _, body) = call(None, None, vec![0xff, 0xff]).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(ErrorResponse::decode(body.as_slice()).unwrap().code, 422);
+}
+
+/// 所有 *Response 的 code/message 字段编号为 2/3，HTTP 状态码与错误信封都依赖这一点
+#[test]
+fn responses_share_the_envelope_field_numbers() {
+    let audit_log = AuditLogResponse {
+        logs: vec![],
+        code: 400,
+        message: "Invalid action".to_string(),
+        total: 404,
+    };
+    let envelope = ErrorResponse::decode(audit_log.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(envelope.code, 400);
+    assert_eq!(envelope.message, "Invalid action");
+    assert_eq!(
+        Protobuf(audit_log).into_response().status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    let console = ConsoleResponse {
+        code: 429,
+        message: "locked".to_string(),
+    };
+    assert_eq!(
+        ErrorResponse::decode(console.encode_to_vec().as_slice())
+            .unwrap()
+            .code,
+        429
+    );
+    assert_eq!(
+        Protobuf(console).into_response().status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    let totp = TotpResponse {
+        code: 401,
+        message: "Invalid verification code".to_string(),
+    };
+    assert_eq!(
+        ErrorResponse::decode(totp.encode_to_vec().as_slice())
+            .unwrap()
+            .code,
+        401
+    );
+    assert_eq!(
+        Protobuf(totp).into_response().status(),
+        StatusCode::UNAUTHORIZED
+    );
+}
@@ -0,0 +1,553 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    middleware::from_fn,
+};
+use db_manager::entity::{audit_log, audit_subject, policy_type, role, user as user_entity};
+use interface_types::proto::common::ErrorResponse;
+use interface_types::proto::policy_type::{
+    PolicyType as ProtoPolicyType, PolicyTypeRequest, PolicyTypeResponse,
+};
+use prost::Message;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, MockDatabase, MockExecResult,
+    QueryFilter, Value,
+};
+use server_main::AppState;
+use server_main::auth::Permissions;
+use server_main::error::{ApiError, api_errors};
+use server_main::listing::{ListQuery, Listing, Page, fetch};
+use server_main::resource::{Access, Resource, patch, required_param, resource_router};
+use tower::ServiceExt;
+use user_auth::db_exchange::{KeyRing, model2token};
+use user_auth::user_auth::UserPermissionLevel;
+
+/// 与 `/api/policy_type` 相同的资源定义
+struct Types;
+
+impl Resource for Types {
+    type Entity = policy_type::Entity;
+    type Model = policy_type::Model;
+    type ActiveModel = policy_type::ActiveModel;
+    type Proto = ProtoPolicyType;
+    type Request = PolicyTypeRequest;
+    type Response = PolicyTypeResponse;
+
+    const NAME: &'static str = "policy type";
+    const ID: policy_type::Column = policy_type::Column::Id;
+    const LISTING: Listing<Self::Entity> = Listing {
+        sort: &[policy_type::Column::Type],
+        search: &[policy_type::Column::Type],
+        filter: &[policy_type::Column::Type],
+        created: None,
+    };
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(t: Self::Model) -> Self::Proto {
+        ProtoPolicyType {
+            id: t.id,
+            r#type: t.r#type.unwrap_or_default(),
+        }
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        PolicyTypeResponse {
+            policy_types: page.items,
+            code: 200,
+            message,
+            total: page.total,
+            next_cursor: page.next_cursor,
+        }
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        patch(&mut active.r#type, payload.r#type);
+        Ok(())
+    }
+
+    fn validate_create(payload: &Self::Request) -> Result<(), ApiError> {
+        if payload.r#type.is_empty() {
+            return Err(ApiError::bad_request("Missing required parameter: type"));
+        }
+        Ok(())
+    }
+}
+
+/// 与 `Types` 相同的表，修改与删除按 `?type=` 查找，删除需要 types.manage 权限
+struct LockedTypes;
+
+impl Resource for LockedTypes {
+    type Entity = policy_type::Entity;
+    type Model = policy_type::Model;
+    type ActiveModel = policy_type::ActiveModel;
+    type Proto = ProtoPolicyType;
+    type Request = PolicyTypeRequest;
+    type Response = PolicyTypeResponse;
+
+    const NAME: &'static str = "policy type";
+    const ID: policy_type::Column = policy_type::Column::Id;
+    const WRITE: Access = Access::Level(UserPermissionLevel::Admin);
+
+    fn to_proto(t: Self::Model) -> Self::Proto {
+        Types::to_proto(t)
+    }
+
+    fn response(page: Page<Self::Proto>, message: String) -> Self::Response {
+        Types::response(page, message)
+    }
+
+    fn assign(payload: Self::Request, active: &mut Self::ActiveModel) -> Result<(), ApiError> {
+        Types::assign(payload, active)
+    }
+
+    async fn locate(
+        db: &DatabaseConnection,
+        params: &[(String, String)],
+    ) -> Result<Self::Model, ApiError> {
+        let name = required_param(params, "type")?;
+        policy_type::Entity::find()
+            .filter(policy_type::Column::Type.eq(name))
+            .one(db)
+            .await?
+            .ok_or_else(|| ApiError::not_found(format!("Policy type '{}' not found", name)))
+    }
+
+    async fn authorize_delete(
+        _db: &DatabaseConnection,
+        _user: &user_entity::Model,
+        permissions: &Permissions,
+        _model: &Self::Model,
+    ) -> Result<(), ApiError> {
+        if permissions.allows("types.manage") {
+            Ok(())
+        } else {
+            Err(ApiError::forbidden("Permission denied: types.manage"))
+        }
+    }
+}
+
+fn user_with_permission(permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id: permission,
+        open_id: format!("openid-{}", permission),
+        nickname: None,
+        avatar: None,
+        permission: Some(permission),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn keys() -> KeyRing {
+    KeyRing::single("test-secret").unwrap()
+}
+
+fn token_with_permission(permission: i32) -> String {
+    model2token(&keys(), &user_with_permission(permission))
+        .expect("token generation should succeed")
+}
+
+fn policy_type(id: i32, name: &str) -> policy_type::Model {
+    policy_type::Model {
+        id,
+        r#type: Some(name.to_string()),
+    }
+}
+
+/// 鉴权用户及其角色权限的查询结果
+fn authorized(db: MockDatabase, user: user_entity::Model, permissions: &[&str]) -> MockDatabase {
+    let granted: Vec<BTreeMap<String, Value>> = permissions
+        .iter()
+        .map(|p| BTreeMap::from([("permission".to_string(), Value::from(p.to_string()))]))
+        .collect();
+    db.append_query_results([vec![user]])
+        .append_query_results([Vec::<BTreeMap<String, Value>>::new()])
+        .append_query_results([Vec::<role::Model>::new()])
+        .append_query_results([granted])
+}
+
+/// 同一事务中写入审计日志：查询操作者的化名并插入一条记录
+fn audited(db: MockDatabase, user: &user_entity::Model) -> MockDatabase {
+    db.append_query_results([vec![audit_subject::Model {
+        id: 1,
+        open_id: user.open_id.clone(),
+        pseudonym: "p_0123456789ab".to_string(),
+        created_at: 0,
+    }]])
+    .append_query_results([vec![audit_log::Model {
+        id: 1,
+        actor: Some("p_0123456789ab".to_string()),
+        action: String::new(),
+        entity: "policy_type".to_string(),
+        entity_id: String::new(),
+        before: None,
+        after: None,
+        ip: None,
+        created_at: 0,
+    }]])
+}
+
+fn app(db: Arc<DatabaseConnection>) -> Router {
+    Router::new()
+        .nest("/types", resource_router::<Types>())
+        .nest("/locked", resource_router::<LockedTypes>())
+        .with_state(AppState {
+            database: db.clone(),
+        })
+        .layer(from_fn(api_errors))
+        .layer(Extension(db))
+        .layer(Extension(Arc::new(keys())))
+}
+
+/// 返回状态码、body 与执行过的 SQL
+async fn call_logged(
+    db: MockDatabase,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Vec<u8>,
+) -> (StatusCode, Vec<u8>, Vec<String>) {
+    let db: Arc<DatabaseConnection> = Arc::new(db.into_connection());
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, token);
+    }
+    let response = app(db.clone())
+        .oneshot(request.body(Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let log = Arc::try_unwrap(db)
+        .expect("router should be dropped")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|t| {
+            t.statements()
+                .iter()
+                .map(|s| s.sql.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (status, body.to_vec(), log)
+}
+
+async fn call(
+    db: MockDatabase,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Vec<u8>,
+) -> (StatusCode, Vec<u8>) {
+    let (status, body, _) = call_logged(db, method, uri, token, body).await;
+    (status, body)
+}
+
+fn envelope(body: &[u8]) -> ErrorResponse {
+    ErrorResponse::decode(body).expect("should be an ErrorResponse")
+}
+
+#[tokio::test]
+async fn list_is_public() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![policy_type(1, "elderly"), policy_type(2, "medical")]]);
+    let (status, body) = call(db, "GET", "/types", None, vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.code, 200);
+    assert_eq!(response.message, "Get policy type list success");
+    let names: Vec<_> = response
+        .policy_types
+        .iter()
+        .map(|t| t.r#type.as_str())
+        .collect();
+    assert_eq!(names, ["elderly", "medical"]);
+}
+
+#[tokio::test]
+async fn writes_require_access() {
+    let payload = PolicyTypeRequest {
+        r#type: "new".to_string(),
+    }
+    .encode_to_vec();
+
+    let db = MockDatabase::new(DbBackend::Postgres);
+    let (status, _) = call(db, "POST", "/types", None, payload.clone()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let provider = token_with_permission(2);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![user_with_permission(2)]]);
+    let (status, body) = call(db, "DELETE", "/types?id=1", Some(&provider), vec![]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(envelope(&body).code, 403);
+}
+
+#[tokio::test]
+async fn create_validates_and_inserts() {
+    let admin = token_with_permission(3);
+
+    // 缺少必填字段
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &[],
+    );
+    let (status, body) = call(db, "POST", "/types", Some(&admin), vec![]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(envelope(&body).message, "Missing required parameter: type");
+
+    // 插入并返回新记录，审计日志在同一事务中写入
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &[],
+    )
+    .append_query_results([vec![policy_type(5, "housing")]]);
+    let db = audited(db, &user_with_permission(3));
+    let payload = PolicyTypeRequest {
+        r#type: "housing".to_string(),
+    }
+    .encode_to_vec();
+    let (status, body, log) =
+        call_logged(db, "POST", "/types", Some(&admin), payload.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.message, "Insert policy type success");
+    assert_eq!(response.policy_types[0].id, 5);
+    assert_eq!(response.policy_types[0].r#type, "housing");
+    assert!(
+        log.iter()
+            .any(|sql| sql.starts_with(r#"INSERT INTO "public"."audit_log""#))
+    );
+
+    // 审计日志写入失败时整个请求失败，插入随事务回滚
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &[],
+    )
+    .append_query_results([vec![policy_type(5, "housing")]]);
+    let (status, body) = call(db, "POST", "/types", Some(&admin), payload).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(envelope(&body).code, 500);
+}
+
+#[tokio::test]
+async fn update_missing_record_is_not_found() {
+    let admin = token_with_permission(3);
+    // 鉴权用户、角色与权限，随后查找目标记录
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &["*"],
+    )
+    .append_query_results([Vec::<policy_type::Model>::new()]);
+    let (status, body) = call(db, "PUT", "/types?id=42", Some(&admin), vec![]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        envelope(&body).message,
+        "Policy type with id '42' not found"
+    );
+
+    for (uri, message) in [
+        ("/types", "Missing required parameter: id"),
+        ("/types?id=x", "Invalid value for id: x"),
+    ] {
+        let db = authorized(
+            MockDatabase::new(DbBackend::Postgres),
+            user_with_permission(3),
+            &["*"],
+        );
+        let (status, body) = call(db, "PUT", uri, Some(&admin), vec![]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(envelope(&body).message, message);
+    }
+}
+
+#[tokio::test]
+async fn delete_runs_row_level_check() {
+    let admin = token_with_permission(3);
+
+    // 按 locate 找到记录，但行级校验拒绝，不执行删除
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &[],
+    )
+    .append_query_results([vec![policy_type(4, "housing")]]);
+    let (status, body, log) =
+        call_logged(db, "DELETE", "/locked?type=housing", Some(&admin), vec![]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(envelope(&body).message, "Permission denied: types.manage");
+    assert!(log.last().unwrap().contains(r#""policy_type"."type" = $1"#));
+    assert!(!log.iter().any(|sql| sql.starts_with("DELETE")));
+
+    // 持有权限时删除找到的记录
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &["types.manage"],
+    )
+    .append_query_results([vec![policy_type(4, "housing")]])
+    .append_exec_results([MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }]);
+    let db = audited(db, &user_with_permission(3));
+    let (status, body, log) =
+        call_logged(db, "DELETE", "/locked?type=housing", Some(&admin), vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.message, "Delete policy type success");
+    assert!(
+        log.iter()
+            .any(|sql| sql.starts_with(r#"DELETE FROM "public"."policy_type""#))
+    );
+
+    // locate 所需的参数缺失
+    let db = authorized(
+        MockDatabase::new(DbBackend::Postgres),
+        user_with_permission(3),
+        &["types.manage"],
+    );
+    let (status, body) = call(db, "DELETE", "/locked", Some(&admin), vec![]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(envelope(&body).message, "Missing required parameter: type");
+}
+
+fn count(n: i64) -> BTreeMap<String, Value> {
+    BTreeMap::from([("num_items".to_string(), n.into())])
+}
+
+#[tokio::test]
+async fn list_pages_by_offset() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![count(3)]])
+        .append_query_results([vec![policy_type(3, "housing")]]);
+    let (status, body, log) = call_logged(
+        db,
+        "GET",
+        "/types?page=1&page_size=2&sort=-type&q=ous",
+        None,
+        vec![],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.total, 3);
+    assert!(response.next_cursor.is_empty());
+    assert_eq!(response.policy_types[0].id, 3);
+
+    assert!(log[0].contains("COUNT"));
+    assert!(log[1].contains(r#"ORDER BY "policy_type"."type" DESC, "policy_type"."id" ASC"#));
+    assert!(log[1].contains("LIKE"));
+    assert!(log[1].contains("LIMIT $2 OFFSET $3"));
+}
+
+#[tokio::test]
+async fn search_matches_wildcards_literally() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![count(0)]])
+        .append_query_results([Vec::<policy_type::Model>::new()])
+        .into_connection();
+    let query = ListQuery::from_pairs(vec![
+        ("page".to_string(), "1".to_string()),
+        ("q".to_string(), r"50%_off\".to_string()),
+    ])
+    .unwrap();
+    let page = fetch(
+        &db,
+        policy_type::Entity::find(),
+        policy_type::Column::Id,
+        &Types::LISTING,
+        &query,
+    )
+    .await
+    .unwrap();
+    assert!(page.items.is_empty());
+
+    // `%`、`_` 与 `\` 按字面值匹配，而不是作为通配符
+    let log = db.into_transaction_log();
+    let search = &log[1].statements()[0];
+    assert!(
+        search
+            .sql
+            .contains(r#""policy_type"."type" LIKE $1 ESCAPE E'\\'"#)
+    );
+    let pattern = search.values.as_ref().unwrap().0[0].clone();
+    assert_eq!(pattern, Value::from(r"%50\%\_off\\%"));
+}
+
+#[tokio::test]
+async fn list_pages_by_cursor() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![count(5)]])
+        .append_query_results([vec![
+            policy_type(3, "a"),
+            policy_type(4, "b"),
+            policy_type(5, "c"),
+        ]]);
+    let (status, body, log) = call_logged(
+        db,
+        "GET",
+        "/types?cursor=2&page_size=2&type=a",
+        None,
+        vec![],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.total, 5);
+    assert_eq!(response.policy_types.len(), 2);
+    assert_eq!(response.next_cursor, "4");
+    assert!(log[1].contains(r#""policy_type"."id" > $2"#));
+    assert!(log[1].contains(r#""policy_type"."type" = $1"#));
+}
+
+#[tokio::test]
+async fn list_rejects_unknown_fields() {
+    for uri in [
+        "/types?name=x",
+        "/types?sort=name",
+        "/types?since=0",
+        "/types?page=x",
+        // 页码乘以每页条数后溢出
+        "/types?page=18446744073709551615&page_size=200",
+        "/types?page=50000000000000000&page_size=200",
+        "/types?cursor=1&sort=type",
+    ] {
+        let db = MockDatabase::new(DbBackend::Postgres);
+        let (status, body) = call(db, "GET", uri, None, vec![]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(envelope(&body).code, 400);
+    }
+}
+
+#[tokio::test]
+async fn get_by_id() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![policy_type(7, "elderly")]]);
+    let (status, body) = call(db, "GET", "/types/7", None, vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    let response = PolicyTypeResponse::decode(body.as_slice()).unwrap();
+    assert_eq!(response.message, "Get policy type success");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.policy_types[0].r#type, "elderly");
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<policy_type::Model>::new()]);
+    let (status, _) = call(db, "GET", "/types/8", None, vec![]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,16 @@
+//! 管理操作审计
+//!
+//! 所有修改数据的接口都会在 `audit_log` 中追加一条记录：操作者、动作、表名、主键、
+//! 变更前后的字段（更新只保留发生变化的字段）、IP 与时间。该表只允许追加，
+//! 数据库触发器会拒绝对它的修改与删除。个人信息与凭据类字段在写入前脱敏，
+//! open_id 替换为 `audit_subject` 中的化名。
+
+pub mod record;
+pub mod snapshot;
+pub mod r#struct;
+pub mod subject;
+
+pub use record::*;
+pub use snapshot::*;
+pub use r#struct::*;
+pub use subject::*;
@@ -0,0 +1,127 @@
+use super::{
+    AuditActor, AuditChange, AuditEntry, AuditError, AuditQuery, audit_entry, find_pseudonym,
+    pseudonym_of, pseudonymize, reveal_actors,
+};
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::audit_log;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, JsonValue, ModelTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Set,
+};
+
+/// Append a prepared entry to the audit log; the actor and open_id fields are
+/// written as pseudonyms.
+pub async fn record_audit_entry<C>(
+    db: &C,
+    actor: &AuditActor,
+    mut entry: AuditEntry,
+) -> Result<audit_log::Model, AuditError>
+where
+    C: ConnectionTrait,
+{
+    for fields in [&mut entry.before, &mut entry.after].into_iter().flatten() {
+        if let JsonValue::Object(fields) = fields {
+            pseudonymize(db, &entry.entity, fields).await?;
+        }
+    }
+
+    let pseudonym = match &actor.open_id {
+        Some(open_id) => Some(pseudonym_of(db, open_id).await?),
+        None => None,
+    };
+
+    Ok(audit_log::ActiveModel {
+        actor: Set(pseudonym),
+        action: Set(entry.action.as_str().to_string()),
+        entity: Set(entry.entity),
+        entity_id: Set(entry.entity_id),
+        before: Set(entry.before),
+        after: Set(entry.after),
+        ip: Set(actor.ip.clone()),
+        created_at: Set(now_timestamp() as i64),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?)
+}
+
+/// Record a change to a single row; updates that changed nothing are skipped.
+pub async fn record_audit<C, M>(
+    db: &C,
+    actor: &AuditActor,
+    change: AuditChange<'_, M>,
+) -> Result<Option<audit_log::Model>, AuditError>
+where
+    C: ConnectionTrait,
+    M: ModelTrait,
+{
+    match audit_entry(change) {
+        Some(entry) => Ok(Some(record_audit_entry(db, actor, entry).await?)),
+        None => Ok(None),
+    }
+}
+
+async fn filtered<C>(db: &C, query: &AuditQuery) -> Result<Select<audit_log::Entity>, AuditError>
+where
+    C: ConnectionTrait,
+{
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since > until
+    {
+        return Err(AuditError::InvalidTimeRange);
+    }
+
+    let mut select = audit_log::Entity::find();
+    // 记录中只有化名，没有化名的操作者不会有任何记录
+    if let Some(actor) = &query.actor_open_id {
+        let pseudonym = find_pseudonym(db, actor).await?;
+        select = select.filter(audit_log::Column::Actor.is_in(pseudonym));
+    }
+    if let Some(action) = query.action {
+        select = select.filter(audit_log::Column::Action.eq(action.as_str()));
+    }
+    if let Some(entity) = &query.entity {
+        select = select.filter(audit_log::Column::Entity.eq(entity.as_str()));
+    }
+    if let Some(entity_id) = &query.entity_id {
+        select = select.filter(audit_log::Column::EntityId.eq(entity_id.as_str()));
+    }
+    if let Some(since) = query.since {
+        select = select.filter(audit_log::Column::CreatedAt.gte(since));
+    }
+    if let Some(until) = query.until {
+        select = select.filter(audit_log::Column::CreatedAt.lte(until));
+    }
+    Ok(select
+        .order_by_desc(audit_log::Column::CreatedAt)
+        .order_by_desc(audit_log::Column::Id))
+}
+
+/// One page (0-based) of matching entries, newest first, with the total count.
+pub async fn list_audit_logs<C>(
+    db: &C,
+    query: &AuditQuery,
+    page: u64,
+    page_size: u64,
+) -> Result<(Vec<audit_log::Model>, u64), AuditError>
+where
+    C: ConnectionTrait,
+{
+    let paginator = filtered(db, query).await?.paginate(db, page_size);
+    let total = paginator.num_items().await?;
+    let logs = paginator.fetch_page(page).await?;
+    Ok((reveal_actors(db, logs).await?, total))
+}
+
+/// Up to `limit` matching entries, newest first.
+pub async fn export_audit_logs<C>(
+    db: &C,
+    query: &AuditQuery,
+    limit: u64,
+) -> Result<Vec<audit_log::Model>, AuditError>
+where
+    C: ConnectionTrait,
+{
+    let logs = filtered(db, query).await?.limit(limit).all(db).await?;
+    reveal_actors(db, logs).await
+}
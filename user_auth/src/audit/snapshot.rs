@@ -0,0 +1,140 @@
+use super::{AuditAction, AuditChange, AuditEntry, REDACTED};
+use sea_orm::sea_query::value::sea_value_to_json_value;
+use sea_orm::{
+    EntityName, EntityTrait, IdenStatic, Iterable, JsonValue, ModelTrait, PrimaryKeyToColumn, Value,
+};
+use serde_json::Map;
+
+/// 写入审计记录前脱敏的字段（表名，列名）
+pub const REDACTED_FIELDS: &[(&str, &str)] = &[
+    ("user", "nickname"),
+    ("user", "avatar"),
+    ("user", "name"),
+    ("user", "phone_number"),
+    ("user", "address"),
+    ("ai_chat", "long_content"),
+    ("feedback", "content"),
+    ("feedback", "phone"),
+    ("invitation_code", "code"),
+    ("local_credential", "password_hash"),
+    ("admin_totp", "secret"),
+    ("refresh_token", "token_hash"),
+    ("totp_recovery_code", "code_hash"),
+    ("wx_session", "session_key"),
+];
+
+/// 写入审计记录时替换为化名的 open_id 字段（表名，列名），见 [`super::pseudonym_of`]
+pub const PSEUDONYMIZED_FIELDS: &[(&str, &str)] = &[
+    ("user", "open_id"),
+    ("feedback", "openid"),
+    ("ai_chat", "openid"),
+];
+
+fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        // 二进制内容（如图片）只记录长度
+        Value::Bytes(Some(bytes)) => JsonValue::String(format!("<{} bytes>", bytes.len())),
+        other => sea_value_to_json_value(other),
+    }
+}
+
+/// All columns of a model as a JSON object, without redaction.
+pub fn model_to_json<M>(model: &M) -> Map<String, JsonValue>
+where
+    M: ModelTrait,
+{
+    <M::Entity as EntityTrait>::Column::iter()
+        .map(|col| (col.as_str().to_string(), value_to_json(&model.get(col))))
+        .collect()
+}
+
+/// Primary key of a model; composite keys are joined with `,`.
+pub fn primary_key_of<M>(model: &M) -> String
+where
+    M: ModelTrait,
+{
+    <M::Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|pk| match value_to_json(&model.get(pk.into_column())) {
+            JsonValue::String(s) => s,
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Replace the values of sensitive fields of `table` in place.
+pub fn redact(table: &str, fields: &mut Map<String, JsonValue>) {
+    for (_, column) in REDACTED_FIELDS.iter().filter(|(t, _)| *t == table) {
+        if let Some(value) = fields.get_mut(*column)
+            && !value.is_null()
+        {
+            *value = JsonValue::String(REDACTED.to_string());
+        }
+    }
+}
+
+/// Build an entry from the state of a row before and after a change.
+///
+/// Objects are compared key by key so an update keeps only the changed fields;
+/// returns `None` when nothing changed.
+pub fn build_entry(
+    entity: &str,
+    entity_id: String,
+    mut before: Option<JsonValue>,
+    mut after: Option<JsonValue>,
+) -> Option<AuditEntry> {
+    // 1) 由变更前后是否存在推断动作
+    let action = match (&before, &after) {
+        (None, None) => return None,
+        (None, Some(_)) => AuditAction::Insert,
+        (Some(_), None) => AuditAction::Delete,
+        (Some(b), Some(a)) if b == a => return None,
+        (Some(_), Some(_)) => AuditAction::Update,
+    };
+
+    // 2) 更新只保留发生变化的字段，需在脱敏前比较
+    if let (Some(JsonValue::Object(b)), Some(JsonValue::Object(a))) = (&mut before, &mut after) {
+        let keys: Vec<String> = a.keys().chain(b.keys()).cloned().collect();
+        for key in keys {
+            if a.get(&key) == b.get(&key) {
+                a.remove(&key);
+                b.remove(&key);
+            }
+        }
+    }
+
+    // 3) 脱敏
+    for fields in [&mut before, &mut after].into_iter().flatten() {
+        if let JsonValue::Object(fields) = fields {
+            redact(entity, fields);
+        }
+    }
+
+    Some(AuditEntry {
+        action,
+        entity: entity.to_string(),
+        entity_id,
+        before,
+        after,
+    })
+}
+
+/// Build the entry for a change to a single model, or `None` for an update that changed nothing.
+pub fn audit_entry<M>(change: AuditChange<'_, M>) -> Option<AuditEntry>
+where
+    M: ModelTrait,
+{
+    let table = M::Entity::default().table_name().to_string();
+    let (model, before, after) = match change {
+        AuditChange::Insert(m) => (m, None, Some(m)),
+        AuditChange::Update(b, a) => (a, Some(b), Some(a)),
+        AuditChange::Delete(m) => (m, Some(m), None),
+    };
+    let snapshot = |m: &M| JsonValue::Object(model_to_json(m));
+    build_entry(
+        &table,
+        primary_key_of(model),
+        before.map(snapshot),
+        after.map(snapshot),
+    )
+}
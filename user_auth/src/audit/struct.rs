@@ -0,0 +1,97 @@
+use sea_orm::JsonValue;
+
+/// 查询接口默认每页条数
+pub const DEFAULT_AUDIT_PAGE_SIZE: u64 = 20;
+/// 查询接口每页条数上限
+pub const MAX_AUDIT_PAGE_SIZE: u64 = 200;
+/// 单次导出的最大条数
+pub const MAX_AUDIT_EXPORT_ROWS: u64 = 10_000;
+/// 脱敏字段写入的占位值
+pub const REDACTED: &str = "[redacted]";
+
+/// 审计记录中的操作者
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditActor {
+    /// 命令行或系统任务为空
+    pub open_id: Option<String>,
+    pub ip: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Insert => "insert",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "insert" => Some(AuditAction::Insert),
+            "update" => Some(AuditAction::Update),
+            "delete" => Some(AuditAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// 一次数据变更
+#[derive(Debug, Clone, Copy)]
+pub enum AuditChange<'a, M> {
+    Insert(&'a M),
+    Update(&'a M, &'a M),
+    Delete(&'a M),
+}
+
+/// 待写入的审计记录，`before` / `after` 已完成比较与脱敏
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub action: AuditAction,
+    pub entity: String,
+    pub entity_id: String,
+    pub before: Option<JsonValue>,
+    pub after: Option<JsonValue>,
+}
+
+/// 审计记录的筛选条件，均为可选
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub actor_open_id: Option<String>,
+    pub action: Option<AuditAction>,
+    pub entity: Option<String>,
+    pub entity_id: Option<String>,
+    /// 起始时间（unix 秒，含）
+    pub since: Option<i64>,
+    /// 结束时间（unix 秒，含）
+    pub until: Option<i64>,
+}
+
+#[derive(Debug)]
+pub enum AuditError {
+    InvalidTimeRange,
+    DatabaseError(String),
+}
+
+impl AuditError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            AuditError::InvalidTimeRange => (400, "Invalid time range".to_string()),
+            AuditError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for AuditError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        AuditError::DatabaseError(err.to_string())
+    }
+}
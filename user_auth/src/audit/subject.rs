@@ -0,0 +1,101 @@
+use super::{AuditError, PSEUDONYMIZED_FIELDS};
+use crate::db_exchange::{now_timestamp, random_hex};
+use db_manager::entity::{audit_log, audit_subject};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, JsonValue, QueryFilter, Set};
+use serde_json::Map;
+use std::collections::HashMap;
+
+/// Existing pseudonym of `open_id`; `None` if nothing was ever recorded for it
+/// or the account has been purged.
+pub async fn find_pseudonym<C>(db: &C, open_id: &str) -> Result<Option<String>, AuditError>
+where
+    C: ConnectionTrait,
+{
+    Ok(audit_subject::Entity::find()
+        .filter(audit_subject::Column::OpenId.eq(open_id))
+        .one(db)
+        .await?
+        .map(|subject| subject.pseudonym))
+}
+
+/// Pseudonym standing in for `open_id` in the audit log, created on first use.
+pub async fn pseudonym_of<C>(db: &C, open_id: &str) -> Result<String, AuditError>
+where
+    C: ConnectionTrait,
+{
+    if let Some(pseudonym) = find_pseudonym(db, open_id).await? {
+        return Ok(pseudonym);
+    }
+
+    // 并发写入时 open_id 冲突什么也不做，再读一次即可
+    audit_subject::Entity::insert(audit_subject::ActiveModel {
+        open_id: Set(open_id.to_string()),
+        pseudonym: Set(format!("p_{}", random_hex(12))),
+        created_at: Set(now_timestamp() as i64),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::column(audit_subject::Column::OpenId)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    find_pseudonym(db, open_id)
+        .await?
+        .ok_or_else(|| AuditError::DatabaseError("Audit subject not found".to_string()))
+}
+
+/// Replace the open_id fields of `table` with their pseudonyms in place.
+pub async fn pseudonymize<C>(
+    db: &C,
+    table: &str,
+    fields: &mut Map<String, JsonValue>,
+) -> Result<(), AuditError>
+where
+    C: ConnectionTrait,
+{
+    for (_, column) in PSEUDONYMIZED_FIELDS.iter().filter(|(t, _)| *t == table) {
+        if let Some(value) = fields.get_mut(*column)
+            && let JsonValue::String(open_id) = value
+        {
+            *value = JsonValue::String(pseudonym_of(db, open_id).await?);
+        }
+    }
+    Ok(())
+}
+
+/// Show the actor of each entry as its open_id again while the pseudonym is
+/// still mapped; actors of purged accounts stay pseudonymous.
+pub async fn reveal_actors<C>(
+    db: &C,
+    mut logs: Vec<audit_log::Model>,
+) -> Result<Vec<audit_log::Model>, AuditError>
+where
+    C: ConnectionTrait,
+{
+    let mut pseudonyms: Vec<String> = logs.iter().filter_map(|log| log.actor.clone()).collect();
+    pseudonyms.sort();
+    pseudonyms.dedup();
+    if pseudonyms.is_empty() {
+        return Ok(logs);
+    }
+
+    let open_ids: HashMap<String, String> = audit_subject::Entity::find()
+        .filter(audit_subject::Column::Pseudonym.is_in(pseudonyms))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|subject| (subject.pseudonym, subject.open_id))
+        .collect();
+    for log in &mut logs {
+        if let Some(actor) = &mut log.actor
+            && let Some(open_id) = open_ids.get(actor)
+        {
+            *actor = open_id.clone();
+        }
+    }
+    Ok(logs)
+}
@@ -0,0 +1,91 @@
+use super::{CaregiverError, CaregiverStatus};
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::{caregiver_action_log, caregiver_link, user as user_entity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+/// 代为操作的目标用户
+#[derive(Debug, Clone)]
+pub enum Delegation {
+    /// 操作自己
+    Own,
+    /// 作为照护人操作被照护人
+    Caregiver(caregiver_link::Model),
+    /// 与目标用户没有关联，由调用方按权限等级决定是否允许
+    Unrelated,
+}
+
+/// Active link between a caregiver and an elder, if any.
+pub async fn find_active_link<C>(
+    db: &C,
+    caregiver_id: i32,
+    elder_id: i32,
+) -> Result<Option<caregiver_link::Model>, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    Ok(caregiver_link::Entity::find()
+        .filter(caregiver_link::Column::CaregiverId.eq(caregiver_id))
+        .filter(caregiver_link::Column::ElderId.eq(elder_id))
+        .filter(caregiver_link::Column::Status.eq(CaregiverStatus::Active.as_str()))
+        .one(db)
+        .await?)
+}
+
+/// Decide how `actor` relates to `target` for per-user endpoints.
+pub async fn resolve_delegation<C>(
+    db: &C,
+    actor: &user_entity::Model,
+    target: &user_entity::Model,
+) -> Result<Delegation, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    if actor.id == target.id {
+        return Ok(Delegation::Own);
+    }
+    Ok(match find_active_link(db, actor.id, target.id).await? {
+        Some(link) => Delegation::Caregiver(link),
+        None => Delegation::Unrelated,
+    })
+}
+
+/// Record an action a caregiver took for an elder.
+pub async fn record_caregiver_action<C>(
+    db: &C,
+    caregiver_id: Option<i32>,
+    elder_id: i32,
+    action: &str,
+    detail: Option<String>,
+) -> Result<caregiver_action_log::Model, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    Ok(caregiver_action_log::ActiveModel {
+        caregiver_id: Set(caregiver_id),
+        elder_id: Set(elder_id),
+        action: Set(action.to_string()),
+        detail: Set(detail),
+        created_at: Set(now_timestamp() as i64),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?)
+}
+
+/// Every recorded action for an elder, newest first.
+pub async fn list_caregiver_actions<C>(
+    db: &C,
+    elder_id: i32,
+) -> Result<Vec<caregiver_action_log::Model>, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    Ok(caregiver_action_log::Entity::find()
+        .filter(caregiver_action_log::Column::ElderId.eq(elder_id))
+        .order_by_desc(caregiver_action_log::Column::CreatedAt)
+        .order_by_desc(caregiver_action_log::Column::Id)
+        .all(db)
+        .await?)
+}
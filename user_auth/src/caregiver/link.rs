@@ -0,0 +1,252 @@
+use super::{CaregiverError, CaregiverStatus, action, record_caregiver_action};
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::{caregiver_link, user as user_entity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+
+async fn find_link<C>(
+    db: &C,
+    caregiver_id: i32,
+    elder_id: i32,
+) -> Result<Option<caregiver_link::Model>, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    Ok(caregiver_link::Entity::find()
+        .filter(caregiver_link::Column::CaregiverId.eq(caregiver_id))
+        .filter(caregiver_link::Column::ElderId.eq(elder_id))
+        .one(db)
+        .await?)
+}
+
+/// Ask to become the caregiver of `elder`; the link stays pending until the
+/// elder accepts it or an admin approves it.
+///
+/// A rejected or revoked link can be requested again.
+pub async fn request_link<C>(
+    db: &C,
+    caregiver: &user_entity::Model,
+    elder: &user_entity::Model,
+    relationship: Option<String>,
+) -> Result<caregiver_link::Model, CaregiverError>
+where
+    C: TransactionTrait,
+{
+    if caregiver.id == elder.id {
+        return Err(CaregiverError::SelfLink);
+    }
+    let txn = db.begin().await?;
+    let now = now_timestamp() as i64;
+    let link = match find_link(&txn, caregiver.id, elder.id).await? {
+        Some(existing)
+            if matches!(
+                CaregiverStatus::of(&existing),
+                Some(CaregiverStatus::Active | CaregiverStatus::Pending)
+            ) =>
+        {
+            return Err(CaregiverError::AlreadyLinked);
+        }
+        Some(existing) => {
+            let mut active: caregiver_link::ActiveModel = existing.into();
+            active.status = Set(CaregiverStatus::Pending.as_str().to_string());
+            active.relationship = Set(relationship);
+            active.requested_by = Set(Some(caregiver.id));
+            active.approved_by = Set(None);
+            active.created_at = Set(now);
+            active.approved_at = Set(None);
+            active.revoked_at = Set(None);
+            active.update(&txn).await?
+        }
+        None => {
+            caregiver_link::ActiveModel {
+                caregiver_id: Set(caregiver.id),
+                elder_id: Set(elder.id),
+                status: Set(CaregiverStatus::Pending.as_str().to_string()),
+                relationship: Set(relationship),
+                requested_by: Set(Some(caregiver.id)),
+                approved_by: Set(None),
+                created_at: Set(now),
+                approved_at: Set(None),
+                revoked_at: Set(None),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?
+        }
+    };
+    record_caregiver_action(
+        &txn,
+        Some(caregiver.id),
+        elder.id,
+        action::LINK_REQUEST,
+        link.relationship.clone(),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// The elder accepts or rejects a pending request.
+pub async fn respond_link<C>(
+    db: &C,
+    elder: &user_entity::Model,
+    caregiver_id: i32,
+    accept: bool,
+) -> Result<caregiver_link::Model, CaregiverError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let link = find_link(&txn, caregiver_id, elder.id)
+        .await?
+        .ok_or(CaregiverError::LinkNotFound)?;
+    if CaregiverStatus::of(&link) != Some(CaregiverStatus::Pending) {
+        return Err(CaregiverError::NotPending);
+    }
+
+    let mut active: caregiver_link::ActiveModel = link.into();
+    if accept {
+        active.status = Set(CaregiverStatus::Active.as_str().to_string());
+        active.approved_by = Set(Some(elder.id));
+        active.approved_at = Set(Some(now_timestamp() as i64));
+    } else {
+        active.status = Set(CaregiverStatus::Rejected.as_str().to_string());
+    }
+    let link = active.update(&txn).await?;
+    record_caregiver_action(
+        &txn,
+        Some(caregiver_id),
+        elder.id,
+        if accept {
+            action::LINK_ACCEPT
+        } else {
+            action::LINK_REJECT
+        },
+        Some(format!("by elder {}", elder.open_id)),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// An admin activates a link directly, for elders who cannot consent in the app.
+pub async fn approve_link<C>(
+    db: &C,
+    admin: &user_entity::Model,
+    caregiver: &user_entity::Model,
+    elder: &user_entity::Model,
+    relationship: Option<String>,
+) -> Result<caregiver_link::Model, CaregiverError>
+where
+    C: TransactionTrait,
+{
+    if caregiver.id == elder.id {
+        return Err(CaregiverError::SelfLink);
+    }
+    let txn = db.begin().await?;
+    let now = now_timestamp() as i64;
+    let link = match find_link(&txn, caregiver.id, elder.id).await? {
+        Some(existing) if CaregiverStatus::of(&existing) == Some(CaregiverStatus::Active) => {
+            return Err(CaregiverError::AlreadyLinked);
+        }
+        Some(existing) => {
+            let mut active: caregiver_link::ActiveModel = existing.into();
+            active.status = Set(CaregiverStatus::Active.as_str().to_string());
+            if relationship.is_some() {
+                active.relationship = Set(relationship);
+            }
+            active.approved_by = Set(Some(admin.id));
+            active.approved_at = Set(Some(now));
+            active.revoked_at = Set(None);
+            active.update(&txn).await?
+        }
+        None => {
+            caregiver_link::ActiveModel {
+                caregiver_id: Set(caregiver.id),
+                elder_id: Set(elder.id),
+                status: Set(CaregiverStatus::Active.as_str().to_string()),
+                relationship: Set(relationship),
+                requested_by: Set(Some(admin.id)),
+                approved_by: Set(Some(admin.id)),
+                created_at: Set(now),
+                approved_at: Set(Some(now)),
+                revoked_at: Set(None),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?
+        }
+    };
+    record_caregiver_action(
+        &txn,
+        Some(caregiver.id),
+        elder.id,
+        action::LINK_APPROVE,
+        Some(format!("by admin {}", admin.open_id)),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// End a link. Either party or an admin (`is_admin`) may revoke it.
+pub async fn revoke_link<C>(
+    db: &C,
+    actor: &user_entity::Model,
+    is_admin: bool,
+    caregiver_id: i32,
+    elder_id: i32,
+) -> Result<caregiver_link::Model, CaregiverError>
+where
+    C: TransactionTrait,
+{
+    if !is_admin && actor.id != caregiver_id && actor.id != elder_id {
+        return Err(CaregiverError::NotParticipant);
+    }
+    let txn = db.begin().await?;
+    let link = find_link(&txn, caregiver_id, elder_id)
+        .await?
+        .filter(|l| {
+            matches!(
+                CaregiverStatus::of(l),
+                Some(CaregiverStatus::Active | CaregiverStatus::Pending)
+            )
+        })
+        .ok_or(CaregiverError::LinkNotFound)?;
+
+    let mut active: caregiver_link::ActiveModel = link.into();
+    active.status = Set(CaregiverStatus::Revoked.as_str().to_string());
+    active.revoked_at = Set(Some(now_timestamp() as i64));
+    let link = active.update(&txn).await?;
+    record_caregiver_action(
+        &txn,
+        Some(caregiver_id),
+        elder_id,
+        action::LINK_REVOKE,
+        Some(format!("by {}", actor.open_id)),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// Links where the user is either the caregiver or the elder.
+pub async fn list_links<C>(
+    db: &C,
+    user_id: i32,
+) -> Result<Vec<caregiver_link::Model>, CaregiverError>
+where
+    C: ConnectionTrait,
+{
+    Ok(caregiver_link::Entity::find()
+        .filter(
+            caregiver_link::Column::CaregiverId
+                .eq(user_id)
+                .or(caregiver_link::Column::ElderId.eq(user_id)),
+        )
+        .order_by_asc(caregiver_link::Column::Id)
+        .all(db)
+        .await?)
+}
@@ -0,0 +1,14 @@
+//! 照护人关联
+//!
+//! 许多高龄用户（`is_important`）无法自己使用小程序，由家属或护工代为操作。
+//! 照护人发起关联后需被照护人本人同意，或由 Admin 直接审批；关联生效后照护人可以
+//! 查看、修改被照护人的资料并代其使用服务。照护人的每一次操作（包括关联的变更）
+//! 都会写入 `caregiver_action_log`。
+
+pub mod delegation;
+pub mod link;
+pub mod r#struct;
+
+pub use delegation::*;
+pub use link::*;
+pub use r#struct::*;
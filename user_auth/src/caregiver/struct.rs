@@ -0,0 +1,84 @@
+use db_manager::entity::caregiver_link;
+
+/// 关联状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaregiverStatus {
+    /// 等待被照护人同意或 Admin 审批
+    Pending,
+    Active,
+    Rejected,
+    Revoked,
+}
+
+impl CaregiverStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaregiverStatus::Pending => "pending",
+            CaregiverStatus::Active => "active",
+            CaregiverStatus::Rejected => "rejected",
+            CaregiverStatus::Revoked => "revoked",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(CaregiverStatus::Pending),
+            "active" => Some(CaregiverStatus::Active),
+            "rejected" => Some(CaregiverStatus::Rejected),
+            "revoked" => Some(CaregiverStatus::Revoked),
+            _ => None,
+        }
+    }
+
+    pub fn of(link: &caregiver_link::Model) -> Option<Self> {
+        Self::parse(&link.status)
+    }
+}
+
+/// 写入操作记录的动作名
+pub mod action {
+    pub const LINK_REQUEST: &str = "link.request";
+    pub const LINK_ACCEPT: &str = "link.accept";
+    pub const LINK_REJECT: &str = "link.reject";
+    pub const LINK_APPROVE: &str = "link.approve";
+    pub const LINK_REVOKE: &str = "link.revoke";
+    pub const USER_INFO: &str = "user.info";
+    pub const USER_MODIFY: &str = "user.modify";
+    pub const AI_CHAT_INSERT: &str = "ai_chat.insert";
+}
+
+#[derive(Debug)]
+pub enum CaregiverError {
+    /// 不能关联自己
+    SelfLink,
+    LinkNotFound,
+    AlreadyLinked,
+    /// 关联不处于待处理状态
+    NotPending,
+    /// 既不是关联的任一方，也不是 Admin
+    NotParticipant,
+    DatabaseError(String),
+}
+
+impl CaregiverError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            CaregiverError::SelfLink => (400, "Cannot link to yourself".to_string()),
+            CaregiverError::LinkNotFound => (404, "Caregiver link not found".to_string()),
+            CaregiverError::AlreadyLinked => (409, "Caregiver link already exists".to_string()),
+            CaregiverError::NotPending => (409, "Caregiver link is not pending".to_string()),
+            CaregiverError::NotParticipant => (
+                403,
+                "Permission denied: not part of this caregiver link".to_string(),
+            ),
+            CaregiverError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for CaregiverError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        CaregiverError::DatabaseError(err.to_string())
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use hmac::{Hmac, Mac, digest::KeyInit};
+use jwt::{AlgorithmType, Header, SignWithKey, Token, VerifyWithStore, header::HeaderType};
+use serde::{Serialize, de::DeserializeOwned};
+use sha2::Sha256;
+
+use super::{ExchangeError, now_timestamp};
+
+/// 只配置单个 JWT 密钥（`SERVER_JWT_SECRET`）时使用的 kid
+pub const DEFAULT_KEY_ID: &str = "default";
+
+/// 签名用途，每种用途使用由主密钥派生的独立密钥，一种 token 无法被当作另一种使用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyPurpose {
+    /// 访问 token
+    Session,
+    /// Admin 二次验证后的 step-up token
+    StepUp,
+}
+
+impl KeyPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyPurpose::Session => "session",
+            KeyPurpose::StepUp => "step_up",
+        }
+    }
+}
+
+/// 一把命名的签名密钥
+#[derive(Clone)]
+pub struct SigningKey {
+    pub kid: String,
+    secret: Vec<u8>,
+    /// unix 秒，到期后该密钥签发的 token 不再被接受
+    pub retire_at: Option<u64>,
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("kid", &self.kid)
+            .field("retire_at", &self.retire_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SigningKey {
+    pub fn new(kid: impl Into<String>, secret: impl Into<Vec<u8>>, retire_at: Option<u64>) -> Self {
+        SigningKey {
+            kid: kid.into(),
+            secret: secret.into(),
+            retire_at,
+        }
+    }
+
+    pub fn is_retired(&self, now: u64) -> bool {
+        self.retire_at.is_some_and(|at| at <= now)
+    }
+
+    /// 主密钥的原始字节，仅用于派生其他密钥（如 cookie 密钥）
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// 派生出某一用途的 HMAC 密钥：HMAC-SHA256(secret, "sd_backend jwt:<purpose>")
+    fn derive(&self, purpose: KeyPurpose) -> Result<Hmac<Sha256>, ExchangeError> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.secret)
+            .map_err(|e| ExchangeError::OtherError(e.to_string()))?;
+        mac.update(b"sd_backend jwt:");
+        mac.update(purpose.as_str().as_bytes());
+        let derived = mac.finalize().into_bytes();
+        <Hmac<Sha256> as KeyInit>::new_from_slice(&derived)
+            .map_err(|e| ExchangeError::OtherError(e.to_string()))
+    }
+}
+
+/// JWT 密钥环
+///
+/// 使用当前密钥签名并在头部写入 `kid`，校验时接受所有未退役的密钥，
+/// 因此轮换密钥时已签发的 token 在旧密钥退役前仍然有效。
+#[derive(Debug, Clone)]
+pub struct KeyRing {
+    active: String,
+    keys: Vec<SigningKey>,
+}
+
+impl KeyRing {
+    /// `active` 必须在 `keys` 中且未退役，kid 不能重复
+    pub fn new(active: impl Into<String>, keys: Vec<SigningKey>) -> Result<Self, ExchangeError> {
+        let active = active.into();
+        let mut seen = std::collections::BTreeSet::new();
+        if let Some(dup) = keys.iter().find(|k| !seen.insert(k.kid.as_str())) {
+            return Err(ExchangeError::OtherError(format!(
+                "duplicate jwt key id: {}",
+                dup.kid
+            )));
+        }
+        if let Some(key) = keys.iter().find(|k| k.secret.is_empty()) {
+            return Err(ExchangeError::OtherError(format!(
+                "jwt key {} has an empty secret",
+                key.kid
+            )));
+        }
+        match keys.iter().find(|k| k.kid == active) {
+            None => Err(ExchangeError::OtherError(format!(
+                "active jwt key {} is not configured",
+                active
+            ))),
+            Some(key) if key.is_retired(now_timestamp()) => Err(ExchangeError::OtherError(
+                format!("active jwt key {} is retired", active),
+            )),
+            Some(_) => Ok(KeyRing { active, keys }),
+        }
+    }
+
+    /// 只有一个密钥的密钥环，kid 为 `default`
+    pub fn single(secret: impl Into<Vec<u8>>) -> Result<Self, ExchangeError> {
+        KeyRing::new(
+            DEFAULT_KEY_ID,
+            vec![SigningKey::new(DEFAULT_KEY_ID, secret, None)],
+        )
+    }
+
+    /// 当前签名用的密钥
+    pub fn active_key(&self) -> &SigningKey {
+        self.keys
+            .iter()
+            .find(|k| k.kid == self.active)
+            .expect("active key is validated in KeyRing::new")
+    }
+
+    pub fn keys(&self) -> &[SigningKey] {
+        &self.keys
+    }
+
+    /// 用当前密钥为 `purpose` 签名，头部带上 `kid`
+    pub fn sign<C: Serialize>(
+        &self,
+        purpose: KeyPurpose,
+        claims: &C,
+    ) -> Result<String, ExchangeError> {
+        let key = self.active_key();
+        let header = Header {
+            algorithm: AlgorithmType::Hs256,
+            key_id: Some(key.kid.clone()),
+            type_: Some(HeaderType::JsonWebToken),
+            ..Default::default()
+        };
+        Token::new(header, claims)
+            .sign_with_key(&key.derive(purpose)?)
+            .map(|token| token.as_str().to_string())
+            .map_err(|e| ExchangeError::TokenGenerationError(e.to_string()))
+    }
+
+    /// 按头部的 `kid` 找到未退役的密钥并校验签名，不检查过期时间
+    pub fn verify<C: DeserializeOwned>(
+        &self,
+        purpose: KeyPurpose,
+        token: &str,
+    ) -> Result<C, ExchangeError> {
+        let now = now_timestamp();
+        let mut store = BTreeMap::new();
+        for key in self.keys.iter().filter(|k| !k.is_retired(now)) {
+            store.insert(key.kid.clone(), key.derive(purpose)?);
+        }
+        token
+            .verify_with_store(&store)
+            .map_err(|_| ExchangeError::InvalidToken)
+    }
+}
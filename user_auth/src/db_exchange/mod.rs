@@ -1,10 +1,16 @@
+use crate::user_status::UserStatusError;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+pub mod key_ring;
+pub mod refresh_token;
+pub mod session;
+pub mod sign_in;
 pub mod token2user;
 pub mod user2token;
 
-pub const EXPIRATION_TIME: u64 = 2592000; // 30 day
+pub const EXPIRATION_TIME: u64 = 900; // 15 min, access token
+pub const REFRESH_EXPIRATION_TIME: u64 = 2592000; // 30 day, refresh token
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -22,6 +28,9 @@ pub struct User {
 pub struct Claims {
     pub sub: String,
     pub exp: u64,
+    /// 签发时用户的 token 版本号，与数据库中的 `token_version` 不一致即视为已吊销
+    #[serde(default)]
+    pub ver: i32,
     pub user: User,
 }
 
@@ -30,13 +39,13 @@ pub enum ExchangeError {
     TokenGenerationError(String),
     InvalidToken,
     TokenExpired,
+    TokenRevoked,
+    RefreshTokenReused,
+    AccountBlocked(UserStatusError),
+    DatabaseError(String),
     OtherError(String),
 }
 
-pub fn jwt_secret_from_env() -> Result<String, ExchangeError> {
-    std::env::var("SERVER_JWT_SECRET").map_err(|e| ExchangeError::OtherError(e.to_string()))
-}
-
 pub fn now_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -48,5 +57,9 @@ pub fn expiration_timestamp() -> u64 {
     now_timestamp() + EXPIRATION_TIME
 }
 
+pub use key_ring::*;
+pub use refresh_token::*;
+pub use session::*;
+pub use sign_in::*;
 pub use token2user::*;
 pub use user2token::*;
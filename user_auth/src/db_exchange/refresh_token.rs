@@ -0,0 +1,190 @@
+use super::{ExchangeError, KeyRing, REFRESH_EXPIRATION_TIME, model2token, now_timestamp};
+use crate::user_status::check_user_status;
+use db_manager::entity::{refresh_token as refresh_entity, user as user_entity};
+use rand::RngCore;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+
+/// 登录、注册或刷新后签发给客户端的一对凭证
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    /// 短期 access JWT，放在 Authorization 头中
+    pub access_token: String,
+    /// 不透明的 refresh token，仅用于 `/user/refresh`
+    pub refresh_token: String,
+}
+
+pub(crate) fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash a raw refresh token; only the hash is ever stored.
+pub fn hash_refresh_token(raw: &str) -> String {
+    Sha256::digest(raw.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub(crate) fn db_err(e: sea_orm::DbErr) -> ExchangeError {
+    ExchangeError::DatabaseError(e.to_string())
+}
+
+async fn insert_refresh_token<C>(
+    db: &C,
+    user_id: i32,
+    family_id: String,
+) -> Result<(refresh_entity::Model, String), ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    let raw = random_hex(32);
+    let now = now_timestamp() as i64;
+    let model = refresh_entity::ActiveModel {
+        user_id: Set(user_id),
+        token_hash: Set(hash_refresh_token(&raw)),
+        family_id: Set(family_id),
+        expires_at: Set(now + REFRESH_EXPIRATION_TIME as i64),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(db_err)?;
+    Ok((model, raw))
+}
+
+/// Issue an access token and a fresh refresh token family for a user.
+///
+/// Called on login and register; every call starts a new family so sessions on
+/// different devices can be rotated and revoked independently.
+pub async fn issue_token_pair<C>(
+    db: &C,
+    keys: &KeyRing,
+    user: &user_entity::Model,
+) -> Result<TokenPair, ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    let (_, refresh_token) = insert_refresh_token(db, user.id, random_hex(16)).await?;
+    Ok(TokenPair {
+        access_token: model2token(keys, user)?,
+        refresh_token,
+    })
+}
+
+/// Exchange a refresh token for a new token pair, rotating the refresh token.
+///
+/// The presented token is consumed. Presenting a token that was already
+/// rotated away is treated as theft: the whole family is revoked and
+/// `RefreshTokenReused` is returned, forcing the user to log in again.
+pub async fn rotate_refresh_token<C>(
+    db: &C,
+    keys: &KeyRing,
+    raw: &str,
+) -> Result<(user_entity::Model, TokenPair), ExchangeError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let txn = db.begin().await.map_err(db_err)?;
+    let now = now_timestamp() as i64;
+
+    // 1) 按摘要查找 refresh token
+    let current = refresh_entity::Entity::find()
+        .filter(refresh_entity::Column::TokenHash.eq(hash_refresh_token(raw)))
+        .one(&txn)
+        .await
+        .map_err(db_err)?
+        .ok_or(ExchangeError::InvalidToken)?;
+
+    // 2) 已失效的 token 再次出现：整个 family 作废
+    if current.revoked_at.is_some() {
+        revoke_family(&txn, &current.family_id).await?;
+        txn.commit().await.map_err(db_err)?;
+        return Err(ExchangeError::RefreshTokenReused);
+    }
+    if current.expires_at < now {
+        return Err(ExchangeError::TokenExpired);
+    }
+
+    // 3) 条件更新占用当前 token，并发刷新时只有一方能成功
+    let consumed = refresh_entity::Entity::update_many()
+        .col_expr(refresh_entity::Column::RevokedAt, Expr::value(now))
+        .filter(refresh_entity::Column::Id.eq(current.id))
+        .filter(refresh_entity::Column::RevokedAt.is_null())
+        .exec(&txn)
+        .await
+        .map_err(db_err)?;
+    if consumed.rows_affected != 1 {
+        revoke_family(&txn, &current.family_id).await?;
+        txn.commit().await.map_err(db_err)?;
+        return Err(ExchangeError::RefreshTokenReused);
+    }
+
+    // 4) 签发同 family 的新 token
+    let user = user_entity::Entity::find_by_id(current.user_id)
+        .one(&txn)
+        .await
+        .map_err(db_err)?
+        .ok_or(ExchangeError::TokenRevoked)?;
+    check_user_status(&user).map_err(ExchangeError::AccountBlocked)?;
+    let (next, refresh_token) =
+        insert_refresh_token(&txn, user.id, current.family_id.clone()).await?;
+    refresh_entity::Entity::update_many()
+        .col_expr(refresh_entity::Column::ReplacedBy, Expr::value(next.id))
+        .filter(refresh_entity::Column::Id.eq(current.id))
+        .exec(&txn)
+        .await
+        .map_err(db_err)?;
+    let access_token = model2token(keys, &user)?;
+    txn.commit().await.map_err(db_err)?;
+
+    Ok((
+        user,
+        TokenPair {
+            access_token,
+            refresh_token,
+        },
+    ))
+}
+
+/// Revoke every still-valid refresh token in a family.
+pub async fn revoke_family<C>(db: &C, family_id: &str) -> Result<(), ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    refresh_entity::Entity::update_many()
+        .col_expr(
+            refresh_entity::Column::RevokedAt,
+            Expr::value(now_timestamp() as i64),
+        )
+        .filter(refresh_entity::Column::FamilyId.eq(family_id))
+        .filter(refresh_entity::Column::RevokedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(db_err)?;
+    Ok(())
+}
+
+/// Revoke every still-valid refresh token of a user.
+pub async fn revoke_user_refresh_tokens<C>(db: &C, user_id: i32) -> Result<(), ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    refresh_entity::Entity::update_many()
+        .col_expr(
+            refresh_entity::Column::RevokedAt,
+            Expr::value(now_timestamp() as i64),
+        )
+        .filter(refresh_entity::Column::UserId.eq(user_id))
+        .filter(refresh_entity::Column::RevokedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(db_err)?;
+    Ok(())
+}
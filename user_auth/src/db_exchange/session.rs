@@ -0,0 +1,84 @@
+use super::{ExchangeError, KeyRing, User, revoke_user_refresh_tokens, token2claims, user2token};
+use crate::field_policy::{USER_FIELDS, Viewer};
+use crate::user_status::check_user_status;
+use db_manager::entity::user as user_entity;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set,
+};
+
+/// Build the `User` carried in token claims from a database row.
+///
+/// Claims are readable by the client, so fields the user may not read about
+/// themselves (see `USER_FIELDS`) are left out.
+pub fn model2user(model: &user_entity::Model) -> User {
+    let viewer = Viewer::own(model);
+    User {
+        open_id: model.open_id.clone(),
+        nickname: USER_FIELDS.read("nickname", viewer, model.nickname.clone()),
+        avatar: USER_FIELDS.read("avatar", viewer, model.avatar.clone()),
+        permission: USER_FIELDS.read("permission", viewer, model.permission),
+        name: USER_FIELDS.read("name", viewer, model.name.clone()),
+        phone_number: USER_FIELDS.read("phone_number", viewer, model.phone_number.clone()),
+        address: USER_FIELDS.read("address", viewer, model.address.clone()),
+        is_important: USER_FIELDS.read("is_important", viewer, model.is_important),
+    }
+}
+
+/// Issue a JWT for a database user, bound to the user's current `token_version`.
+pub fn model2token(keys: &KeyRing, model: &user_entity::Model) -> Result<String, ExchangeError> {
+    user2token(keys, &model2user(model), model.token_version)
+}
+
+/// Verify a JWT and return the user as currently stored in the database.
+///
+/// Besides signature and expiration this checks that the user still exists,
+/// that the token version matches and that the account is not suspended or
+/// deleted, so revoked tokens and blocked accounts are rejected immediately.
+/// Callers must authorize against the returned row, not against the claims.
+pub async fn verify_token<C>(
+    db: &C,
+    keys: &KeyRing,
+    token: &str,
+) -> Result<user_entity::Model, ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    let claims = token2claims(keys, token)?;
+
+    let model = user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(claims.sub.clone()))
+        .one(db)
+        .await
+        .map_err(|e| ExchangeError::DatabaseError(e.to_string()))?
+        .ok_or(ExchangeError::TokenRevoked)?;
+
+    // 先检查账号状态：封禁时 token 也已被吊销，但应返回明确的封禁原因
+    check_user_status(&model).map_err(ExchangeError::AccountBlocked)?;
+    if model.token_version != claims.ver {
+        return Err(ExchangeError::TokenRevoked);
+    }
+
+    Ok(model)
+}
+
+/// Revoke every token issued to the user so far.
+///
+/// Bumps `token_version` so outstanding access tokens fail `verify_token`, and
+/// revokes all refresh tokens so they can no longer be exchanged.
+pub async fn revoke_user_tokens<C>(
+    db: &C,
+    model: user_entity::Model,
+) -> Result<user_entity::Model, ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    revoke_user_refresh_tokens(db, model.id).await?;
+    let next_version = model.token_version + 1;
+    let mut active: user_entity::ActiveModel = model.clone().into();
+    active.id = ActiveValue::Unchanged(model.id);
+    active.token_version = Set(next_version);
+    active
+        .update(db)
+        .await
+        .map_err(|e| ExchangeError::DatabaseError(e.to_string()))
+}
@@ -0,0 +1,96 @@
+use super::refresh_token::db_err;
+use super::{ExchangeError, KeyRing, TokenPair, issue_token_pair};
+use crate::user_status::check_user_status;
+use crate::wx_auth::save_session_key;
+use db_manager::entity::user as user_entity;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+
+/// 首次登录时随登录请求提交的资料
+#[derive(Debug, Clone, Default)]
+pub struct OnboardingProfile {
+    pub nickname: Option<String>,
+    pub name: Option<String>,
+    pub phone_number: Option<String>,
+    pub address: Option<String>,
+}
+
+impl OnboardingProfile {
+    pub fn is_empty(&self) -> bool {
+        self.nickname.is_none()
+            && self.name.is_none()
+            && self.phone_number.is_none()
+            && self.address.is_none()
+    }
+}
+
+/// 登录结果
+#[derive(Debug, Clone)]
+pub struct SignInResult {
+    pub user: user_entity::Model,
+    pub tokens: TokenPair,
+    /// 本次登录是否创建了新用户
+    pub is_new: bool,
+}
+
+/// Sign in by open_id, creating the user on first sign-in.
+///
+/// Idempotent: concurrent or repeated calls for the same open_id create one
+/// user. The onboarding profile is only applied when the user is created;
+/// user creation, session key storage and token issuing share one transaction.
+pub async fn sign_in<C>(
+    db: &C,
+    keys: &KeyRing,
+    open_id: &str,
+    session_key: Option<&str>,
+    profile: Option<OnboardingProfile>,
+) -> Result<SignInResult, ExchangeError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await.map_err(db_err)?;
+    let profile = profile.unwrap_or_default();
+
+    // 1) 不存在则创建，open_id 冲突时什么也不做
+    let inserted = user_entity::Entity::insert(user_entity::ActiveModel {
+        open_id: Set(open_id.to_string()),
+        nickname: Set(profile.nickname),
+        name: Set(profile.name),
+        phone_number: Set(profile.phone_number),
+        address: Set(profile.address),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::column(user_entity::Column::OpenId)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await
+    .map_err(db_err)?;
+
+    let user = user_entity::Entity::find()
+        .filter(user_entity::Column::OpenId.eq(open_id))
+        .one(&txn)
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| ExchangeError::DatabaseError("User not found".to_string()))?;
+
+    // 2) 封禁或已删除的账号不签发 token
+    check_user_status(&user).map_err(ExchangeError::AccountBlocked)?;
+
+    // 3) 保存 session_key 并签发凭证
+    if let Some(session_key) = session_key {
+        save_session_key(&txn, user.id, session_key)
+            .await
+            .map_err(db_err)?;
+    }
+    let tokens = issue_token_pair(&txn, keys, &user).await?;
+
+    txn.commit().await.map_err(db_err)?;
+    Ok(SignInResult {
+        user,
+        tokens,
+        is_new: inserted == 1,
+    })
+}
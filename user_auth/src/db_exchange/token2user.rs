@@ -1,21 +1,23 @@
-use super::{Claims, ExchangeError, User, jwt_secret_from_env, now_timestamp};
-use hmac::{Hmac, digest::KeyInit};
-use jwt::VerifyWithKey;
-use sha2::Sha256;
+use super::{Claims, ExchangeError, KeyPurpose, KeyRing, User, now_timestamp};
 
 /// Parse a JWT string into a `User`, validating signature and expiration.
-pub fn token2user(token: &str) -> Result<User, ExchangeError> {
-    let secret = jwt_secret_from_env()?;
-    let key: Hmac<Sha256> = Hmac::new_from_slice(secret.as_bytes())
-        .map_err(|e| ExchangeError::OtherError(e.to_string()))?;
+///
+/// Only the signed claims are checked; use `verify_token` to also check the
+/// token version and load the current user from the database.
+pub fn token2user(keys: &KeyRing, token: &str) -> Result<User, ExchangeError> {
+    token2claims(keys, token).map(|claims| claims.user)
+}
 
-    let claims: Claims = token
-        .verify_with_key(&key)
-        .map_err(|_| ExchangeError::InvalidToken)?;
+/// Parse a JWT string into its `Claims`, validating signature and expiration.
+///
+/// The `kid` header selects the key; tokens signed by a retired key, by an
+/// unknown key or for another purpose are rejected as invalid.
+pub fn token2claims(keys: &KeyRing, token: &str) -> Result<Claims, ExchangeError> {
+    let claims: Claims = keys.verify(KeyPurpose::Session, token)?;
 
     if claims.exp < now_timestamp() {
         return Err(ExchangeError::TokenExpired);
     }
 
-    Ok(claims.user)
+    Ok(claims)
 }
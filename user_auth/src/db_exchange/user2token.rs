@@ -1,21 +1,17 @@
-use super::{Claims, ExchangeError, User, expiration_timestamp, jwt_secret_from_env};
-use hmac::{Hmac, digest::KeyInit};
-use jwt::SignWithKey;
-use sha2::Sha256;
+use super::{Claims, ExchangeError, KeyPurpose, KeyRing, User, expiration_timestamp};
 
 /// Generate a JWT for the given user with an embedded expiration timestamp.
-pub fn user2token(user: &User) -> Result<String, ExchangeError> {
-    let secret = jwt_secret_from_env()?;
-    let key: Hmac<Sha256> = Hmac::new_from_slice(secret.as_bytes())
-        .map_err(|e| ExchangeError::TokenGenerationError(e.to_string()))?;
-
+///
+/// Signed with the active key of the key ring for the session purpose.
+/// `version` must be the user's current `token_version`, otherwise the token
+/// is rejected by `verify_token`.
+pub fn user2token(keys: &KeyRing, user: &User, version: i32) -> Result<String, ExchangeError> {
     let claims = Claims {
         sub: user.open_id.clone(),
         exp: expiration_timestamp(),
+        ver: version,
         user: user.clone(),
     };
 
-    claims
-        .sign_with_key(&key)
-        .map_err(|e| ExchangeError::TokenGenerationError(e.to_string()))
+    keys.sign(KeyPurpose::Session, &claims)
 }
@@ -0,0 +1,15 @@
+//! 字段级读写策略
+//!
+//! 以声明的方式为消息的每个字段规定谁可以读、谁可以写（见 `FieldRule`），
+//! 在实体与 protobuf 相互转换时统一应用：写入时拒绝越权字段，返回时隐藏无权查看的字段。
+//! 未声明的字段一律不可读写。
+//!
+//! 访问者相对于数据所属用户的身份由 `Viewer` 描述，可以同时满足多个 `Audience`。
+
+pub mod policy;
+pub mod r#struct;
+pub mod user;
+
+pub use policy::*;
+pub use r#struct::*;
+pub use user::*;
@@ -0,0 +1,52 @@
+use super::{FieldPolicyError, FieldRule, Viewer};
+
+/// 一个消息的全部字段策略
+#[derive(Debug, Clone, Copy)]
+pub struct FieldPolicy {
+    pub rules: &'static [FieldRule],
+}
+
+impl FieldPolicy {
+    pub const fn new(rules: &'static [FieldRule]) -> Self {
+        FieldPolicy { rules }
+    }
+
+    fn rule(&self, field: &str) -> Option<&FieldRule> {
+        self.rules.iter().find(|r| r.field == field)
+    }
+
+    /// Undeclared fields are never readable.
+    pub fn can_read(&self, field: &str, viewer: Viewer) -> bool {
+        self.rule(field)
+            .is_some_and(|r| r.read.iter().any(|a| viewer.is(*a)))
+    }
+
+    /// Undeclared fields are never writable.
+    pub fn can_write(&self, field: &str, viewer: Viewer) -> bool {
+        self.rule(field)
+            .is_some_and(|r| r.write.iter().any(|a| viewer.is(*a)))
+    }
+
+    /// Keep `value` only if the viewer may read `field`.
+    pub fn read<T>(&self, field: &str, viewer: Viewer, value: Option<T>) -> Option<T> {
+        value.filter(|_| self.can_read(field, viewer))
+    }
+
+    /// Check every field a request sets; fails with all denied fields at once.
+    pub fn check_writes<'a>(
+        &self,
+        fields: impl IntoIterator<Item = &'a str>,
+        viewer: Viewer,
+    ) -> Result<(), FieldPolicyError> {
+        let denied: Vec<&'static str> = fields
+            .into_iter()
+            .filter(|f| !self.can_write(f, viewer))
+            .map(|f| self.rule(f).map_or("unknown field", |r| r.field))
+            .collect();
+        if denied.is_empty() {
+            Ok(())
+        } else {
+            Err(FieldPolicyError::WriteDenied(denied))
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::user_auth::{UserPermissionAuthorizeResult, UserPermissionLevel, authorize_user};
+use db_manager::entity::user as user_entity;
+
+/// 字段策略中的访问者身份
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Audience {
+    /// 数据所属用户本人
+    Owner,
+    /// 所属用户生效的照护人
+    Caregiver,
+    /// 权限等级高于所属用户（见 `authorize_user`）
+    Manager,
+    /// Admin
+    Admin,
+}
+
+/// 当前用户相对于数据所属用户的身份
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Viewer {
+    pub owner: bool,
+    pub caregiver: bool,
+    pub manager: bool,
+    pub admin: bool,
+}
+
+impl Viewer {
+    /// 用户访问自己的数据
+    pub fn own(user: &user_entity::Model) -> Self {
+        Self::of(user, user, false)
+    }
+
+    /// `actor` 访问 `subject` 的数据，`caregiver` 表示两者之间存在生效的照护关联
+    pub fn of(actor: &user_entity::Model, subject: &user_entity::Model, caregiver: bool) -> Self {
+        let actor_level = actor.permission.unwrap_or(0);
+        let subject_level = UserPermissionLevel::from(subject.permission.unwrap_or(0));
+        Viewer {
+            owner: actor.id == subject.id,
+            caregiver,
+            manager: authorize_user(actor_level, subject_level)
+                == UserPermissionAuthorizeResult::Authorized,
+            admin: UserPermissionLevel::from(actor_level) == UserPermissionLevel::Admin,
+        }
+    }
+
+    pub fn is(&self, audience: Audience) -> bool {
+        match audience {
+            Audience::Owner => self.owner,
+            Audience::Caregiver => self.caregiver,
+            Audience::Manager => self.manager,
+            Audience::Admin => self.admin,
+        }
+    }
+
+    /// 是否可以访问该用户的记录（本人、照护人或更高权限），具体字段再按策略判断
+    pub fn related(&self) -> bool {
+        self.owner || self.caregiver || self.manager
+    }
+}
+
+/// 单个字段的读写策略
+#[derive(Debug, Clone, Copy)]
+pub struct FieldRule {
+    pub field: &'static str,
+    pub read: &'static [Audience],
+    pub write: &'static [Audience],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldPolicyError {
+    /// 请求修改了无权写入的字段
+    WriteDenied(Vec<&'static str>),
+}
+
+impl FieldPolicyError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            FieldPolicyError::WriteDenied(fields) => (
+                403,
+                format!("Permission denied: cannot modify {}", fields.join(", ")),
+            ),
+        }
+    }
+}
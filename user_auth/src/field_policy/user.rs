@@ -0,0 +1,53 @@
+use super::{Audience, FieldPolicy, FieldRule};
+
+const PROFILE_READ: &[Audience] = &[
+    Audience::Owner,
+    Audience::Caregiver,
+    Audience::Manager,
+    Audience::Admin,
+];
+const PROFILE_WRITE: &[Audience] = &[Audience::Owner, Audience::Caregiver, Audience::Manager];
+const ADMIN_ONLY: &[Audience] = &[Audience::Admin];
+
+/// `user` 表资料字段的读写策略，适用于 `User`、`UserRequest` 与 `AdminManagedUser`
+///
+/// - 资料字段：本人、照护人与更高权限的用户可读写，Admin 可读
+/// - `permission`：本人与 Admin 可读，仅 Admin 可写
+/// - `is_important`：仅 Admin 可读写
+pub const USER_FIELDS: FieldPolicy = FieldPolicy::new(&[
+    FieldRule {
+        field: "nickname",
+        read: PROFILE_READ,
+        write: PROFILE_WRITE,
+    },
+    FieldRule {
+        field: "name",
+        read: PROFILE_READ,
+        write: PROFILE_WRITE,
+    },
+    FieldRule {
+        field: "phone_number",
+        read: PROFILE_READ,
+        write: PROFILE_WRITE,
+    },
+    FieldRule {
+        field: "address",
+        read: PROFILE_READ,
+        write: PROFILE_WRITE,
+    },
+    FieldRule {
+        field: "avatar",
+        read: PROFILE_READ,
+        write: PROFILE_WRITE,
+    },
+    FieldRule {
+        field: "permission",
+        read: &[Audience::Owner, Audience::Admin],
+        write: ADMIN_ONLY,
+    },
+    FieldRule {
+        field: "is_important",
+        read: ADMIN_ONLY,
+        write: ADMIN_ONLY,
+    },
+]);
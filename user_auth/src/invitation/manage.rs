@@ -0,0 +1,137 @@
+use super::{InvitationDetail, InvitationError, InvitationOptions, MAX_INVITATION_EXPIRE_SECONDS};
+use crate::db_exchange::{now_timestamp, random_hex};
+use crate::user_auth::UserPermissionLevel;
+use db_manager::entity::{invitation_code, invitation_redemption, user as user_entity};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::collections::HashMap;
+
+/// Persist a new invitation code issued by `issuer`.
+pub async fn issue_invitation<C>(
+    db: &C,
+    issuer: &user_entity::Model,
+    options: InvitationOptions,
+) -> Result<invitation_code::Model, InvitationError>
+where
+    C: ConnectionTrait,
+{
+    if options.apply_type != UserPermissionLevel::Provider.level()
+        && options.apply_type != UserPermissionLevel::Admin.level()
+    {
+        return Err(InvitationError::InvalidApplyType);
+    }
+    if options.max_uses < 1 {
+        return Err(InvitationError::InvalidMaxUses);
+    }
+    if options.expire_seconds == 0 || options.expire_seconds > MAX_INVITATION_EXPIRE_SECONDS {
+        return Err(InvitationError::InvalidExpiry);
+    }
+
+    let target_user_id = match options.target_open_id {
+        Some(open_id) => Some(
+            user_entity::Entity::find()
+                .filter(user_entity::Column::OpenId.eq(open_id))
+                .one(db)
+                .await?
+                .ok_or(InvitationError::TargetUserNotFound)?
+                .id,
+        ),
+        None => None,
+    };
+
+    let now = now_timestamp() as i64;
+    let model = invitation_code::ActiveModel {
+        code: Set(random_hex(8).to_uppercase()),
+        apply_type: Set(options.apply_type),
+        issued_by: Set(issuer.id),
+        target_user_id: Set(target_user_id),
+        max_uses: Set(options.max_uses),
+        used_count: Set(0),
+        expires_at: Set(now + options.expire_seconds as i64),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(model)
+}
+
+/// List the codes issued by `issuer_id`, newest first, with their redemptions.
+pub async fn list_invitations<C>(
+    db: &C,
+    issuer_id: i32,
+) -> Result<Vec<InvitationDetail>, InvitationError>
+where
+    C: ConnectionTrait,
+{
+    let invitations = invitation_code::Entity::find()
+        .filter(invitation_code::Column::IssuedBy.eq(issuer_id))
+        .order_by_desc(invitation_code::Column::CreatedAt)
+        .find_with_related(invitation_redemption::Entity)
+        .all(db)
+        .await?;
+
+    // 一次性取出涉及的用户 open_id
+    let mut user_ids: Vec<i32> = vec![issuer_id];
+    for (invitation, redemptions) in &invitations {
+        user_ids.extend(invitation.target_user_id);
+        user_ids.extend(redemptions.iter().map(|r| r.user_id));
+    }
+    user_ids.sort_unstable();
+    user_ids.dedup();
+    let open_ids: HashMap<i32, String> = user_entity::Entity::find()
+        .filter(user_entity::Column::Id.is_in(user_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.open_id))
+        .collect();
+    let open_id_of = |id: i32| open_ids.get(&id).cloned().unwrap_or_default();
+
+    Ok(invitations
+        .into_iter()
+        .map(|(invitation, redemptions)| InvitationDetail {
+            issued_by_open_id: open_id_of(invitation.issued_by),
+            target_open_id: invitation.target_user_id.map(open_id_of),
+            redemptions: redemptions
+                .into_iter()
+                .map(|r| (open_id_of(r.user_id), r))
+                .collect(),
+            invitation,
+        })
+        .collect())
+}
+
+/// Revoke a code issued by `issuer_id`. Revoking twice is a no-op.
+pub async fn revoke_invitation<C>(
+    db: &C,
+    issuer_id: i32,
+    invitation_id: i32,
+) -> Result<invitation_code::Model, InvitationError>
+where
+    C: ConnectionTrait,
+{
+    let invitation = invitation_code::Entity::find_by_id(invitation_id)
+        .filter(invitation_code::Column::IssuedBy.eq(issuer_id))
+        .one(db)
+        .await?
+        .ok_or(InvitationError::NotFound)?;
+    if invitation.revoked_at.is_some() {
+        return Ok(invitation);
+    }
+
+    invitation_code::Entity::update_many()
+        .col_expr(
+            invitation_code::Column::RevokedAt,
+            Expr::value(now_timestamp() as i64),
+        )
+        .filter(invitation_code::Column::Id.eq(invitation.id))
+        .exec(db)
+        .await?;
+    invitation_code::Entity::find_by_id(invitation.id)
+        .one(db)
+        .await?
+        .ok_or(InvitationError::NotFound)
+}
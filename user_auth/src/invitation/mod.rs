@@ -0,0 +1,7 @@
+pub mod manage;
+pub mod redeem;
+pub mod r#struct;
+
+pub use manage::*;
+pub use redeem::*;
+pub use r#struct::*;
@@ -0,0 +1,83 @@
+use super::InvitationError;
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::{invitation_code, invitation_redemption, user as user_entity};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+
+/// Redeem an invitation code for `user`, granting its permission.
+///
+/// Use counting is done with a conditional update inside a transaction, so a
+/// single-use code can not be redeemed twice by concurrent requests. Every
+/// successful redemption is logged in `invitation_redemption`.
+pub async fn redeem_invitation<C>(
+    db: &C,
+    code: &str,
+    user: &user_entity::Model,
+) -> Result<(invitation_code::Model, user_entity::Model), InvitationError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let now = now_timestamp() as i64;
+
+    // 1) 校验邀请码状态
+    let invitation = invitation_code::Entity::find()
+        .filter(invitation_code::Column::Code.eq(code.trim().to_uppercase()))
+        .one(&txn)
+        .await?
+        .ok_or(InvitationError::NotFound)?;
+    if invitation.revoked_at.is_some() {
+        return Err(InvitationError::Revoked);
+    }
+    if invitation.expires_at < now {
+        return Err(InvitationError::Expired);
+    }
+    if invitation
+        .target_user_id
+        .is_some_and(|target| target != user.id)
+    {
+        return Err(InvitationError::NotForThisUser);
+    }
+    if user.permission.unwrap_or(0) >= invitation.apply_type {
+        return Err(InvitationError::AlreadyGranted);
+    }
+
+    // 2) 条件更新占用一次使用次数
+    let consumed = invitation_code::Entity::update_many()
+        .col_expr(
+            invitation_code::Column::UsedCount,
+            Expr::col(invitation_code::Column::UsedCount).add(1),
+        )
+        .filter(invitation_code::Column::Id.eq(invitation.id))
+        .filter(
+            Expr::col(invitation_code::Column::UsedCount)
+                .lt(Expr::col(invitation_code::Column::MaxUses)),
+        )
+        .filter(invitation_code::Column::RevokedAt.is_null())
+        .exec(&txn)
+        .await?;
+    if consumed.rows_affected != 1 {
+        return Err(InvitationError::Exhausted);
+    }
+
+    // 3) 记录兑换并更新权限
+    invitation_redemption::ActiveModel {
+        invitation_id: Set(invitation.id),
+        user_id: Set(user.id),
+        previous_permission: Set(user.permission),
+        redeemed_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    let mut active: user_entity::ActiveModel = user.clone().into();
+    active.id = ActiveValue::Unchanged(user.id);
+    active.permission = Set(Some(invitation.apply_type));
+    let updated = active.update(&txn).await?;
+
+    txn.commit().await?;
+    Ok((invitation, updated))
+}
@@ -0,0 +1,93 @@
+use db_manager::entity::{invitation_code, invitation_redemption};
+
+/// 默认有效期：3 分钟，与旧的无状态校验码一致
+pub const DEFAULT_INVITATION_EXPIRE_SECONDS: u64 = 180;
+/// 自定义有效期上限：30 天
+pub const MAX_INVITATION_EXPIRE_SECONDS: u64 = 2592000;
+
+/// 签发邀请码时的参数
+#[derive(Debug, Clone)]
+pub struct InvitationOptions {
+    /// 兑换后授予的权限：2（Provider）或 3（Admin）
+    pub apply_type: i32,
+    /// 最多可兑换次数，至少为 1
+    pub max_uses: i32,
+    /// 有效期（秒）
+    pub expire_seconds: u64,
+    /// 非空时仅该 open_id 对应的用户可兑换
+    pub target_open_id: Option<String>,
+}
+
+impl InvitationOptions {
+    /// 单次使用、默认有效期、不绑定用户
+    pub fn new(apply_type: i32) -> Self {
+        InvitationOptions {
+            apply_type,
+            max_uses: 1,
+            expire_seconds: DEFAULT_INVITATION_EXPIRE_SECONDS,
+            target_open_id: None,
+        }
+    }
+}
+
+/// 邀请码及其兑换记录
+#[derive(Debug, Clone)]
+pub struct InvitationDetail {
+    pub invitation: invitation_code::Model,
+    pub issued_by_open_id: String,
+    pub target_open_id: Option<String>,
+    /// (兑换人 open_id, 兑换记录)
+    pub redemptions: Vec<(String, invitation_redemption::Model)>,
+}
+
+#[derive(Debug)]
+pub enum InvitationError {
+    InvalidApplyType,
+    InvalidMaxUses,
+    InvalidExpiry,
+    TargetUserNotFound,
+    NotFound,
+    Expired,
+    Revoked,
+    Exhausted,
+    NotForThisUser,
+    AlreadyGranted,
+    DatabaseError(String),
+}
+
+impl InvitationError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            InvitationError::InvalidApplyType => {
+                (400, "Invalid apply_type: must be 2 or 3".to_string())
+            }
+            InvitationError::InvalidMaxUses => {
+                (400, "Invalid max_uses: must be at least 1".to_string())
+            }
+            InvitationError::InvalidExpiry => (
+                400,
+                format!(
+                    "Invalid expire_seconds: must be between 1 and {}",
+                    MAX_INVITATION_EXPIRE_SECONDS
+                ),
+            ),
+            InvitationError::TargetUserNotFound => (404, "Target user not found".to_string()),
+            InvitationError::NotFound => (404, "Apply code not found".to_string()),
+            InvitationError::Expired => (400, "Apply code expired".to_string()),
+            InvitationError::Revoked => (400, "Apply code revoked".to_string()),
+            InvitationError::Exhausted => (400, "Apply code has no uses left".to_string()),
+            InvitationError::NotForThisUser => {
+                (403, "Apply code is bound to another user".to_string())
+            }
+            InvitationError::AlreadyGranted => (400, "Permission already granted".to_string()),
+            InvitationError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for InvitationError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        InvitationError::DatabaseError(err.to_string())
+    }
+}
@@ -1,3 +1,12 @@
+pub mod audit;
+pub mod caregiver;
 pub mod db_exchange;
+pub mod field_policy;
+pub mod invitation;
+pub mod local_auth;
+pub mod personal_data;
+pub mod rbac;
+pub mod totp;
 pub mod user_auth;
+pub mod user_status;
 pub mod wx_auth;
@@ -0,0 +1,257 @@
+use std::sync::OnceLock;
+
+use super::password::{hash_password, validate_password, validate_username, verify_password};
+use super::{LOCKOUT_SECONDS, LocalAuthError, MAX_FAILED_ATTEMPTS};
+use crate::db_exchange::{ExchangeError, now_timestamp, revoke_user_tokens};
+use crate::user_status::check_user_status;
+use db_manager::entity::{local_credential, user as user_entity};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, ModelTrait, QueryFilter,
+    Set, TransactionTrait, Value,
+};
+
+fn exchange_err(err: ExchangeError) -> LocalAuthError {
+    match err {
+        ExchangeError::DatabaseError(e) => LocalAuthError::DatabaseError(e),
+        other => LocalAuthError::DatabaseError(format!("{:?}", other)),
+    }
+}
+
+/// Local account of a user, if any.
+pub async fn find_local_account<C>(
+    db: &C,
+    user_id: i32,
+) -> Result<Option<local_credential::Model>, LocalAuthError>
+where
+    C: ConnectionTrait,
+{
+    Ok(local_credential::Entity::find()
+        .filter(local_credential::Column::UserId.eq(user_id))
+        .one(db)
+        .await?)
+}
+
+/// Give an existing user a username and password.
+pub async fn create_local_account<C>(
+    db: &C,
+    user_id: i32,
+    username: &str,
+    password: &str,
+) -> Result<local_credential::Model, LocalAuthError>
+where
+    C: ConnectionTrait,
+{
+    let username = username.trim().to_lowercase();
+    validate_username(&username)?;
+    validate_password(password)?;
+
+    let taken = local_credential::Entity::find()
+        .filter(local_credential::Column::Username.eq(username.clone()))
+        .one(db)
+        .await?;
+    if taken.is_some() || find_local_account(db, user_id).await?.is_some() {
+        return Err(LocalAuthError::UsernameTaken);
+    }
+
+    let now = now_timestamp() as i64;
+    Ok(local_credential::ActiveModel {
+        user_id: Set(user_id),
+        username: Set(username),
+        password_hash: Set(hash_password(password)?),
+        failed_attempts: Set(0),
+        locked_until: Set(None),
+        password_changed_at: Set(now),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?)
+}
+
+/// A table that counts consecutive failed attempts and locks the row after too many.
+pub trait Lockout: EntityTrait {
+    /// 主键列
+    const ID: Self::Column;
+    /// 连续失败次数列
+    const FAILED_ATTEMPTS: Self::Column;
+    /// 锁定截止时间列（unix 秒）
+    const LOCKED_UNTIL: Self::Column;
+}
+
+impl Lockout for local_credential::Entity {
+    const ID: Self::Column = local_credential::Column::Id;
+    const FAILED_ATTEMPTS: Self::Column = local_credential::Column::FailedAttempts;
+    const LOCKED_UNTIL: Self::Column = local_credential::Column::LockedUntil;
+}
+
+/// Count one more failed attempt on row `id`, returning the lock expiry if the row is locked.
+///
+/// The counter is incremented by a single conditional `UPDATE`, so concurrent failures
+/// are never lost. After `MAX_FAILED_ATTEMPTS` consecutive failures the counter restarts
+/// and the lock lasts `LOCKOUT_SECONDS`. Shared with the second-factor check.
+pub async fn register_failure<E, C>(db: &C, id: i32, now: i64) -> Result<Option<i64>, DbErr>
+where
+    E: Lockout,
+    C: ConnectionTrait,
+{
+    // 1) 在数据库中累加并判断是否达到上限，两列都按更新前的值计算
+    let attempts = Expr::col(E::FAILED_ATTEMPTS).add(1);
+    let reaches_limit = Expr::expr(attempts.clone()).gte(MAX_FAILED_ATTEMPTS);
+    E::update_many()
+        .col_expr(
+            E::FAILED_ATTEMPTS,
+            Expr::case(reaches_limit.clone(), 0)
+                .finally(attempts)
+                .into(),
+        )
+        .col_expr(
+            E::LOCKED_UNTIL,
+            Expr::case(reaches_limit, now + LOCKOUT_SECONDS as i64)
+                .finally(Expr::col(E::LOCKED_UNTIL))
+                .into(),
+        )
+        .filter(E::ID.eq(id))
+        .exec(db)
+        .await?;
+
+    // 2) 读取更新后的锁定时间，并发请求中任何一次达到上限都会锁定
+    let locked_until = E::find()
+        .filter(E::ID.eq(id))
+        .one(db)
+        .await?
+        .map(|row| row.get(E::LOCKED_UNTIL));
+    Ok(match locked_until {
+        Some(Value::BigInt(Some(until))) if until > now => Some(until),
+        _ => None,
+    })
+}
+
+/// Check a username and password, returning the linked user.
+///
+/// Every failure counts towards the lockout; after `MAX_FAILED_ATTEMPTS`
+/// consecutive failures the account is locked for `LOCKOUT_SECONDS`.
+pub async fn login_with_password<C>(
+    db: &C,
+    username: &str,
+    password: &str,
+) -> Result<user_entity::Model, LocalAuthError>
+where
+    C: ConnectionTrait,
+{
+    let now = now_timestamp() as i64;
+    let username = username.trim().to_lowercase();
+
+    // 1) 查找账号，不存在时同样做一次哈希校验，避免通过耗时判断用户名是否存在
+    let Some(account) = local_credential::Entity::find()
+        .filter(local_credential::Column::Username.eq(username))
+        .one(db)
+        .await?
+    else {
+        let _ = verify_password(password, dummy_hash());
+        return Err(LocalAuthError::InvalidCredentials);
+    };
+
+    // 2) 锁定期内直接拒绝
+    if let Some(until) = account.locked_until
+        && until > now
+    {
+        return Err(LocalAuthError::Locked { until });
+    }
+
+    // 3) 校验密码，失败累计次数
+    if !verify_password(password, &account.password_hash) {
+        let locked_until =
+            register_failure::<local_credential::Entity, _>(db, account.id, now).await?;
+        return Err(match locked_until {
+            Some(until) => LocalAuthError::Locked { until },
+            None => LocalAuthError::InvalidCredentials,
+        });
+    }
+
+    // 4) 成功后清零失败次数
+    let user_id = account.user_id;
+    if account.failed_attempts != 0 || account.locked_until.is_some() {
+        let mut active: local_credential::ActiveModel = account.into();
+        active.failed_attempts = Set(0);
+        active.locked_until = Set(None);
+        active.update(db).await?;
+    }
+
+    let user = user_entity::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or(LocalAuthError::UserNotFound)?;
+    check_user_status(&user).map_err(LocalAuthError::AccountBlocked)?;
+    Ok(user)
+}
+
+/// Store a new password, clear the lockout and revoke every session of the user.
+async fn set_password<C>(
+    db: &C,
+    account: local_credential::Model,
+    password: &str,
+) -> Result<user_entity::Model, LocalAuthError>
+where
+    C: TransactionTrait,
+{
+    validate_password(password)?;
+    let password_hash = hash_password(password)?;
+    let txn = db.begin().await?;
+
+    let user = user_entity::Entity::find_by_id(account.user_id)
+        .one(&txn)
+        .await?
+        .ok_or(LocalAuthError::UserNotFound)?;
+    let mut active: local_credential::ActiveModel = account.into();
+    active.password_hash = Set(password_hash);
+    active.failed_attempts = Set(0);
+    active.locked_until = Set(None);
+    active.password_changed_at = Set(now_timestamp() as i64);
+    active.update(&txn).await?;
+    let user = revoke_user_tokens(&txn, user).await.map_err(exchange_err)?;
+
+    txn.commit().await?;
+    Ok(user)
+}
+
+/// Change the password of the current user after checking the old one.
+pub async fn change_password<C>(
+    db: &C,
+    user_id: i32,
+    old_password: &str,
+    new_password: &str,
+) -> Result<user_entity::Model, LocalAuthError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let account = find_local_account(db, user_id)
+        .await?
+        .ok_or(LocalAuthError::AccountNotFound)?;
+    if !verify_password(old_password, &account.password_hash) {
+        return Err(LocalAuthError::InvalidCredentials);
+    }
+    set_password(db, account, new_password).await
+}
+
+/// Reset the password of another user, used by admins.
+pub async fn reset_password<C>(
+    db: &C,
+    user_id: i32,
+    new_password: &str,
+) -> Result<user_entity::Model, LocalAuthError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let account = find_local_account(db, user_id)
+        .await?
+        .ok_or(LocalAuthError::AccountNotFound)?;
+    set_password(db, account, new_password).await
+}
+
+/// Hash verified against when the username does not exist, so that unknown and
+/// known usernames take the same time.
+fn dummy_hash() -> &'static str {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    DUMMY_HASH.get_or_init(|| hash_password("sd_backend-dummy-password").unwrap_or_default())
+}
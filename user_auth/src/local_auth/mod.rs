@@ -0,0 +1,15 @@
+//! 本地账号（用户名 + 密码）
+//!
+//! 供无法使用微信登录的管理后台使用，每个本地账号关联一个已有的 `user` 记录。
+//! 密码使用 argon2 哈希保存，连续登录失败会临时锁定账号；
+//! 后台使用加密 cookie 保存会话，并以 CSRF token 保护写操作。
+
+pub mod account;
+pub mod password;
+pub mod session;
+pub mod r#struct;
+
+pub use account::*;
+pub use password::*;
+pub use session::*;
+pub use r#struct::*;
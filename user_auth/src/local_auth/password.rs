@@ -0,0 +1,45 @@
+use argon2::password_hash::{PasswordHash, SaltString, rand_core::OsRng};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+
+use super::{LocalAuthError, MIN_PASSWORD_LENGTH};
+
+/// 校验用户名：3-32 位小写字母、数字、`_`、`.`、`-`
+pub fn validate_username(username: &str) -> Result<(), LocalAuthError> {
+    let valid = (3..=32).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_.-".contains(c));
+    if valid {
+        Ok(())
+    } else {
+        Err(LocalAuthError::InvalidUsername)
+    }
+}
+
+/// 校验密码强度
+pub fn validate_password(password: &str) -> Result<(), LocalAuthError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(LocalAuthError::WeakPassword);
+    }
+    Ok(())
+}
+
+/// 使用 argon2id 与随机盐哈希密码，返回 PHC 字符串
+pub fn hash_password(password: &str) -> Result<String, LocalAuthError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| LocalAuthError::HashError(e.to_string()))
+}
+
+/// 校验密码是否与 PHC 字符串匹配
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,67 @@
+use sha2::{Digest, Sha512};
+
+use super::{CONSOLE_SESSION_SECONDS, ConsoleSession};
+use crate::db_exchange::{ExchangeError, KeyRing, now_timestamp, random_hex};
+use crate::user_status::check_user_status;
+use db_manager::entity::user as user_entity;
+use sea_orm::{ConnectionTrait, EntityTrait};
+
+/// 加密后台会话 cookie 的 64 字节密钥
+///
+/// 优先使用配置的 cookie 密钥，未设置时由 JWT 密钥环的当前密钥派生
+/// （此时轮换 JWT 密钥会使已登录的后台会话失效）
+pub fn console_cookie_key(cookie_secret: Option<&str>, key_ring: &KeyRing) -> [u8; 64] {
+    let secret = match cookie_secret {
+        Some(secret) => secret.as_bytes(),
+        None => key_ring.active_key().secret(),
+    };
+    let mut hasher = Sha512::new();
+    hasher.update(b"sd_backend console cookie:");
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+impl ConsoleSession {
+    /// 为用户新建会话，同时生成随机 CSRF token
+    pub fn new(user: &user_entity::Model) -> Self {
+        ConsoleSession {
+            user_id: user.id,
+            ver: user.token_version,
+            exp: now_timestamp() + CONSOLE_SESSION_SECONDS,
+            csrf: random_hex(16),
+        }
+    }
+
+    /// 序列化为 cookie 值（cookie 本身由 server_main 加密）
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 解析 cookie 值，格式错误时返回 `None`
+    pub fn decode(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
+/// Check a console session against the database and return the latest user row.
+pub async fn verify_console_session<C>(
+    db: &C,
+    session: &ConsoleSession,
+) -> Result<user_entity::Model, ExchangeError>
+where
+    C: ConnectionTrait,
+{
+    if session.exp <= now_timestamp() {
+        return Err(ExchangeError::TokenExpired);
+    }
+    let model = user_entity::Entity::find_by_id(session.user_id)
+        .one(db)
+        .await
+        .map_err(|e| ExchangeError::DatabaseError(e.to_string()))?
+        .ok_or(ExchangeError::InvalidToken)?;
+    check_user_status(&model).map_err(ExchangeError::AccountBlocked)?;
+    if model.token_version != session.ver {
+        return Err(ExchangeError::TokenRevoked);
+    }
+    Ok(model)
+}
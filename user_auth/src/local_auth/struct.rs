@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::user_status::UserStatusError;
+
+/// 密码最短长度
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+/// 连续失败多少次后锁定账号
+pub const MAX_FAILED_ATTEMPTS: i32 = 5;
+/// 锁定时长（秒）
+pub const LOCKOUT_SECONDS: u64 = 900;
+/// 后台 cookie 会话有效期（秒）
+pub const CONSOLE_SESSION_SECONDS: u64 = 28800;
+/// 后台会话 cookie 名
+pub const CONSOLE_SESSION_COOKIE: &str = "sd_console_session";
+/// 携带 CSRF token 的请求头
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// 保存在加密 cookie 中的后台会话
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleSession {
+    pub user_id: i32,
+    /// 登录时用户的 token 版本号，吊销 token 或修改密码后会话随之失效
+    pub ver: i32,
+    pub exp: u64,
+    /// 写操作需在 `x-csrf-token` 头中回传
+    pub csrf: String,
+}
+
+#[derive(Debug)]
+pub enum LocalAuthError {
+    /// 用户名或密码错误，不区分用户名是否存在
+    InvalidCredentials,
+    /// 连续失败次数过多，锁定到 `until`
+    Locked {
+        until: i64,
+    },
+    InvalidUsername,
+    WeakPassword,
+    UsernameTaken,
+    /// 用户没有本地账号
+    AccountNotFound,
+    UserNotFound,
+    AccountBlocked(UserStatusError),
+    HashError(String),
+    DatabaseError(String),
+}
+
+impl LocalAuthError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            LocalAuthError::InvalidCredentials => (401, "Invalid username or password".to_string()),
+            LocalAuthError::Locked { until } => (
+                429,
+                format!("Too many failed attempts, account locked until {}", until),
+            ),
+            LocalAuthError::InvalidUsername => (
+                400,
+                "Username must be 3-32 characters of a-z, 0-9, '_', '.' or '-'".to_string(),
+            ),
+            LocalAuthError::WeakPassword => (
+                400,
+                format!(
+                    "Password must be at least {} characters",
+                    MIN_PASSWORD_LENGTH
+                ),
+            ),
+            LocalAuthError::UsernameTaken => (409, "Username already taken".to_string()),
+            LocalAuthError::AccountNotFound => (404, "Local account not found".to_string()),
+            LocalAuthError::UserNotFound => (404, "User not found".to_string()),
+            LocalAuthError::AccountBlocked(e) => e.code_and_message(),
+            LocalAuthError::HashError(e) => (500, format!("Password hash error: {}", e)),
+            LocalAuthError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for LocalAuthError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        LocalAuthError::DatabaseError(err.to_string())
+    }
+}
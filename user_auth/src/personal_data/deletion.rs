@@ -0,0 +1,217 @@
+use super::{
+    ACCOUNT_DELETION_GRACE_SECONDS, DELETED_OPEN_ID_PREFIX, PersonalDataError, PurgeReport,
+    find_avatar_media,
+};
+use crate::db_exchange::now_timestamp;
+use crate::user_status::UserStatus;
+use db_manager::entity::{
+    account_deletion, admin_totp, ai_chat, audit_subject, caregiver_action_log, caregiver_link,
+    dinner_provider_owner, feedback, local_credential, mutil_media, refresh_token,
+    totp_recovery_code, user as user_entity, user_role, user_status_log, wx_session,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+
+/// Pending deletion request of a user, if any.
+pub async fn find_account_deletion<C>(
+    db: &C,
+    user_id: i32,
+) -> Result<Option<account_deletion::Model>, PersonalDataError>
+where
+    C: ConnectionTrait,
+{
+    Ok(account_deletion::Entity::find()
+        .filter(account_deletion::Column::UserId.eq(user_id))
+        .one(db)
+        .await?)
+}
+
+/// Request deletion of the user's account. Personal data is removed once the
+/// grace period has passed; until then the user can still sign in and cancel.
+pub async fn request_account_deletion<C>(
+    db: &C,
+    user: &user_entity::Model,
+) -> Result<account_deletion::Model, PersonalDataError>
+where
+    C: ConnectionTrait,
+{
+    if user.open_id.starts_with(DELETED_OPEN_ID_PREFIX) {
+        return Err(PersonalDataError::AccountDeleted);
+    }
+    if find_account_deletion(db, user.id).await?.is_some() {
+        return Err(PersonalDataError::DeletionPending);
+    }
+    let now = now_timestamp();
+    Ok(account_deletion::ActiveModel {
+        user_id: Set(user.id),
+        requested_at: Set(now as i64),
+        purge_at: Set((now + ACCOUNT_DELETION_GRACE_SECONDS) as i64),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?)
+}
+
+/// Withdraw a pending deletion request during the grace period.
+pub async fn cancel_account_deletion<C>(db: &C, user_id: i32) -> Result<(), PersonalDataError>
+where
+    C: ConnectionTrait,
+{
+    let deleted = account_deletion::Entity::delete_many()
+        .filter(account_deletion::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(PersonalDataError::DeletionNotFound);
+    }
+    Ok(())
+}
+
+/// Remove a user's personal data and anonymize the `user` row.
+///
+/// Rows owned only by the user (chats, feedback, avatar, credentials, sessions,
+/// roles, caregiver links) are deleted. The `user` row keeps its primary key so
+/// audit records written by or about the user stay consistent, but every
+/// personal field is cleared and `open_id` is replaced, so the same WeChat
+/// account signs up as a new user next time. The user's audit pseudonym is
+/// forgotten, which unlinks the append-only audit log from the person.
+pub async fn purge_account<C>(
+    db: &C,
+    user: user_entity::Model,
+) -> Result<user_entity::Model, PersonalDataError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let user_id = user.id;
+
+    // 1) 按 open_id 关联的数据
+    ai_chat::Entity::delete_many()
+        .filter(ai_chat::Column::Openid.eq(user.open_id.clone()))
+        .exec(&txn)
+        .await?;
+    feedback::Entity::delete_many()
+        .filter(feedback::Column::Openid.eq(user.open_id.clone()))
+        .exec(&txn)
+        .await?;
+    audit_subject::Entity::delete_many()
+        .filter(audit_subject::Column::OpenId.eq(user.open_id.clone()))
+        .exec(&txn)
+        .await?;
+    if let Some(avatar) = find_avatar_media(&txn, &user).await? {
+        mutil_media::Entity::delete_by_id(avatar.id)
+            .exec(&txn)
+            .await?;
+    }
+
+    // 2) 登录凭据、会话与授权
+    refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    wx_session::Entity::delete_many()
+        .filter(wx_session::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    local_credential::Entity::delete_many()
+        .filter(local_credential::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    totp_recovery_code::Entity::delete_many()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    admin_totp::Entity::delete_many()
+        .filter(admin_totp::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    user_role::Entity::delete_many()
+        .filter(user_role::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    dinner_provider_owner::Entity::delete_many()
+        .filter(dinner_provider_owner::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+
+    // 3) 照护关联及针对该用户的操作记录
+    caregiver_action_log::Entity::delete_many()
+        .filter(caregiver_action_log::Column::ElderId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    caregiver_link::Entity::delete_many()
+        .filter(
+            caregiver_link::Column::CaregiverId
+                .eq(user_id)
+                .or(caregiver_link::Column::ElderId.eq(user_id)),
+        )
+        .exec(&txn)
+        .await?;
+
+    // 4) 记录状态变更并匿名化
+    user_status_log::ActiveModel {
+        user_id: Set(user_id),
+        operator_id: Set(None),
+        from_status: Set(user.status.clone()),
+        to_status: Set(UserStatus::Deleted.as_str().to_string()),
+        reason: Set(Some("account deletion requested by user".to_string())),
+        suspended_until: Set(None),
+        created_at: Set(now_timestamp() as i64),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    let mut active: user_entity::ActiveModel = user.clone().into();
+    active.id = ActiveValue::Unchanged(user_id);
+    active.open_id = Set(format!("{}{}", DELETED_OPEN_ID_PREFIX, user_id));
+    active.nickname = Set(None);
+    active.avatar = Set(None);
+    active.permission = Set(Some(0));
+    active.name = Set(None);
+    active.phone_number = Set(None);
+    active.address = Set(None);
+    active.is_important = Set(None);
+    active.token_version = Set(user.token_version + 1);
+    active.status = Set(UserStatus::Deleted.as_str().to_string());
+    active.suspended_reason = Set(None);
+    active.suspended_until = Set(None);
+    let anonymized = active.update(&txn).await?;
+
+    account_deletion::Entity::delete_many()
+        .filter(account_deletion::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(anonymized)
+}
+
+/// Purge every account whose grace period ended before `now`.
+///
+/// A failing account does not stop the batch: it is reported in
+/// [`PurgeReport::failed`] and its request is kept for the next run.
+pub async fn purge_due_accounts<C>(db: &C, now: u64) -> Result<PurgeReport, PersonalDataError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let due = account_deletion::Entity::find()
+        .filter(account_deletion::Column::PurgeAt.lte(now as i64))
+        .order_by_asc(account_deletion::Column::PurgeAt)
+        .find_also_related(user_entity::Entity)
+        .all(db)
+        .await?;
+
+    let mut report = PurgeReport::default();
+    for (_, user) in due {
+        if let Some(user) = user {
+            let user_id = user.id;
+            match purge_account(db, user).await {
+                Ok(purged) => report.purged.push(purged.id),
+                Err(err) => report.failed.push((user_id, err)),
+            }
+        }
+    }
+    Ok(report)
+}
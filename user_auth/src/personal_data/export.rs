@@ -0,0 +1,161 @@
+use super::{ExportFile, PersonalDataError};
+use db_manager::entity::{
+    ai_chat, caregiver_action_log, caregiver_link, feedback, local_credential, mutil_media,
+    user as user_entity,
+};
+use sea_orm::prelude::Uuid;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::{Value, json};
+
+fn json_file(path: &str, value: Value) -> ExportFile {
+    ExportFile {
+        path: path.to_string(),
+        content: serde_json::to_vec_pretty(&value).unwrap_or_default(),
+    }
+}
+
+/// The avatar stored in `mutil_media`, when `user.avatar` holds its uuid.
+pub async fn find_avatar_media<C>(
+    db: &C,
+    user: &user_entity::Model,
+) -> Result<Option<mutil_media::Model>, PersonalDataError>
+where
+    C: ConnectionTrait,
+{
+    let Some(uuid) = user.avatar.as_deref().and_then(|a| Uuid::parse_str(a).ok()) else {
+        return Ok(None);
+    };
+    Ok(mutil_media::Entity::find()
+        .filter(mutil_media::Column::Uuid.eq(uuid))
+        .one(db)
+        .await?)
+}
+
+/// Collect everything tied to a user as files for the export archive.
+///
+/// Secrets (password hashes, TOTP secrets, token hashes, WeChat session keys)
+/// are never exported; only the fact that they exist is listed in the profile.
+pub async fn export_personal_data<C>(
+    db: &C,
+    user: &user_entity::Model,
+) -> Result<Vec<ExportFile>, PersonalDataError>
+where
+    C: ConnectionTrait,
+{
+    if user.open_id.starts_with(super::DELETED_OPEN_ID_PREFIX) {
+        return Err(PersonalDataError::AccountDeleted);
+    }
+
+    // 1) 资料与登录方式
+    let credential = local_credential::Entity::find()
+        .filter(local_credential::Column::UserId.eq(user.id))
+        .one(db)
+        .await?;
+    let mut files = vec![json_file(
+        "profile.json",
+        json!({
+            "open_id": user.open_id,
+            "nickname": user.nickname,
+            "name": user.name,
+            "phone_number": user.phone_number,
+            "address": user.address,
+            "avatar": user.avatar,
+            "is_important": user.is_important,
+            "permission": user.permission,
+            "status": user.status,
+            "console_username": credential.map(|c| c.username),
+        }),
+    )];
+
+    // 2) 头像
+    if let Some(media) = find_avatar_media(db, user).await?
+        && let Some(file) = media.file
+    {
+        files.push(ExportFile {
+            path: format!("avatar.{}", media.r#type.as_deref().unwrap_or("bin")),
+            content: file,
+        });
+    }
+
+    // 3) AI 聊天记录
+    let chats = ai_chat::Entity::find()
+        .filter(ai_chat::Column::Openid.eq(user.open_id.clone()))
+        .order_by_asc(ai_chat::Column::Id)
+        .all(db)
+        .await?;
+    files.push(json_file(
+        "ai_chat.json",
+        Value::Array(
+            chats
+                .into_iter()
+                .map(|c| json!({ "id": c.id, "index": c.index, "content": c.long_content }))
+                .collect(),
+        ),
+    ));
+
+    // 4) 反馈
+    let feedbacks = feedback::Entity::find()
+        .filter(feedback::Column::Openid.eq(user.open_id.clone()))
+        .order_by_asc(feedback::Column::Id)
+        .all(db)
+        .await?;
+    files.push(json_file(
+        "feedback.json",
+        Value::Array(
+            feedbacks
+                .into_iter()
+                .map(|f| {
+                    json!({
+                        "id": f.id,
+                        "type": f.r#type,
+                        "content": f.content,
+                        "phone": f.phone,
+                        "created_time": f.created_time.to_rfc3339(),
+                    })
+                })
+                .collect(),
+        ),
+    ));
+
+    // 5) 照护关联与照护人的操作记录
+    let links = caregiver_link::Entity::find()
+        .filter(
+            caregiver_link::Column::CaregiverId
+                .eq(user.id)
+                .or(caregiver_link::Column::ElderId.eq(user.id)),
+        )
+        .order_by_asc(caregiver_link::Column::Id)
+        .all(db)
+        .await?;
+    let actions = caregiver_action_log::Entity::find()
+        .filter(caregiver_action_log::Column::ElderId.eq(user.id))
+        .order_by_asc(caregiver_action_log::Column::Id)
+        .all(db)
+        .await?;
+    files.push(json_file(
+        "caregiver.json",
+        json!({
+            "links": links
+                .into_iter()
+                .map(|l| json!({
+                    "role": if l.caregiver_id == user.id { "caregiver" } else { "elder" },
+                    "status": l.status,
+                    "relationship": l.relationship,
+                    "created_at": l.created_at,
+                    "approved_at": l.approved_at,
+                    "revoked_at": l.revoked_at,
+                }))
+                .collect::<Vec<_>>(),
+            "actions": actions
+                .into_iter()
+                .map(|a| json!({
+                    "action": a.action,
+                    "detail": a.detail,
+                    "created_at": a.created_at,
+                }))
+                .collect::<Vec<_>>(),
+        }),
+    ));
+
+    Ok(files)
+}
@@ -0,0 +1,15 @@
+//! 个人信息导出与账号注销
+//!
+//! 按《个人信息保护法》的要求，用户可以自助导出与自己 open_id 关联的全部数据
+//! （资料、头像、AI 聊天记录、反馈等），也可以申请注销账号。注销申请有宽限期，
+//! 期间可以撤回；宽限期结束后删除个人数据，`user` 行只保留匿名化后的主键，
+//! 以免破坏其他用户的审计记录（邀请码签发人、状态变更操作人等）。审计日志中只有
+//! open_id 的化名，注销时删除化名的对应关系，这些记录便无法再关联到本人。
+
+pub mod deletion;
+pub mod export;
+pub mod r#struct;
+
+pub use deletion::*;
+pub use export::*;
+pub use r#struct::*;
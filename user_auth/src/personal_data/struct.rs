@@ -0,0 +1,51 @@
+/// 注销申请的宽限期：15 天
+pub const ACCOUNT_DELETION_GRACE_SECONDS: u64 = 15 * 24 * 60 * 60;
+
+/// 注销后 `user.open_id` 的前缀，原 open_id 再次登录时会注册为新用户
+pub const DELETED_OPEN_ID_PREFIX: &str = "deleted:";
+
+/// 导出压缩包中的一个文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportFile {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+/// 一次批量注销的结果
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    /// 已注销的用户 id
+    pub purged: Vec<i32>,
+    /// 注销失败的用户 id 与原因，申请保留，下次执行时重试
+    pub failed: Vec<(i32, PersonalDataError)>,
+}
+
+#[derive(Debug)]
+pub enum PersonalDataError {
+    /// 已有待执行的注销申请
+    DeletionPending,
+    DeletionNotFound,
+    /// 账号已注销
+    AccountDeleted,
+    DatabaseError(String),
+}
+
+impl PersonalDataError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            PersonalDataError::DeletionPending => {
+                (409, "Account deletion already requested".to_string())
+            }
+            PersonalDataError::DeletionNotFound => (404, "No pending account deletion".to_string()),
+            PersonalDataError::AccountDeleted => (410, "Account deleted".to_string()),
+            PersonalDataError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for PersonalDataError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        PersonalDataError::DatabaseError(err.to_string())
+    }
+}
@@ -0,0 +1,64 @@
+use super::{PermissionSet, RbacError, default_role_name};
+use crate::user_auth::UserPermissionLevel;
+use db_manager::entity::{role, role_permission, user as user_entity, user_role};
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect};
+
+/// Names of every role a user holds: the builtin role matching the user's
+/// permission level plus the roles assigned in `user_role`.
+pub async fn load_roles<C>(db: &C, user: &user_entity::Model) -> Result<Vec<role::Model>, RbacError>
+where
+    C: ConnectionTrait,
+{
+    let level = UserPermissionLevel::from(user.permission.unwrap_or(0));
+    let assigned = user_role::Entity::find()
+        .select_only()
+        .column(user_role::Column::RoleId)
+        .filter(user_role::Column::UserId.eq(user.id))
+        .into_tuple::<i32>()
+        .all(db)
+        .await?;
+
+    Ok(role::Entity::find()
+        .filter(
+            Condition::any()
+                .add(role::Column::Name.eq(default_role_name(&level)))
+                .add(role::Column::Id.is_in(assigned)),
+        )
+        .all(db)
+        .await?)
+}
+
+/// Load the effective permissions of a user from the database.
+pub async fn load_permissions<C>(
+    db: &C,
+    user: &user_entity::Model,
+) -> Result<PermissionSet, RbacError>
+where
+    C: ConnectionTrait,
+{
+    let role_ids: Vec<i32> = load_roles(db, user)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    let permissions = role_permission::Entity::find()
+        .select_only()
+        .column(role_permission::Column::Permission)
+        .filter(role_permission::Column::RoleId.is_in(role_ids))
+        .into_tuple::<String>()
+        .all(db)
+        .await?;
+    Ok(PermissionSet::new(permissions))
+}
+
+/// Check whether a user holds `permission`.
+pub async fn authorize_permission<C>(
+    db: &C,
+    user: &user_entity::Model,
+    permission: &str,
+) -> Result<bool, RbacError>
+where
+    C: ConnectionTrait,
+{
+    Ok(load_permissions(db, user).await?.allows(permission))
+}
@@ -0,0 +1,227 @@
+use super::permission::{ALL, is_valid_permission};
+use super::{PermissionSet, RbacError, RoleDetail};
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::{role, role_permission, user_role};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+
+async fn find_role<C>(db: &C, name: &str) -> Result<role::Model, RbacError>
+where
+    C: ConnectionTrait,
+{
+    role::Entity::find()
+        .filter(role::Column::Name.eq(name))
+        .one(db)
+        .await?
+        .ok_or_else(|| RbacError::RoleNotFound(name.to_string()))
+}
+
+/// List all roles with their permissions.
+pub async fn list_roles<C>(db: &C) -> Result<Vec<RoleDetail>, RbacError>
+where
+    C: ConnectionTrait,
+{
+    let roles = role::Entity::find()
+        .order_by_asc(role::Column::Id)
+        .find_with_related(role_permission::Entity)
+        .all(db)
+        .await?;
+    Ok(roles
+        .into_iter()
+        .map(|(role, permissions)| RoleDetail {
+            name: role.name,
+            description: role.description,
+            is_builtin: role.is_builtin,
+            permissions: permissions.into_iter().map(|p| p.permission).collect(),
+        })
+        .collect())
+}
+
+/// Reject any permission the granter does not hold itself.
+fn ensure_can_grant<'a, I>(granter: &PermissionSet, permissions: I) -> Result<(), RbacError>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    match permissions.into_iter().find(|p| !granter.can_grant(p)) {
+        Some(denied) => Err(RbacError::GrantNotAllowed(denied.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Reject assigning or removing a role whose permissions the granter does not hold.
+///
+/// The builtin admin role is only managed by a caller holding `*`.
+async fn ensure_can_grant_role<C>(
+    db: &C,
+    granter: &PermissionSet,
+    role: &role::Model,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    if role.name == "admin" && !granter.can_grant(ALL) {
+        return Err(RbacError::GrantNotAllowed(ALL.to_string()));
+    }
+    let permissions = role_permission::Entity::find()
+        .filter(role_permission::Column::RoleId.eq(role.id))
+        .all(db)
+        .await?;
+    ensure_can_grant(granter, permissions.iter().map(|p| &p.permission))
+}
+
+/// Create a role, or replace the description and permissions of an existing one.
+///
+/// Builtin roles (admin and the roles mapped from permission levels) can not be
+/// edited. `granter` is the caller's effective permissions: a role may only hold
+/// permissions the caller holds.
+pub async fn upsert_role<C>(
+    db: &C,
+    granter: &PermissionSet,
+    name: &str,
+    description: Option<String>,
+    permissions: Vec<String>,
+) -> Result<role::Model, RbacError>
+where
+    C: TransactionTrait,
+{
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(RbacError::InvalidRoleName);
+    }
+    if name == "admin" {
+        return Err(RbacError::BuiltinRole(name.to_string()));
+    }
+    if let Some(invalid) = permissions.iter().find(|p| !is_valid_permission(p)) {
+        return Err(RbacError::InvalidPermission(invalid.clone()));
+    }
+    ensure_can_grant(granter, &permissions)?;
+
+    let txn = db.begin().await?;
+
+    // 1) 新建或更新角色
+    let role = match role::Entity::find()
+        .filter(role::Column::Name.eq(name))
+        .one(&txn)
+        .await?
+    {
+        Some(existing) if existing.is_builtin => {
+            return Err(RbacError::BuiltinRole(existing.name));
+        }
+        Some(existing) => {
+            let mut active: role::ActiveModel = existing.into();
+            active.description = Set(description);
+            active.update(&txn).await?
+        }
+        None => {
+            role::ActiveModel {
+                name: Set(name.to_string()),
+                description: Set(description),
+                is_builtin: Set(false),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?
+        }
+    };
+
+    // 2) 整体替换权限
+    role_permission::Entity::delete_many()
+        .filter(role_permission::Column::RoleId.eq(role.id))
+        .exec(&txn)
+        .await?;
+    let mut permissions = permissions;
+    permissions.sort();
+    permissions.dedup();
+    if !permissions.is_empty() {
+        role_permission::Entity::insert_many(permissions.into_iter().map(|permission| {
+            role_permission::ActiveModel {
+                role_id: Set(role.id),
+                permission: Set(permission),
+                ..Default::default()
+            }
+        }))
+        .exec(&txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+    Ok(role)
+}
+
+/// Delete a custom role. Builtin roles can not be deleted.
+pub async fn delete_role<C>(db: &C, name: &str) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    let role = find_role(db, name).await?;
+    if role.is_builtin {
+        return Err(RbacError::BuiltinRole(role.name));
+    }
+    role::Entity::delete_by_id(role.id).exec(db).await?;
+    Ok(())
+}
+
+/// Assign a role to a user. Assigning a role twice is a no-op.
+///
+/// `granter` must hold every permission of the role; the builtin admin role can
+/// only be assigned by a caller holding `*`.
+pub async fn assign_role<C>(
+    db: &C,
+    granter: &PermissionSet,
+    user_id: i32,
+    role_name: &str,
+    granted_by: Option<i32>,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    let role = find_role(db, role_name).await?;
+    ensure_can_grant_role(db, granter, &role).await?;
+
+    let existing = user_role::Entity::find()
+        .filter(user_role::Column::UserId.eq(user_id))
+        .filter(user_role::Column::RoleId.eq(role.id))
+        .one(db)
+        .await?;
+    if existing.is_none() {
+        user_role::ActiveModel {
+            user_id: Set(user_id),
+            role_id: Set(role.id),
+            granted_by: Set(granted_by),
+            created_at: Set(now_timestamp() as i64),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Remove a role from a user.
+///
+/// Subject to the same check as `assign_role`: `granter` must hold every
+/// permission of the role.
+pub async fn unassign_role<C>(
+    db: &C,
+    granter: &PermissionSet,
+    user_id: i32,
+    role_name: &str,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    let role = find_role(db, role_name).await?;
+    ensure_can_grant_role(db, granter, &role).await?;
+    user_role::Entity::delete_many()
+        .filter(user_role::Column::UserId.eq(user_id))
+        .filter(user_role::Column::RoleId.eq(role.id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
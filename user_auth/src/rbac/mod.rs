@@ -0,0 +1,10 @@
+pub mod authorize;
+pub mod manage;
+pub mod owner;
+pub mod permission;
+pub mod r#struct;
+
+pub use authorize::*;
+pub use manage::*;
+pub use owner::*;
+pub use r#struct::*;
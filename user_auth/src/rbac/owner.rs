@@ -0,0 +1,110 @@
+use super::RbacError;
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::{dinner_provider, dinner_provider_owner};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+/// Ids of the dinner providers (canteens) operated by a user.
+pub async fn owned_provider_ids<C>(db: &C, user_id: i32) -> Result<Vec<i32>, RbacError>
+where
+    C: ConnectionTrait,
+{
+    Ok(dinner_provider_owner::Entity::find()
+        .select_only()
+        .column(dinner_provider_owner::Column::DinnerProviderId)
+        .filter(dinner_provider_owner::Column::UserId.eq(user_id))
+        .order_by_asc(dinner_provider_owner::Column::DinnerProviderId)
+        .into_tuple::<i32>()
+        .all(db)
+        .await?)
+}
+
+/// Fail with `NotProviderOwner` unless the user operates `dinner_provider_id`.
+pub async fn ensure_provider_owner<C>(
+    db: &C,
+    user_id: i32,
+    dinner_provider_id: i32,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    let owned = dinner_provider_owner::Entity::find()
+        .filter(dinner_provider_owner::Column::UserId.eq(user_id))
+        .filter(dinner_provider_owner::Column::DinnerProviderId.eq(dinner_provider_id))
+        .one(db)
+        .await?;
+    match owned {
+        Some(_) => Ok(()),
+        None => Err(RbacError::NotProviderOwner(dinner_provider_id)),
+    }
+}
+
+/// Owners of a dinner provider.
+pub async fn list_provider_owners<C>(
+    db: &C,
+    dinner_provider_id: i32,
+) -> Result<Vec<dinner_provider_owner::Model>, RbacError>
+where
+    C: ConnectionTrait,
+{
+    Ok(dinner_provider_owner::Entity::find()
+        .filter(dinner_provider_owner::Column::DinnerProviderId.eq(dinner_provider_id))
+        .order_by_asc(dinner_provider_owner::Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// Let a user operate a dinner provider. Assigning twice is a no-op.
+pub async fn assign_provider_owner<C>(
+    db: &C,
+    user_id: i32,
+    dinner_provider_id: i32,
+    granted_by: Option<i32>,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    if dinner_provider::Entity::find_by_id(dinner_provider_id)
+        .one(db)
+        .await?
+        .is_none()
+    {
+        return Err(RbacError::DinnerProviderNotFound(dinner_provider_id));
+    }
+    let existing = dinner_provider_owner::Entity::find()
+        .filter(dinner_provider_owner::Column::UserId.eq(user_id))
+        .filter(dinner_provider_owner::Column::DinnerProviderId.eq(dinner_provider_id))
+        .one(db)
+        .await?;
+    if existing.is_none() {
+        dinner_provider_owner::ActiveModel {
+            user_id: Set(user_id),
+            dinner_provider_id: Set(dinner_provider_id),
+            granted_by: Set(granted_by),
+            created_at: Set(now_timestamp() as i64),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Stop a user from operating a dinner provider.
+pub async fn unassign_provider_owner<C>(
+    db: &C,
+    user_id: i32,
+    dinner_provider_id: i32,
+) -> Result<(), RbacError>
+where
+    C: ConnectionTrait,
+{
+    dinner_provider_owner::Entity::delete_many()
+        .filter(dinner_provider_owner::Column::UserId.eq(user_id))
+        .filter(dinner_provider_owner::Column::DinnerProviderId.eq(dinner_provider_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! 权限标识
+//!
+//! 权限以 `资源.操作` 的字符串保存在 `role_permission` 表中。
+//! `*` 表示全部权限，`资源.*` 表示该资源下的全部操作。
+
+/// 全部权限，内置 admin 角色持有
+pub const ALL: &str = "*";
+/// 健康指南类型与内容的增删改
+pub const HEALTH_GUIDE_WRITE: &str = "health_guide.write";
+/// 餐食信息的新增与修改，内置 provider 角色持有
+pub const MEAL_WRITE: &str = "meal.write";
+/// 餐食与服务商的新增、删除等管理操作
+pub const MEAL_MANAGE: &str = "meal.manage";
+/// 反馈的查看与导出
+pub const FEEDBACK_MANAGE: &str = "feedback.manage";
+/// 角色的创建、修改与分配
+pub const ROLE_MANAGE: &str = "role.manage";
+/// 审计记录的查询与导出
+pub const AUDIT_READ: &str = "audit.read";
+
+/// 系统中已定义的全部权限，用于校验管理员提交的角色配置
+pub const KNOWN_PERMISSIONS: [&str; 6] = [
+    HEALTH_GUIDE_WRITE,
+    MEAL_WRITE,
+    MEAL_MANAGE,
+    FEEDBACK_MANAGE,
+    ROLE_MANAGE,
+    AUDIT_READ,
+];
+
+/// 需要二次验证（step-up）的权限，持有者可以绑定 TOTP
+pub const PRIVILEGED_PERMISSIONS: [&str; 4] =
+    [MEAL_MANAGE, FEEDBACK_MANAGE, ROLE_MANAGE, AUDIT_READ];
+
+/// 是否为需要二次验证的权限
+pub fn is_privileged(permission: &str) -> bool {
+    PRIVILEGED_PERMISSIONS.contains(&permission)
+}
+
+/// 权限标识是否合法：已定义的权限、`*` 或已定义资源的 `资源.*`
+pub fn is_valid_permission(permission: &str) -> bool {
+    if permission == ALL || KNOWN_PERMISSIONS.contains(&permission) {
+        return true;
+    }
+    match permission.strip_suffix(".*") {
+        Some(resource) => KNOWN_PERMISSIONS
+            .iter()
+            .any(|known| known.split('.').next() == Some(resource)),
+        None => false,
+    }
+}
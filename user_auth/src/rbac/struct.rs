@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+
+use super::permission::{ALL, PRIVILEGED_PERMISSIONS};
+use crate::user_auth::UserPermissionLevel;
+
+/// 与权限等级对应的内置角色名，旧客户端依赖的等级据此映射为角色
+pub fn default_role_name(level: &UserPermissionLevel) -> &'static str {
+    match level {
+        UserPermissionLevel::Admin => "admin",
+        UserPermissionLevel::Provider => "provider",
+        UserPermissionLevel::User => "user",
+        UserPermissionLevel::Guest => "guest",
+    }
+}
+
+/// 用户生效的权限集合
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSet {
+    permissions: BTreeSet<String>,
+}
+
+impl PermissionSet {
+    pub fn new<I, S>(permissions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        PermissionSet {
+            permissions: permissions.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 是否允许 `permission`，支持 `*` 与 `资源.*` 通配
+    pub fn allows(&self, permission: &str) -> bool {
+        if self.permissions.contains(ALL) || self.permissions.contains(permission) {
+            return true;
+        }
+        permission
+            .split_once('.')
+            .is_some_and(|(resource, _)| self.permissions.contains(&format!("{}.*", resource)))
+    }
+
+    /// 持有该集合的操作者能否授予 `permission`
+    ///
+    /// 只能授予自己已经持有的权限：`*` 需要自己持有 `*`，`资源.*` 需要持有 `*` 或 `资源.*`
+    pub fn can_grant(&self, permission: &str) -> bool {
+        self.allows(permission)
+    }
+
+    /// 是否持有任一需要二次验证的权限（含通配）
+    pub fn has_privileged(&self) -> bool {
+        PRIVILEGED_PERMISSIONS.iter().any(|p| self.allows(p))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.permissions.iter()
+    }
+}
+
+/// 角色及其权限
+#[derive(Debug, Clone)]
+pub struct RoleDetail {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_builtin: bool,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum RbacError {
+    RoleNotFound(String),
+    BuiltinRole(String),
+    InvalidRoleName,
+    InvalidPermission(String),
+    /// 授予了操作者自身不持有的权限
+    GrantNotAllowed(String),
+    DinnerProviderNotFound(i32),
+    NotProviderOwner(i32),
+    DatabaseError(String),
+}
+
+impl RbacError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            RbacError::RoleNotFound(name) => (404, format!("Role not found: {}", name)),
+            RbacError::BuiltinRole(name) => {
+                (400, format!("Builtin role can not be changed: {}", name))
+            }
+            RbacError::InvalidRoleName => (400, "Invalid role name".to_string()),
+            RbacError::InvalidPermission(p) => (400, format!("Invalid permission: {}", p)),
+            RbacError::GrantNotAllowed(p) => (
+                403,
+                format!("Permission denied: can not grant {} without holding it", p),
+            ),
+            RbacError::DinnerProviderNotFound(id) => {
+                (404, format!("Dinner provider not found: {}", id))
+            }
+            RbacError::NotProviderOwner(id) => (
+                403,
+                format!("Permission denied: not an owner of dinner provider {}", id),
+            ),
+            RbacError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for RbacError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        RbacError::DatabaseError(err.to_string())
+    }
+}
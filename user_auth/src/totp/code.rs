@@ -0,0 +1,137 @@
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+use super::{TOTP_DIGITS, TOTP_ISSUER, TOTP_PERIOD, TOTP_SKEW};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 base32 编码（无填充），验证器应用普遍使用该格式
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// base32 解码，忽略大小写、空格与填充，含非法字符时返回 `None`
+pub fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// 生成 160 位随机密钥（base32）
+pub fn generate_totp_secret() -> String {
+    let mut bytes = [0u8; 20];
+    rand::rng().fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// HOTP（RFC 4226）
+pub fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = <Hmac<Sha1> as Mac>::new_from_slice(secret).expect("HMAC accepts any key size");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(TOTP_DIGITS)
+}
+
+/// `unix` 时刻所在的时间步
+pub fn totp_step(unix: u64) -> i64 {
+    (unix / TOTP_PERIOD) as i64
+}
+
+/// `unix` 时刻的验证码，补足前导零
+pub fn totp_code(secret: &[u8], unix: u64) -> String {
+    format!(
+        "{:0width$}",
+        hotp(secret, totp_step(unix) as u64),
+        width = TOTP_DIGITS as usize
+    )
+}
+
+/// 校验验证码，返回匹配的时间步
+///
+/// 允许 `TOTP_SKEW` 个时间步的误差；`after_step` 为上次使用的时间步，
+/// 不大于它的时间步视为重放
+pub fn verify_totp_code(
+    secret_base32: &str,
+    code: &str,
+    unix: u64,
+    after_step: Option<i64>,
+) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let secret = base32_decode(secret_base32)?;
+    let current = totp_step(unix);
+    (current - TOTP_SKEW..=current + TOTP_SKEW)
+        .filter(|step| *step >= 0 && after_step.is_none_or(|last| *step > last))
+        .find(|step| {
+            let expected = format!(
+                "{:0width$}",
+                hotp(&secret, *step as u64),
+                width = TOTP_DIGITS as usize
+            );
+            constant_time_eq(expected.as_bytes(), code.as_bytes())
+        })
+}
+
+/// 供验证器扫码的 `otpauth://totp/...` URI
+pub fn otpauth_uri(account: &str, secret_base32: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+        issuer = percent_encode(TOTP_ISSUER),
+        account = percent_encode(account),
+        secret = secret_base32,
+        digits = TOTP_DIGITS,
+        period = TOTP_PERIOD,
+    )
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
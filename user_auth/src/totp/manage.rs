@@ -0,0 +1,283 @@
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+
+use super::code::{generate_totp_secret, otpauth_uri, verify_totp_code};
+use super::{RECOVERY_CODE_COUNT, TOTP_DIGITS, TotpEnrollment, TotpError};
+use crate::db_exchange::{now_timestamp, random_hex};
+use crate::local_auth::{Lockout, register_failure};
+use db_manager::entity::{admin_totp, totp_recovery_code, user as user_entity};
+
+impl Lockout for admin_totp::Entity {
+    const ID: Self::Column = admin_totp::Column::Id;
+    const FAILED_ATTEMPTS: Self::Column = admin_totp::Column::FailedAttempts;
+    const LOCKED_UNTIL: Self::Column = admin_totp::Column::LockedUntil;
+}
+
+/// 恢复码去掉分隔符并转小写后取 sha256
+fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// TOTP binding of a user, if any (enabled or pending confirmation).
+pub async fn find_totp<C>(db: &C, user_id: i32) -> Result<Option<admin_totp::Model>, TotpError>
+where
+    C: ConnectionTrait,
+{
+    Ok(admin_totp::Entity::find()
+        .filter(admin_totp::Column::UserId.eq(user_id))
+        .one(db)
+        .await?)
+}
+
+/// Whether the user has a confirmed TOTP binding.
+pub async fn totp_enabled<C>(db: &C, user_id: i32) -> Result<bool, TotpError>
+where
+    C: ConnectionTrait,
+{
+    Ok(find_totp(db, user_id).await?.is_some_and(|t| t.enabled))
+}
+
+/// Number of recovery codes the user has not used yet.
+pub async fn remaining_recovery_codes<C>(db: &C, user_id: i32) -> Result<u64, TotpError>
+where
+    C: ConnectionTrait,
+{
+    Ok(totp_recovery_code::Entity::find()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .filter(totp_recovery_code::Column::UsedAt.is_null())
+        .count(db)
+        .await?)
+}
+
+/// Generate a new secret for the user, replacing any pending one.
+///
+/// The binding stays disabled until `confirm_totp_enrollment` sees a valid code.
+pub async fn begin_totp_enrollment<C>(
+    db: &C,
+    user: &user_entity::Model,
+) -> Result<TotpEnrollment, TotpError>
+where
+    C: ConnectionTrait,
+{
+    let secret = generate_totp_secret();
+    let now = now_timestamp() as i64;
+    match find_totp(db, user.id).await? {
+        Some(existing) if existing.enabled => return Err(TotpError::AlreadyEnabled),
+        Some(existing) => {
+            let mut active: admin_totp::ActiveModel = existing.into();
+            active.secret = Set(secret.clone());
+            active.last_used_step = Set(None);
+            active.created_at = Set(now);
+            active.update(db).await?;
+        }
+        None => {
+            admin_totp::ActiveModel {
+                user_id: Set(user.id),
+                secret: Set(secret.clone()),
+                enabled: Set(false),
+                last_used_step: Set(None),
+                created_at: Set(now),
+                enabled_at: Set(None),
+                failed_attempts: Set(0),
+                locked_until: Set(None),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+
+    let account = user
+        .nickname
+        .clone()
+        .unwrap_or_else(|| user.open_id.clone());
+    Ok(TotpEnrollment {
+        otpauth_uri: otpauth_uri(&account, &secret),
+        secret,
+    })
+}
+
+/// Replace the user's recovery codes, returning the new plaintext codes.
+async fn replace_recovery_codes<C>(db: &C, user_id: i32) -> Result<Vec<String>, TotpError>
+where
+    C: ConnectionTrait,
+{
+    totp_recovery_code::Entity::delete_many()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+
+    let now = now_timestamp() as i64;
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let raw = random_hex(5);
+            format!("{}-{}", &raw[..5], &raw[5..])
+        })
+        .collect();
+    totp_recovery_code::Entity::insert_many(codes.iter().map(|code| {
+        totp_recovery_code::ActiveModel {
+            user_id: Set(user_id),
+            code_hash: Set(hash_recovery_code(code)),
+            used_at: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        }
+    }))
+    .exec_without_returning(db)
+    .await?;
+    Ok(codes)
+}
+
+/// Enable a pending binding once the user proves the authenticator works.
+///
+/// Returns the recovery codes; they are only stored hashed and cannot be shown again.
+pub async fn confirm_totp_enrollment<C>(
+    db: &C,
+    user_id: i32,
+    code: &str,
+) -> Result<Vec<String>, TotpError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let totp = find_totp(&txn, user_id)
+        .await?
+        .ok_or(TotpError::NotEnrolled)?;
+    if totp.enabled {
+        return Err(TotpError::AlreadyEnabled);
+    }
+    let step = verify_totp_code(&totp.secret, code, now_timestamp(), None)
+        .ok_or(TotpError::InvalidCode)?;
+
+    let mut active: admin_totp::ActiveModel = totp.into();
+    active.enabled = Set(true);
+    active.enabled_at = Set(Some(now_timestamp() as i64));
+    active.last_used_step = Set(Some(step));
+    active.update(&txn).await?;
+    let codes = replace_recovery_codes(&txn, user_id).await?;
+
+    txn.commit().await?;
+    Ok(codes)
+}
+
+/// Check a second factor: a current TOTP code or an unused recovery code.
+///
+/// Both are single use: a TOTP code cannot be replayed within its time step and
+/// a recovery code is marked used. Failures count towards the same lockout as the
+/// local login (`local_auth::register_failure`); while locked every code is rejected.
+pub async fn verify_second_factor<C>(db: &C, user_id: i32, code: &str) -> Result<(), TotpError>
+where
+    C: ConnectionTrait,
+{
+    let totp = find_totp(db, user_id)
+        .await?
+        .filter(|t| t.enabled)
+        .ok_or(TotpError::NotEnrolled)?;
+
+    // 1) 锁定期内直接拒绝
+    let now = now_timestamp() as i64;
+    if let Some(until) = totp.locked_until
+        && until > now
+    {
+        return Err(TotpError::Locked { until });
+    }
+
+    // 2) 校验验证码或恢复码，失败累计次数
+    if !check_code(db, &totp, code.trim()).await? {
+        let locked_until = register_failure::<admin_totp::Entity, _>(db, totp.id, now).await?;
+        return Err(match locked_until {
+            Some(until) => TotpError::Locked { until },
+            None => TotpError::InvalidCode,
+        });
+    }
+
+    // 3) 成功后清零失败次数
+    if totp.failed_attempts != 0 || totp.locked_until.is_some() {
+        let mut active: admin_totp::ActiveModel = totp.into();
+        active.failed_attempts = Set(0);
+        active.locked_until = Set(None);
+        active.update(db).await?;
+    }
+    Ok(())
+}
+
+/// 校验并消耗验证码或恢复码
+async fn check_code<C>(db: &C, totp: &admin_totp::Model, code: &str) -> Result<bool, TotpError>
+where
+    C: ConnectionTrait,
+{
+    if code.len() == TOTP_DIGITS as usize {
+        let Some(step) = verify_totp_code(&totp.secret, code, now_timestamp(), totp.last_used_step)
+        else {
+            return Ok(false);
+        };
+        // 条件更新，两个并发请求使用同一验证码时只有一个成功
+        let result = admin_totp::Entity::update_many()
+            .col_expr(admin_totp::Column::LastUsedStep, Expr::value(step))
+            .filter(admin_totp::Column::Id.eq(totp.id))
+            .filter(
+                admin_totp::Column::LastUsedStep
+                    .is_null()
+                    .or(admin_totp::Column::LastUsedStep.lt(step)),
+            )
+            .exec(db)
+            .await?;
+        return Ok(result.rows_affected != 0);
+    }
+
+    let result = totp_recovery_code::Entity::update_many()
+        .col_expr(
+            totp_recovery_code::Column::UsedAt,
+            Expr::value(now_timestamp() as i64),
+        )
+        .filter(totp_recovery_code::Column::UserId.eq(totp.user_id))
+        .filter(totp_recovery_code::Column::CodeHash.eq(hash_recovery_code(code)))
+        .filter(totp_recovery_code::Column::UsedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected != 0)
+}
+
+/// Replace the user's recovery codes with a fresh set.
+///
+/// The caller checks the second factor first with `verify_second_factor`, outside
+/// any transaction the new codes are written in, so failed attempts are never rolled back.
+pub async fn regenerate_recovery_codes<C>(db: &C, user_id: i32) -> Result<Vec<String>, TotpError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let codes = replace_recovery_codes(&txn, user_id).await?;
+    txn.commit().await?;
+    Ok(codes)
+}
+
+/// Remove the user's TOTP binding and recovery codes.
+pub async fn disable_totp<C>(db: &C, user_id: i32) -> Result<(), TotpError>
+where
+    C: TransactionTrait,
+{
+    let txn = db.begin().await?;
+    totp_recovery_code::Entity::delete_many()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    admin_totp::Entity::delete_many()
+        .filter(admin_totp::Column::UserId.eq(user_id))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(())
+}
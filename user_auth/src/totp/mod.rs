@@ -0,0 +1,19 @@
+//! Admin 二次验证（TOTP，RFC 6238）
+//!
+//! Admin 可以删除任意食堂、政策文件和用户数据，仅靠微信登录态保护风险较大。
+//! 本模块提供：
+//! - 绑定：生成密钥与 `otpauth://` URI（可生成二维码供验证器扫描），首次校验通过后启用
+//! - 校验：6 位验证码（允许前后各一个时间步的误差，同一时间步不可重复使用）或一次性恢复码
+//! - 提权：校验通过后签发短期的 step-up token，在有效期内 Admin 操作无需再次输入验证码
+//!
+//! 是否强制要求二次验证由 `TotpPolicy` 决定，由 server_main 的权限中间件执行。
+
+pub mod code;
+pub mod manage;
+pub mod step_up;
+pub mod r#struct;
+
+pub use code::*;
+pub use manage::*;
+pub use step_up::*;
+pub use r#struct::*;
@@ -0,0 +1,82 @@
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+
+use super::manage::totp_enabled;
+use super::{STEP_UP_SECONDS, TotpError, TotpPolicy};
+use crate::db_exchange::{KeyPurpose, KeyRing, now_timestamp};
+use db_manager::entity::user as user_entity;
+
+const STEP_UP_PURPOSE: &str = "step_up";
+
+/// step-up token 的声明，使用 `KeyPurpose::StepUp` 的密钥签名，不能与访问 token 互相冒用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StepUpClaims {
+    sub: String,
+    exp: u64,
+    ver: i32,
+    purpose: String,
+}
+
+/// Issue a short-lived token proving the user just passed the second factor.
+///
+/// Returns the token and its expiration. The token is bound to the user's
+/// `token_version`, so revoking the user's tokens also ends the step-up.
+pub fn issue_step_up_token(
+    keys: &KeyRing,
+    user: &user_entity::Model,
+) -> Result<(String, u64), TotpError> {
+    let exp = now_timestamp() + STEP_UP_SECONDS;
+    let claims = StepUpClaims {
+        sub: user.open_id.clone(),
+        exp,
+        ver: user.token_version,
+        purpose: STEP_UP_PURPOSE.to_string(),
+    };
+    let token = keys
+        .sign(KeyPurpose::StepUp, &claims)
+        .map_err(|e| TotpError::TokenError(format!("{:?}", e)))?;
+    Ok((token, exp))
+}
+
+/// Check that `token` is a valid, unexpired step-up token for `user`.
+pub fn verify_step_up_token(
+    keys: &KeyRing,
+    token: &str,
+    user: &user_entity::Model,
+) -> Result<(), TotpError> {
+    let claims: StepUpClaims = keys
+        .verify(KeyPurpose::StepUp, token)
+        .map_err(|_| TotpError::StepUpRequired)?;
+    if claims.purpose != STEP_UP_PURPOSE
+        || claims.sub != user.open_id
+        || claims.ver != user.token_version
+        || claims.exp < now_timestamp()
+    {
+        return Err(TotpError::StepUpRequired);
+    }
+    Ok(())
+}
+
+/// Apply the second-factor policy to an Admin-level request.
+pub async fn check_admin_step_up<C>(
+    db: &C,
+    keys: &KeyRing,
+    policy: TotpPolicy,
+    user: &user_entity::Model,
+    step_up_token: Option<&str>,
+) -> Result<(), TotpError>
+where
+    C: ConnectionTrait,
+{
+    if policy == TotpPolicy::Off {
+        return Ok(());
+    }
+    if !totp_enabled(db, user.id).await? {
+        return match policy {
+            TotpPolicy::Required => Err(TotpError::EnrollmentRequired),
+            _ => Ok(()),
+        };
+    }
+    let token = step_up_token.ok_or(TotpError::StepUpRequired)?;
+    verify_step_up_token(keys, token, user)
+}
@@ -0,0 +1,108 @@
+/// 验证码位数
+pub const TOTP_DIGITS: u32 = 6;
+/// 时间步长（秒）
+pub const TOTP_PERIOD: u64 = 30;
+/// 允许的时间步误差（前后各几个时间步）
+pub const TOTP_SKEW: i64 = 1;
+/// 写入 otpauth URI 的发行方
+pub const TOTP_ISSUER: &str = "SD_backend";
+/// 每次生成的恢复码数量
+pub const RECOVERY_CODE_COUNT: usize = 10;
+/// step-up token 有效期（秒）
+pub const STEP_UP_SECONDS: u64 = 600;
+/// 携带 step-up token 的请求头
+pub const STEP_UP_HEADER: &str = "x-step-up-token";
+
+/// Admin 二次验证策略，由配置项 `auth.admin_totp_policy` 指定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TotpPolicy {
+    /// 不检查二次验证
+    Off,
+    /// 已绑定 TOTP 的 Admin 必须先完成二次验证（默认）
+    #[default]
+    Enrolled,
+    /// 所有 Admin 都必须绑定并完成二次验证，未绑定时只能访问绑定接口
+    Required,
+}
+
+impl TotpPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(TotpPolicy::Off),
+            "enrolled" => Some(TotpPolicy::Enrolled),
+            "required" => Some(TotpPolicy::Required),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TotpPolicy::Off => "off",
+            TotpPolicy::Enrolled => "enrolled",
+            TotpPolicy::Required => "required",
+        }
+    }
+}
+
+/// 新生成的 TOTP 密钥，确认前不会启用
+#[derive(Debug, Clone)]
+pub struct TotpEnrollment {
+    /// base32 编码的密钥，供无法扫码时手动输入
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug)]
+pub enum TotpError {
+    /// 未绑定或尚未确认
+    NotEnrolled,
+    AlreadyEnabled,
+    /// 验证码或恢复码错误
+    InvalidCode,
+    /// 连续失败次数过多，锁定到该时间（unix 秒）
+    Locked {
+        until: i64,
+    },
+    /// 策略要求绑定 TOTP，但用户尚未绑定
+    EnrollmentRequired,
+    /// 需要先完成二次验证
+    StepUpRequired,
+    TokenError(String),
+    DatabaseError(String),
+}
+
+impl TotpError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            TotpError::NotEnrolled => (404, "Two-factor authentication not enabled".to_string()),
+            TotpError::AlreadyEnabled => {
+                (409, "Two-factor authentication already enabled".to_string())
+            }
+            TotpError::InvalidCode => (401, "Invalid verification code".to_string()),
+            TotpError::Locked { until } => (
+                429,
+                format!(
+                    "Too many failed attempts, two-factor verification locked until {}",
+                    until
+                ),
+            ),
+            TotpError::EnrollmentRequired => (
+                403,
+                "Two-factor authentication required: enroll via /api/totp/enroll".to_string(),
+            ),
+            TotpError::StepUpRequired => (
+                403,
+                "Two-factor verification required: verify via /api/totp/verify".to_string(),
+            ),
+            TotpError::TokenError(e) => (500, format!("Token error: {}", e)),
+            TotpError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for TotpError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        TotpError::DatabaseError(err.to_string())
+    }
+}
@@ -0,0 +1,83 @@
+use super::{UserStatus, UserStatusError};
+use crate::db_exchange::{ExchangeError, now_timestamp, revoke_user_tokens};
+use db_manager::entity::{user as user_entity, user_status_log};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+
+/// Change a user's status and record the change in `user_status_log`.
+///
+/// Suspending or deleting an account also revokes every token it holds, so
+/// the user is signed out immediately. `operator_id` is the admin's `user.id`,
+/// or `None` for changes made from the command line.
+pub async fn change_user_status<C>(
+    db: &C,
+    operator_id: Option<i32>,
+    target: user_entity::Model,
+    status: UserStatus,
+    reason: Option<String>,
+    suspended_until: Option<i64>,
+) -> Result<user_entity::Model, UserStatusError>
+where
+    C: TransactionTrait,
+{
+    let (reason, suspended_until) = match status {
+        UserStatus::Suspended => (reason, suspended_until),
+        UserStatus::Deleted => (reason, None),
+        UserStatus::Active => (None, None),
+    };
+
+    let txn = db.begin().await?;
+
+    // 1) 记录状态变更
+    user_status_log::ActiveModel {
+        user_id: Set(target.id),
+        operator_id: Set(operator_id),
+        from_status: Set(target.status.clone()),
+        to_status: Set(status.as_str().to_string()),
+        reason: Set(reason.clone()),
+        suspended_until: Set(suspended_until),
+        created_at: Set(now_timestamp() as i64),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    // 2) 更新用户状态
+    let mut active: user_entity::ActiveModel = target.clone().into();
+    active.id = ActiveValue::Unchanged(target.id);
+    active.status = Set(status.as_str().to_string());
+    active.suspended_reason = Set(reason);
+    active.suspended_until = Set(suspended_until);
+    let mut updated = active.update(&txn).await?;
+
+    // 3) 封禁或删除时吊销全部 token
+    if status != UserStatus::Active {
+        updated = revoke_user_tokens(&txn, updated)
+            .await
+            .map_err(|e| match e {
+                ExchangeError::DatabaseError(e) => UserStatusError::DatabaseError(e),
+                other => UserStatusError::DatabaseError(format!("{:?}", other)),
+            })?;
+    }
+
+    txn.commit().await?;
+    Ok(updated)
+}
+
+/// Status change history of a user, newest first.
+pub async fn list_status_logs<C>(
+    db: &C,
+    user_id: i32,
+) -> Result<Vec<user_status_log::Model>, UserStatusError>
+where
+    C: ConnectionTrait,
+{
+    Ok(user_status_log::Entity::find()
+        .filter(user_status_log::Column::UserId.eq(user_id))
+        .order_by_desc(user_status_log::Column::CreatedAt)
+        .order_by_desc(user_status_log::Column::Id)
+        .all(db)
+        .await?)
+}
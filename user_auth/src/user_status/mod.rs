@@ -0,0 +1,5 @@
+pub mod change;
+pub mod r#struct;
+
+pub use change::*;
+pub use r#struct::*;
@@ -0,0 +1,95 @@
+use crate::db_exchange::now_timestamp;
+use db_manager::entity::user as user_entity;
+
+/// 账号状态，保存在 `user.status` 列中
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserStatus {
+    Active,
+    Suspended,
+    Deleted,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+            UserStatus::Deleted => "deleted",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "active" => Some(UserStatus::Active),
+            "suspended" => Some(UserStatus::Suspended),
+            "deleted" => Some(UserStatus::Deleted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum UserStatusError {
+    /// 账号被封禁，`until` 为空表示无限期
+    Suspended {
+        reason: Option<String>,
+        until: Option<i64>,
+    },
+    Deleted,
+    InvalidStatus(String),
+    DatabaseError(String),
+}
+
+impl UserStatusError {
+    /// 对应的响应 code 与消息：封禁为 423，已删除为 410
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            UserStatusError::Suspended { reason, until } => {
+                let mut message = "Account suspended".to_string();
+                if let Some(reason) = reason {
+                    message.push_str(&format!(": {}", reason));
+                }
+                if let Some(until) = until {
+                    message.push_str(&format!(" (until {})", until));
+                }
+                (423, message)
+            }
+            UserStatusError::Deleted => (410, "Account deleted".to_string()),
+            UserStatusError::InvalidStatus(s) => (400, format!("Invalid status: {}", s)),
+            UserStatusError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for UserStatusError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        UserStatusError::DatabaseError(err.to_string())
+    }
+}
+
+/// 账号当前生效的状态，封禁到期后视为 Active
+pub fn effective_status(user: &user_entity::Model) -> UserStatus {
+    match UserStatus::parse(&user.status) {
+        Some(UserStatus::Suspended)
+            if user
+                .suspended_until
+                .is_some_and(|until| until <= now_timestamp() as i64) =>
+        {
+            UserStatus::Active
+        }
+        Some(status) => status,
+        None => UserStatus::Active,
+    }
+}
+
+/// 拒绝已封禁或已删除的账号
+pub fn check_user_status(user: &user_entity::Model) -> Result<(), UserStatusError> {
+    match effective_status(user) {
+        UserStatus::Active => Ok(()),
+        UserStatus::Suspended => Err(UserStatusError::Suspended {
+            reason: user.suspended_reason.clone(),
+            until: user.suspended_until,
+        }),
+        UserStatus::Deleted => Err(UserStatusError::Deleted),
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+use super::r#struct::{WxAccessTokenResponse, WxAuthError, WxAuthServerConfig};
+
+/// access_token 到期前多久刷新
+pub const ACCESS_TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+struct CachedAccessToken {
+    token: String,
+    refresh_at: Instant,
+}
+
+/// 微信服务端接口客户端
+///
+/// 缓存 access_token 并在到期前自动刷新，并发的刷新请求合并为一次。
+/// 订阅消息、小程序码、内容安全等接口都通过它调用：
+///
+/// ```ignore
+/// let client = WxApiClient::new(config.wx.clone());
+/// let resp: serde_json::Value = client
+///     .post_json("/wxa/msg_sec_check", &serde_json::json!({ "content": "hello" }))
+///     .await?;
+/// ```
+pub struct WxApiClient {
+    config: WxAuthServerConfig,
+    http: reqwest::Client,
+    access_token: Mutex<Option<CachedAccessToken>>,
+}
+
+impl WxApiClient {
+    pub fn new(config: WxAuthServerConfig) -> Self {
+        WxApiClient {
+            config,
+            http: reqwest::Client::new(),
+            access_token: Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &WxAuthServerConfig {
+        &self.config
+    }
+
+    /// 获取有效的 access_token，必要时刷新
+    ///
+    /// 刷新期间持有锁，其他并发调用等待后直接使用新的 access_token
+    pub async fn access_token(&self) -> Result<String, WxAuthError> {
+        let mut cached = self.access_token.lock().await;
+        if let Some(token) = cached.as_ref()
+            && Instant::now() < token.refresh_at
+        {
+            return Ok(token.token.clone());
+        }
+
+        let token = self.fetch_access_token().await?;
+        let value = token.token.clone();
+        *cached = Some(token);
+        Ok(value)
+    }
+
+    /// 丢弃缓存的 access_token，下次调用时重新获取
+    pub async fn invalidate_access_token(&self) {
+        *self.access_token.lock().await = None;
+    }
+
+    /// GET <BASE_URL>/cgi-bin/token?grant_type=client_credential&appid=APPID&secret=APPSECRET
+    async fn fetch_access_token(&self) -> Result<CachedAccessToken, WxAuthError> {
+        let url = format!(
+            "{}/cgi-bin/token?grant_type=client_credential&appid={}&secret={}",
+            self.config.base_url, self.config.appid, self.config.secret
+        );
+        let response: WxAccessTokenResponse = self.http.get(&url).send().await?.json().await?;
+        if let Some(err) = response.errcode.and_then(WxAuthError::from_errcode) {
+            return Err(err);
+        }
+        let token = response.access_token.ok_or(WxAuthError::WxSystemError)?;
+        let expires_in = Duration::from_secs(response.expires_in.unwrap_or(7200));
+        Ok(CachedAccessToken {
+            token,
+            refresh_at: Instant::now() + expires_in.saturating_sub(ACCESS_TOKEN_REFRESH_MARGIN),
+        })
+    }
+
+    /// 调用 GET 接口，自动附带 access_token
+    pub async fn get_json<T>(&self, path: &str, query: &[(&str, &str)]) -> Result<T, WxAuthError>
+    where
+        T: DeserializeOwned,
+    {
+        self.call(|token| {
+            self.http
+                .get(format!("{}{}", self.config.base_url, path))
+                .query(&[("access_token", token)])
+                .query(query)
+        })
+        .await
+    }
+
+    /// 调用 POST JSON 接口，自动附带 access_token
+    pub async fn post_json<T, B>(&self, path: &str, body: &B) -> Result<T, WxAuthError>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        self.call(|token| {
+            self.http
+                .post(format!("{}{}", self.config.base_url, path))
+                .query(&[("access_token", token)])
+                .json(body)
+        })
+        .await
+    }
+
+    /// 发送请求并检查 errcode，access_token 失效时刷新后重试一次
+    async fn call<T, F>(&self, build: F) -> Result<T, WxAuthError>
+    where
+        T: DeserializeOwned,
+        F: Fn(&str) -> reqwest::RequestBuilder,
+    {
+        let token = self.access_token().await?;
+        match self.send(build(&token)).await {
+            Err(err) if err.is_access_token_error() => {
+                self.invalidate_access_token().await;
+                let token = self.access_token().await?;
+                self.send(build(&token)).await
+            }
+            result => result,
+        }
+    }
+
+    async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, WxAuthError>
+    where
+        T: DeserializeOwned,
+    {
+        let value: serde_json::Value = request.send().await?.json().await?;
+        let errcode = value
+            .get("errcode")
+            .and_then(|c| c.as_i64())
+            .unwrap_or_default() as i32;
+        if let Some(err) = WxAuthError::from_errcode(errcode) {
+            return Err(err);
+        }
+        serde_json::from_value(value).map_err(|_| WxAuthError::WxSystemError)
+    }
+}
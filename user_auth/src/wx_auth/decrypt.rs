@@ -0,0 +1,69 @@
+use aes::Aes128;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use cbc::cipher::{BlockDecryptMut, KeyIvInit, block_padding::Pkcs7};
+use serde::de::DeserializeOwned;
+
+use super::r#struct::{WxDecryptError, WxPhoneInfo, WxWatermark};
+
+type Aes128CbcDec = cbc::Decryptor<Aes128>;
+
+/// 解密小程序 `wx.getPhoneNumber`、`wx.getUserInfo` 等接口返回的加密数据
+///
+/// 对称解密使用 AES-128-CBC，数据采用 PKCS#7 填充：
+/// - 密钥为 base64 解码后的 session_key
+/// - 初始向量为 base64 解码后的 iv
+///
+/// 解密后校验 watermark 中的 appid 与本小程序一致
+pub fn decrypt_wx_data<T>(
+    session_key: &str,
+    encrypted_data: &str,
+    iv: &str,
+    appid: &str,
+) -> Result<T, WxDecryptError>
+where
+    T: DeserializeOwned,
+{
+    let key = STANDARD
+        .decode(session_key)
+        .map_err(|_| WxDecryptError::InvalidSessionKey)?;
+    let iv = STANDARD
+        .decode(iv)
+        .map_err(|_| WxDecryptError::InvalidData)?;
+    let mut data = STANDARD
+        .decode(encrypted_data)
+        .map_err(|_| WxDecryptError::InvalidData)?;
+
+    let cipher = Aes128CbcDec::new_from_slices(&key, &iv).map_err(|_| {
+        if key.len() != 16 {
+            WxDecryptError::InvalidSessionKey
+        } else {
+            WxDecryptError::InvalidData
+        }
+    })?;
+    let plain = cipher
+        .decrypt_padded_mut::<Pkcs7>(&mut data)
+        .map_err(|_| WxDecryptError::DecryptFailed)?;
+
+    let value: serde_json::Value =
+        serde_json::from_slice(plain).map_err(|_| WxDecryptError::DecryptFailed)?;
+    let watermark: WxWatermark = value
+        .get("watermark")
+        .cloned()
+        .and_then(|w| serde_json::from_value(w).ok())
+        .ok_or(WxDecryptError::WatermarkMismatch)?;
+    if watermark.appid != appid {
+        return Err(WxDecryptError::WatermarkMismatch);
+    }
+
+    serde_json::from_value(value).map_err(|_| WxDecryptError::InvalidPayload)
+}
+
+/// 解密手机号数据
+pub fn decrypt_phone_number(
+    session_key: &str,
+    encrypted_data: &str,
+    iv: &str,
+    appid: &str,
+) -> Result<WxPhoneInfo, WxDecryptError> {
+    decrypt_wx_data(session_key, encrypted_data, iv, appid)
+}
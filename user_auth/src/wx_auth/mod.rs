@@ -1,6 +1,15 @@
+mod client;
+mod decrypt;
 mod session2json;
+mod session_key;
 mod r#struct;
 
+pub use client::{ACCESS_TOKEN_REFRESH_MARGIN, WxApiClient};
+pub use decrypt::{decrypt_phone_number, decrypt_wx_data};
+pub use session_key::{load_session_key, save_session_key};
 pub use session2json::wx_auth_session_to_json;
 
-pub use r#struct::{WxAuthError, WxAuthResponse, WxAuthServerConfig};
+pub use r#struct::{
+    DEFAULT_WX_BASE_URL, WxAccessTokenResponse, WxAuthError, WxAuthResponse, WxAuthServerConfig,
+    WxDecryptError, WxPhoneInfo, WxWatermark,
+};
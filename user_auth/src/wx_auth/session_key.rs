@@ -0,0 +1,51 @@
+use db_manager::entity::wx_session;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+
+use super::r#struct::WxDecryptError;
+use crate::db_exchange::now_timestamp;
+
+/// 保存用户最近一次登录获得的 session_key，覆盖旧值
+pub async fn save_session_key<C>(db: &C, user_id: i32, session_key: &str) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let existing = wx_session::Entity::find()
+        .filter(wx_session::Column::UserId.eq(user_id))
+        .one(db)
+        .await?;
+    let now = now_timestamp() as i64;
+    match existing {
+        Some(existing) => {
+            let mut active: wx_session::ActiveModel = existing.into();
+            active.session_key = Set(session_key.to_string());
+            active.updated_at = Set(now);
+            active.update(db).await?;
+        }
+        None => {
+            wx_session::ActiveModel {
+                user_id: Set(user_id),
+                session_key: Set(session_key.to_string()),
+                updated_at: Set(now),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取用户保存的 session_key
+pub async fn load_session_key<C>(db: &C, user_id: i32) -> Result<String, WxDecryptError>
+where
+    C: ConnectionTrait,
+{
+    wx_session::Entity::find()
+        .filter(wx_session::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .map(|s| s.session_key)
+        .ok_or(WxDecryptError::SessionKeyNotFound)
+}
@@ -1,7 +1,11 @@
 use serde::Deserialize;
 
+/// 微信接口默认地址
+pub const DEFAULT_WX_BASE_URL: &str = "https://api.weixin.qq.com";
+
 /// 微信授权服务器配置
 /// 包括 appid、secret和base_url
+#[derive(Debug, Clone)]
 pub struct WxAuthServerConfig {
     pub appid: String,
     pub secret: String,
@@ -16,17 +20,6 @@ impl WxAuthServerConfig {
             base_url,
         }
     }
-
-    /// 从环境变量中读取配置
-    ///
-    /// `SERVER_WX_APPID`和`SERVER_WX_SECRET`环境变量必须存在
-    pub fn from_env() -> Self {
-        let appid = std::env::var("SERVER_WX_APPID").expect("SERVER_WX_APPID not found");
-        let secret = std::env::var("SERVER_WX_SECRET").expect("SERVER_WX_SECRET not found");
-        let base_url = std::env::var("SERVER_WX_BASEURL")
-            .unwrap_or_else(|_| "https://api.weixin.qq.com".to_string());
-        WxAuthServerConfig::new(appid, secret, base_url)
-    }
 }
 
 /// 微信授权错误
@@ -36,10 +29,38 @@ pub enum WxAuthError {
     CodeError,
     UserBlockedError,
     TooMuchRequestError,
+    /// access_token 或 appsecret 无效（40001、40125）
+    InvalidCredentialError,
+    /// access_token 已过期（42001、40014）
+    AccessTokenExpiredError,
     UnknownError(i32),
     NetworkError(reqwest::Error),
 }
 
+impl WxAuthError {
+    /// 将微信接口返回的 errcode 映射为错误，0 表示成功
+    pub fn from_errcode(errcode: i32) -> Option<Self> {
+        match errcode {
+            0 => None,
+            -1 => Some(WxAuthError::WxSystemError),
+            40029 => Some(WxAuthError::CodeError),
+            40226 => Some(WxAuthError::UserBlockedError),
+            45011 => Some(WxAuthError::TooMuchRequestError),
+            40001 | 40125 => Some(WxAuthError::InvalidCredentialError),
+            42001 | 40014 => Some(WxAuthError::AccessTokenExpiredError),
+            code => Some(WxAuthError::UnknownError(code)),
+        }
+    }
+
+    /// access_token 失效，重新获取后可以重试
+    pub fn is_access_token_error(&self) -> bool {
+        matches!(
+            self,
+            WxAuthError::InvalidCredentialError | WxAuthError::AccessTokenExpiredError
+        )
+    }
+}
+
 impl From<reqwest::Error> for WxAuthError {
     fn from(error: reqwest::Error) -> Self {
         WxAuthError::NetworkError(error)
@@ -48,7 +69,7 @@ impl From<reqwest::Error> for WxAuthError {
 
 /// 微信登录接口返回结构体
 ///
-/// |参数名|	类型|	说明|
+/// |参数名|    类型|    说明|
 /// |------|------|------|
 /// |session_key|string|会话密钥|
 /// |unionid|string|用户在开放平台的唯一标识符，若当前小程序已绑定到微信开放平台帐号下会返回，详见 UnionID 机制说明。|
@@ -63,3 +84,90 @@ pub struct WxAuthResponse {
     pub errcode: Option<i32>,
     pub errmsg: Option<String>,
 }
+
+/// 加密数据解密错误
+#[derive(Debug)]
+pub enum WxDecryptError {
+    /// 没有保存的 session_key，需要重新登录
+    SessionKeyNotFound,
+    /// session_key 不是合法的 base64 编码的 16 字节密钥
+    InvalidSessionKey,
+    /// encryptedData 或 iv 格式错误
+    InvalidData,
+    /// 解密失败，通常是 session_key 已过期
+    DecryptFailed,
+    /// watermark 缺失或 appid 不一致
+    WatermarkMismatch,
+    /// 解密结果缺少需要的字段
+    InvalidPayload,
+    DatabaseError(String),
+}
+
+impl WxDecryptError {
+    /// 对应的响应 code 与消息
+    pub fn code_and_message(&self) -> (i32, String) {
+        match self {
+            WxDecryptError::SessionKeyNotFound => (
+                401,
+                "WeiXin session not found, please login again".to_string(),
+            ),
+            WxDecryptError::InvalidSessionKey => (
+                401,
+                "Invalid WeiXin session, please login again".to_string(),
+            ),
+            WxDecryptError::InvalidData => (400, "Invalid encrypted data or iv".to_string()),
+            WxDecryptError::DecryptFailed => (
+                400,
+                "Failed to decrypt data, WeiXin session may be expired".to_string(),
+            ),
+            WxDecryptError::WatermarkMismatch => {
+                (400, "Encrypted data watermark mismatch".to_string())
+            }
+            WxDecryptError::InvalidPayload => (400, "Decrypted data is missing fields".to_string()),
+            WxDecryptError::DatabaseError(e) => (500, format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for WxDecryptError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        WxDecryptError::DatabaseError(err.to_string())
+    }
+}
+
+/// 加密数据中的数据水印
+#[derive(Debug, Clone, Deserialize)]
+pub struct WxWatermark {
+    pub appid: String,
+    pub timestamp: i64,
+}
+
+/// `wx.getPhoneNumber` 解密后的手机号信息
+///
+/// |参数名|    类型|    说明|
+/// |------|------|------|
+/// |phoneNumber|string|用户绑定的手机号（国外手机号会有区号）|
+/// |purePhoneNumber|string|没有区号的手机号|
+/// |countryCode|string|区号|
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WxPhoneInfo {
+    pub phone_number: String,
+    pub pure_phone_number: String,
+    pub country_code: String,
+    pub watermark: WxWatermark,
+}
+
+/// 获取 access_token 接口返回结构体
+///
+/// |参数名|    类型|    说明|
+/// |------|------|------|
+/// |access_token|string|获取到的凭证|
+/// |expires_in|number|凭证有效时间，单位：秒|
+#[derive(Debug, Clone, Deserialize)]
+pub struct WxAccessTokenResponse {
+    pub access_token: Option<String>,
+    pub expires_in: Option<u64>,
+    pub errcode: Option<i32>,
+    pub errmsg: Option<String>,
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use db_manager::entity::{audit_log, audit_subject, mutil_media, user as user_entity};
+use sea_orm::{DatabaseConnection, DbBackend, JsonValue, MockDatabase, Value};
+use user_auth::audit::{
+    AuditAction, AuditActor, AuditChange, AuditError, AuditQuery, REDACTED, audit_entry,
+    export_audit_logs, list_audit_logs, record_audit,
+};
+
+fn sql_of(db: DatabaseConnection) -> Vec<String> {
+    db.into_transaction_log()
+        .iter()
+        .flat_map(|t| t.statements().iter().map(|s| s.sql.clone()))
+        .collect()
+}
+
+fn user(nickname: &str, name: &str) -> user_entity::Model {
+    user_entity::Model {
+        id: 7,
+        open_id: "elder".to_string(),
+        nickname: Some(nickname.to_string()),
+        avatar: None,
+        permission: Some(1),
+        name: Some(name.to_string()),
+        phone_number: None,
+        address: None,
+        is_important: Some(false),
+        token_version: 1,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn subject(open_id: &str, pseudonym: &str) -> audit_subject::Model {
+    audit_subject::Model {
+        id: 1,
+        open_id: open_id.to_string(),
+        pseudonym: pseudonym.to_string(),
+        created_at: 0,
+    }
+}
+
+fn log(before: JsonValue, after: JsonValue) -> audit_log::Model {
+    audit_log::Model {
+        id: 1,
+        actor: Some("p_admin01".to_string()),
+        action: "update".to_string(),
+        entity: "user".to_string(),
+        entity_id: "7".to_string(),
+        before: Some(before),
+        after: Some(after),
+        ip: Some("10.0.0.1".to_string()),
+        created_at: 0,
+    }
+}
+
+#[test]
+fn update_keeps_changed_fields_and_redacts_personal_data() {
+    let before = user("old", "Zhang San");
+    let after = user("new", "Li Si");
+
+    let entry = audit_entry(AuditChange::Update(&before, &after)).unwrap();
+    assert_eq!(entry.action, AuditAction::Update);
+    assert_eq!(entry.entity, "user");
+    assert_eq!(entry.entity_id, "7");
+
+    let before = entry.before.unwrap();
+    let after = entry.after.unwrap();
+    assert_eq!(before.as_object().unwrap().len(), 2);
+    // 昵称与姓名发生了变化但内容被脱敏
+    assert_eq!(before["nickname"], REDACTED);
+    assert_eq!(after["nickname"], REDACTED);
+    assert_eq!(before["name"], REDACTED);
+    assert_eq!(after["name"], REDACTED);
+
+    // 没有变化的更新不记录
+    let same = user("same", "Zhang San");
+    assert!(audit_entry(AuditChange::Update(&same, &same.clone())).is_none());
+}
+
+#[test]
+fn insert_and_delete_snapshot_whole_row() {
+    let media = mutil_media::Model {
+        id: 3,
+        uuid: None,
+        file: Some(vec![0xff; 16]),
+        r#type: Some("webp".to_string()),
+    };
+
+    let inserted = audit_entry(AuditChange::Insert(&media)).unwrap();
+    assert_eq!(inserted.action, AuditAction::Insert);
+    assert!(inserted.before.is_none());
+    let after = inserted.after.unwrap();
+    // 二进制内容只记录长度
+    assert_eq!(after["file"], "<16 bytes>");
+    assert_eq!(after["type"], "webp");
+    assert_eq!(after["uuid"], JsonValue::Null);
+
+    let deleted = audit_entry(AuditChange::Delete(&media)).unwrap();
+    assert_eq!(deleted.action, AuditAction::Delete);
+    assert_eq!(deleted.entity_id, "3");
+    assert!(deleted.after.is_none());
+}
+
+#[tokio::test]
+async fn record_writes_actor_and_ip() {
+    let before = user("old", "Zhang San");
+    let after = user("new", "Zhang San");
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[subject("admin", "p_admin01")]])
+        .append_query_results([[log(
+            serde_json::json!({ "nickname": "old" }),
+            serde_json::json!({ "nickname": "new" }),
+        )]])
+        .into_connection();
+    let actor = AuditActor {
+        open_id: Some("admin".to_string()),
+        ip: Some("10.0.0.1".to_string()),
+    };
+
+    let recorded = record_audit(&db, &actor, AuditChange::Update(&before, &after))
+        .await
+        .unwrap();
+    assert!(recorded.is_some());
+
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 2);
+    let insert = &log[1].statements()[0];
+    assert!(
+        insert
+            .sql
+            .starts_with("INSERT INTO \"public\".\"audit_log\"")
+    );
+    let values = format!("{:?}", insert.values);
+    // 操作者只以化名写入
+    assert!(values.contains("\"p_admin01\""));
+    assert!(!values.contains("\"admin\""));
+    assert!(values.contains("\"10.0.0.1\""));
+    assert!(values.contains("\"update\""));
+    assert!(!values.contains("Zhang San"));
+}
+
+#[tokio::test]
+async fn record_replaces_open_id_with_pseudonym() {
+    let inserted = user("Xiaoming", "Zhang San");
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[subject("elder", "p_0123abcd")]])
+        .append_query_results([[log(JsonValue::Null, JsonValue::Null)]])
+        .into_connection();
+
+    record_audit(&db, &AuditActor::default(), AuditChange::Insert(&inserted))
+        .await
+        .unwrap();
+
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 2);
+    assert!(log[0].statements()[0].sql.contains("\"audit_subject\""));
+    let values = format!("{:?}", log[1].statements()[0].values);
+    assert!(values.contains("p_0123abcd"));
+    assert!(!values.contains("elder"));
+    assert!(!values.contains("Xiaoming"));
+}
+
+#[tokio::test]
+async fn list_filters_and_counts() {
+    let count = BTreeMap::from([("num_items".to_string(), Value::BigInt(Some(1)))]);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[subject("admin", "p_admin01")]])
+        .append_query_results([[count]])
+        .append_query_results([[log(
+            serde_json::json!({ "is_important": false }),
+            serde_json::json!({ "is_important": true }),
+        )]])
+        .append_query_results([[subject("admin", "p_admin01")]])
+        .into_connection();
+    let query = AuditQuery {
+        actor_open_id: Some("admin".to_string()),
+        action: Some(AuditAction::Update),
+        entity: Some("user".to_string()),
+        since: Some(100),
+        ..Default::default()
+    };
+
+    let (logs, total) = list_audit_logs(&db, &query, 0, 20).await.unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(logs.len(), 1);
+    // 化名对应关系还在时显示 open_id
+    assert_eq!(logs[0].actor.as_deref(), Some("admin"));
+
+    // 按 open_id 筛选时先换成化名
+    let statements = sql_of(db);
+    assert!(statements[0].contains("\"audit_subject\""));
+    assert!(statements[2].contains("\"actor\" IN ($1)"));
+    assert!(statements[2].contains("\"action\" = $2"));
+    assert!(statements[2].contains("\"created_at\" >= $4"));
+    assert!(statements[2].contains("ORDER BY \"audit_log\".\"created_at\" DESC"));
+
+    // 起止时间颠倒时直接拒绝
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    let query = AuditQuery {
+        since: Some(200),
+        until: Some(100),
+        ..Default::default()
+    };
+    assert!(matches!(
+        list_audit_logs(&db, &query, 0, 20).await,
+        Err(AuditError::InvalidTimeRange)
+    ));
+}
+
+#[tokio::test]
+async fn actors_of_purged_accounts_stay_pseudonymous() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[log(JsonValue::Null, JsonValue::Null)]])
+        .append_query_results([Vec::<audit_subject::Model>::new()])
+        .into_connection();
+
+    let logs = export_audit_logs(&db, &AuditQuery::default(), 10)
+        .await
+        .unwrap();
+    assert_eq!(logs[0].actor.as_deref(), Some("p_admin01"));
+
+    // 已注销的用户没有化名，按其 open_id 筛选查不到任何记录
+    let count = BTreeMap::from([("num_items".to_string(), Value::BigInt(Some(0)))]);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<audit_subject::Model>::new()])
+        .append_query_results([[count]])
+        .append_query_results([Vec::<audit_log::Model>::new()])
+        .into_connection();
+    let query = AuditQuery {
+        actor_open_id: Some("admin".to_string()),
+        ..Default::default()
+    };
+    let (logs, total) = list_audit_logs(&db, &query, 0, 20).await.unwrap();
+    assert_eq!((logs.len(), total), (0, 0));
+}
@@ -0,0 +1,160 @@
+use db_manager::entity::{caregiver_action_log, caregiver_link, user as user_entity};
+use sea_orm::{DbBackend, MockDatabase};
+use user_auth::caregiver::{
+    CaregiverError, CaregiverStatus, Delegation, action, request_link, resolve_delegation,
+    respond_link, revoke_link,
+};
+
+fn user(id: i32, open_id: &str) -> user_entity::Model {
+    user_entity::Model {
+        id,
+        open_id: open_id.to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(1),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: Some(id == 2),
+        token_version: 1,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn link(status: CaregiverStatus) -> caregiver_link::Model {
+    caregiver_link::Model {
+        id: 1,
+        caregiver_id: 1,
+        elder_id: 2,
+        status: status.as_str().to_string(),
+        relationship: Some("daughter".to_string()),
+        requested_by: Some(1),
+        approved_by: None,
+        created_at: 0,
+        approved_at: None,
+        revoked_at: None,
+    }
+}
+
+fn log(action: &str) -> caregiver_action_log::Model {
+    caregiver_action_log::Model {
+        id: 1,
+        caregiver_id: Some(1),
+        elder_id: 2,
+        action: action.to_string(),
+        detail: None,
+        created_at: 0,
+    }
+}
+
+#[tokio::test]
+async fn request_creates_pending_link_and_logs_it() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<caregiver_link::Model>::new()])
+        .append_query_results([[link(CaregiverStatus::Pending)]])
+        .append_query_results([[log(action::LINK_REQUEST)]])
+        .into_connection();
+
+    let created = request_link(
+        &db,
+        &user(1, "caregiver"),
+        &user(2, "elder"),
+        Some("daughter".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        CaregiverStatus::of(&created),
+        Some(CaregiverStatus::Pending)
+    );
+
+    let statements: Vec<String> = db
+        .into_transaction_log()
+        .iter()
+        .map(|t| format!("{:?}", t))
+        .collect();
+    assert!(
+        statements
+            .iter()
+            .any(|s| s.contains("caregiver_action_log") && s.contains("link.request"))
+    );
+}
+
+#[tokio::test]
+async fn request_rejects_self_and_duplicate_links() {
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    let err = request_link(&db, &user(1, "caregiver"), &user(1, "caregiver"), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CaregiverError::SelfLink));
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[link(CaregiverStatus::Active)]])
+        .into_connection();
+    let err = request_link(&db, &user(1, "caregiver"), &user(2, "elder"), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CaregiverError::AlreadyLinked));
+}
+
+#[tokio::test]
+async fn respond_requires_pending_link() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[link(CaregiverStatus::Revoked)]])
+        .into_connection();
+    let err = respond_link(&db, &user(2, "elder"), 1, true)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CaregiverError::NotPending));
+}
+
+#[tokio::test]
+async fn only_participants_or_admin_can_revoke() {
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    let err = revoke_link(&db, &user(9, "stranger"), false, 1, 2)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CaregiverError::NotParticipant));
+
+    let mut revoked = link(CaregiverStatus::Revoked);
+    revoked.revoked_at = Some(1);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[link(CaregiverStatus::Active)]])
+        .append_query_results([[revoked]])
+        .append_query_results([[log(action::LINK_REVOKE)]])
+        .into_connection();
+    let updated = revoke_link(&db, &user(9, "admin"), true, 1, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        CaregiverStatus::of(&updated),
+        Some(CaregiverStatus::Revoked)
+    );
+}
+
+#[tokio::test]
+async fn delegation_requires_active_link() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[link(CaregiverStatus::Active)]])
+        .append_query_results([Vec::<caregiver_link::Model>::new()])
+        .into_connection();
+    let caregiver = user(1, "caregiver");
+    let elder = user(2, "elder");
+
+    assert!(matches!(
+        resolve_delegation(&db, &caregiver, &caregiver)
+            .await
+            .unwrap(),
+        Delegation::Own
+    ));
+    assert!(matches!(
+        resolve_delegation(&db, &caregiver, &elder).await.unwrap(),
+        Delegation::Caregiver(l) if l.elder_id == 2
+    ));
+    assert!(matches!(
+        resolve_delegation(&db, &elder, &caregiver).await.unwrap(),
+        Delegation::Unrelated
+    ));
+}
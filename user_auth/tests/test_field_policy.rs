@@ -0,0 +1,80 @@
+use db_manager::entity::user as user_entity;
+use user_auth::db_exchange::model2user;
+use user_auth::field_policy::{FieldPolicyError, USER_FIELDS, Viewer};
+
+fn user(id: i32, permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id,
+        open_id: format!("user-{}", id),
+        nickname: Some("nick".to_string()),
+        avatar: None,
+        permission: Some(permission),
+        name: Some("Li Lei".to_string()),
+        phone_number: Some("13800138000".to_string()),
+        address: None,
+        is_important: Some(true),
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+#[test]
+fn self_edits_are_limited_to_profile_fields() {
+    let me = user(1, 1);
+    let viewer = Viewer::own(&me);
+    assert!(
+        USER_FIELDS
+            .check_writes(["nickname", "phone_number"], viewer)
+            .is_ok()
+    );
+    assert_eq!(
+        USER_FIELDS.check_writes(["nickname", "permission", "is_important"], viewer),
+        Err(FieldPolicyError::WriteDenied(vec![
+            "permission",
+            "is_important"
+        ]))
+    );
+
+    // 照护人与更高权限的 Provider 同样只能修改资料
+    let caregiver = Viewer::of(&user(2, 1), &me, true);
+    assert!(USER_FIELDS.check_writes(["name"], caregiver).is_ok());
+    assert!(!USER_FIELDS.can_write("is_important", caregiver));
+    let provider = Viewer::of(&user(3, 2), &me, false);
+    assert!(USER_FIELDS.can_write("address", provider));
+    assert!(!USER_FIELDS.can_write("permission", provider));
+}
+
+#[test]
+fn admin_only_fields_are_hidden_from_lower_roles() {
+    let me = user(1, 1);
+    let own = Viewer::own(&me);
+    assert_eq!(USER_FIELDS.read("is_important", own, me.is_important), None);
+    assert_eq!(USER_FIELDS.read("permission", own, me.permission), Some(1));
+    assert_eq!(
+        USER_FIELDS.read("phone_number", own, me.phone_number.clone()),
+        me.phone_number
+    );
+
+    let admin = Viewer::of(&user(9, 3), &me, false);
+    assert_eq!(
+        USER_FIELDS.read("is_important", admin, me.is_important),
+        Some(true)
+    );
+    assert!(USER_FIELDS.can_write("permission", admin));
+
+    // 无关用户看不到任何字段，未声明的字段对任何人都不可读写
+    let stranger = Viewer::of(&user(4, 1), &me, false);
+    assert!(!stranger.related());
+    assert!(!USER_FIELDS.can_read("nickname", stranger));
+    assert!(!USER_FIELDS.can_read("token_version", admin));
+    assert!(!USER_FIELDS.can_write("open_id", admin));
+}
+
+#[test]
+fn token_claims_follow_read_policy() {
+    assert_eq!(model2user(&user(1, 1)).is_important, None);
+    assert_eq!(model2user(&user(1, 1)).permission, Some(1));
+    assert_eq!(model2user(&user(9, 3)).is_important, Some(true));
+}
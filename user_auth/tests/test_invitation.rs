@@ -0,0 +1,124 @@
+use db_manager::entity::{invitation_code, invitation_redemption, user as user_entity};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult};
+use user_auth::db_exchange::now_timestamp;
+use user_auth::invitation::{
+    InvitationError, InvitationOptions, issue_invitation, redeem_invitation,
+};
+
+fn user(id: i32, permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id,
+        open_id: format!("openid-{}", id),
+        nickname: None,
+        avatar: None,
+        permission: Some(permission),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn invitation(target_user_id: Option<i32>, revoked_at: Option<i64>) -> invitation_code::Model {
+    let now = now_timestamp() as i64;
+    invitation_code::Model {
+        id: 1,
+        code: "ABCDEF0123456789".to_string(),
+        apply_type: 2,
+        issued_by: 100,
+        target_user_id,
+        max_uses: 1,
+        used_count: 0,
+        expires_at: now + 180,
+        revoked_at,
+        created_at: now,
+    }
+}
+
+fn exec(rows: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: rows,
+    }
+}
+
+#[tokio::test]
+async fn issue_rejects_invalid_options() {
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    let admin = user(100, 3);
+
+    let err = issue_invitation(&db, &admin, InvitationOptions::new(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvitationError::InvalidApplyType));
+
+    let mut options = InvitationOptions::new(2);
+    options.max_uses = 0;
+    let err = issue_invitation(&db, &admin, options).await.unwrap_err();
+    assert!(matches!(err, InvitationError::InvalidMaxUses));
+}
+
+#[tokio::test]
+async fn redeem_grants_permission_and_logs() {
+    let redeemer = user(5, 1);
+    let mut upgraded = redeemer.clone();
+    upgraded.permission = Some(2);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![invitation(None, None)]])
+        .append_exec_results([exec(1)])
+        .append_query_results([vec![invitation_redemption::Model {
+            id: 1,
+            invitation_id: 1,
+            user_id: 5,
+            previous_permission: Some(1),
+            redeemed_at: now_timestamp() as i64,
+        }]])
+        .append_query_results([vec![upgraded]])
+        .into_connection();
+
+    let (_, updated) = redeem_invitation(&db, "abcdef0123456789", &redeemer)
+        .await
+        .unwrap();
+    assert_eq!(updated.permission, Some(2));
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("INSERT INTO \\\"public\\\".\\\"invitation_redemption\\\""));
+}
+
+#[tokio::test]
+async fn redeem_rejects_revoked_code() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![invitation(None, Some(1))]])
+        .into_connection();
+    let err = redeem_invitation(&db, "ABCDEF0123456789", &user(5, 1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvitationError::Revoked));
+}
+
+#[tokio::test]
+async fn redeem_rejects_other_user_when_bound() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![invitation(Some(6), None)]])
+        .into_connection();
+    let err = redeem_invitation(&db, "ABCDEF0123456789", &user(5, 1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvitationError::NotForThisUser));
+}
+
+#[tokio::test]
+async fn redeem_rejects_exhausted_code() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![invitation(None, None)]])
+        .append_exec_results([exec(0)])
+        .into_connection();
+    let err = redeem_invitation(&db, "ABCDEF0123456789", &user(5, 1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, InvitationError::Exhausted));
+}
@@ -0,0 +1,111 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+use user_auth::db_exchange::{
+    ExchangeError, KeyPurpose, KeyRing, SigningKey, User, now_timestamp, token2claims, user2token,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Payload {
+    sub: String,
+}
+
+fn user() -> User {
+    User {
+        open_id: "openid-rotate".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(1),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+    }
+}
+
+fn ring(active: &str, keys: &[(&str, &str, Option<u64>)]) -> KeyRing {
+    KeyRing::new(
+        active,
+        keys.iter()
+            .map(|(kid, secret, retire_at)| SigningKey::new(*kid, *secret, *retire_at))
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn tokens_carry_kid_and_survive_rotation() {
+    let token = user2token(&ring("k1", &[("k1", "first", None)]), &user(), 0).unwrap();
+    let header = token.split('.').next().unwrap();
+    let header = String::from_utf8(URL_SAFE_NO_PAD.decode(header).unwrap()).unwrap();
+    assert!(header.contains(r#""kid":"k1""#));
+
+    // k2 becomes active; k1 is still accepted until it retires.
+    let rotated = ring("k2", &[("k2", "second", None), ("k1", "first", None)]);
+    let claims = token2claims(&rotated, &token).unwrap();
+    assert_eq!(claims.sub, "openid-rotate");
+
+    let retired = ring(
+        "k2",
+        &[
+            ("k2", "second", None),
+            ("k1", "first", Some(now_timestamp() - 1)),
+        ],
+    );
+    let err = token2claims(&retired, &token).unwrap_err();
+    assert!(matches!(err, ExchangeError::InvalidToken));
+
+    // Once the key is dropped entirely the kid is unknown.
+    let err = token2claims(&ring("k2", &[("k2", "second", None)]), &token).unwrap_err();
+    assert!(matches!(err, ExchangeError::InvalidToken));
+}
+
+#[test]
+fn single_secret_uses_default_kid() {
+    let legacy = KeyRing::single("legacy").unwrap();
+    assert_eq!(legacy.active_key().kid, "default");
+    let token = user2token(&legacy, &user(), 3).unwrap();
+    let claims = token2claims(&KeyRing::single("legacy").unwrap(), &token).unwrap();
+    assert_eq!(claims.ver, 3);
+}
+
+#[test]
+fn purposes_do_not_cross() {
+    let ring = ring("a", &[("a", "shared-secret", None)]);
+    let payload = Payload {
+        sub: "x".to_string(),
+    };
+    let session = ring.sign(KeyPurpose::Session, &payload).unwrap();
+    assert_eq!(
+        ring.verify::<Payload>(KeyPurpose::Session, &session)
+            .unwrap(),
+        payload
+    );
+    assert!(
+        ring.verify::<Payload>(KeyPurpose::StepUp, &session)
+            .is_err()
+    );
+
+    let step_up = ring.sign(KeyPurpose::StepUp, &payload).unwrap();
+    assert!(
+        ring.verify::<Payload>(KeyPurpose::Session, &step_up)
+            .is_err()
+    );
+}
+
+#[test]
+fn rejects_bad_configuration() {
+    let past = now_timestamp() - 10;
+    assert!(KeyRing::new("a", vec![SigningKey::new("a", "s", Some(past))]).is_err());
+    assert!(KeyRing::new("missing", vec![SigningKey::new("a", "s", None)]).is_err());
+    assert!(
+        KeyRing::new(
+            "a",
+            vec![
+                SigningKey::new("a", "s", None),
+                SigningKey::new("a", "t", None)
+            ]
+        )
+        .is_err()
+    );
+    assert!(KeyRing::single("").is_err());
+}
@@ -0,0 +1,163 @@
+use db_manager::entity::{local_credential, user as user_entity};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult};
+use user_auth::db_exchange::now_timestamp;
+use user_auth::local_auth::{
+    ConsoleSession, LOCKOUT_SECONDS, LocalAuthError, MAX_FAILED_ATTEMPTS, hash_password,
+    login_with_password, validate_password, validate_username, verify_password,
+};
+
+fn user() -> user_entity::Model {
+    user_entity::Model {
+        id: 4,
+        open_id: "console-admin".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(3),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 2,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn credential(password: &str, failed_attempts: i32) -> local_credential::Model {
+    local_credential::Model {
+        id: 1,
+        user_id: 4,
+        username: "admin".to_string(),
+        password_hash: hash_password(password).unwrap(),
+        failed_attempts,
+        locked_until: None,
+        password_changed_at: 0,
+        created_at: 0,
+    }
+}
+
+#[test]
+fn hashes_are_salted_and_verify() {
+    let first = hash_password("correct horse").unwrap();
+    let second = hash_password("correct horse").unwrap();
+    assert_ne!(first, second);
+    assert!(first.starts_with("$argon2id$"));
+    assert!(verify_password("correct horse", &first));
+    assert!(!verify_password("wrong horse", &first));
+    assert!(!verify_password("correct horse", "not-a-hash"));
+}
+
+#[test]
+fn validates_username_and_password() {
+    assert!(validate_username("canteen.admin").is_ok());
+    assert!(matches!(
+        validate_username("Admin"),
+        Err(LocalAuthError::InvalidUsername)
+    ));
+    assert!(validate_username("ab").is_err());
+    assert!(matches!(
+        validate_password("short"),
+        Err(LocalAuthError::WeakPassword)
+    ));
+    assert!(validate_password("long enough").is_ok());
+}
+
+#[tokio::test]
+async fn correct_password_returns_user() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![credential("s3cret-pass", 0)]])
+        .append_query_results([vec![user()]])
+        .into_connection();
+    let model = login_with_password(&db, " Admin ", "s3cret-pass")
+        .await
+        .unwrap();
+    assert_eq!(model.open_id, "console-admin");
+}
+
+#[tokio::test]
+async fn wrong_password_counts_and_locks() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![credential("s3cret-pass", 0)]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![credential("s3cret-pass", 1)]])
+        .into_connection();
+    let err = login_with_password(&db, "admin", "guess")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, LocalAuthError::InvalidCredentials));
+    assert_eq!(err.code_and_message().0, 401);
+    // The counter is incremented in the database, not written back from the read.
+    let update = db
+        .into_transaction_log()
+        .iter()
+        .flat_map(|t| {
+            t.statements()
+                .iter()
+                .map(|s| s.sql.clone())
+                .collect::<Vec<_>>()
+        })
+        .find(|sql| sql.starts_with("UPDATE"))
+        .unwrap();
+    assert!(
+        update.contains(r#"SET "failed_attempts" = (CASE WHEN ("failed_attempts" + $1 >= $2)"#)
+    );
+
+    // The failure that reaches the limit locks the account.
+    let mut last = credential("s3cret-pass", MAX_FAILED_ATTEMPTS - 1);
+    let locked = local_credential::Model {
+        failed_attempts: 0,
+        locked_until: Some(now_timestamp() as i64 + LOCKOUT_SECONDS as i64),
+        ..last.clone()
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![last.clone()]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![locked]])
+        .into_connection();
+    let err = login_with_password(&db, "admin", "guess")
+        .await
+        .unwrap_err();
+    let LocalAuthError::Locked { until } = err else {
+        panic!("expected lockout, got {:?}", err);
+    };
+    assert!(until >= now_timestamp() as i64 + LOCKOUT_SECONDS as i64 - 1);
+
+    // While locked even the right password is rejected.
+    last.locked_until = Some(until);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![last]])
+        .into_connection();
+    let err = login_with_password(&db, "admin", "s3cret-pass")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code_and_message().0, 429);
+}
+
+#[tokio::test]
+async fn unknown_username_is_indistinguishable() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<local_credential::Model>::new()])
+        .into_connection();
+    let err = login_with_password(&db, "nobody", "whatever")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, LocalAuthError::InvalidCredentials));
+}
+
+#[test]
+fn console_session_round_trips() {
+    let session = ConsoleSession::new(&user());
+    assert_eq!(session.ver, 2);
+    assert_eq!(session.csrf.len(), 32);
+    let decoded = ConsoleSession::decode(&session.encode()).unwrap();
+    assert_eq!(decoded.user_id, 4);
+    assert_eq!(decoded.csrf, session.csrf);
+    assert!(ConsoleSession::decode("garbage").is_none());
+}
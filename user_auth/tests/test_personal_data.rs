@@ -0,0 +1,235 @@
+use db_manager::entity::{
+    account_deletion, ai_chat, caregiver_action_log, caregiver_link, feedback, local_credential,
+    mutil_media, user as user_entity, user_status_log,
+};
+use sea_orm::prelude::{DateTimeWithTimeZone, Uuid};
+use sea_orm::{DbBackend, DbErr, MockDatabase, MockExecResult};
+use user_auth::personal_data::{
+    ACCOUNT_DELETION_GRACE_SECONDS, PersonalDataError, cancel_account_deletion,
+    export_personal_data, purge_account, purge_due_accounts, request_account_deletion,
+};
+
+const AVATAR: &str = "6f1c1b8e-4a57-4bb5-9a8e-4c1f3e0d2a10";
+
+fn user(avatar: Option<&str>) -> user_entity::Model {
+    user_entity::Model {
+        id: 7,
+        open_id: "export-user".to_string(),
+        nickname: Some("Grandpa".to_string()),
+        avatar: avatar.map(str::to_string),
+        permission: Some(1),
+        name: Some("Li Lei".to_string()),
+        phone_number: Some("13800138000".to_string()),
+        address: Some("Haidian".to_string()),
+        is_important: Some(true),
+        token_version: 2,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn deletion(requested_at: i64) -> account_deletion::Model {
+    account_deletion::Model {
+        id: 1,
+        user_id: 7,
+        requested_at,
+        purge_at: requested_at + ACCOUNT_DELETION_GRACE_SECONDS as i64,
+    }
+}
+
+#[tokio::test]
+async fn export_bundles_profile_avatar_chats_and_feedback() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[local_credential::Model {
+            id: 1,
+            user_id: 7,
+            username: "lilei".to_string(),
+            password_hash: "$argon2id$secret-hash".to_string(),
+            failed_attempts: 0,
+            locked_until: None,
+            password_changed_at: 0,
+            created_at: 0,
+        }]])
+        .append_query_results([[mutil_media::Model {
+            id: 3,
+            uuid: Some(Uuid::parse_str(AVATAR).unwrap()),
+            file: Some(vec![1, 2, 3]),
+            r#type: Some("webp".to_string()),
+        }]])
+        .append_query_results([[ai_chat::Model {
+            id: 11,
+            index: Some("morning".to_string()),
+            openid: Some("export-user".to_string()),
+            long_content: Some("how is my blood pressure".to_string()),
+        }]])
+        .append_query_results([[feedback::Model {
+            id: 21,
+            r#type: Some("service".to_string()),
+            content: Some("the canteen is great".to_string()),
+            phone: None,
+            openid: Some("export-user".to_string()),
+            created_time: DateTimeWithTimeZone::default(),
+        }]])
+        .append_query_results([Vec::<caregiver_link::Model>::new()])
+        .append_query_results([Vec::<caregiver_action_log::Model>::new()])
+        .into_connection();
+
+    let files = export_personal_data(&db, &user(Some(AVATAR)))
+        .await
+        .unwrap();
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "profile.json",
+            "avatar.webp",
+            "ai_chat.json",
+            "feedback.json",
+            "caregiver.json"
+        ]
+    );
+
+    let text = |path: &str| {
+        String::from_utf8(
+            files
+                .iter()
+                .find(|f| f.path == path)
+                .unwrap()
+                .content
+                .clone(),
+        )
+        .unwrap()
+    };
+    assert!(text("profile.json").contains("13800138000"));
+    assert!(text("profile.json").contains("lilei"));
+    assert!(!text("profile.json").contains("argon2id"));
+    assert!(text("ai_chat.json").contains("how is my blood pressure"));
+    assert!(text("feedback.json").contains("the canteen is great"));
+    assert_eq!(files[1].content, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn deletion_request_has_grace_period_and_is_unique() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<account_deletion::Model>::new()])
+        .append_query_results([[deletion(1_000)]])
+        .into_connection();
+    let created = request_account_deletion(&db, &user(None)).await.unwrap();
+    assert_eq!(
+        created.purge_at - created.requested_at,
+        ACCOUNT_DELETION_GRACE_SECONDS as i64
+    );
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[deletion(1_000)]])
+        .into_connection();
+    let err = request_account_deletion(&db, &user(None))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PersonalDataError::DeletionPending));
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .into_connection();
+    let err = cancel_account_deletion(&db, 7).await.unwrap_err();
+    assert!(matches!(err, PersonalDataError::DeletionNotFound));
+}
+
+fn anonymized() -> user_entity::Model {
+    let mut anonymized = user(None);
+    anonymized.open_id = "deleted:7".to_string();
+    anonymized.nickname = None;
+    anonymized.name = None;
+    anonymized.phone_number = None;
+    anonymized.address = None;
+    anonymized.is_important = None;
+    anonymized.permission = Some(0);
+    anonymized.token_version = 3;
+    anonymized.status = "deleted".to_string();
+    anonymized
+}
+
+fn status_log() -> user_status_log::Model {
+    user_status_log::Model {
+        id: 1,
+        user_id: 7,
+        operator_id: None,
+        from_status: "active".to_string(),
+        to_status: "deleted".to_string(),
+        reason: None,
+        suspended_until: None,
+        created_at: 0,
+    }
+}
+
+const DONE: MockExecResult = MockExecResult {
+    last_insert_id: 0,
+    rows_affected: 1,
+};
+
+#[tokio::test]
+async fn purge_anonymizes_user_and_removes_personal_rows() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_exec_results(std::iter::repeat_n(DONE, 12))
+        .append_query_results([[status_log()]])
+        .append_query_results([[anonymized()]])
+        .append_exec_results([DONE])
+        .into_connection();
+
+    let purged = purge_account(&db, user(None)).await.unwrap();
+    assert_eq!(purged.open_id, "deleted:7");
+    assert_eq!(purged.phone_number, None);
+
+    let log = format!("{:?}", db.into_transaction_log());
+    for table in [
+        "ai_chat",
+        "feedback",
+        "audit_subject",
+        "refresh_token",
+        "wx_session",
+        "local_credential",
+        "admin_totp",
+        "caregiver_link",
+        "account_deletion",
+    ] {
+        assert!(
+            log.contains(&format!("DELETE FROM \\\"public\\\".\\\"{}\\\"", table)),
+            "missing delete of {}",
+            table
+        );
+    }
+    assert!(!log.contains("13800138000"));
+}
+
+#[tokio::test]
+async fn purge_due_accounts_continues_after_a_failure() {
+    let mut failing = user(None);
+    failing.id = 8;
+    failing.open_id = "failing-user".to_string();
+    let mut failing_deletion = deletion(0);
+    failing_deletion.id = 2;
+    failing_deletion.user_id = 8;
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([[
+            (failing_deletion, Some(failing)),
+            (deletion(0), Some(user(None))),
+        ]])
+        .append_exec_errors([DbErr::Custom("connection reset".to_string())])
+        .append_exec_results(std::iter::repeat_n(DONE, 12))
+        .append_query_results([[status_log()]])
+        .append_query_results([[anonymized()]])
+        .append_exec_results([DONE])
+        .into_connection();
+
+    let report = purge_due_accounts(&db, ACCOUNT_DELETION_GRACE_SECONDS)
+        .await
+        .unwrap();
+    assert_eq!(report.purged, vec![7]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, 8);
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use db_manager::entity::{
+    dinner_provider, dinner_provider_owner, role, role_permission, user as user_entity,
+};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult, Value};
+use user_auth::rbac::{
+    PermissionSet, RbacError, assign_provider_owner, assign_role, ensure_provider_owner,
+    load_permissions, permission, permission::is_valid_permission, unassign_role, upsert_role,
+};
+
+fn user(permission: i32) -> user_entity::Model {
+    user_entity::Model {
+        id: 5,
+        open_id: "rbac-user".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(permission),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn row(column: &str, value: Value) -> BTreeMap<String, Value> {
+    BTreeMap::from([(column.to_string(), value)])
+}
+
+#[test]
+fn permission_set_wildcards() {
+    let admin = PermissionSet::new([permission::ALL]);
+    assert!(admin.allows(permission::ROLE_MANAGE));
+
+    let meal = PermissionSet::new(["meal.*", permission::HEALTH_GUIDE_WRITE]);
+    assert!(meal.allows(permission::MEAL_WRITE));
+    assert!(meal.allows(permission::MEAL_MANAGE));
+    assert!(meal.allows(permission::HEALTH_GUIDE_WRITE));
+    assert!(!meal.allows(permission::FEEDBACK_MANAGE));
+
+    assert!(!PermissionSet::default().allows(permission::MEAL_WRITE));
+}
+
+#[test]
+fn validates_permission_names() {
+    assert!(is_valid_permission("*"));
+    assert!(is_valid_permission(permission::MEAL_WRITE));
+    assert!(is_valid_permission("health_guide.*"));
+    assert!(!is_valid_permission("unknown.*"));
+    assert!(!is_valid_permission("meal.delete_everything"));
+}
+
+#[tokio::test]
+async fn loads_permissions_of_builtin_and_assigned_roles() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![row("role_id", 7.into())]])
+        .append_query_results([vec![
+            role::Model {
+                id: 2,
+                name: "provider".to_string(),
+                description: None,
+                is_builtin: true,
+            },
+            role::Model {
+                id: 7,
+                name: "health_guide_editor".to_string(),
+                description: None,
+                is_builtin: false,
+            },
+        ]])
+        .append_query_results([vec![
+            row("permission", permission::MEAL_WRITE.into()),
+            row("permission", permission::HEALTH_GUIDE_WRITE.into()),
+        ]])
+        .into_connection();
+
+    let permissions = load_permissions(&db, &user(2)).await.unwrap();
+    assert!(permissions.allows(permission::MEAL_WRITE));
+    assert!(permissions.allows(permission::HEALTH_GUIDE_WRITE));
+    assert!(!permissions.allows(permission::MEAL_MANAGE));
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("provider"));
+}
+
+#[tokio::test]
+async fn rejects_invalid_role_changes() {
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    let granter = PermissionSet::new([permission::ALL]);
+
+    let err = upsert_role(&db, &granter, "admin", None, vec![])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::BuiltinRole(_)));
+
+    let err = upsert_role(&db, &granter, "Bad Name", None, vec![])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::InvalidRoleName));
+
+    let err = upsert_role(
+        &db,
+        &granter,
+        "editor",
+        None,
+        vec!["meal.everything".to_string()],
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code_and_message().0, 400);
+
+    // Validation happens before any query is sent.
+    assert!(db.into_transaction_log().is_empty());
+}
+
+#[tokio::test]
+async fn builtin_roles_can_not_be_edited() {
+    let granter = PermissionSet::new([permission::ALL]);
+    for name in ["guest", "user", "provider"] {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![role::Model {
+                id: 2,
+                name: name.to_string(),
+                description: None,
+                is_builtin: true,
+            }]])
+            .into_connection();
+        let err = upsert_role(&db, &granter, name, None, vec![permission::ALL.to_string()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RbacError::BuiltinRole(n) if n == name));
+
+        // 只查询了角色，没有修改描述或权限
+        let log = format!("{:?}", db.into_transaction_log());
+        assert!(!log.contains("UPDATE"));
+        assert!(!log.contains("DELETE"));
+    }
+}
+
+#[test]
+fn grants_are_limited_to_held_permissions() {
+    let manager = PermissionSet::new([permission::ROLE_MANAGE, "meal.*"]);
+    assert!(manager.can_grant(permission::MEAL_WRITE));
+    assert!(manager.can_grant("meal.*"));
+    assert!(!manager.can_grant(permission::ALL));
+    assert!(!manager.can_grant("role.*"));
+    assert!(!manager.can_grant(permission::AUDIT_READ));
+    assert!(PermissionSet::new([permission::ALL]).can_grant(permission::ALL));
+}
+
+#[tokio::test]
+async fn role_manager_can_not_escalate() {
+    let manager = PermissionSet::new([permission::ROLE_MANAGE, permission::MEAL_WRITE]);
+
+    // 创建持有 `*` 或自己没有的权限的角色
+    for granted in [permission::ALL, permission::AUDIT_READ] {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        let err = upsert_role(&db, &manager, "root", None, vec![granted.to_string()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RbacError::GrantNotAllowed(_)));
+        assert_eq!(err.code_and_message().0, 403);
+        assert!(db.into_transaction_log().is_empty());
+    }
+
+    // 分配内置 admin 角色
+    let admin = role::Model {
+        id: 1,
+        name: "admin".to_string(),
+        description: None,
+        is_builtin: true,
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![admin]])
+        .into_connection();
+    let err = assign_role(&db, &manager, 5, "admin", Some(5))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::GrantNotAllowed(_)));
+
+    // 分配持有自己没有的权限的自定义角色
+    let auditor = role::Model {
+        id: 9,
+        name: "auditor".to_string(),
+        description: None,
+        is_builtin: false,
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![auditor]])
+        .append_query_results([vec![role_permission::Model {
+            id: 1,
+            role_id: 9,
+            permission: permission::AUDIT_READ.to_string(),
+        }]])
+        .into_connection();
+    let err = assign_role(&db, &manager, 5, "auditor", Some(5))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::GrantNotAllowed(p) if p == permission::AUDIT_READ));
+}
+
+#[tokio::test]
+async fn role_manager_can_not_unassign_roles_above_itself() {
+    let manager = PermissionSet::new([permission::ROLE_MANAGE, permission::MEAL_WRITE]);
+
+    // 取消他人的 admin 角色
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![role::Model {
+            id: 1,
+            name: "admin".to_string(),
+            description: None,
+            is_builtin: true,
+        }]])
+        .into_connection();
+    let err = unassign_role(&db, &manager, 5, "admin").await.unwrap_err();
+    assert!(matches!(err, RbacError::GrantNotAllowed(p) if p == permission::ALL));
+    assert!(!format!("{:?}", db.into_transaction_log()).contains("DELETE"));
+
+    // 取消持有自己没有的权限的自定义角色
+    let auditor = role::Model {
+        id: 9,
+        name: "auditor".to_string(),
+        description: None,
+        is_builtin: false,
+    };
+    let audit_read = role_permission::Model {
+        id: 1,
+        role_id: 9,
+        permission: permission::AUDIT_READ.to_string(),
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![auditor.clone()]])
+        .append_query_results([vec![audit_read.clone()]])
+        .into_connection();
+    let err = unassign_role(&db, &manager, 5, "auditor")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::GrantNotAllowed(p) if p == permission::AUDIT_READ));
+    assert!(!format!("{:?}", db.into_transaction_log()).contains("DELETE"));
+
+    // 持有该权限的操作者可以取消
+    let auditor_manager = PermissionSet::new([permission::ROLE_MANAGE, permission::AUDIT_READ]);
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![auditor]])
+        .append_query_results([vec![audit_read]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection();
+    unassign_role(&db, &auditor_manager, 5, "auditor")
+        .await
+        .unwrap();
+    assert!(format!("{:?}", db.into_transaction_log()).contains("DELETE"));
+}
+
+#[tokio::test]
+async fn provider_can_only_operate_owned_canteens() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![dinner_provider_owner::Model {
+            id: 1,
+            user_id: 5,
+            dinner_provider_id: 3,
+            granted_by: Some(1),
+            created_at: 0,
+        }]])
+        .append_query_results([Vec::<dinner_provider_owner::Model>::new()])
+        .into_connection();
+
+    assert!(ensure_provider_owner(&db, 5, 3).await.is_ok());
+    let err = ensure_provider_owner(&db, 5, 4).await.unwrap_err();
+    assert!(matches!(err, RbacError::NotProviderOwner(4)));
+    assert_eq!(err.code_and_message().0, 403);
+}
+
+#[tokio::test]
+async fn assigning_unknown_canteen_fails() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<dinner_provider::Model>::new()])
+        .into_connection();
+
+    let err = assign_provider_owner(&db, 5, 42, Some(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RbacError::DinnerProviderNotFound(42)));
+    assert_eq!(err.code_and_message().0, 404);
+}
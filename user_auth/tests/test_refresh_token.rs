@@ -0,0 +1,106 @@
+use db_manager::entity::{refresh_token as refresh_entity, user as user_entity};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult};
+use user_auth::db_exchange::{
+    ExchangeError, KeyRing, hash_refresh_token, now_timestamp, rotate_refresh_token, token2claims,
+};
+
+fn keys() -> KeyRing {
+    KeyRing::single("refresh-secret").unwrap()
+}
+
+fn user() -> user_entity::Model {
+    user_entity::Model {
+        id: 7,
+        open_id: "refresh-user".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(1),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 2,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn stored(id: i32, raw: &str, revoked_at: Option<i64>) -> refresh_entity::Model {
+    let now = now_timestamp() as i64;
+    refresh_entity::Model {
+        id,
+        user_id: 7,
+        token_hash: hash_refresh_token(raw),
+        family_id: "family".to_string(),
+        expires_at: now + 3600,
+        revoked_at,
+        replaced_by: None,
+        created_at: now,
+    }
+}
+
+fn exec(rows: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: rows,
+    }
+}
+
+#[test]
+fn hash_is_stable_and_not_plaintext() {
+    let hash = hash_refresh_token("abc");
+    assert_eq!(hash, hash_refresh_token("abc"));
+    assert_ne!(hash, "abc");
+    assert_eq!(hash.len(), 64);
+}
+
+#[tokio::test]
+async fn rotation_issues_new_pair() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![stored(1, "old", None)]])
+        .append_query_results([vec![user()]])
+        .append_query_results([vec![stored(2, "new", None)]])
+        .append_exec_results([exec(1), exec(1)])
+        .into_connection();
+
+    let (model, pair) = rotate_refresh_token(&db, &keys, "old").await.unwrap();
+    assert_eq!(model.id, 7);
+    assert_ne!(pair.refresh_token, "old");
+    let claims = token2claims(&keys, &pair.access_token).unwrap();
+    assert_eq!(claims.sub, "refresh-user");
+    assert_eq!(claims.ver, 2);
+}
+
+#[tokio::test]
+async fn reused_token_revokes_family() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![stored(1, "old", Some(1))]])
+        .append_exec_results([exec(2)])
+        .into_connection();
+
+    let err = rotate_refresh_token(&db, &keys, "old").await.unwrap_err();
+    assert!(matches!(err, ExchangeError::RefreshTokenReused));
+
+    let log = db.into_transaction_log();
+    let revoked_family = log.iter().any(|t| {
+        let t = format!("{:?}", t);
+        t.contains("UPDATE") && t.contains("family_id") && t.contains("IS NULL")
+    });
+    assert!(revoked_family, "family should be revoked: {:?}", log);
+}
+
+#[tokio::test]
+async fn unknown_token_is_invalid() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<refresh_entity::Model>::new()])
+        .into_connection();
+
+    let err = rotate_refresh_token(&db, &keys, "missing")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ExchangeError::InvalidToken));
+}
@@ -0,0 +1,61 @@
+use db_manager::entity::user as user_entity;
+use sea_orm::{DbBackend, MockDatabase};
+use user_auth::db_exchange::{ExchangeError, KeyRing, model2token, verify_token};
+
+fn keys() -> KeyRing {
+    KeyRing::single("session-secret").unwrap()
+}
+
+fn model(token_version: i32) -> user_entity::Model {
+    user_entity::Model {
+        id: 1,
+        open_id: "session-user".to_string(),
+        nickname: Some("nick".to_string()),
+        avatar: None,
+        permission: Some(1),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+#[tokio::test]
+async fn verify_token_accepts_current_version() {
+    let keys = keys();
+    let token = model2token(&keys, &model(3)).unwrap();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![model(3)]])
+        .into_connection();
+
+    let found = verify_token(&db, &keys, &token).await.unwrap();
+    assert_eq!(found, model(3));
+}
+
+#[tokio::test]
+async fn verify_token_rejects_bumped_version() {
+    let keys = keys();
+    let token = model2token(&keys, &model(0)).unwrap();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![model(1)]])
+        .into_connection();
+
+    let err = verify_token(&db, &keys, &token).await.unwrap_err();
+    assert!(matches!(err, ExchangeError::TokenRevoked));
+}
+
+#[tokio::test]
+async fn verify_token_rejects_deleted_user() {
+    let keys = keys();
+    let token = model2token(&keys, &model(0)).unwrap();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([Vec::<user_entity::Model>::new()])
+        .into_connection();
+
+    let err = verify_token(&db, &keys, &token).await.unwrap_err();
+    assert!(matches!(err, ExchangeError::TokenRevoked));
+}
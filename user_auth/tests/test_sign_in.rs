@@ -0,0 +1,109 @@
+use db_manager::entity::{refresh_token as refresh_entity, user as user_entity, wx_session};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult};
+use user_auth::db_exchange::{ExchangeError, KeyRing, OnboardingProfile, now_timestamp, sign_in};
+
+fn keys() -> KeyRing {
+    KeyRing::single("sign-in-secret").unwrap()
+}
+
+fn user(status: &str) -> user_entity::Model {
+    user_entity::Model {
+        id: 11,
+        open_id: "sign-in-user".to_string(),
+        nickname: Some("阿姨".to_string()),
+        avatar: None,
+        permission: Some(0),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: status.to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn refresh_row() -> refresh_entity::Model {
+    let now = now_timestamp() as i64;
+    refresh_entity::Model {
+        id: 1,
+        user_id: 11,
+        token_hash: "hash".to_string(),
+        family_id: "family".to_string(),
+        expires_at: now + 3600,
+        revoked_at: None,
+        replaced_by: None,
+        created_at: now,
+    }
+}
+
+fn exec(rows: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected: rows,
+    }
+}
+
+#[tokio::test]
+async fn creates_new_user_with_profile() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_exec_results([exec(1)])
+        .append_query_results([vec![user("active")]])
+        .append_query_results([Vec::<wx_session::Model>::new()])
+        .append_query_results([vec![wx_session::Model {
+            id: 1,
+            user_id: 11,
+            session_key: "key".to_string(),
+            updated_at: 0,
+        }]])
+        .append_query_results([vec![refresh_row()]])
+        .into_connection();
+
+    let profile = OnboardingProfile {
+        nickname: Some("阿姨".to_string()),
+        ..Default::default()
+    };
+    let result = sign_in(&db, &keys, "sign-in-user", Some("key"), Some(profile))
+        .await
+        .unwrap();
+    assert!(result.is_new);
+    assert_eq!(result.user.id, 11);
+    assert!(!result.tokens.access_token.is_empty());
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("ON CONFLICT"));
+    assert!(log.contains("阿姨"));
+    assert!(log.contains("wx_session"));
+}
+
+#[tokio::test]
+async fn existing_user_signs_in_again() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_exec_results([exec(0)])
+        .append_query_results([vec![user("active")]])
+        .append_query_results([vec![refresh_row()]])
+        .into_connection();
+
+    let result = sign_in(&db, &keys, "sign-in-user", None, None)
+        .await
+        .unwrap();
+    assert!(!result.is_new);
+    assert_eq!(result.user.open_id, "sign-in-user");
+}
+
+#[tokio::test]
+async fn suspended_user_is_rejected() {
+    let keys = keys();
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_exec_results([exec(0)])
+        .append_query_results([vec![user("suspended")]])
+        .into_connection();
+
+    let err = sign_in(&db, &keys, "sign-in-user", None, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ExchangeError::AccountBlocked(_)));
+}
@@ -1,24 +1,10 @@
-use std::env;
-use std::sync::{Mutex, OnceLock};
+use user_auth::db_exchange::{
+    Claims, DEFAULT_KEY_ID, ExchangeError, KeyPurpose, KeyRing, SigningKey, User,
+    token2user::token2user,
+};
 
-use hmac::{Hmac, digest::KeyInit};
-use jwt::SignWithKey;
-use sha2::Sha256;
-use user_auth::db_exchange::{Claims, ExchangeError, User, token2user::token2user};
-
-static ENV_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
-
-fn env_lock() -> &'static Mutex<()> {
-    ENV_MUTEX.get_or_init(|| Mutex::new(()))
-}
-
-fn with_secret<F: FnOnce() -> R, R>(secret: &str, f: F) -> R {
-    let _guard = env_lock().lock().expect("env mutex poisoned");
-    // std::env::set_var is unsafe in this environment; confine it here under a lock.
-    unsafe {
-        env::set_var("SERVER_JWT_SECRET", secret);
-    }
-    f()
+fn ring(secret: &str) -> KeyRing {
+    KeyRing::single(secret).unwrap()
 }
 
 fn make_user() -> User {
@@ -35,22 +21,21 @@ fn make_user() -> User {
 }
 
 fn sign_with_secret(claims: &Claims, secret: &str) -> String {
-    let key: Hmac<Sha256> =
-        Hmac::new_from_slice(secret.as_bytes()).expect("failed to build key for test");
-    claims
-        .sign_with_key(&key)
-        .expect("failed to sign claims for test")
+    KeyRing::new(
+        DEFAULT_KEY_ID,
+        vec![SigningKey::new(DEFAULT_KEY_ID, secret, None)],
+    )
+    .and_then(|ring| ring.sign(KeyPurpose::Session, claims))
+    .expect("failed to sign claims for test")
 }
 
 #[test]
 fn rejects_invalid_token_format() {
-    with_secret("secret", || {
-        let err = token2user("not-a-jwt").unwrap_err();
-        match err {
-            ExchangeError::InvalidToken => {}
-            other => panic!("expected InvalidToken, got {:?}", other),
-        }
-    });
+    let err = token2user(&ring("secret"), "not-a-jwt").unwrap_err();
+    match err {
+        ExchangeError::InvalidToken => {}
+        other => panic!("expected InvalidToken, got {:?}", other),
+    }
 }
 
 #[test]
@@ -60,17 +45,16 @@ fn rejects_wrong_signature() {
     let claims = Claims {
         sub: user.open_id.clone(),
         exp: u64::MAX, // far in the future
+        ver: 0,
         user,
     };
     let token = sign_with_secret(&claims, "secret-a");
 
-    with_secret("secret-b", || {
-        let err = token2user(&token).unwrap_err();
-        match err {
-            ExchangeError::InvalidToken => {}
-            other => panic!("expected InvalidToken, got {:?}", other),
-        }
-    });
+    let err = token2user(&ring("secret-b"), &token).unwrap_err();
+    match err {
+        ExchangeError::InvalidToken => {}
+        other => panic!("expected InvalidToken, got {:?}", other),
+    }
 }
 
 #[test]
@@ -79,15 +63,14 @@ fn rejects_expired_token() {
     let claims = Claims {
         sub: user.open_id.clone(),
         exp: 1, // definitely in the past
+        ver: 0,
         user,
     };
     let token = sign_with_secret(&claims, "secret");
 
-    with_secret("secret", || {
-        let err = token2user(&token).unwrap_err();
-        match err {
-            ExchangeError::TokenExpired => {}
-            other => panic!("expected TokenExpired, got {:?}", other),
-        }
-    });
+    let err = token2user(&ring("secret"), &token).unwrap_err();
+    match err {
+        ExchangeError::TokenExpired => {}
+        other => panic!("expected TokenExpired, got {:?}", other),
+    }
 }
@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use db_manager::DatabaseConfig;
+use db_manager::entity::{admin_totp, user as user_entity};
+use db_manager::migrator::Migrator;
+use dotenvy::dotenv;
+use sea_orm::{
+    ActiveModelTrait, Database, DbBackend, EntityTrait, MockDatabase, MockExecResult, Set,
+};
+use sea_orm_migration::MigratorTrait;
+use user_auth::db_exchange::{KeyRing, now_timestamp};
+use user_auth::local_auth::MAX_FAILED_ATTEMPTS;
+use user_auth::totp::{
+    TotpError, TotpPolicy, base32_decode, base32_encode, check_admin_step_up, issue_step_up_token,
+    otpauth_uri, totp_code, totp_step, verify_second_factor, verify_step_up_token,
+    verify_totp_code,
+};
+
+/// RFC 6238 appendix B test secret (SHA1).
+const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+fn keys() -> KeyRing {
+    KeyRing::single("test-secret").unwrap()
+}
+
+fn admin() -> user_entity::Model {
+    user_entity::Model {
+        id: 3,
+        open_id: "totp-admin".to_string(),
+        nickname: Some("Ops Admin".to_string()),
+        avatar: None,
+        permission: Some(3),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 1,
+        status: "active".to_string(),
+        suspended_reason: None,
+        suspended_until: None,
+    }
+}
+
+fn binding(enabled: bool, last_used_step: Option<i64>) -> admin_totp::Model {
+    admin_totp::Model {
+        id: 1,
+        user_id: 3,
+        secret: base32_encode(RFC_SECRET),
+        enabled,
+        last_used_step,
+        created_at: 0,
+        enabled_at: None,
+        failed_attempts: 0,
+        locked_until: None,
+    }
+}
+
+#[test]
+fn matches_rfc6238_vectors() {
+    // The RFC lists 8-digit codes; 6-digit codes are their last six digits.
+    assert_eq!(totp_code(RFC_SECRET, 59), "287082");
+    assert_eq!(totp_code(RFC_SECRET, 1111111109), "081804");
+    assert_eq!(totp_code(RFC_SECRET, 2000000000), "279037");
+}
+
+#[test]
+fn base32_round_trips() {
+    let encoded = base32_encode(RFC_SECRET);
+    assert_eq!(encoded, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+    assert_eq!(base32_decode(&encoded.to_lowercase()).unwrap(), RFC_SECRET);
+    assert!(base32_decode("not base32!").is_none());
+}
+
+#[test]
+fn accepts_skew_and_rejects_replay() {
+    let secret = base32_encode(RFC_SECRET);
+    let now = 1111111109;
+    let previous = totp_code(RFC_SECRET, now - 30);
+    assert_eq!(
+        verify_totp_code(&secret, &previous, now, None),
+        Some(totp_step(now) - 1)
+    );
+    let stale = totp_code(RFC_SECRET, now - 90);
+    assert_eq!(verify_totp_code(&secret, &stale, now, None), None);
+
+    let current = totp_code(RFC_SECRET, now);
+    assert_eq!(
+        verify_totp_code(&secret, &current, now, Some(totp_step(now))),
+        None
+    );
+    assert_eq!(verify_totp_code(&secret, "12345", now, None), None);
+}
+
+#[test]
+fn builds_otpauth_uri() {
+    let uri = otpauth_uri("Ops Admin", "ABC234");
+    assert_eq!(
+        uri,
+        "otpauth://totp/SD_backend:Ops%20Admin?secret=ABC234&issuer=SD_backend&algorithm=SHA1&digits=6&period=30"
+    );
+}
+
+#[test]
+fn step_up_token_is_bound_to_token_version() {
+    let keys = keys();
+    let user = admin();
+    let (token, _) = issue_step_up_token(&keys, &user).unwrap();
+    assert!(verify_step_up_token(&keys, &token, &user).is_ok());
+
+    let mut revoked = admin();
+    revoked.token_version += 1;
+    assert!(matches!(
+        verify_step_up_token(&keys, &token, &revoked),
+        Err(TotpError::StepUpRequired)
+    ));
+
+    // An access token is not a step-up token.
+    let access = user_auth::db_exchange::model2token(&keys, &user).unwrap();
+    assert!(verify_step_up_token(&keys, &access, &user).is_err());
+}
+
+#[tokio::test]
+async fn policy_decides_when_step_up_is_needed() {
+    let keys = keys();
+    let user = admin();
+
+    // Off never touches the database.
+    let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+    assert!(
+        check_admin_step_up(&db, &keys, TotpPolicy::Off, &user, None)
+            .await
+            .is_ok()
+    );
+
+    let not_enrolled = || {
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([Vec::<admin_totp::Model>::new()])
+            .into_connection()
+    };
+    assert!(
+        check_admin_step_up(&not_enrolled(), &keys, TotpPolicy::Enrolled, &user, None)
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        check_admin_step_up(&not_enrolled(), &keys, TotpPolicy::Required, &user, None).await,
+        Err(TotpError::EnrollmentRequired)
+    ));
+
+    let enrolled = || {
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![binding(true, None)]])
+            .into_connection()
+    };
+    let err = check_admin_step_up(&enrolled(), &keys, TotpPolicy::Enrolled, &user, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code_and_message().0, 403);
+    let (token, _) = issue_step_up_token(&keys, &user).unwrap();
+    assert!(
+        check_admin_step_up(
+            &enrolled(),
+            &keys,
+            TotpPolicy::Enrolled,
+            &user,
+            Some(&token)
+        )
+        .await
+        .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn recovery_codes_are_single_use() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![binding(true, None)]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![binding(true, None)]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        // 失败次数 +1，再读取锁定时间
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![binding(true, None)]])
+        .into_connection();
+    assert!(verify_second_factor(&db, 3, "abcde-12345").await.is_ok());
+    assert!(matches!(
+        verify_second_factor(&db, 3, "abcde-12345").await,
+        Err(TotpError::InvalidCode)
+    ));
+}
+
+#[tokio::test]
+async fn repeated_failures_lock_the_second_factor() {
+    let almost = admin_totp::Model {
+        failed_attempts: 4,
+        ..binding(true, None)
+    };
+    let locked = admin_totp::Model {
+        failed_attempts: 0,
+        locked_until: Some(i64::MAX),
+        ..almost.clone()
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![almost]])
+        .append_exec_results([
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            },
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+        ])
+        .append_query_results([vec![locked]])
+        .into_connection();
+    assert!(matches!(
+        verify_second_factor(&db, 3, "wrong-code").await,
+        Err(TotpError::Locked { .. })
+    ));
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("locked_until"));
+
+    // 锁定期内不再校验验证码
+    let locked = admin_totp::Model {
+        locked_until: Some(i64::MAX),
+        ..binding(true, None)
+    };
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![locked]])
+        .into_connection();
+    let err = verify_second_factor(&db, 3, "abcde-12345")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code_and_message().0, 429);
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
+
+#[tokio::test]
+async fn pending_binding_is_not_a_second_factor() {
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![binding(false, None)]])
+        .into_connection();
+    assert!(matches!(
+        verify_second_factor(&db, 3, "123456").await,
+        Err(TotpError::NotEnrolled)
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_failures_lock_the_second_factor() {
+    dotenv().ok();
+    let uri = std::env::var("SERVER_DB_URI").expect("SERVER_DB_URI must be set");
+    let db = Arc::new(
+        Database::connect(DatabaseConfig::new(uri).connect_options())
+            .await
+            .expect("failed to connect to database"),
+    );
+    Migrator::up(db.as_ref(), None).await.unwrap();
+
+    // 1) 新建一个已启用二次验证的用户
+    let user = user_entity::ActiveModel {
+        open_id: Set(format!(
+            "totp-race-{}-{}",
+            std::process::id(),
+            now_timestamp()
+        )),
+        permission: Set(Some(3)),
+        token_version: Set(0),
+        status: Set("active".to_string()),
+        ..Default::default()
+    }
+    .insert(db.as_ref())
+    .await
+    .unwrap();
+    let totp = admin_totp::ActiveModel {
+        user_id: Set(user.id),
+        secret: Set(base32_encode(RFC_SECRET)),
+        enabled: Set(true),
+        created_at: Set(0),
+        failed_attempts: Set(0),
+        ..Default::default()
+    }
+    .insert(db.as_ref())
+    .await
+    .unwrap();
+
+    // 2) 同时提交刚好达到上限次数的错误验证码，每一次失败都要计入
+    let attempts: Vec<_> = (0..MAX_FAILED_ATTEMPTS)
+        .map(|_| {
+            let db = db.clone();
+            tokio::spawn(
+                async move { verify_second_factor(db.as_ref(), user.id, "wrong-code").await },
+            )
+        })
+        .collect();
+    for attempt in attempts {
+        assert!(attempt.await.unwrap().is_err());
+    }
+
+    let after = admin_totp::Entity::find_by_id(totp.id)
+        .one(db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    user_entity::Entity::delete_by_id(user.id)
+        .exec(db.as_ref())
+        .await
+        .unwrap();
+    assert!(after.locked_until.unwrap_or(0) > now_timestamp() as i64);
+}
@@ -1,12 +1,7 @@
-use std::env;
+use user_auth::db_exchange::{KeyRing, User, token2user::token2user, user2token::user2token};
 
-use user_auth::db_exchange::{User, token2user::token2user, user2token::user2token};
-
-fn set_secret() {
-    // std::env::set_var is unsafe in this environment; confine it here.
-    unsafe {
-        env::set_var("SERVER_JWT_SECRET", "test-secret");
-    }
+fn keys() -> KeyRing {
+    KeyRing::single("test-secret").unwrap()
 }
 
 fn make_user() -> User {
@@ -24,9 +19,9 @@ fn make_user() -> User {
 
 #[test]
 fn generates_non_empty_token() {
-    set_secret();
+    let keys = keys();
     let user = make_user();
-    let token = user2token(&user).expect("token generation should succeed");
+    let token = user2token(&keys, &user, 0).expect("token generation should succeed");
     assert!(
         !token.trim().is_empty(),
         "generated token should not be empty"
@@ -35,11 +30,11 @@ fn generates_non_empty_token() {
 
 #[test]
 fn roundtrip_user_to_token_and_back() {
-    set_secret();
+    let keys = keys();
     let user = make_user();
 
-    let token = user2token(&user).expect("token generation should succeed");
-    let parsed = token2user(&token).expect("token should parse back to user");
+    let token = user2token(&keys, &user, 0).expect("token generation should succeed");
+    let parsed = token2user(&keys, &token).expect("token should parse back to user");
 
     assert_eq!(parsed.open_id, user.open_id);
     assert_eq!(parsed.nickname, user.nickname);
@@ -0,0 +1,102 @@
+use db_manager::entity::{user as user_entity, user_status_log};
+use sea_orm::{DbBackend, MockDatabase, MockExecResult};
+use user_auth::db_exchange::now_timestamp;
+use user_auth::user_status::{
+    UserStatus, UserStatusError, change_user_status, check_user_status, effective_status,
+};
+
+fn user(status: &str, suspended_until: Option<i64>) -> user_entity::Model {
+    user_entity::Model {
+        id: 9,
+        open_id: "status-user".to_string(),
+        nickname: None,
+        avatar: None,
+        permission: Some(1),
+        name: None,
+        phone_number: None,
+        address: None,
+        is_important: None,
+        token_version: 0,
+        status: status.to_string(),
+        suspended_reason: Some("spam".to_string()),
+        suspended_until,
+    }
+}
+
+#[test]
+fn suspension_expires() {
+    let past = now_timestamp() as i64 - 10;
+    let future = now_timestamp() as i64 + 3600;
+    assert_eq!(
+        effective_status(&user("suspended", Some(past))),
+        UserStatus::Active
+    );
+    assert_eq!(
+        effective_status(&user("suspended", Some(future))),
+        UserStatus::Suspended
+    );
+    assert_eq!(
+        effective_status(&user("suspended", None)),
+        UserStatus::Suspended
+    );
+}
+
+#[test]
+fn blocked_accounts_get_clear_codes() {
+    let err = check_user_status(&user("suspended", None)).unwrap_err();
+    assert!(matches!(err, UserStatusError::Suspended { .. }));
+    assert_eq!(
+        err.code_and_message(),
+        (423, "Account suspended: spam".to_string())
+    );
+
+    let err = check_user_status(&user("deleted", None)).unwrap_err();
+    assert_eq!(err.code_and_message().0, 410);
+
+    assert!(check_user_status(&user("active", None)).is_ok());
+}
+
+#[tokio::test]
+async fn suspending_logs_and_revokes_tokens() {
+    let target = user("active", None);
+    let mut suspended = user("suspended", None);
+    suspended.suspended_reason = Some("abuse".to_string());
+    let mut revoked = suspended.clone();
+    revoked.token_version = 1;
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results([vec![user_status_log::Model {
+            id: 1,
+            user_id: 9,
+            operator_id: Some(1),
+            from_status: "active".to_string(),
+            to_status: "suspended".to_string(),
+            reason: Some("abuse".to_string()),
+            suspended_until: None,
+            created_at: now_timestamp() as i64,
+        }]])
+        .append_query_results([vec![suspended]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .append_query_results([vec![revoked]])
+        .into_connection();
+
+    let updated = change_user_status(
+        &db,
+        Some(1),
+        target,
+        UserStatus::Suspended,
+        Some("abuse".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(updated.status, "suspended");
+    assert_eq!(updated.token_version, 1);
+
+    let log = format!("{:?}", db.into_transaction_log());
+    assert!(log.contains("user_status_log"));
+    assert!(log.contains("refresh_token"));
+}
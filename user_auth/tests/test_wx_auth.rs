@@ -4,16 +4,24 @@ use user_auth::wx_auth::*;
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn wx_config() -> WxAuthServerConfig {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let base_url =
+            std::env::var("SERVER_WX_BASEURL").unwrap_or_else(|_| DEFAULT_WX_BASE_URL.to_string());
+        WxAuthServerConfig::new(var("SERVER_WX_APPID"), var("SERVER_WX_SECRET"), base_url)
+    }
+
     #[tokio::test]
     async fn test_wx_auth_correct() {
         dotenv().ok();
         if std::env::var("SERVER_WX_BASEURL").is_ok() {
             let js_code = std::env::var("TEST_SERVER_WX_JS_CODE").unwrap();
-            let result = wx_auth_session_to_json(&js_code).await;
+            let result = wx_auth_session_to_json(&wx_config(), &js_code).await;
             assert!(result.is_ok());
         } else {
             let js_code = std::env::var("TEST_SERVER_WX_JS_CODE").unwrap();
-            let result = wx_auth_session_to_json(&js_code).await;
+            let result = wx_auth_session_to_json(&wx_config(), &js_code).await;
             assert!(result.is_err());
         }
     }
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use user_auth::wx_auth::{WxApiClient, WxAuthError, WxAuthServerConfig};
+
+/// Minimal stand-in for the WeChat API: `/cgi-bin/token` hands out `token-N`,
+/// `/wxa/echo` answers 42001 for `token-1` and echoes any other token.
+async fn mock_server(expires_in: u64) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let token_hits = Arc::new(AtomicUsize::new(0));
+    let hits = token_hits.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let hits = hits.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                let body = if path.starts_with("/cgi-bin/token") {
+                    let n = hits.fetch_add(1, Ordering::SeqCst) + 1;
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    format!(
+                        r#"{{"access_token":"token-{}","expires_in":{}}}"#,
+                        n, expires_in
+                    )
+                } else if path.contains("access_token=token-1") {
+                    r#"{"errcode":42001,"errmsg":"access_token expired"}"#.to_string()
+                } else {
+                    let token = path.split("access_token=").nth(1).unwrap_or("");
+                    let token = token.split('&').next().unwrap_or("");
+                    format!(r#"{{"errcode":0,"token":"{}"}}"#, token)
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            });
+        }
+    });
+    (base_url, token_hits)
+}
+
+fn client(base_url: String) -> WxApiClient {
+    WxApiClient::new(WxAuthServerConfig::new(
+        "appid".to_string(),
+        "secret".to_string(),
+        base_url,
+    ))
+}
+
+#[tokio::test]
+async fn concurrent_refreshes_are_coalesced() {
+    let (base_url, hits) = mock_server(7200).await;
+    let client = Arc::new(client(base_url));
+
+    let tasks: Vec<_> = (0..10)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.access_token().await.unwrap() })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(task.await.unwrap(), "token-1");
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn refreshes_before_expiry() {
+    // expires_in is shorter than the refresh margin, so every call refreshes.
+    let (base_url, hits) = mock_server(60).await;
+    let client = client(base_url);
+    assert_eq!(client.access_token().await.unwrap(), "token-1");
+    assert_eq!(client.access_token().await.unwrap(), "token-2");
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn expired_token_is_refreshed_and_retried() {
+    let (base_url, hits) = mock_server(7200).await;
+    let client = client(base_url);
+    let response: serde_json::Value = client.get_json("/wxa/echo", &[]).await.unwrap();
+    assert_eq!(response["token"], "token-2");
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn maps_errcodes() {
+    assert!(WxAuthError::from_errcode(0).is_none());
+    assert!(matches!(
+        WxAuthError::from_errcode(45011),
+        Some(WxAuthError::TooMuchRequestError)
+    ));
+    assert!(matches!(
+        WxAuthError::from_errcode(40029),
+        Some(WxAuthError::CodeError)
+    ));
+    assert!(
+        WxAuthError::from_errcode(42001)
+            .unwrap()
+            .is_access_token_error()
+    );
+    assert!(matches!(
+        WxAuthError::from_errcode(12345),
+        Some(WxAuthError::UnknownError(12345))
+    ));
+}
@@ -0,0 +1,67 @@
+use aes::Aes128;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use cbc::cipher::{BlockEncryptMut, KeyIvInit, block_padding::Pkcs7};
+use user_auth::wx_auth::{WxDecryptError, decrypt_phone_number};
+
+const SESSION_KEY: [u8; 16] = *b"0123456789abcdef";
+const IV: [u8; 16] = *b"fedcba9876543210";
+
+/// Encrypt `plain` the way WeChat does, returning base64 (session_key, encrypted_data, iv).
+fn encrypt(plain: &str) -> (String, String, String) {
+    let mut buf = plain.as_bytes().to_vec();
+    buf.resize(plain.len() + 16, 0);
+    let encrypted = cbc::Encryptor::<Aes128>::new(&SESSION_KEY.into(), &IV.into())
+        .encrypt_padded_mut::<Pkcs7>(&mut buf, plain.len())
+        .unwrap()
+        .to_vec();
+    (
+        STANDARD.encode(SESSION_KEY),
+        STANDARD.encode(encrypted),
+        STANDARD.encode(IV),
+    )
+}
+
+fn phone_payload(appid: &str) -> String {
+    format!(
+        r#"{{"phoneNumber":"13800138000","purePhoneNumber":"13800138000","countryCode":"86","watermark":{{"appid":"{}","timestamp":1700000000}}}}"#,
+        appid
+    )
+}
+
+#[test]
+fn decrypts_phone_number() {
+    let (key, data, iv) = encrypt(&phone_payload("wx-test-app"));
+    let phone = decrypt_phone_number(&key, &data, &iv, "wx-test-app").unwrap();
+    assert_eq!(phone.pure_phone_number, "13800138000");
+    assert_eq!(phone.country_code, "86");
+    assert_eq!(phone.watermark.timestamp, 1700000000);
+}
+
+#[test]
+fn rejects_other_appid() {
+    let (key, data, iv) = encrypt(&phone_payload("wx-other-app"));
+    let err = decrypt_phone_number(&key, &data, &iv, "wx-test-app").unwrap_err();
+    assert!(matches!(err, WxDecryptError::WatermarkMismatch));
+}
+
+#[test]
+fn rejects_missing_watermark() {
+    let (key, data, iv) =
+        encrypt(r#"{"phoneNumber":"1","purePhoneNumber":"1","countryCode":"86"}"#);
+    let err = decrypt_phone_number(&key, &data, &iv, "wx-test-app").unwrap_err();
+    assert!(matches!(err, WxDecryptError::WatermarkMismatch));
+}
+
+#[test]
+fn rejects_wrong_session_key() {
+    let (_, data, iv) = encrypt(&phone_payload("wx-test-app"));
+    let other_key = STANDARD.encode(b"another-key-1234");
+    let err = decrypt_phone_number(&other_key, &data, &iv, "wx-test-app").unwrap_err();
+    assert!(matches!(
+        err,
+        WxDecryptError::DecryptFailed | WxDecryptError::WatermarkMismatch
+    ));
+
+    let err = decrypt_phone_number("not base64!", &data, &iv, "wx-test-app").unwrap_err();
+    assert!(matches!(err, WxDecryptError::InvalidSessionKey));
+}